@@ -28,6 +28,24 @@ mod m20250406_140902_add_well_known_institutions;
 mod m20250415_110254_add_award_series;
 mod m20250415_214310_add_break_release_time;
 mod m20250501_160227_fix_schema_bugs;
+mod m20250601_120000_add_itemized_scores;
+mod m20250602_120000_add_tab_ranking_config;
+mod m20250603_120000_add_log_snapshots;
+mod m20250604_120000_add_swing_teams;
+mod m20250605_120000_add_venue_attributes;
+mod m20250606_120000_add_self_declared_availability;
+mod m20250607_120000_add_debate_pins;
+mod m20250608_120000_add_round_draw_seed;
+mod m20250609_120000_add_tournament_draw_config;
+mod m20250610_120000_add_trainee_adjudicators;
+mod m20250611_120000_add_tournament_ballot_validation_config;
+mod m20250612_120000_add_backup_ballot_author;
+mod m20250613_120000_add_speaker_substitutions;
+mod m20250614_120000_add_user_tournament_roles;
+mod m20250615_120000_add_log_authors;
+mod m20250616_120000_add_log_compaction;
+mod m20250617_120000_add_break_draw_seed;
+mod m20250618_120000_store_scores_in_hundredths;
 
 pub struct Migrator;
 
@@ -62,6 +80,24 @@ impl MigratorTrait for Migrator {
             Box::new(m20250415_110254_add_award_series::Migration),
             Box::new(m20250415_214310_add_break_release_time::Migration),
             Box::new(m20250501_160227_fix_schema_bugs::Migration),
+            Box::new(m20250601_120000_add_itemized_scores::Migration),
+            Box::new(m20250602_120000_add_tab_ranking_config::Migration),
+            Box::new(m20250603_120000_add_log_snapshots::Migration),
+            Box::new(m20250604_120000_add_swing_teams::Migration),
+            Box::new(m20250605_120000_add_venue_attributes::Migration),
+            Box::new(m20250606_120000_add_self_declared_availability::Migration),
+            Box::new(m20250607_120000_add_debate_pins::Migration),
+            Box::new(m20250608_120000_add_round_draw_seed::Migration),
+            Box::new(m20250609_120000_add_tournament_draw_config::Migration),
+            Box::new(m20250610_120000_add_trainee_adjudicators::Migration),
+            Box::new(m20250611_120000_add_tournament_ballot_validation_config::Migration),
+            Box::new(m20250612_120000_add_backup_ballot_author::Migration),
+            Box::new(m20250613_120000_add_speaker_substitutions::Migration),
+            Box::new(m20250614_120000_add_user_tournament_roles::Migration),
+            Box::new(m20250615_120000_add_log_authors::Migration),
+            Box::new(m20250616_120000_add_log_compaction::Migration),
+            Box::new(m20250617_120000_add_break_draw_seed::Migration),
+            Box::new(m20250618_120000_store_scores_in_hundredths::Migration),
         ]
     }
 }
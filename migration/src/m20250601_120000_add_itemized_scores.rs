@@ -0,0 +1,95 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(AdjudicatorSpeechScore::Table)
+                    .add_column(
+                        ColumnDef::new(AdjudicatorSpeechScore::ScoreItems)
+                            .text()
+                            .null(),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(AdjudicatorTeamScore::Table)
+                    .add_column(
+                        ColumnDef::new(AdjudicatorTeamScore::ScoreItems)
+                            .text()
+                            .null(),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Tournament::Table)
+                    .add_column(
+                        ColumnDef::new(Tournament::ScoreCategories)
+                            .text()
+                            .null(),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(AdjudicatorSpeechScore::Table)
+                    .drop_column(AdjudicatorSpeechScore::ScoreItems)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(AdjudicatorTeamScore::Table)
+                    .drop_column(AdjudicatorTeamScore::ScoreItems)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Tournament::Table)
+                    .drop_column(Tournament::ScoreCategories)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum AdjudicatorSpeechScore {
+    Table,
+    ScoreItems
+}
+
+#[derive(DeriveIden)]
+enum AdjudicatorTeamScore {
+    Table,
+    ScoreItems
+}
+
+#[derive(DeriveIden)]
+enum Tournament {
+    Table,
+    ScoreCategories
+}
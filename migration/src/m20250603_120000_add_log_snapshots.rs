@@ -0,0 +1,41 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // The state of the entity after the change. Needed as base of three-way merges
+        // and for the entity history, since the entity tables only hold the current state.
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(TournamentLog::Table)
+                    .add_column(
+                        ColumnDef::new(TournamentLog::Snapshot)
+                            .text()
+                            .null(),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(TournamentLog::Table)
+                    .drop_column(TournamentLog::Snapshot)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum TournamentLog {
+    Table,
+    Snapshot
+}
@@ -0,0 +1,116 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(TournamentVenue::Table)
+                    .add_column(
+                        ColumnDef::new(TournamentVenue::Capacity)
+                            .integer()
+                            .null(),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(TournamentVenue::Table)
+                    .add_column(
+                        ColumnDef::new(TournamentVenue::IsAccessible)
+                            .boolean()
+                            .not_null()
+                            .default(false),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(TournamentVenue::Table)
+                    .add_column(
+                        ColumnDef::new(TournamentVenue::Building)
+                            .string()
+                            .null(),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(TournamentVenue::Table)
+                    .add_column(
+                        ColumnDef::new(TournamentVenue::IsTopRoom)
+                            .boolean()
+                            .not_null()
+                            .default(false),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Participant::Table)
+                    .add_column(
+                        ColumnDef::new(Participant::RequiresAccessibleVenue)
+                            .boolean()
+                            .not_null()
+                            .default(false),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Participant::Table)
+                    .drop_column(Participant::RequiresAccessibleVenue)
+                    .to_owned(),
+            )
+            .await?;
+
+        for column in [TournamentVenue::IsTopRoom, TournamentVenue::Building, TournamentVenue::IsAccessible, TournamentVenue::Capacity] {
+            manager
+                .alter_table(
+                    Table::alter()
+                        .table(TournamentVenue::Table)
+                        .drop_column(column)
+                        .to_owned(),
+                )
+                .await?;
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+enum TournamentVenue {
+    Table,
+    Capacity,
+    IsAccessible,
+    Building,
+    IsTopRoom
+}
+
+#[derive(DeriveIden)]
+enum Participant {
+    Table,
+    RequiresAccessibleVenue
+}
@@ -0,0 +1,64 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Tournament::Table)
+                    .add_column(
+                        ColumnDef::new(Tournament::AllowSelfDeclaredAvailability)
+                            .boolean()
+                            .not_null()
+                            .default(false),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Tournament::Table)
+                    .add_column(
+                        ColumnDef::new(Tournament::AvailabilityDeadlineMinutes)
+                            .integer()
+                            .not_null()
+                            .default(60),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Tournament::Table)
+                    .drop_column(Tournament::AvailabilityDeadlineMinutes)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Tournament::Table)
+                    .drop_column(Tournament::AllowSelfDeclaredAvailability)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Tournament {
+    Table,
+    AllowSelfDeclaredAvailability,
+    AvailabilityDeadlineMinutes
+}
@@ -0,0 +1,69 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Adjudicator::Table)
+                    .add_column(
+                        ColumnDef::new(Adjudicator::IsTrainee)
+                            .boolean()
+                            .not_null()
+                            .default(false),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(FeedbackForm::Table)
+                    .add_column(
+                        ColumnDef::new(FeedbackForm::ShowChairsForTrainees)
+                            .boolean()
+                            .not_null()
+                            .default(false),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Adjudicator::Table)
+                    .drop_column(Adjudicator::IsTrainee)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(FeedbackForm::Table)
+                    .drop_column(FeedbackForm::ShowChairsForTrainees)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Adjudicator {
+    Table,
+    IsTrainee
+}
+
+#[derive(DeriveIden)]
+enum FeedbackForm {
+    Table,
+    ShowChairsForTrainees
+}
@@ -0,0 +1,108 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[derive(DeriveIden)]
+enum SpeakerSubstitution {
+    Table,
+    Uuid,
+    RoundId,
+    TeamId,
+    SpeakerId,
+    ReplacedSpeakerId
+}
+
+#[derive(DeriveIden)]
+enum TournamentRound {
+    Table,
+    Uuid
+}
+
+#[derive(DeriveIden)]
+enum Team {
+    Table,
+    Uuid
+}
+
+#[derive(DeriveIden)]
+enum Participant {
+    Table,
+    Uuid
+}
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                TableCreateStatement::new()
+                    .table(SpeakerSubstitution::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(SpeakerSubstitution::Uuid)
+                            .uuid()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(SpeakerSubstitution::RoundId).uuid().not_null())
+                    .col(ColumnDef::new(SpeakerSubstitution::TeamId).uuid().not_null())
+                    .col(ColumnDef::new(SpeakerSubstitution::SpeakerId).uuid().not_null())
+                    .col(ColumnDef::new(SpeakerSubstitution::ReplacedSpeakerId).uuid().null())
+                    .foreign_key(
+                        ForeignKeyCreateStatement::new()
+                            .from_tbl(SpeakerSubstitution::Table)
+                            .from_col(SpeakerSubstitution::RoundId)
+                            .to_tbl(TournamentRound::Table)
+                            .to_col(TournamentRound::Uuid)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade)
+                    )
+                    .foreign_key(
+                        ForeignKeyCreateStatement::new()
+                            .from_tbl(SpeakerSubstitution::Table)
+                            .from_col(SpeakerSubstitution::TeamId)
+                            .to_tbl(Team::Table)
+                            .to_col(Team::Uuid)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade)
+                    )
+                    .foreign_key(
+                        ForeignKeyCreateStatement::new()
+                            .from_tbl(SpeakerSubstitution::Table)
+                            .from_col(SpeakerSubstitution::SpeakerId)
+                            .to_tbl(Participant::Table)
+                            .to_col(Participant::Uuid)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade)
+                    )
+                    .foreign_key(
+                        ForeignKeyCreateStatement::new()
+                            .from_tbl(SpeakerSubstitution::Table)
+                            .from_col(SpeakerSubstitution::ReplacedSpeakerId)
+                            .to_tbl(Participant::Table)
+                            .to_col(Participant::Uuid)
+                            .on_delete(ForeignKeyAction::SetNull)
+                            .on_update(ForeignKeyAction::Cascade)
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager.create_index(
+            IndexCreateStatement::new()
+                .name("idx-speaker-substitution-round-id")
+                .table(SpeakerSubstitution::Table)
+                .col(SpeakerSubstitution::RoundId)
+                .to_owned(),
+        ).await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(TableDropStatement::new().table(SpeakerSubstitution::Table).to_owned())
+            .await
+    }
+}
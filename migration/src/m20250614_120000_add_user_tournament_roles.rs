@@ -0,0 +1,41 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Existing rows keep full access to their tournaments
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(UserTournament::Table)
+                    .add_column(
+                        ColumnDef::new(UserTournament::Role)
+                            .string()
+                            .not_null()
+                            .default("TabDirector"),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(UserTournament::Table)
+                    .drop_column(UserTournament::Role)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum UserTournament {
+    Table,
+    Role
+}
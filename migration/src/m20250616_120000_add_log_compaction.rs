@@ -0,0 +1,135 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[derive(DeriveIden)]
+enum TournamentLogSnapshot {
+    Table,
+    Uuid,
+    TournamentId,
+    LogEntryId,
+    SequenceIdx,
+    Timestamp
+}
+
+#[derive(DeriveIden)]
+enum TournamentLogPrunedEntry {
+    Table,
+    Uuid,
+    TournamentId,
+    SequenceIdx,
+    SnapshotId,
+    Timestamp,
+    TargetType,
+    TargetUuid,
+    Snapshot,
+    Author
+}
+
+#[derive(DeriveIden)]
+enum Tournament {
+    Table,
+    Uuid
+}
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                TableCreateStatement::new()
+                    .table(TournamentLogSnapshot::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(TournamentLogSnapshot::Uuid)
+                            .uuid()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(TournamentLogSnapshot::TournamentId).uuid().not_null())
+                    .col(ColumnDef::new(TournamentLogSnapshot::LogEntryId).uuid().not_null())
+                    .col(ColumnDef::new(TournamentLogSnapshot::SequenceIdx).integer().not_null())
+                    .col(ColumnDef::new(TournamentLogSnapshot::Timestamp).date_time().not_null())
+                    .foreign_key(
+                        ForeignKeyCreateStatement::new()
+                            .from_tbl(TournamentLogSnapshot::Table)
+                            .from_col(TournamentLogSnapshot::TournamentId)
+                            .to_tbl(Tournament::Table)
+                            .to_col(Tournament::Uuid)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade)
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                TableCreateStatement::new()
+                    .table(TournamentLogPrunedEntry::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(TournamentLogPrunedEntry::Uuid)
+                            .uuid()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(TournamentLogPrunedEntry::TournamentId).uuid().not_null())
+                    .col(ColumnDef::new(TournamentLogPrunedEntry::SequenceIdx).integer().not_null())
+                    .col(ColumnDef::new(TournamentLogPrunedEntry::SnapshotId).uuid().not_null())
+                    .col(ColumnDef::new(TournamentLogPrunedEntry::Timestamp).date_time().not_null())
+                    .col(ColumnDef::new(TournamentLogPrunedEntry::TargetType).string().not_null())
+                    .col(ColumnDef::new(TournamentLogPrunedEntry::TargetUuid).uuid().not_null())
+                    .col(ColumnDef::new(TournamentLogPrunedEntry::Snapshot).text().null())
+                    .col(ColumnDef::new(TournamentLogPrunedEntry::Author).text().null())
+                    .foreign_key(
+                        ForeignKeyCreateStatement::new()
+                            .from_tbl(TournamentLogPrunedEntry::Table)
+                            .from_col(TournamentLogPrunedEntry::TournamentId)
+                            .to_tbl(Tournament::Table)
+                            .to_col(Tournament::Uuid)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade)
+                    )
+                    .foreign_key(
+                        ForeignKeyCreateStatement::new()
+                            .from_tbl(TournamentLogPrunedEntry::Table)
+                            .from_col(TournamentLogPrunedEntry::SnapshotId)
+                            .to_tbl(TournamentLogSnapshot::Table)
+                            .to_col(TournamentLogSnapshot::Uuid)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade)
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager.create_index(
+            IndexCreateStatement::new()
+                .name("idx-tournament-log-snapshot-tournament-id")
+                .table(TournamentLogSnapshot::Table)
+                .col(TournamentLogSnapshot::TournamentId)
+                .to_owned(),
+        ).await?;
+
+        manager.create_index(
+            IndexCreateStatement::new()
+                .name("idx-tournament-log-pruned-entry-target-uuid")
+                .table(TournamentLogPrunedEntry::Table)
+                .col(TournamentLogPrunedEntry::TargetUuid)
+                .to_owned(),
+        ).await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(TableDropStatement::new().table(TournamentLogPrunedEntry::Table).to_owned())
+            .await?;
+        manager
+            .drop_table(TableDropStatement::new().table(TournamentLogSnapshot::Table).to_owned())
+            .await
+    }
+}
@@ -0,0 +1,43 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+/// Scores are stored in hundredths of a point, so that fractional scores such as half points are possible.
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        scale_scores(manager, |score| score.mul(100)).await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        scale_scores(manager, |score| score.div(100)).await
+    }
+}
+
+async fn scale_scores(manager: &SchemaManager<'_>, scale: fn(Expr) -> SimpleExpr) -> Result<(), DbErr> {
+    manager.exec_stmt(
+        Query::update()
+            .table(AdjudicatorSpeechScore::Table)
+            .value(AdjudicatorSpeechScore::ManualTotalScore, scale(Expr::col(AdjudicatorSpeechScore::ManualTotalScore)))
+            .to_owned()
+    ).await?;
+    manager.exec_stmt(
+        Query::update()
+            .table(AdjudicatorTeamScore::Table)
+            .value(AdjudicatorTeamScore::ManualTotalScore, scale(Expr::col(AdjudicatorTeamScore::ManualTotalScore)))
+            .to_owned()
+    ).await
+}
+
+#[derive(DeriveIden)]
+enum AdjudicatorSpeechScore {
+    Table,
+    ManualTotalScore
+}
+
+#[derive(DeriveIden)]
+enum AdjudicatorTeamScore {
+    Table,
+    ManualTotalScore
+}
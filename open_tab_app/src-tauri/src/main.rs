@@ -16,14 +16,14 @@ use open_tab_entities::{
     derived_models::{DrawPresentationInfo, RegistrationInfo},
     domain::{
         self,
-        ballot::{BallotParseError, SpeechRole},
-        debate_backup_ballot::DebateBackupBallot,
+        ballot::BallotParseError,
+        ballot_validation::{BallotValidationError, BallotValidationErrors},
         entity::LoadEntity,
     },
     schema::{self},
     tab::{AugmentedBreakRelevantTabView, AugmentedTabView, BreakRelevantTabView, TabView},
     utilities::BatchLoadError,
-    EntityGroup, EntityTypeId,
+    EntityGroup, EntityTypeId, LogAuthor,
 };
 use open_tab_reports::{
     make_open_office_ballots,
@@ -38,8 +38,12 @@ use open_tab_server::{
         CreateUserRequest, CreateUserRequestError, CreateUserResponse, GetTokenRequest,
         GetTokenResponse,
     },
+    notify::LogHeadEvent,
     response::APIErrorResponse,
-    sync::{reconcile_changes, FatLog, ReconciliationOutcome, SyncRequest, SyncRequestResponse},
+    sync::{
+        decode_log_body, encode_log_body, reconcile_changes, FatLogPage, MergeConflict,
+        ReconciliationOutcome, SyncRequest, SyncRequestResponse, MAX_LOG_PAGE_SIZE,
+    },
     tournament::CreateTournamentRequest,
 };
 //use open_tab_server::TournamentChanges;
@@ -57,11 +61,13 @@ use open_tab_app_backend::{
     draw::{
         evaluation::{DrawEvaluator, DrawIssue},
         preliminary::PreliminaryDrawError,
+        repair::DrawRepair,
     },
     draw_view::{DrawBallot, LoadedDrawView},
     feedback::FormTemplate,
     import::CSVReaderConfig,
     tournament_status_view::LoadedTournamentStatusView,
+    undo::{UndoEntry, UndoStack},
     Action, LoadedView, TournamentParticipantsInfo, View,
 };
 
@@ -69,7 +75,7 @@ use tauri_plugin_dialog::{DialogExt, MessageDialogBuilder, MessageDialogButtons,
 use thiserror::Error;
 use tokio::{sync::Mutex, sync::RwLock};
 
-use std::sync::Arc;
+use std::sync::{atomic::{AtomicBool, Ordering}, Arc};
 
 mod identity;
 mod settings;
@@ -271,35 +277,86 @@ struct ActionResponse {
     success: bool,
     message: Option<String>,
     error: Option<String>,
+    /// Set when the action was rejected because a ballot violates the tournament's validation rules
+    ballot_validation_errors: Option<Vec<BallotValidationError>>,
 }
 
 async fn execute_action_impl(
     action: Action,
+    author: LogAuthor,
     db: &DatabaseConnection,
     view_cache: &mut ViewCache,
+    undo_stack: &mut UndoStack,
 ) -> Result<Vec<ChangeNotification>, anyhow::Error> {
     let transaction = db.begin().await?;
-    let changes: EntityGroup = action.execute(&transaction).await?;
+    let changes: EntityGroup = action.execute(&transaction).await?.with_author(author);
+
+    let undo_entry = UndoEntry::save_changes(&transaction, &changes).await?;
+
+    touch_tournament(&transaction, changes.tournament_id).await?;
+    transaction.commit().await?;
+    undo_stack.push(undo_entry);
 
-    changes.save_all_and_log(&transaction).await?;
+    get_view_notifications(db, view_cache, &changes).await
+}
 
+async fn touch_tournament<C>(db: &C, tournament_id: Uuid) -> Result<(), anyhow::Error>
+where
+    C: sea_orm::ConnectionTrait,
+{
     let tournament = schema::tournament::ActiveModel {
-        uuid: ActiveValue::Unchanged(changes.tournament_id),
+        uuid: ActiveValue::Unchanged(tournament_id),
         last_modified: ActiveValue::Set(chrono::Utc::now().naive_utc()),
         ..Default::default()
     };
 
-    tournament.update(&transaction).await?;
-    transaction.commit().await?;
+    tournament.update(db).await?;
+    Ok(())
+}
+
+async fn get_view_notifications(
+    db: &DatabaseConnection,
+    view_cache: &mut ViewCache,
+    changes: &EntityGroup,
+) -> Result<Vec<ChangeNotification>, anyhow::Error> {
     let transaction = db.begin().await?;
 
     let notifications = view_cache
-        .update_and_get_changes(&transaction, &changes)
+        .update_and_get_changes(&transaction, changes)
         .await?;
     transaction.commit().await?;
     Ok(notifications)
 }
 
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+enum HistoryDirection {
+    Undo,
+    Redo,
+}
+
+async fn step_history_impl(
+    tournament_id: Uuid,
+    direction: HistoryDirection,
+    db: &DatabaseConnection,
+    view_cache: &mut ViewCache,
+    undo_stack: &mut UndoStack,
+) -> Result<Vec<ChangeNotification>, anyhow::Error> {
+    let transaction = db.begin().await?;
+    let changes = match direction {
+        HistoryDirection::Undo => undo_stack.undo(&transaction, tournament_id).await?,
+        HistoryDirection::Redo => undo_stack.redo(&transaction, tournament_id).await?,
+    };
+
+    touch_tournament(&transaction, tournament_id).await?;
+    transaction.commit().await?;
+    match direction {
+        HistoryDirection::Undo => undo_stack.confirm_undo(tournament_id),
+        HistoryDirection::Redo => undo_stack.confirm_redo(tournament_id),
+    }
+
+    get_view_notifications(db, view_cache, &changes).await
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 struct ChangeNotificationSet {
     changes: Vec<ChangeNotification>,
@@ -329,17 +386,12 @@ where
     }
 }
 
-#[tauri::command]
-async fn execute_action(
-    app: AppHandle,
-    action: Action,
-    db: State<'_, DatabaseConnection>,
-    view_cache: State<'_, Mutex<ViewCache>>,
-) -> Result<ActionResponse, ()> {
-    let mut view_cache = view_cache.lock().await;
-    let result = execute_action_impl(action, db.inner(), &mut *view_cache).await;
-
-    Ok(match result {
+async fn notify_and_get_action_response(
+    app: &AppHandle,
+    db: &DatabaseConnection,
+    result: Result<Vec<ChangeNotification>, anyhow::Error>,
+) -> ActionResponse {
+    match result {
         Ok(notifications) => {
             // TODO: Handle this more gracefully
             app.emit(
@@ -353,13 +405,73 @@ async fn execute_action(
                 success: true,
                 message: None,
                 error: None,
+                ballot_validation_errors: None,
             }
         }
         Err(err) => ActionResponse {
             success: false,
-            error: Some(error_to_end_user_message(db.inner(), &err).await),
+            error: Some(error_to_end_user_message(db, &err).await),
             message: None,
+            ballot_validation_errors: err.downcast_ref::<BallotValidationErrors>().map(|e| e.errors.clone()),
         },
+    }
+}
+
+#[tauri::command]
+async fn execute_action(
+    app: AppHandle,
+    action: Action,
+    db: State<'_, DatabaseConnection>,
+    view_cache: State<'_, Mutex<ViewCache>>,
+    undo_stack: State<'_, Mutex<UndoStack>>,
+    settings: State<'_, RwLock<AppSettings>>,
+) -> Result<ActionResponse, ()> {
+    let author = settings.read().await.identity.as_log_author();
+    let mut view_cache = view_cache.lock().await;
+    let mut undo_stack = undo_stack.lock().await;
+    let result = execute_action_impl(action, author, db.inner(), &mut *view_cache, &mut *undo_stack).await;
+
+    Ok(notify_and_get_action_response(&app, db.inner(), result).await)
+}
+
+#[tauri::command]
+async fn step_action_history(
+    app: AppHandle,
+    tournament_id: Uuid,
+    direction: HistoryDirection,
+    db: State<'_, DatabaseConnection>,
+    view_cache: State<'_, Mutex<ViewCache>>,
+    undo_stack: State<'_, Mutex<UndoStack>>,
+) -> Result<ActionResponse, ()> {
+    let mut view_cache = view_cache.lock().await;
+    let mut undo_stack = undo_stack.lock().await;
+    let result = step_history_impl(
+        tournament_id,
+        direction,
+        db.inner(),
+        &mut *view_cache,
+        &mut *undo_stack,
+    )
+    .await;
+
+    Ok(notify_and_get_action_response(&app, db.inner(), result).await)
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct ActionHistoryState {
+    can_undo: bool,
+    can_redo: bool,
+}
+
+#[tauri::command]
+async fn get_action_history_state(
+    tournament_id: Uuid,
+    undo_stack: State<'_, Mutex<UndoStack>>,
+) -> Result<ActionHistoryState, ()> {
+    let undo_stack = undo_stack.lock().await;
+    Ok(ActionHistoryState {
+        can_undo: undo_stack.can_undo(tournament_id),
+        can_redo: undo_stack.can_redo(tournament_id),
     })
 }
 
@@ -477,102 +589,11 @@ async fn auto_accept_ballots<C>(
 where
     C: sea_orm::ConnectionTrait,
 {
-    let groups = changes.as_group_map();
-    if groups.debate_backup_ballots.is_empty() {
-        return Ok(None);
-    }
-    println!(
-        "Accepting {} new ballots",
-        groups.debate_backup_ballots.len()
-    );
-    let debates_by_id = TournamentDebate::get_many(
-        db,
-        groups
-            .debate_backup_ballots
-            .iter()
-            .map(|b| b.debate_id)
-            .collect_vec(),
-    )
-    .await?
-    .into_iter()
-    .map(|debate| (debate.uuid, debate))
-    .collect::<HashMap<_, _>>();
-    println!("Loaded {} debates", debates_by_id.len());
-    let current_debate_ballots_by_id = Ballot::get_many(
-        db,
-        debates_by_id.values().map(|d| d.ballot_id).collect_vec(),
-    )
-    .await?
-    .into_iter()
-    .map(|ballot| (ballot.uuid, ballot))
-    .collect::<HashMap<_, _>>();
-    println!(
-        "Loaded {} debate ballots",
-        current_debate_ballots_by_id.len()
-    );
-
-    let new_ballots_by_id = Ballot::get_many(
-        db,
-        groups
-            .debate_backup_ballots
-            .iter()
-            .map(|b| b.ballot_id)
-            .collect_vec(),
-    )
-    .await?
-    .into_iter()
-    .map(|ballot| (ballot.uuid, ballot))
-    .collect::<HashMap<_, _>>();
-    println!(
-        "Loaded {} backup ballots",
-        current_debate_ballots_by_id.len()
-    );
-
-    let mut new_changes = EntityGroup::new(changes.tournament_id);
-    for new_backup_ballot in groups.debate_backup_ballots.iter() {
-        let old_debate = debates_by_id.get(&new_backup_ballot.debate_id).unwrap();
-        let old_ballot = current_debate_ballots_by_id
-            .get(&old_debate.ballot_id)
-            .unwrap();
-        let new_ballot = new_ballots_by_id.get(&new_backup_ballot.ballot_id).unwrap();
-
-        if !old_ballot.is_scored()
-            && old_ballot.government.team == new_ballot.government.team
-            && old_ballot.opposition.team == new_ballot.opposition.team
-            && old_ballot
-                .speeches
-                .iter()
-                .filter_map(|s| match s.role {
-                    SpeechRole::NonAligned => Some(s.speaker),
-                    _ => None,
-                })
-                .collect_vec()
-                == new_ballot
-                    .speeches
-                    .iter()
-                    .filter_map(|s| match s.role {
-                        SpeechRole::NonAligned => Some(s.speaker),
-                        _ => None,
-                    })
-                    .collect_vec()
-        {
-            new_changes.add(Entity::TournamentDebate(TournamentDebate {
-                ballot_id: new_ballot.uuid,
-                is_complete: true,
-                ..old_debate.clone()
-            }));
-            new_changes.delete(EntityTypeId::Ballot, old_ballot.uuid);
-
-            new_changes.add(Entity::DebateBackupBallot(DebateBackupBallot {
-                was_seen: true,
-                ..*new_backup_ballot
-            }))
-        }
-    }
-
-    Ok(Some(new_changes))
+    Ok(open_tab_app_backend::ballot_confirmation::confirm_submitted_ballots(changes, db).await?)
 }
 
+/// Pulls and integrates the next page of remote changes.
+/// Returns whether the remote has more changes waiting.
 async fn pull_remote_changes<C>(
     target_tournament_remote: &schema::tournament_remote::Model,
     client: &Client,
@@ -580,7 +601,7 @@ async fn pull_remote_changes<C>(
     api_key: &String,
     view_cache: &Mutex<ViewCache>,
     app_handle: &AppHandle,
-) -> Result<Option<EntityGroup>, SyncError>
+) -> Result<bool, SyncError>
 where
     C: sea_orm::ConnectionTrait + TransactionTrait,
 {
@@ -593,7 +614,12 @@ where
         remote_url = format!("{}?since={}", remote_url, last_common_ancestor);
     }
 
-    let response = client.get(remote_url).bearer_auth(api_key).send().await?;
+    let response = client
+        .get(remote_url)
+        .bearer_auth(api_key)
+        .header(reqwest::header::ACCEPT_ENCODING, "gzip")
+        .send()
+        .await?;
 
     if response.status() == 403 || response.status() == 401 {
         return Err(SyncError::NotAuthorized);
@@ -613,7 +639,14 @@ where
             error_response.message
         )));
     }
-    let remote_changes: FatLog<Entity, EntityTypeId> = response.json().await?;
+    let is_compressed = response
+        .headers()
+        .get(reqwest::header::CONTENT_ENCODING)
+        .is_some_and(|encoding| encoding == "gzip");
+    let body = response.bytes().await?;
+    let remote_page: FatLogPage<Entity, EntityTypeId> = decode_log_body(&body, is_compressed)?;
+    let has_more = remote_page.has_more;
+    let remote_changes = remote_page.log;
 
     if remote_changes.log.len() > 0 {
         dbg!("Integrating remote changes", remote_changes.log.len());
@@ -624,65 +657,22 @@ where
             target_tournament_remote.tournament_id,
             remote_changes,
             target_tournament_remote.last_synced_change,
-            open_tab_server::sync::MergeStrategy::AlwaysLocal,
+            open_tab_server::sync::MergeStrategy::ThreeWay,
             true,
         )
         .await?;
 
-        match outcome {
+        // Conflicting fields kept their local value, the user is notified to resolve them
+        let (new_last_common_ancestor, entity_group, conflicts) = match outcome {
             ReconciliationOutcome::Success {
                 new_last_common_ancestor,
                 entity_group,
-            } => {
-                let update = schema::tournament_remote::ActiveModel {
-                    uuid: ActiveValue::Unchanged(target_tournament_remote.uuid),
-                    last_synced_change: ActiveValue::Set(Some(new_last_common_ancestor)),
-                    ..Default::default()
-                };
-                update.update(&transaction).await?;
-                transaction.commit().await?;
-
-                if let Some(entity_group) = entity_group {
-                    let transaction = db.begin().await?;
-                    let mut view_cache = view_cache.lock().await;
-                    let notifications = view_cache
-                        .update_and_get_changes(&transaction, &entity_group)
-                        .await?;
-                    app_handle
-                        .emit(
-                            "views-changed",
-                            ChangeNotificationSet {
-                                changes: notifications,
-                            },
-                        )
-                        .expect("Event send failed");
-                    transaction.rollback().await?;
-
-                    let transaction = db.begin().await?;
-                    let new_changes = auto_accept_ballots(&entity_group, &transaction).await?;
-                    if let Some(new_changes) = new_changes {
-                        new_changes.save_all_and_log(&transaction).await?;
-                        transaction.commit().await?;
-                        let transaction = db.begin().await?;
-                        let notifications = view_cache
-                            .update_and_get_changes(&transaction, &new_changes)
-                            .await?;
-                        transaction.rollback().await?;
-                        app_handle
-                            .emit(
-                                "views-changed",
-                                ChangeNotificationSet {
-                                    changes: notifications,
-                                },
-                            )
-                            .expect("Event send failed");
-                    } else {
-                        transaction.rollback().await?;
-                    }
-                }
-
-                return Ok(None);
-            }
+            } => (new_last_common_ancestor, entity_group, vec![]),
+            ReconciliationOutcome::Conflict {
+                new_last_common_ancestor,
+                entity_group,
+                conflicts,
+            } => (new_last_common_ancestor, entity_group, conflicts),
             ReconciliationOutcome::Reject => {
                 transaction.rollback().await?;
                 return Err(SyncError::Other("Reconciliation failed".to_string()));
@@ -693,12 +683,75 @@ where
                     "Reconciliation failed: Invalid tournament".to_string(),
                 ));
             }
+        };
+
+        let update = schema::tournament_remote::ActiveModel {
+            uuid: ActiveValue::Unchanged(target_tournament_remote.uuid),
+            last_synced_change: ActiveValue::Set(Some(new_last_common_ancestor)),
+            ..Default::default()
+        };
+        update.update(&transaction).await?;
+        transaction.commit().await?;
+
+        if !conflicts.is_empty() {
+            app_handle
+                .emit(
+                    "sync-conflicts",
+                    SyncConflictsMessage {
+                        tournament_id: target_tournament_remote.tournament_id,
+                        conflicts,
+                    },
+                )
+                .expect("Event send failed");
+        }
+
+        if let Some(entity_group) = entity_group {
+            let transaction = db.begin().await?;
+            let mut view_cache = view_cache.lock().await;
+            let notifications = view_cache
+                .update_and_get_changes(&transaction, &entity_group)
+                .await?;
+            app_handle
+                .emit(
+                    "views-changed",
+                    ChangeNotificationSet {
+                        changes: notifications,
+                    },
+                )
+                .expect("Event send failed");
+            transaction.rollback().await?;
+
+            let transaction = db.begin().await?;
+            let new_changes = auto_accept_ballots(&entity_group, &transaction).await?;
+            if let Some(new_changes) = new_changes {
+                let author = app_handle.state::<RwLock<AppSettings>>().read().await.identity.as_log_author();
+                let new_changes = new_changes.with_author(author);
+                new_changes.save_all_and_log(&transaction).await?;
+                transaction.commit().await?;
+                let transaction = db.begin().await?;
+                let notifications = view_cache
+                    .update_and_get_changes(&transaction, &new_changes)
+                    .await?;
+                transaction.rollback().await?;
+                app_handle
+                    .emit(
+                        "views-changed",
+                        ChangeNotificationSet {
+                            changes: notifications,
+                        },
+                    )
+                    .expect("Event send failed");
+            } else {
+                transaction.rollback().await?;
+            }
         }
     }
 
-    Ok(None)
+    Ok(has_more)
 }
 
+/// Pushes local changes in pages of bounded size. Each accepted page is
+/// recorded as synced, so an interrupted push resumes after the last one.
 async fn try_push_changes<C>(
     target_tournament_remote: &schema::tournament_remote::Model,
     client: &Client,
@@ -713,66 +766,85 @@ where
         target_tournament_remote.url, target_tournament_remote.tournament_id
     );
 
-    let transaction = db.begin().await?;
+    let mut last_synced_change = target_tournament_remote.last_synced_change;
 
-    let change_log = open_tab_server::sync::get_entity_changes_since(
-        &transaction,
-        target_tournament_remote.tournament_id,
-        target_tournament_remote.last_synced_change,
-    )
-    .await?;
+    loop {
+        let transaction = db.begin().await?;
 
-    transaction.rollback().await?;
+        let change_page = open_tab_server::sync::get_entity_changes_page_since(
+            &transaction,
+            target_tournament_remote.tournament_id,
+            last_synced_change,
+            Some(MAX_LOG_PAGE_SIZE),
+        )
+        .await?;
 
-    if change_log.log.len() == 0 {
-        return Ok(());
-    }
-    dbg!("Pushing changes", change_log.log.len());
+        transaction.rollback().await?;
 
-    let response = client
-        .post(remote_url)
-        .bearer_auth(api_key)
-        .json(&SyncRequest {
-            log: change_log,
-            last_common_ancestor: target_tournament_remote.last_synced_change,
-        })
-        .send()
-        .await?;
+        if change_page.log.log.len() == 0 {
+            return Ok(());
+        }
+        dbg!("Pushing changes", change_page.log.log.len());
 
-    if response.status() == 200 {
-        let response = response.json::<SyncRequestResponse>().await?;
-        match response.outcome {
-            open_tab_server::sync::APIReconciliationOutcome::Success {
-                new_last_common_ancestor,
-            } => {
-                let transaction = db.begin().await?;
+        let body = encode_log_body(
+            &SyncRequest {
+                log: change_page.log,
+                last_common_ancestor: last_synced_change,
+            },
+            true,
+        )?;
+
+        let response = client
+            .post(&remote_url)
+            .bearer_auth(api_key)
+            .header(reqwest::header::CONTENT_TYPE, "application/json")
+            .header(reqwest::header::CONTENT_ENCODING, "gzip")
+            .body(body)
+            .send()
+            .await?;
+
+        if response.status() == 200 {
+            let response = response.json::<SyncRequestResponse>().await?;
+            match response.outcome {
+                open_tab_server::sync::APIReconciliationOutcome::Success {
+                    new_last_common_ancestor,
+                }
+                | open_tab_server::sync::APIReconciliationOutcome::Conflict {
+                    new_last_common_ancestor,
+                    ..
+                } => {
+                    let transaction = db.begin().await?;
 
-                let update = schema::tournament_remote::ActiveModel {
-                    uuid: ActiveValue::Unchanged(target_tournament_remote.uuid),
-                    last_synced_change: ActiveValue::Set(Some(new_last_common_ancestor)),
-                    ..Default::default()
-                };
-                update.update(&transaction).await?;
+                    let update = schema::tournament_remote::ActiveModel {
+                        uuid: ActiveValue::Unchanged(target_tournament_remote.uuid),
+                        last_synced_change: ActiveValue::Set(Some(new_last_common_ancestor)),
+                        ..Default::default()
+                    };
+                    update.update(&transaction).await?;
 
-                transaction.commit().await?;
-            }
-            open_tab_server::sync::APIReconciliationOutcome::Reject
-            | open_tab_server::sync::APIReconciliationOutcome::InvalidTournament => {
-                return Err(SyncError::SyncRejection);
-            }
-        };
-    } else if response.status() == 403 || response.status() == 401 {
-        return Err(SyncError::NotAuthorized);
-    } else if response.status() == 404 {
-        return Err(SyncError::TournamentDoesNotExist);
-    } else {
-        return Err(SyncError::Other(format!(
-            "Unexpected response status: {}",
-            response.status()
-        )));
-    }
+                    transaction.commit().await?;
+                    last_synced_change = Some(new_last_common_ancestor);
+                }
+                open_tab_server::sync::APIReconciliationOutcome::Reject
+                | open_tab_server::sync::APIReconciliationOutcome::InvalidTournament => {
+                    return Err(SyncError::SyncRejection);
+                }
+            };
+        } else if response.status() == 403 || response.status() == 401 {
+            return Err(SyncError::NotAuthorized);
+        } else if response.status() == 404 {
+            return Err(SyncError::TournamentDoesNotExist);
+        } else {
+            return Err(SyncError::Other(format!(
+                "Unexpected response status: {}",
+                response.status()
+            )));
+        }
 
-    Ok(())
+        if !change_page.has_more {
+            return Ok(());
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -813,7 +885,7 @@ async fn evaluate_ballots(
         .map(|r| r.uuid)
         .collect_vec();
 
-    let evaluator = DrawEvaluator::new(Default::default(), relevant_rounds, context);
+    let evaluator = DrawEvaluator::new(draw_view.evaluator_config.clone(), relevant_rounds, context);
 
     let eval_results = ballots.iter().map(|b| evaluator.find_issues_in_ballot(b));
 
@@ -939,6 +1011,106 @@ async fn guess_csv_config(path: String) -> Result<CSVReaderConfig, ()> {
     result.map_err(|_| ())
 }
 
+#[tauri::command]
+async fn get_draw_repair_proposal(
+    db: State<'_, DatabaseConnection>,
+    round_id: Uuid,
+    withdrawn_participants: Vec<Uuid>,
+    added_participants: Vec<Uuid>,
+) -> Result<DrawRepair, ()> {
+    open_tab_app_backend::frontend_queries::query_draw_repair_proposal(
+        db.inner(),
+        round_id,
+        withdrawn_participants,
+        added_participants,
+    )
+    .await
+    .map_err(|_| ())
+}
+
+/// Tracks the log head announced by a remote, so the sync loop can pull as
+/// soon as the remote changes and skip pulls while nothing happens there.
+#[derive(Default)]
+struct RemoteLogWatch {
+    is_connected: AtomicBool,
+    log_head: std::sync::Mutex<Option<Uuid>>,
+    changed: tokio::sync::Notify,
+}
+
+impl RemoteLogWatch {
+    fn is_up_to_date(&self, last_synced_change: Option<Uuid>) -> bool {
+        self.is_connected.load(Ordering::SeqCst)
+            && last_synced_change.is_some()
+            && *self.log_head.lock().unwrap() == last_synced_change
+    }
+}
+
+/// How long the event stream may stay silent before we assume the
+/// connection is dead. The server sends keep-alives more often than this.
+const LOG_EVENT_TIMEOUT: Duration = Duration::from_secs(45);
+
+async fn watch_remote_log(
+    client: Client,
+    remote_url: String,
+    tournament_id: Uuid,
+    api_key: String,
+    watch: Arc<RemoteLogWatch>,
+) -> Result<(), SyncError> {
+    let mut response = client
+        .get(format!("{}/api/tournament/{}/log/events", remote_url, tournament_id))
+        .bearer_auth(api_key)
+        .send()
+        .await?;
+
+    if response.status() == 403 || response.status() == 401 {
+        return Err(SyncError::NotAuthorized);
+    }
+    if !response.status().is_success() {
+        return Err(SyncError::Other(format!(
+            "Could not subscribe to log events: {}",
+            response.status()
+        )));
+    }
+
+    watch.is_connected.store(true, Ordering::SeqCst);
+
+    let mut pending = String::new();
+    let result = loop {
+        let chunk = match tokio::time::timeout(LOG_EVENT_TIMEOUT, response.chunk()).await {
+            Ok(Ok(Some(chunk))) => chunk,
+            Ok(Ok(None)) => break Ok(()),
+            Ok(Err(e)) => break Err(SyncError::from(e)),
+            Err(_) => break Err(SyncError::Other("Log event stream timed out".into())),
+        };
+        pending.push_str(&String::from_utf8_lossy(&chunk));
+
+        while let Some(end) = pending.find("\n\n") {
+            let frame: String = pending.drain(..end + 2).collect();
+            let is_log_head = frame
+                .lines()
+                .any(|line| line.strip_prefix("event:").map(|e| e.trim()) == Some("log_head"));
+            let data = frame
+                .lines()
+                .filter_map(|line| line.strip_prefix("data:"))
+                .map(|d| d.trim())
+                .collect::<String>();
+            if !is_log_head {
+                continue;
+            }
+            if let Ok(event) = serde_json::from_str::<LogHeadEvent>(&data) {
+                *watch.log_head.lock().unwrap() = event.log_head;
+                watch.changed.notify_one();
+            }
+        }
+    };
+
+    // Without the connection we need to poll again
+    watch.is_connected.store(false, Ordering::SeqCst);
+    watch.changed.notify_one();
+
+    result
+}
+
 struct OpenTournamentManager {
     tournament_processes: HashMap<Uuid, ProcessInfo>,
     update_msg_sender: tokio::sync::mpsc::Sender<ConnectivityStatusMessage>,
@@ -1017,6 +1189,7 @@ impl OpenTournamentManager {
                 sync_frequency: chrono::Duration::seconds(5),
                 update_msg_sender: info.update_msg_sender.clone(),
                 identity_provider,
+                log_watch: Arc::new(RemoteLogWatch::default()),
             };
 
             let join_handle = tauri::async_runtime::spawn(process.run());
@@ -1055,6 +1228,13 @@ struct TournamentUpdateProcess {
     sync_frequency: chrono::Duration,
     update_msg_sender: tokio::sync::mpsc::Sender<ConnectivityStatusMessage>,
     identity_provider: Arc<IdentityProvider>,
+    log_watch: Arc<RemoteLogWatch>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct SyncConflictsMessage {
+    tournament_id: Uuid,
+    conflicts: Vec<MergeConflict>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -1089,13 +1269,18 @@ enum TournamentUpdateError {
 impl TournamentUpdateProcess {
     async fn run(self) -> Result<(), TournamentUpdateError> {
         let mut last_sync = None;
+        let mut log_watch_task: Option<(String, tauri::async_runtime::JoinHandle<()>)> = None;
 
         loop {
             println!("Start sync attempt");
             if let Some(last_sync) = last_sync {
                 let time_passed = chrono::Utc::now().naive_utc() - last_sync;
                 if time_passed < self.sync_frequency {
-                    tokio::time::sleep((self.sync_frequency - time_passed).to_std().unwrap()).await;
+                    // Announcements from the remote end the wait early
+                    tokio::select! {
+                        _ = tokio::time::sleep((self.sync_frequency - time_passed).to_std().unwrap()) => {},
+                        _ = self.log_watch.changed.notified() => {},
+                    }
                 }
             }
 
@@ -1123,6 +1308,9 @@ impl TournamentUpdateProcess {
 
                 transaction.rollback().await?;
 
+                if let Some((_, handle)) = log_watch_task.take() {
+                    handle.abort();
+                }
                 break Err(TournamentUpdateError::NoRemote);
             }
             let target_tournament_remote = target_tournament_remote.unwrap();
@@ -1201,15 +1389,44 @@ impl TournamentUpdateProcess {
                 transaction.commit().await?;
             }
 
-            let remote = pull_remote_changes(
-                &target_tournament_remote,
-                &self.client,
-                db,
-                &api_key,
-                self.app_handle.state::<Mutex<ViewCache>>().inner(),
-                &self.app_handle,
-            )
-            .await;
+            let needs_new_log_watch = match &log_watch_task {
+                Some((url, handle)) => url != &target_tournament_remote.url || handle.inner().is_finished(),
+                None => true,
+            };
+            if needs_new_log_watch {
+                if let Some((_, handle)) = log_watch_task.take() {
+                    handle.abort();
+                }
+                let watch = watch_remote_log(
+                    self.client.clone(),
+                    target_tournament_remote.url.clone(),
+                    self.tournament_id,
+                    api_key.clone(),
+                    self.log_watch.clone(),
+                );
+                let handle = tauri::async_runtime::spawn(async move {
+                    if let Err(err) = watch.await {
+                        println!("Log event stream closed: {}", err);
+                    }
+                });
+                log_watch_task = Some((target_tournament_remote.url.clone(), handle));
+            }
+
+            // While we are connected to the event stream, we only need to pull
+            // if the remote announced changes we don't have yet.
+            let remote = if self.log_watch.is_up_to_date(target_tournament_remote.last_synced_change) {
+                Ok(false)
+            } else {
+                pull_remote_changes(
+                    &target_tournament_remote,
+                    &self.client,
+                    db,
+                    &api_key,
+                    self.app_handle.state::<Mutex<ViewCache>>().inner(),
+                    &self.app_handle,
+                )
+                .await
+            };
             if !remote.is_ok() {
                 let err = remote.err().unwrap();
                 match &err {
@@ -1264,6 +1481,12 @@ impl TournamentUpdateProcess {
                     })?;
                 continue;
             }
+            if remote.unwrap() {
+                // Fetch the remaining pages first, our changes can only be
+                // pushed on top of the complete remote log.
+                last_sync = None;
+                continue;
+            }
             let transaction = db.begin().await.unwrap();
             let target_tournament_remote = schema::tournament_remote::Entity::find()
                 .filter(schema::tournament_remote::Column::TournamentId.eq(self.tournament_id))
@@ -1831,7 +2054,8 @@ async fn create_tournament(
     };
     let (all_nodes, all_edges) = config.get_tournament_graph(tournament.uuid);
     tournament.name = config.name;
-    let mut changes = EntityGroup::new(tournament.uuid);
+    let author = app.state::<RwLock<AppSettings>>().read().await.identity.as_log_author();
+    let mut changes = EntityGroup::new(tournament.uuid).with_author(author);
 
     if config.use_default_feedback_system {
         let template_path = 
@@ -1928,7 +2152,10 @@ fn main() {
             subscribe_to_view,
             unsubscribe_from_view,
             execute_action,
+            step_action_history,
+            get_action_history_state,
             guess_csv_config,
+            get_draw_repair_proposal,
             evaluate_ballots,
             get_tournament_list,
             open_tournament,
@@ -1947,6 +2174,7 @@ fn main() {
         ])
         .manage(db)
         .manage(Mutex::new(ViewCache::new()))
+        .manage(Mutex::new(UndoStack::new()))
         .manage(std::sync::Mutex::new(DownloadProgress::new()))
         .manage(open_tournaments_manager)
         .manage(RwLock::new(settings))
@@ -1,5 +1,7 @@
 use std::{collections::HashMap, fs::File, path::PathBuf};
 
+use open_tab_entities::LogAuthor;
+use sea_orm::prelude::Uuid;
 use serde::{Deserialize, Serialize};
 use tauri::{App, AppHandle, Emitter, Manager, State};
 use tokio::sync::RwLock;
@@ -8,6 +10,36 @@ use tokio::sync::RwLock;
 pub struct AppSettings {
     pub known_remotes: Vec<RemoteSettings>,
     pub known_api_keys: HashMap<String, String>,
+    #[serde(default)]
+    pub identity: AppIdentity,
+}
+
+/// Identifies this installation as the author of changes made in it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AppIdentity {
+    pub app_id: Uuid,
+    pub name: String,
+}
+
+impl Default for AppIdentity {
+    fn default() -> Self {
+        Self {
+            app_id: Uuid::new_v4(),
+            name: std::env::var("USER")
+                .or_else(|_| std::env::var("USERNAME"))
+                .unwrap_or("Open Tab".to_string()),
+        }
+    }
+}
+
+impl AppIdentity {
+    pub fn as_log_author(&self) -> LogAuthor {
+        LogAuthor::App {
+            app_id: self.app_id,
+            name: self.name.clone(),
+            user_id: None,
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -61,6 +93,7 @@ impl Default for AppSettings {
                 },
             ],
             known_api_keys: HashMap::new(),
+            identity: AppIdentity::default(),
         }
     }
 }
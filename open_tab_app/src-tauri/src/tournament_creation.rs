@@ -24,7 +24,7 @@ impl TournamentCreationConfig {
 
         let prelim_node = TournamentPlanNode::new(
             tournament_id,
-            PlanNodeType::Round { config: open_tab_entities::domain::tournament_plan_node::RoundGroupConfig::Preliminaries { num_roundtrips: num_prelim_roundtrips }, rounds: vec![] }
+            PlanNodeType::Round { config: open_tab_entities::domain::tournament_plan_node::RoundGroupConfig::Preliminaries { num_roundtrips: num_prelim_roundtrips, draw_mode: Default::default() }, rounds: vec![] }
         );
 
         let prelim_node_uuid = prelim_node.uuid;
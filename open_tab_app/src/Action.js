@@ -9,7 +9,7 @@ export const ErrorHandlingContext = createContext({ handleError: (error) => { }
  * 
  * @param {string} type 
  * @param {*} params 
- * @param {Function} handleError
+ * @param {Function} handleError Called with the error message and, if a ballot was rejected, the list of validation errors
  * @returns 
  */
 export async function executeAction(type, params, handleError = null) {
@@ -25,6 +25,32 @@ export async function executeAction(type, params, handleError = null) {
     }
     else {
         console.error("Error when executing action", type, result.error);
+        if (handleError !== null) {
+            handleError(result.error, result.ballot_validation_errors);
+        }
+        return false;
+    }
+}
+
+/**
+ * Undoes or redoes the last action in the tournament
+ * 
+ * @param {string} tournamentId 
+ * @param {"Undo" | "Redo"} direction 
+ * @param {Function} handleError Called with the error message if the step is not possible
+ * @returns 
+ */
+export async function stepActionHistory(tournamentId, direction, handleError = null) {
+    let result = await invoke("step_action_history", {
+        tournamentId: tournamentId,
+        direction: direction
+    });
+
+    if (result.success == true) {
+        return true;
+    }
+    else {
+        console.error("Error when stepping action history", direction, result.error);
         if (handleError !== null) {
             handleError(result.error);
         }
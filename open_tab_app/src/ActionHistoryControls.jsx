@@ -0,0 +1,82 @@
+import React, { useCallback, useContext, useEffect, useState } from 'react';
+import { invoke } from '@tauri-apps/api/core';
+import { listen } from '@tauri-apps/api/event';
+import { TournamentContext } from './TournamentContext';
+import { ErrorHandlingContext, stepActionHistory } from './Action';
+
+
+function isTextInput(element) {
+    if (!element) {
+        return false;
+    }
+    let tagName = element.tagName;
+    return tagName == "INPUT" || tagName == "TEXTAREA" || tagName == "SELECT" || element.isContentEditable;
+}
+
+/**
+ * Undo and redo buttons for the actions in the current tournament.
+ * Also handles Ctrl/Cmd+Z, Ctrl/Cmd+Shift+Z and Ctrl+Y, unless a text field is focused.
+ */
+function ActionHistoryControls() {
+    let tournamentId = useContext(TournamentContext).uuid;
+    let errorContext = useContext(ErrorHandlingContext);
+    let [historyState, setHistoryState] = useState({can_undo: false, can_redo: false});
+
+    let updateHistoryState = useCallback(() => {
+        invoke("get_action_history_state", {tournamentId: tournamentId}).then((state) => {
+            setHistoryState(state);
+        });
+    }, [tournamentId]);
+
+    let step = useCallback((direction) => {
+        stepActionHistory(tournamentId, direction, errorContext.handleError).then(() => {
+            updateHistoryState();
+        });
+    }, [tournamentId, errorContext, updateHistoryState]);
+
+    useEffect(() => {
+        updateHistoryState();
+
+        // Actions and synced changes both change what can be undone
+        const unlisten = listen('views-changed', () => {
+            updateHistoryState();
+        });
+
+        return () => {
+            unlisten.then(unlisten => unlisten())
+        }
+    }, [updateHistoryState]);
+
+    useEffect(() => {
+        let onKeyDown = (event) => {
+            if (!(event.ctrlKey || event.metaKey) || isTextInput(document.activeElement)) {
+                return;
+            }
+            let key = event.key.toLowerCase();
+            if (key == "z" && !event.shiftKey) {
+                event.preventDefault();
+                step("Undo");
+            }
+            else if ((key == "z" && event.shiftKey) || (key == "y" && event.ctrlKey)) {
+                event.preventDefault();
+                step("Redo");
+            }
+        };
+
+        window.addEventListener("keydown", onKeyDown);
+        return () => {
+            window.removeEventListener("keydown", onKeyDown);
+        }
+    }, [step]);
+
+    return <div className="flex ml-3 mt-1 mb-1 space-x-2 text-sm">
+        <button className="disabled:text-gray-400" disabled={!historyState.can_undo} onClick={() => step("Undo")} title="Undo (Ctrl+Z)">
+            Undo
+        </button>
+        <button className="disabled:text-gray-400" disabled={!historyState.can_redo} onClick={() => step("Redo")} title="Redo (Ctrl+Shift+Z)">
+            Redo
+        </button>
+    </div>
+}
+
+export default ActionHistoryControls;
@@ -5,6 +5,8 @@ import { invoke } from "@tauri-apps/api/core";
 import { emit, listen } from '@tauri-apps/api/event'
 
 import ConnectivityStatus from "./ConnectivityStatus";
+import SyncConflicts from "./SyncConflicts";
+import ActionHistoryControls from "./ActionHistoryControls";
 
 import "./App.css";
 
@@ -53,6 +55,7 @@ function SideNav(props) {
 
   // Final item is a buffer so we never have the last item blocked by the connectivity status
   return <nav className="bg-gray-100 w-60 h-full overflow-y-scroll">
+    <ActionHistoryControls />
     <NavItem href="/">
       Assistant
     </NavItem>
@@ -119,6 +122,7 @@ function WindowFrame(props) {
     <div className="absolute bottom-0 left-0">
         <ConnectivityStatus state="ok" lastUpdate="2 minutes" message="Connection is stable." />
     </div>
+    <SyncConflicts />
 
     <Main>
       <Outlet />
@@ -92,11 +92,68 @@ function WaitForBreakStep({ node_uuid }) {
 
 
 
+function formatBallotField(field, names) {
+    let role = { "government": "Government", "opposition": "Opposition", "non_aligned": "Non-Aligned" }[field.role];
+    switch (field.type) {
+        case "Team":
+            return `${role} team`;
+        case "Speaker":
+            return `${role} speaker ${field.position + 1}`;
+        case "SpeechScore":
+            return `${role} speech ${field.position + 1} (${names[field.adjudicator_id] || "Unknown"})`;
+        case "TeamScore":
+            return `${role} team score (${names[field.adjudicator_id] || "Unknown"})`;
+        default:
+            return field.type;
+    }
+}
+
+function formatBallotFieldValue(value, names) {
+    if (value === null) {
+        return "-";
+    }
+    switch (value.type) {
+        case "Score":
+            return value.score;
+        case "Ids":
+            return value.uuids.map((uuid) => names[uuid] || "Unknown").join(", ");
+        default:
+            return value.uuid ? (names[value.uuid] || "Unknown") : "-";
+    }
+}
+
+function PendingBallotDifferences({ pendingDebate, names }) {
+    let columns = [
+        ...(pendingDebate.current_ballot_id ? [{ id: pendingDebate.current_ballot_id, name: "Current" }] : []),
+        ...pendingDebate.submissions.map((submission) => ({ id: submission.backup_ballot_id, name: submission.author_name || "Unknown" }))
+    ];
+
+    return <div className='pt-2'>
+        <h2 className='font-bold'>Debate {pendingDebate.debate_index + 1}</h2>
+        {pendingDebate.differences.length == 0 ? <p className='text-sm'>All submissions agree.</p> :
+            <table className='text-sm'>
+                <thead>
+                    <tr>
+                        <th></th>
+                        {columns.map((column) => <th key={column.id} className='pl-2'>{column.name}</th>)}
+                    </tr>
+                </thead>
+                <tbody>
+                    {pendingDebate.differences.map((difference, idx) => <tr key={idx}>
+                        <td>{formatBallotField(difference.field, names)}</td>
+                        {columns.map((column) => <td key={column.id} className='pl-2 text-center bg-yellow-100'>{formatBallotFieldValue(difference.values[column.id] ?? null, names)}</td>)}
+                    </tr>)}
+                </tbody>
+            </table>
+        }
+    </div>
+}
+
 function WaitForResultsStep({ round_uuid, num_submitted, num_expected }) {
     let isDone = num_submitted >= num_expected;
     let errorContext = useContext(ErrorHandlingContext);
     let tournamentContext = useContext(TournamentContext);
-    let pendingBallotsView = useView({type: "PendingBallots", tournament_id: tournamentContext.uuid}, {pending_ballot_counts: {}});
+    let pendingBallotsView = useView({type: "PendingBallots", tournament_id: tournamentContext.uuid}, {pending_ballot_counts: {}, pending_debates: [], names: {}});
 
     return <div className='w-full'>
         <h1>Results</h1>
@@ -123,6 +180,12 @@ function WaitForResultsStep({ round_uuid, num_submitted, num_expected }) {
             </p> : []
         }
 
+        {
+            pendingBallotsView.pending_debates.filter((pendingDebate) => pendingDebate.round_id == round_uuid).map(
+                (pendingDebate) => <PendingBallotDifferences key={pendingDebate.debate_id} pendingDebate={pendingDebate} names={pendingBallotsView.names} />
+            )
+        }
+
         <DateTimeSelectorButton
             buttonFactory={Button}
             buttonProps={{ role: (isDone ? "primary" : "secondary") }}
@@ -2,7 +2,7 @@
 
 import React, { useCallback, useContext, useEffect } from "react";
 import { useState, useMemo } from "react";
-import { executeAction } from "./Action";
+import { ErrorHandlingContext, executeAction } from "./Action";
 import { getPath, useView } from "./View";
 import { open } from '@tauri-apps/plugin-dialog';
 import { TournamentContext } from "./TournamentContext";
@@ -47,6 +47,7 @@ function ScoreOverview(props) {
 
 function BackupBallotList(props) {
     let tournamentId = useContext(TournamentContext).uuid;
+    let errorContext = useContext(ErrorHandlingContext);
     return <div>
         {props.backup_ballots.map((backup_ballot) =>
         <div key={backup_ballot.uuid}>
@@ -59,7 +60,7 @@ function BackupBallotList(props) {
                     executeAction("UpdateScores", {
                         "debate_id": props.debateId,
                         "update": {"SetBallot": backup_ballot.uuid}
-                    })
+                    }, errorContext.handleError)
                 
             }>
                 Make Primary
@@ -364,8 +365,16 @@ function SpeakerSelectBox(props) {
 
 
 function ScoreInputCell(props) {
+    // Keep the typed text, so that unfinished half points like "72." are not cut off while typing
+    let [text, setText] = useState(props.score !== null ? props.score.toString() : "");
+    useEffect(() => {
+        if (parseFloat(text) !== props.score) {
+            setText(props.score !== null ? props.score.toString() : "");
+        }
+    }, [props.score]);
+
     return <input className="m-0 w-full text-center" onChange={evt => {
-        var value = parseInt(evt.target.value);
+        var value = parseFloat(evt.target.value);
         if (evt.target.value === "" || isNaN(value)) {
             value = null
         }
@@ -373,15 +382,91 @@ function ScoreInputCell(props) {
         if (value > props.maxScore) {
             return
         }
+        setText(evt.target.value);
         props.onChange(value);
-    }} value={props.score !== null ? props.score : ""} />
+    }} value={text} />
+}
+
+function SpeakerSubstitutionList(props) {
+    let tournamentContext = useContext(TournamentContext);
+    let errorContext = useContext(ErrorHandlingContext);
+    let participants = useView({ type: "ParticipantsList", tournament_uuid: tournamentContext.uuid }, { "teams": {}, "adjudicators": {}, "speakers_without_team": {} });
+    let [newSubstitution, setNewSubstitution] = useState({ team_id: "", speaker_id: "", replaced_speaker_id: "" });
+
+    let teams = Object.values(participants.teams).sort((a, b) => a.name.localeCompare(b.name));
+    // Reserve speakers do not belong to a team, but can still substitute
+    let speakerNames = Object.fromEntries([
+        ...teams.flatMap((team) => Object.values(team.members).map((member) => [member.uuid, member.name])),
+        ...Object.values(participants.speakers_without_team || {}).map((speaker) => [speaker.uuid, speaker.name])
+    ]);
+    let teamNames = Object.fromEntries(teams.map((team) => [team.uuid, team.name]));
+    let selectedTeam = participants.teams[newSubstitution.team_id];
+
+    return <div className="overflow-hidden sm:rounded-lg border m-2 p-1 text-sm">
+        <h1 className="text-center">Substitutions</h1>
+        <table className="w-full">
+            <tbody>
+                {props.substitutions.map((substitution) => <tr key={substitution.uuid}>
+                    <td>{speakerNames[substitution.speaker_id] || "Unknown"}</td>
+                    <td>for {teamNames[substitution.team_id] || "Unknown"}</td>
+                    <td>{substitution.replaced_speaker_id ? `replacing ${speakerNames[substitution.replaced_speaker_id] || "Unknown"}` : ""}</td>
+                    <td>
+                        <button onClick={() => executeAction("UpdateSpeakerSubstitutions", {
+                            tournament_id: tournamentContext.uuid,
+                            deleted_substitutions: [substitution.uuid]
+                        }, errorContext.handleError)}>Remove</button>
+                    </td>
+                </tr>)}
+                <tr>
+                    <td>
+                        <select value={newSubstitution.speaker_id} onChange={(e) => setNewSubstitution({ ...newSubstitution, speaker_id: e.target.value })}>
+                            <option value="">Speaker…</option>
+                            {Object.entries(speakerNames).sort((a, b) => a[1].localeCompare(b[1])).map(([uuid, name]) => <option key={uuid} value={uuid}>{name}</option>)}
+                        </select>
+                    </td>
+                    <td>
+                        <select value={newSubstitution.team_id} onChange={(e) => setNewSubstitution({ ...newSubstitution, team_id: e.target.value, replaced_speaker_id: "" })}>
+                            <option value="">Team…</option>
+                            {teams.map((team) => <option key={team.uuid} value={team.uuid}>{team.name}</option>)}
+                        </select>
+                    </td>
+                    <td>
+                        <select value={newSubstitution.replaced_speaker_id} onChange={(e) => setNewSubstitution({ ...newSubstitution, replaced_speaker_id: e.target.value })}>
+                            <option value="">Replacing nobody</option>
+                            {selectedTeam ? Object.values(selectedTeam.members).map((member) => <option key={member.uuid} value={member.uuid}>{member.name}</option>) : []}
+                        </select>
+                    </td>
+                    <td>
+                        <button
+                            disabled={!newSubstitution.speaker_id || !newSubstitution.team_id}
+                            onClick={() => executeAction("UpdateSpeakerSubstitutions", {
+                                tournament_id: tournamentContext.uuid,
+                                added_substitutions: [{
+                                    round_id: props.roundId,
+                                    team_id: newSubstitution.team_id,
+                                    speaker_id: newSubstitution.speaker_id,
+                                    replaced_speaker_id: newSubstitution.replaced_speaker_id || null
+                                }]
+                            }, errorContext.handleError).then((success) => {
+                                if (success) {
+                                    setNewSubstitution({ team_id: "", speaker_id: "", replaced_speaker_id: "" });
+                                }
+                            })}
+                        >Add</button>
+                    </td>
+                </tr>
+            </tbody>
+        </table>
+    </div>
 }
 
 export function RoundResultList(props) {
-    let debates = useView({type: "RoundResults", round_uuid: props.roundId}, {"debates": []});
+    let debates = useView({type: "RoundResults", round_uuid: props.roundId}, {"debates": [], "substitutions": []});
     let [activeBallot, setActiveBallot] = useState(null);
+    let errorContext = useContext(ErrorHandlingContext);
     return <div className="w-full h-full overflow-auto justify-center">
         <div className="p-4">
+        <SpeakerSubstitutionList roundId={props.roundId} substitutions={debates.substitutions} />
         {
             debates.debates.map((debate) => 
                 <DebateResultCard key={debate.uuid} debate={debate} onStartEditDebateBallot={(debateId, initialValues) => setActiveBallot({"debateId": debateId, "initialBallot": initialValues})} />
@@ -398,8 +483,12 @@ export function RoundResultList(props) {
                         executeAction("UpdateScores", {
                             "debate_id": activeBallot.debateId,
                             "update": {"NewBallot": ballot}
+                        }, errorContext.handleError).then((success) => {
+                            // Keep the editor open so rejected ballots can be corrected
+                            if (success) {
+                                setActiveBallot(null);
+                            }
                         });
-                        setActiveBallot(null);
                     }}    
                 /> : []
             }
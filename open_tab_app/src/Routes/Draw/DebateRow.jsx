@@ -8,6 +8,54 @@ import { SpeakerItem } from "./SpeakerItem";
 import { TeamItem } from "./TeamItem";
 import { VenueSelector } from "./VenueSelector";
 
+const EMPTY_PINS = {
+    government: false,
+    opposition: false,
+    non_aligned_speakers: [],
+    chair: false,
+    wings: [],
+    president: false
+};
+
+function PinToggle({label, pinned, onChange}) {
+    return <button
+        className={`ml-1 px-1 text-xs rounded border ${pinned ? "bg-blue-500 text-white border-blue-500" : "text-gray-500 border-gray-300"}`}
+        onClick={() => onChange(!pinned)}
+    >
+        {label}
+    </button>;
+}
+
+function toggleIndex(indices, idx, pinned) {
+    return pinned ? [...indices.filter((i) => i !== idx), idx] : indices.filter((i) => i !== idx);
+}
+
+function PinSelector({debate, onPinsChange}) {
+    let pins = debate.pins || EMPTY_PINS;
+    let ballot = debate.ballot;
+    let numWings = Math.max(ballot.adjudicators.length - 1, 0);
+
+    return <span className="ml-2 text-xs text-gray-500">
+        Pinned:
+        <PinToggle label="Gov" pinned={pins.government} onChange={(pinned) => onPinsChange({...pins, government: pinned})} />
+        <PinToggle label="Opp" pinned={pins.opposition} onChange={(pinned) => onPinsChange({...pins, opposition: pinned})} />
+        {ballot.non_aligned_speakers.map((_, idx) => <PinToggle
+            key={`na-${idx}`}
+            label={`NA ${idx + 1}`}
+            pinned={pins.non_aligned_speakers.includes(idx)}
+            onChange={(pinned) => onPinsChange({...pins, non_aligned_speakers: toggleIndex(pins.non_aligned_speakers, idx, pinned)})}
+        />)}
+        <PinToggle label="Chair" pinned={pins.chair} onChange={(pinned) => onPinsChange({...pins, chair: pinned})} />
+        {[...Array(numWings).keys()].map((idx) => <PinToggle
+            key={`wing-${idx}`}
+            label={`Wing ${idx + 1}`}
+            pinned={pins.wings.includes(idx)}
+            onChange={(pinned) => onPinsChange({...pins, wings: toggleIndex(pins.wings, idx, pinned)})}
+        />)}
+        <PinToggle label="President" pinned={pins.president} onChange={(pinned) => onPinsChange({...pins, president: pinned})} />
+    </span>;
+}
+
 export const DebateRow = memo(function DebateRow(props) {
     let ballot = props.debate.ballot;
     let [localHighlightedIssues, setLocalHighlightedIssues] = useState({
@@ -29,7 +77,10 @@ export const DebateRow = memo(function DebateRow(props) {
  
     return <>
         <tr >
-                <td colSpan="4">Debate {props.debate.index + 1}: <VenueSelector venue={props.debate.venue} onVenueChange={(venue) => props.onVenueChange(venue, props.debate)} /></td> 
+                <td colSpan="4">
+                    Debate {props.debate.index + 1}: <VenueSelector venue={props.debate.venue} onVenueChange={(venue) => props.onVenueChange(venue, props.debate)} />
+                    <PinSelector debate={props.debate} onPinsChange={(pins) => props.onPinsChange(pins, props.debate)} />
+                </td> 
         </tr>
         <tr className="flex flex-row w-full border-t border-b">
             <td className="border-r w-[35%] pl-1 pr-1 flex flex-col justify-center">
@@ -82,6 +133,18 @@ export const DebateRow = memo(function DebateRow(props) {
                             highlightedIssues={highlightedIssues.adjudicators[idx]}
                             dragSwapHighlight={props.dragSwapHighlight && props.dragSwapHighlight.adjudicatorId == adjudicator.uuid ? props.dragSwapHighlight : null} />)}
                     </DropList>
+                    <div className="text-xs text-gray-500 mt-1">Trainees</div>
+                    <DropList minWidth={"200px"} type="adjudicator" collection={["debates", props.debate.index, "ballot", "trainees"]}>
+                        {(ballot.trainees || []).map((trainee) => <AdjudicatorItem
+                            key={trainee.uuid}
+                            adjudicator={trainee}
+                            expandIssues={shouldExpandLocalIssues}
+                            onHighlightIssues={(uuid, shouldExpand) => {
+                                setLocalHighlightedIssues(find_issues_with_target(ballot, uuid));
+                                setShouldExpandLocalIssues(shouldExpand);
+                            }}
+                            dragSwapHighlight={props.dragSwapHighlight && props.dragSwapHighlight.adjudicatorId == trainee.uuid ? props.dragSwapHighlight : null} />)}
+                    </DropList>
                 </div>
             </td>
             <td className="w-[20%] pl-1 pr-1 border-l">
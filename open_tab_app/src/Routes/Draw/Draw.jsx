@@ -70,6 +70,10 @@ function simulateDragOutcome(draw, from, to, isSwap) {
                 from.collection = ["debates", val.position.debate_index, "ballot", "president"];
                 from.index = undefined;
             }
+            else if (val.position.position.type === "Trainee") {
+                from.collection = ["debates", val.position.debate_index, "ballot", "trainees"];
+                from.index = val.position.position.position;
+            }
         }
     }
 
@@ -213,6 +217,10 @@ function DrawTable({roundId, debates, dragHighlightedIssues, dragSwapHighlight})
     let onVenueChange = useCallback((venue, debate) => {
         executeAction("UpdateDraw", { tournament_id: tournament.uuid, updated_debates: [{ ...debate, venue: venue }] }, errorContext.handleError);
     }, [tournament.uuid]);
+
+    let onPinsChange = useCallback((pins, debate) => {
+        executeAction("UpdateDraw", { tournament_id: tournament.uuid, updated_debates: [{ ...debate, pins: pins }] }, errorContext.handleError);
+    }, [tournament.uuid]);
     return <div className="flex-1 h-full min-w-0" style={
         {
             scrollbarWidth: "none"
@@ -229,7 +237,8 @@ function DrawTable({roundId, debates, dragHighlightedIssues, dragSwapHighlight})
                         debate={debate}
                         dragHighlightedIssues={dragHighlightedIssues ? dragHighlightedIssues[debateIdx] : null}
                         dragSwapHighlight={dragSwapHighlight.debateIdx == debateIdx ? dragSwapHighlight : null}
-                        onVenueChange={onVenueChange} />;
+                        onVenueChange={onVenueChange}
+                        onPinsChange={onPinsChange} />;
                     })
                 }
                 </tbody>
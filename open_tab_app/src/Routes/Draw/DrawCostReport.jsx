@@ -0,0 +1,71 @@
+import { useView } from "../../View";
+
+
+function positionToStr(position) {
+    switch (position.type) {
+        case "Government":
+            return "Gov.";
+        case "Opposition":
+            return "Opp.";
+        case "NonAligned":
+            return `Non-Aligned ${position.position + 1}`;
+        default:
+            return position.type;
+    }
+}
+
+function costToStr(total) {
+    return total === null || total === undefined ? "Clash" : total;
+}
+
+function CostBreakdown({ option }) {
+    if (option.adjudicator_cost) {
+        let terms = Object.entries(option.adjudicator_cost).filter(([_, value]) => value !== 0);
+        if (terms.length === 0) {
+            return null;
+        }
+        return <div className="text-xs text-gray-500">
+            {terms.map(([name, value]) => `${name.replaceAll("_", " ")}: ${value}`).join(", ")}
+        </div>;
+    }
+
+    if (option.issues.length === 0) {
+        return null;
+    }
+    return <div className="text-xs text-gray-500">
+        {option.issues.map((issue) => `${issue.type} (${issue.severity})`).join(", ")}
+    </div>;
+}
+
+function PositionReport({ position, names }) {
+    return <div className="border-b pb-1">
+        <div className="flex justify-between text-sm">
+            <span><span className="font-semibold">{positionToStr(position.position)}</span> {names[position.chosen.participant_id] || "Unknown"}</span>
+            <span>{costToStr(position.chosen.total)}</span>
+        </div>
+        <CostBreakdown option={position.chosen} />
+        {position.alternatives.map((alternative) => <div key={alternative.participant_id} className="flex justify-between text-xs pl-2">
+            <span>{names[alternative.participant_id] || "Unknown"}{alternative.within_randomization ? " *" : ""}</span>
+            <span>{costToStr(alternative.total)}</span>
+        </div>)}
+    </div>;
+}
+
+export function DrawCostReport({ round_id }) {
+    let report = useView({ type: "DrawCostReport", round_uuid: round_id }, null);
+
+    if (report === null) {
+        return <div className="p-4">Loading...</div>;
+    }
+
+    return <div className="p-4 space-y-4">
+        <div className="text-xs text-gray-500">
+            {report.draw_seed !== null ? `Seed: ${report.draw_seed}. ` : ""}
+            Alternatives marked with * are within the randomization of the draw.
+        </div>
+        {report.debates.map((debate) => <div key={debate.debate_index}>
+            <h2 className="font-bold">Debate {debate.debate_index + 1}</h2>
+            {debate.positions.map((position, idx) => <PositionReport key={idx} position={position} names={report.names} />)}
+        </div>)}
+    </div>;
+}
@@ -0,0 +1,94 @@
+import { useContext, useState } from "react";
+import { invoke } from "@tauri-apps/api/core";
+import { ErrorHandlingContext, executeAction } from "../../Action";
+
+
+function positionToStr(position) {
+    switch (position.type) {
+        case "Government":
+            return "Gov.";
+        case "Opposition":
+            return "Opp.";
+        case "NonAligned":
+            return `Non-Aligned ${position.position + 1}`;
+        default:
+            return position.type;
+    }
+}
+
+function ParticipantStatusSelector({ name, status, onChange }) {
+    return <tr>
+        <td className="text-sm">{name}</td>
+        <td>
+            <select className="text-xs" value={status} onChange={(e) => onChange(e.target.value)}>
+                <option value="unchanged">-</option>
+                <option value="withdrawn">Withdrawn</option>
+                <option value="added">Added</option>
+            </select>
+        </td>
+    </tr>;
+}
+
+export function DrawRepairEditor({ round_id, adjudicator_index, team_index }) {
+    let errorContext = useContext(ErrorHandlingContext);
+    let [statuses, setStatuses] = useState({});
+    let [proposal, setProposal] = useState(null);
+
+    let names = {};
+    for (let entry of team_index) {
+        names[entry.team.uuid] = entry.team.name;
+        for (let member of entry.team.members) {
+            names[member.uuid] = member.name;
+        }
+    }
+    for (let entry of adjudicator_index) {
+        names[entry.adjudicator.uuid] = entry.adjudicator.name;
+    }
+
+    let withdrawn = Object.keys(statuses).filter((uuid) => statuses[uuid] === "withdrawn");
+    let added = Object.keys(statuses).filter((uuid) => statuses[uuid] === "added");
+
+    let setStatus = (uuid, status) => {
+        setStatuses({ ...statuses, [uuid]: status });
+        setProposal(null);
+    };
+
+    return <div className="p-4 space-y-4">
+        <table className="w-full">
+            <tbody>
+                {team_index.map((entry) => <ParticipantStatusSelector key={entry.team.uuid} name={entry.team.name} status={statuses[entry.team.uuid] || "unchanged"} onChange={(status) => setStatus(entry.team.uuid, status)} />)}
+                {adjudicator_index.map((entry) => <ParticipantStatusSelector key={entry.adjudicator.uuid} name={entry.adjudicator.name} status={statuses[entry.adjudicator.uuid] || "unchanged"} onChange={(status) => setStatus(entry.adjudicator.uuid, status)} />)}
+            </tbody>
+        </table>
+        <button
+            className="bg-blue-500 hover:bg-blue-600 text-white py-1 px-2 rounded"
+            onClick={() => {
+                invoke("get_draw_repair_proposal", { roundId: round_id, withdrawnParticipants: withdrawn, addedParticipants: added }).then(
+                    (result) => setProposal(result)
+                );
+            }}
+        >
+            Preview Repair
+        </button>
+        {proposal !== null ? <div>
+            <ul className="text-sm">
+                {proposal.changes.map((change, idx) => <li key={idx}>
+                    Debate {change.debate_index + 1}, {positionToStr(change.position)}: {change.previous ? names[change.previous] || "Unknown" : "Empty"} → {change.new ? names[change.new] || "Unknown" : "Empty"}
+                </li>)}
+            </ul>
+            {proposal.unplaced.length > 0 ? <p className="text-sm text-red-500">Could not place: {proposal.unplaced.map((uuid) => names[uuid] || "Unknown").join(", ")}</p> : []}
+            {proposal.unplaced_teams.length > 0 ? <p className="text-sm text-red-500">No team position for: {proposal.unplaced_teams.map((uuid) => names[uuid] || "Unknown").join(", ")}</p> : []}
+            {proposal.vacancies.length > 0 ? <p className="text-sm text-red-500">Empty positions: {proposal.vacancies.map((vacancy) => `Debate ${vacancy.debate_index + 1}, ${positionToStr(vacancy.position)}`).join("; ")}</p> : []}
+            <button
+                className="bg-blue-500 hover:bg-blue-600 text-white py-1 px-2 rounded mt-2"
+                onClick={() => {
+                    executeAction("RedrawRound", { round_id: round_id, mode: "RepairDraw", withdrawn_participants: withdrawn, added_participants: added }, errorContext.handleError);
+                    setStatuses({});
+                    setProposal(null);
+                }}
+            >
+                Apply Repair
+            </button>
+        </div> : []}
+    </div>;
+}
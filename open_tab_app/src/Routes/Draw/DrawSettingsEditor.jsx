@@ -1,5 +1,5 @@
 import { ask } from "@tauri-apps/plugin-dialog";
-import { useContext, createContext } from 'react';
+import { useContext, createContext, useState } from 'react';
 import { ErrorHandlingContext, executeAction } from "../../Action";
 
 
@@ -16,6 +16,9 @@ export const DrawEditorSettingsContext = createContext({
 export function DrawSettingsEditor({ round_id }) {
     let settings = useContext(DrawEditorSettingsContext);
     let errorContext = useContext(ErrorHandlingContext);
+    let [seed, setSeed] = useState("");
+    let parsedSeed = parseInt(seed);
+    let seedArg = isNaN(parsedSeed) ? null : parsedSeed;
 
     const handleShowMiscIssuesChange = () => {
         settings.updateSettings({ ...settings, showMiscIssues: !settings.showMiscIssues });
@@ -98,6 +101,19 @@ export function DrawSettingsEditor({ round_id }) {
                 </label>
             </div>
 
+            <div className="mt-4">
+                <label className="flex items-center space-x-2 text-sm font-medium text-gray-700">
+                    <span>Seed</span>
+                    <input
+                        type="text"
+                        className="text-sm w-32"
+                        placeholder="Random"
+                        value={seed}
+                        onChange={(e) => setSeed(e.target.value)}
+                    />
+                </label>
+            </div>
+
             <div className="mt-4">
                 <button
                     className="bg-blue-500 hover:bg-blue-600 text-white py-1 px-2 rounded"
@@ -120,13 +136,26 @@ export function DrawSettingsEditor({ round_id }) {
                             (result) => {
                                 console.log(result);
                                 if (result === true) {
-                                    executeAction("RedrawRound", { round_id: round_id, mode: "MissingNonAligned" }, errorContext.handleError);
+                                    executeAction("RedrawRound", { round_id: round_id, mode: "MissingNonAligned", seed: seedArg }, errorContext.handleError);
                                 }
                             })
                     }}
                 >
                     Assign Missing Non-Aligned
                 </button>
+                <button
+                    className="bg-blue-500 hover:bg-blue-600 text-white py-1 px-2 rounded"
+                    onClick={() => {
+                        ask('Are you sure? This will override all positions that are not pinned.', { title: 'Reallocate Draw', type: 'warning' }).then(
+                            (result) => {
+                                if (result === true) {
+                                    executeAction("RedrawRound", { round_id: round_id, mode: "AroundPins", seed: seedArg }, errorContext.handleError);
+                                }
+                            })
+                    }}
+                >
+                    Reallocate Around Pins
+                </button>
             </div>
         </div>
     );
@@ -3,6 +3,8 @@ import { TabGroup, Tab } from "../../TabGroup";
 import { DragItem, DropSlot } from "../../UI/DragDrop";
 import { TRAY_DRAG_PATH } from "./Draw";
 import { DrawSettingsEditor } from "./DrawSettingsEditor";
+import { DrawRepairEditor } from "./DrawRepairEditor";
+import { DrawCostReport } from "./DrawCostReport";
 
 function adjPositionToStr(position) {
     if (position.type == "NotSet") {
@@ -141,6 +143,12 @@ export function DrawToolTray({ round_id, adjudicator_index, team_index, isDraggi
                 <Tab name="Teams">
                     <TeamTable team_index={team_index} />
                 </Tab>
+                <Tab name="Repair" autoScroll={false}>
+                    <DrawRepairEditor round_id={round_id} adjudicator_index={adjudicator_index} team_index={team_index} />
+                </Tab>
+                <Tab name="Costs" autoScroll={false}>
+                    <DrawCostReport round_id={round_id} />
+                </Tab>
                 <Tab name="Settings" autoScroll={false}>
                     <DrawSettingsEditor round_id={round_id} />
                 </Tab>
@@ -26,6 +26,7 @@ export function ChangeRoleView({ participant, currentType, allTeams }) {
                     updatedPart.chair_skill = 50;
                     updatedPart.panel_skill = 50;
                     updatedPart.unavailable_rounds = [];
+                    updatedPart.is_trainee = false;
                     delete updatedPart.team_id;
                     executeAction("UpdateParticipants", {
                         tournament_id: tournamentContext.uuid, updated_participants: [
@@ -44,6 +45,7 @@ export function ChangeRoleView({ participant, currentType, allTeams }) {
                 delete updatedPart.chair_skill;
                 delete updatedPart.panel_skill;
                 delete updatedPart.unavailable_rounds;
+                delete updatedPart.is_trainee;
             }
 
             if (isCreate) {
@@ -173,6 +175,17 @@ function InnerParticipantDetailView({ onClose, participant, institutions, break_
                 }} />
                 <label className="ml-1">Only show initials on tab</label>
             </div>
+
+            <div className="flex flex-row items-center">
+                <input type="checkbox" checked={modifiedParticipant.requires_accessible_venue} onChange={(e) => {
+                    setChanges(
+                        (changes) => {
+                            return { ...changes, requires_accessible_venue: e.target.checked };
+                        }
+                    )
+                }} />
+                <label className="ml-1">Needs an accessible venue</label>
+            </div>
         </Section>
 
         <Section title="Break Category">
@@ -399,6 +412,16 @@ function InnerParticipantDetailView({ onClose, participant, institutions, break_
                         </div>
                     </div>
 
+                    <label className="flex items-center justify-center mt-2">
+                        <input type="checkbox" checked={modifiedParticipant.is_trainee || false} onChange={(e) => {
+                            let isTrainee = e.target.checked;
+                            setChanges((changes) => {
+                                return { ...changes, is_trainee: isTrainee };
+                            });
+                        }} />
+                        <span className="pl-2">Trainee (shadows panels without a vote)</span>
+                    </label>
+
                     <SortableTable rowId={"round_uuid"} data={availability} columns={[
                         {
                             "key": "round_name",
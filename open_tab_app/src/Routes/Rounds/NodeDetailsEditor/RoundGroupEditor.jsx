@@ -71,9 +71,39 @@ function PreliminariesConfigurator({ config, onChangeConfig }) {
                 });
             }
         } />
+        <PreliminariesDrawModeSelector mode={config.draw_mode || "AvoidClashes"} onChange={(newValue) => {
+            onChangeConfig({
+                ...config,
+                draw_mode: newValue
+            });
+        }} />
     </div>
 }
 
+function PreliminariesDrawModeSelector({ mode, onChange }) {
+    let options = [
+        {
+            value: "AvoidClashes",
+            label: "Avoid Clashes"
+        },
+        {
+            value: "Random",
+            label: "Random"
+        },
+        {
+            value: "PowerPaired",
+            label: "Power-Pairing"
+        },
+        {
+            value: "InstitutionSeeded",
+            label: "Spread Institutions"
+        },
+    ]
+    return <Select label="Draw Mode" options={options} value={mode} onChange={(e) => {
+        onChange(e.target.value);
+    }} />
+}
+
 function TeamFoldMethodSelector({ method, onChange }) {
     let options = [
         {
@@ -112,6 +142,10 @@ function TeamAssignmentRuleSelector({ method, onChange }) {
             value: "InvertPrevious",
             label: "Try to avoid prev. position"
         },
+        {
+            value: "BalanceHistory",
+            label: "Balance gov./opp. appearances"
+        },
      ]
     return <Select label="Team Fold Method" options={options} value={method} onChange={(e) => {
         onChange(e.target.value);
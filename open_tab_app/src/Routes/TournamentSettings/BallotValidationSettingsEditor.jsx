@@ -0,0 +1,100 @@
+import React from "react";
+import { executeAction } from "../../Action";
+import { TournamentContext } from "../../TournamentContext";
+
+const OPTIONAL_BOUNDS = [
+    ["min_speech_score", "Minimum speech score"],
+    ["max_speech_score", "Maximum speech score"],
+    ["min_team_score", "Minimum team score"],
+    ["max_team_score", "Maximum team score"],
+    ["max_panel_deviation", "Maximum panel deviation"],
+];
+
+const CONFIRMATION_POLICIES = [
+    ["Manual", "Confirm every ballot manually"],
+    ["FirstSubmission", "Accept the first submission"],
+    ["MatchingSubmissions", "Accept two matching submissions"],
+    ["ChairOrMatchingSubmissions", "Accept the chair's or two matching submissions"],
+];
+
+export default function BallotValidationSettingsEditor({ statusView }) {
+    let tournament = React.useContext(TournamentContext);
+    let config = statusView.ballot_validation_config;
+
+    let updateConfig = (changes) => {
+        executeAction("UpdateBallotValidationConfig", {
+            tournament_id: tournament.uuid,
+            ballot_validation_config: { ...config, ...changes }
+        });
+    };
+
+    return <div>
+        <h1 className="font-bold">Ballot Validation</h1>
+        <table className="text-sm">
+            <tbody>
+                {OPTIONAL_BOUNDS.map(([key, label]) => <tr key={key}>
+                    <td className="pr-2">{label}</td>
+                    <td>
+                        <input
+                            type="number"
+                            step="0.5"
+                            className="w-20 border rounded"
+                            placeholder="None"
+                            value={config[key] ?? ""}
+                            onChange={(e) => {
+                                let value = parseFloat(e.target.value);
+                                updateConfig({ [key]: isNaN(value) ? null : value });
+                            }}
+                        />
+                    </td>
+                </tr>)}
+                <tr>
+                    <td className="pr-2">Score step</td>
+                    <td>
+                        <input
+                            type="number"
+                            step="0.5"
+                            min="0"
+                            className="w-20 border rounded"
+                            value={config.score_step}
+                            onChange={(e) => {
+                                let value = parseFloat(e.target.value);
+                                if (!isNaN(value)) {
+                                    updateConfig({ score_step: value });
+                                }
+                            }}
+                        />
+                    </td>
+                </tr>
+                <tr>
+                    <td className="pr-2">Submitted ballots</td>
+                    <td>
+                        <select
+                            className="border rounded"
+                            value={config.confirmation_policy}
+                            onChange={(e) => updateConfig({ confirmation_policy: e.target.value })}
+                        >
+                            {CONFIRMATION_POLICIES.map(([policy, label]) => <option key={policy} value={policy}>{label}</option>)}
+                        </select>
+                    </td>
+                </tr>
+                <tr>
+                    <td className="pr-2">Allow tied team totals</td>
+                    <td>
+                        <input
+                            type="checkbox"
+                            checked={config.allow_tied_team_totals}
+                            onChange={(e) => updateConfig({ allow_tied_team_totals: e.target.checked })}
+                        />
+                    </td>
+                </tr>
+            </tbody>
+        </table>
+        <button
+            className="mt-2 text-sm underline"
+            onClick={() => executeAction("UpdateBallotValidationConfig", { tournament_id: tournament.uuid, ballot_validation_config: null })}
+        >
+            Accept all ballots
+        </button>
+    </div>
+}
@@ -0,0 +1,84 @@
+import React from "react";
+import { executeAction } from "../../Action";
+import { TournamentContext } from "../../TournamentContext";
+
+const CLASH_FACTORS = [
+    ["adj_adj_clash_factor", "Adjudicator - Adjudicator"],
+    ["adj_team_clash_factor", "Adjudicator - Team"],
+    ["adj_speaker_clash_factor", "Adjudicator - Non-Aligned"],
+    ["team_team_clash_factor", "Team - Team"],
+    ["team_speaker_clash_factor", "Team - Non-Aligned"],
+    ["speaker_speaker_clash_factor", "Non-Aligned - Non-Aligned"],
+];
+
+const REPEAT_SEVERITIES = [
+    ["adj_adj_repeat_clash_severity", "Adjudicator - Adjudicator"],
+    ["adj_team_repeat_clash_severity", "Adjudicator - Team Speaker"],
+    ["adj_non_aligned_speaker_repeat_clash_severity", "Adjudicator - Non-Aligned"],
+    ["team_team_repeat_clash_severity", "Team - Team"],
+    ["team_speaker_repeat_clash_severity", "Team - Non-Aligned"],
+    ["non_aligned_speakers_repeat_clash_severity", "Non-Aligned - Non-Aligned"],
+];
+
+const ADJUDICATOR_WEIGHTS = [
+    ["feedback_weight", "Feedback skill"],
+    ["moderation_weight", "Moderation skill"],
+    ["max_discussion_improvement_weight", "Discussion improvement"],
+    ["bias_weight", "Bias"],
+    ["variance_weight", "Variance"],
+    ["trainee_mentor_weight", "Trainee mentoring"],
+    ["hard_clash_threshold", "Hard clash threshold"],
+];
+
+function WeightTable({ title, fields, config, onChange }) {
+    return <table className="text-sm">
+        <thead>
+            <tr><th className="text-left" colSpan={2}>{title}</th></tr>
+        </thead>
+        <tbody>
+            {fields.map(([key, label]) => <tr key={key}>
+                <td className="pr-2">{label}</td>
+                <td>
+                    <input
+                        type="number"
+                        step="any"
+                        className="w-20 border rounded"
+                        value={config[key]}
+                        onChange={(e) => {
+                            let value = parseFloat(e.target.value);
+                            if (!isNaN(value)) {
+                                onChange({ [key]: value });
+                            }
+                        }}
+                    />
+                </td>
+            </tr>)}
+        </tbody>
+    </table>;
+}
+
+export default function DrawConfigSettingsEditor({ statusView }) {
+    let tournament = React.useContext(TournamentContext);
+
+    let updateConfig = (changes) => {
+        executeAction("UpdateDrawConfig", {
+            tournament_id: tournament.uuid,
+            draw_config: { ...statusView.draw_config, ...changes }
+        });
+    };
+
+    return <div>
+        <h1 className="font-bold">Draw Weights</h1>
+        <div className="flex space-x-4">
+            <WeightTable title="Clash Factors" fields={CLASH_FACTORS} config={statusView.draw_config} onChange={updateConfig} />
+            <WeightTable title="Repeated Meetings" fields={REPEAT_SEVERITIES} config={statusView.draw_config} onChange={updateConfig} />
+            <WeightTable title="Adjudicators" fields={ADJUDICATOR_WEIGHTS} config={statusView.draw_config} onChange={updateConfig} />
+        </div>
+        <button
+            className="mt-2 text-sm underline"
+            onClick={() => executeAction("UpdateDrawConfig", { tournament_id: tournament.uuid, draw_config: null })}
+        >
+            Restore defaults
+        </button>
+    </div>
+}
@@ -0,0 +1,49 @@
+import React from "react";
+import { executeAction } from "../../Action";
+import { TournamentContext } from "../../TournamentContext";
+
+export default function SelfDeclaredAvailabilitySettingsEditor({statusView}) {
+    let tournament = React.useContext(TournamentContext);
+
+    let updateSettings = (changes) => {
+        executeAction("UpdateTournament", {
+            tournament_id: tournament.uuid,
+            allow_self_declared_clashes: statusView.allow_self_declared_clashes,
+            allow_speaker_self_declared_clashes: statusView.allow_speaker_self_declared_clashes,
+            ...changes
+        });
+    };
+
+    return <div>
+        <h1 className="font-bold">Adjudicator Availability</h1>
+        <div className="flex space-x-4">
+            <label className="flex items-center space-x-2">
+                <input
+                    type="checkbox"
+                    checked={statusView.allow_self_declared_availability}
+                    onChange={(e) => {
+                        updateSettings({ allow_self_declared_availability: e.target.checked });
+                    }}
+                />
+                <span>Adjudicators can mark rounds they can not judge</span>
+            </label>
+            <label className="flex items-center space-x-2">
+                <span>Changes close</span>
+                <input
+                    type="number"
+                    min={0}
+                    className="w-16 border rounded"
+                    value={statusView.availability_deadline_minutes}
+                    disabled={!statusView.allow_self_declared_availability}
+                    onChange={(e) => {
+                        let minutes = parseInt(e.target.value);
+                        if (!isNaN(minutes)) {
+                            updateSettings({ availability_deadline_minutes: minutes });
+                        }
+                    }}
+                />
+                <span>minutes before the draw release</span>
+            </label>
+        </div>
+    </div>
+}
@@ -10,6 +10,9 @@ import { invoke } from "@tauri-apps/api/core";
 import SettingsEditor from "./SettingsEditor";
 import { executeAction } from "../../Action";
 import SelfDeclaredClashSettingsEditor from "./SelfDeclaredClashSettingsEditor";
+import SelfDeclaredAvailabilitySettingsEditor from "./SelfDeclaredAvailabilitySettingsEditor";
+import DrawConfigSettingsEditor from "./DrawConfigSettingsEditor";
+import BallotValidationSettingsEditor from "./BallotValidationSettingsEditor";
 
 export default function TournamentViewRoute(props) {
     let tournament = useContext(TournamentContext);
@@ -43,11 +46,21 @@ export default function TournamentViewRoute(props) {
 
                     {
                         statusView.remote_url ?
-                            <SelfDeclaredClashSettingsEditor statusView={statusView} />
+                            <>
+                                <SelfDeclaredClashSettingsEditor statusView={statusView} />
+                                <SelfDeclaredAvailabilitySettingsEditor statusView={statusView} />
+                            </>
                             :
                             []
                     }
 
+                    <div className="pt-2">
+                        <DrawConfigSettingsEditor statusView={statusView} />
+                    </div>
+
+                    <div className="pt-2">
+                        <BallotValidationSettingsEditor statusView={statusView} />
+                    </div>
                 </div>
                 :
                 <p>Loading</p>
@@ -0,0 +1,104 @@
+import React, { useContext, useEffect, useState } from 'react';
+import { listen } from '@tauri-apps/api/event';
+import { TournamentContext } from './TournamentContext';
+import { ErrorHandlingContext, executeAction } from './Action';
+import ModalOverlay from './UI/Modal';
+import Button from './UI/Button';
+
+
+function formatValue(value) {
+    if (value === null || value === undefined) {
+        return <span className="italic text-gray-500">Not set</span>;
+    }
+    return <pre className="text-xs whitespace-pre-wrap">{JSON.stringify(value, null, 2)}</pre>;
+}
+
+function conflictKey(conflict) {
+    return `${conflict.entity_type}/${conflict.entity_uuid}${conflict.path}`;
+}
+
+/**
+ * A field that was changed differently on this device and on the server.
+ * The local value is kept unless the remote value is chosen.
+ */
+function ConflictEntry({ conflict, onKeepLocal, onUseRemote }) {
+    return <div className="border-b pb-2 mb-2">
+        <h3 className="font-bold">{conflict.entity_type} {conflict.path || "(whole entry)"}</h3>
+        <table className="w-full text-sm">
+            <thead>
+                <tr>
+                    <th className="text-left">Before</th>
+                    <th className="text-left">This device</th>
+                    <th className="text-left">Server</th>
+                </tr>
+            </thead>
+            <tbody>
+                <tr className="align-top">
+                    <td>{formatValue(conflict.base)}</td>
+                    <td>{formatValue(conflict.local)}</td>
+                    <td>{formatValue(conflict.remote)}</td>
+                </tr>
+            </tbody>
+        </table>
+        <div className="flex justify-end space-x-2 mt-1">
+            <Button role="secondary" onClick={onKeepLocal}>Keep this device</Button>
+            <Button role="primary" onClick={onUseRemote}>Use server</Button>
+        </div>
+    </div>
+}
+
+function SyncConflicts() {
+    let tournamentId = useContext(TournamentContext).uuid;
+    let errorContext = useContext(ErrorHandlingContext);
+    let [conflicts, setConflicts] = useState([]);
+
+    useEffect(() => {
+        const unlisten = listen('sync-conflicts', (event) => {
+            if (event.payload.tournament_id == tournamentId) {
+                setConflicts((conflicts) => {
+                    // A later sync reports the current state of the same field
+                    let newKeys = new Set(event.payload.conflicts.map(conflictKey));
+                    return [...conflicts.filter((c) => !newKeys.has(conflictKey(c))), ...event.payload.conflicts];
+                });
+            }
+        });
+
+        return () => {
+            unlisten.then(unlisten => unlisten())
+        }
+    }, [tournamentId]);
+
+    let dismiss = (conflict) => {
+        setConflicts((conflicts) => conflicts.filter((c) => conflictKey(c) != conflictKey(conflict)));
+    };
+
+    return <ModalOverlay open={conflicts.length > 0} windowClassName="w-2/3">
+        <h2 className="text-lg font-bold mb-1">Conflicting changes</h2>
+        <p className="text-sm mb-4">
+            These fields were changed both on this device and on the server.
+            The changes from this device were kept.
+        </p>
+        {
+            conflicts.map((conflict) => <ConflictEntry
+                key={conflictKey(conflict)}
+                conflict={conflict}
+                onKeepLocal={() => dismiss(conflict)}
+                onUseRemote={() => {
+                    executeAction("ResolveMergeConflict", {
+                        tournament_id: tournamentId,
+                        entity_type: conflict.entity_type,
+                        entity_uuid: conflict.entity_uuid,
+                        path: conflict.path,
+                        value: conflict.remote
+                    }, errorContext.handleError).then((success) => {
+                        if (success) {
+                            dismiss(conflict);
+                        }
+                    });
+                }}
+            />)
+        }
+    </ModalOverlay>
+}
+
+export default SyncConflicts;
@@ -45,6 +45,55 @@ export default function VenueOverview() {
                                 } />
                             }
                         },
+                        {
+                            "key": "building",
+                            "header": "Building",
+                            cellFactory: (value, rowIdx, colIdx, rowValue) => {
+                                return <EditableCell key={colIdx} value={value || ""} onChange={
+                                    (newBuilding) => {
+                                        let newVenue = {... rowValue};
+                                        newVenue.building = newBuilding.length > 0 ? newBuilding : null;
+                                        executeAction("UpdateVenues", {updated_venues: [newVenue], tournament_id: tournamentId})
+                                    }
+                                } />
+                            }
+                        },
+                        {
+                            "key": "capacity",
+                            "header": "Capacity",
+                            cellFactory: (value, rowIdx, colIdx, rowValue) => {
+                                return <EditableCell key={colIdx} value={value !== null && value !== undefined ? value.toString() : ""} onChange={
+                                    (newCapacity) => {
+                                        let newVenue = {... rowValue};
+                                        let parsedCapacity = parseInt(newCapacity);
+                                        newVenue.capacity = isNaN(parsedCapacity) ? null : parsedCapacity;
+                                        executeAction("UpdateVenues", {updated_venues: [newVenue], tournament_id: tournamentId})
+                                    }
+                                } />
+                            }
+                        },
+                        {
+                            "key": "is_accessible",
+                            "header": "Accessible",
+                            cellFactory: (value, rowIdx, colIdx, rowValue) => {
+                                return <td key={colIdx}><input type="checkbox" checked={value} onChange={(e) => {
+                                    let newVenue = {... rowValue};
+                                    newVenue.is_accessible = e.target.checked;
+                                    executeAction("UpdateVenues", {updated_venues: [newVenue], tournament_id: tournamentId})
+                                }} /></td>
+                            }
+                        },
+                        {
+                            "key": "is_top_room",
+                            "header": "Top Room",
+                            cellFactory: (value, rowIdx, colIdx, rowValue) => {
+                                return <td key={colIdx}><input type="checkbox" checked={value} onChange={(e) => {
+                                    let newVenue = {... rowValue};
+                                    newVenue.is_top_room = e.target.checked;
+                                    executeAction("UpdateVenues", {updated_venues: [newVenue], tournament_id: tournamentId})
+                                }} /></td>
+                            }
+                        },
                     ]
                 }
             />
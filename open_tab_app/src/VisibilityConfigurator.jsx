@@ -32,6 +32,12 @@ export const VisibilityConfigurator = ({
                 show_teams_for_presidents: "Show Teams to Presidents",
                 show_non_aligned_for_presidents: "Show Non-Aligned to Presidents"
             }
+        },
+        trainees: {
+            title: "Target: Trainees",
+            options: {
+                show_chairs_for_trainees: "Show Chairs to Trainees"
+            }
         }
     };
 
@@ -131,7 +137,7 @@ export const VisibilityConfigurator = ({
                 {/* Divider */}
                 <div className="border-t border-gray-200"></div>
 
-                {/* Presidents last */}
+                {/* Presidents third */}
                 <div className="p-4 bg-gray-50">
                     <div className="flex justify-between items-center mb-4">
                         <h3 className="text-xl font-semibold text-gray-700">{groupedConfig.presidents.title}</h3>
@@ -157,6 +163,36 @@ export const VisibilityConfigurator = ({
                         ))}
                     </div>
                 </div>
+
+                {/* Divider */}
+                <div className="border-t border-gray-200"></div>
+
+                {/* Trainees last */}
+                <div className="p-4 bg-gray-50">
+                    <div className="flex justify-between items-center mb-4">
+                        <h3 className="text-xl font-semibold text-gray-700">{groupedConfig.trainees.title}</h3>
+                        <button
+                            onClick={() => handleGroupToggle('trainees')}
+                            className="text-sm px-3 py-1 bg-blue-100 text-blue-800 rounded hover:bg-blue-200 transition-colors"
+                        >
+                            Toggle All
+                        </button>
+                    </div>
+                    <div className="grid grid-cols-2 gap-3">
+                        {Object.entries(groupedConfig.trainees.options).map(([key, label]) => (
+                            <div key={key} className="flex items-center">
+                                <label className="flex items-center cursor-pointer">
+                                    <input
+                                        type="checkbox"
+                                        checked={visibility[key]}
+                                        onChange={() => handleToggle(key)}
+                                        className="w-5 h-5 text-blue-600 rounded focus:ring-2 focus:ring-blue-500" />
+                                    <span className="ml-2 text-gray-700">{label}</span>
+                                </label>
+                            </div>
+                        ))}
+                    </div>
+                </div>
             </div>
 
             <div className="mb-6 flex space-x-4 mt-1">
@@ -0,0 +1,41 @@
+use open_tab_entities::{prelude::*, domain::entity::LoadEntity};
+use sea_orm::prelude::Uuid;
+use async_trait::async_trait;
+use serde::{Serialize, Deserialize};
+
+use crate::{actions::ActionTrait, feedback_ratings::{load_rating_suggestions, FeedbackRatingConfig}};
+
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ApplyAdjudicatorRatingSuggestionsAction {
+    pub tournament_id: Uuid,
+    /// Restricts the update to these adjudicators. All suggestions are applied if not set.
+    #[serde(default)]
+    pub adjudicator_ids: Option<Vec<Uuid>>,
+}
+
+
+#[async_trait]
+impl ActionTrait for ApplyAdjudicatorRatingSuggestionsAction {
+    async fn get_changes<C>(self, db: &C) -> Result<EntityGroup, anyhow::Error> where C: sea_orm::ConnectionTrait {
+        let suggestions = load_rating_suggestions(db, self.tournament_id, &FeedbackRatingConfig::default()).await?;
+
+        let suggestions = suggestions.into_iter().filter(|s| {
+            s.has_changes() && self.adjudicator_ids.as_ref().map(|ids| ids.contains(&s.adjudicator_id)).unwrap_or(true)
+        }).collect::<Vec<_>>();
+
+        let participants = Participant::get_many(db, suggestions.iter().map(|s| s.adjudicator_id).collect()).await?;
+
+        let mut groups = EntityGroup::new(self.tournament_id);
+
+        for (mut participant, suggestion) in participants.into_iter().zip(suggestions) {
+            if let ParticipantRole::Adjudicator(adj) = &mut participant.role {
+                adj.chair_skill = suggestion.proposed_chair_skill;
+                adj.panel_skill = suggestion.proposed_panel_skill;
+                groups.add(Entity::Participant(participant));
+            }
+        }
+
+        Ok(groups)
+    }
+}
@@ -0,0 +1,47 @@
+use async_trait::async_trait;
+use open_tab_entities::{domain::entity::LoadEntity, prelude::{Participant, Team}, Entity, EntityGroup};
+use sea_orm::prelude::Uuid;
+use serde::{Serialize, Deserialize};
+
+use crate::{draw::swing_teams::{make_swing_team, next_swing_team_name}, ActionTrait};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CreateSwingTeamAction {
+    pub tournament_id: Uuid,
+    /// Spare speakers or adjudicators that form the team.
+    /// The team may have fewer than three members.
+    #[serde(default)]
+    pub member_ids: Vec<Uuid>,
+    /// Rounds the team swings in. Adjudicators in the team are unavailable for adjudication in these rounds,
+    /// so they are required if the team has adjudicator members.
+    #[serde(default)]
+    pub round_ids: Vec<Uuid>,
+    /// Defaults to the next free "Swing N" name
+    #[serde(default)]
+    pub name: Option<String>,
+}
+
+#[async_trait]
+impl ActionTrait for CreateSwingTeamAction {
+    async fn get_changes<C>(self, db: &C) -> Result<EntityGroup, anyhow::Error> where C: sea_orm::ConnectionTrait {
+        let mut g = EntityGroup::new(
+            self.tournament_id
+        );
+
+        let teams = Team::get_all_in_tournament(db, self.tournament_id).await?;
+        let members = Participant::get_many(db, self.member_ids).await?;
+
+        let name = self.name.unwrap_or_else(|| next_swing_team_name(teams.iter().map(|t| &t.name)));
+        let teams_by_id = teams.into_iter().map(|t| (t.uuid, t)).collect();
+        let (team, members) = make_swing_team(self.tournament_id, name, members.iter().collect(), &self.round_ids, &teams_by_id)?;
+
+        g.add(Entity::Team(team));
+        for member in members {
+            g.add(Entity::Participant(member));
+        }
+
+        Ok(
+            g
+        )
+    }
+}
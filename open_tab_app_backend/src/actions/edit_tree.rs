@@ -117,7 +117,7 @@ impl ActionTrait for EditTreeAction {
         match self.action {
             EditTreeActionType::AddPreliminaryRounds { parent: parent_node } => {
                 let node = TournamentPlanNode::new(self.tournament_id, open_tab_entities::domain::tournament_plan_node::PlanNodeType::Round {
-                    config: open_tab_entities::domain::tournament_plan_node::RoundGroupConfig::Preliminaries { num_roundtrips: 1 },
+                    config: open_tab_entities::domain::tournament_plan_node::RoundGroupConfig::Preliminaries { num_roundtrips: 1, draw_mode: Default::default() },
                     rounds: vec![]
                 });
                 if let Some(parent_node) = parent_node {
@@ -2,13 +2,13 @@ use std::{sync::Arc, collections::{HashSet, HashMap}, cmp::Ordering};
 
 use itertools::{Itertools, izip, repeat_n};
 use async_trait::async_trait;
-use open_tab_entities::{derived_models::{BackupBallot, BreakNodeBackgroundInfo, NodeExecutionError}, domain::{self, entity::LoadEntity, tournament_break::TournamentBreak, tournament_plan_edge::TournamentPlanEdge, tournament_plan_node::{BreakConfig, PlanNodeType, RoundGroupConfig, TournamentPlanNode}, tournament_venue::TournamentVenue}, prelude::*, schema::speaker, tab::TeamRoundRole, EntityTypeId};
+use open_tab_entities::{derived_models::{BackupBallot, BreakNodeBackgroundInfo, NodeExecutionError}, domain::{self, entity::LoadEntity, tournament::DrawConfig, tournament_break::TournamentBreak, tournament_plan_edge::TournamentPlanEdge, tournament_plan_node::{BreakConfig, PlanNodeType, RoundGroupConfig, TournamentPlanNode}, tournament_venue::TournamentVenue}, prelude::*, schema::speaker, tab::{TabRankingConfig, TeamRoundRole}, EntityTypeId};
 use open_tab_entities::domain::tournament_plan_node::TournamentEligibleBreakCategory;
 
-use rand::{thread_rng, Rng};
+use rand::{rngs::StdRng, Rng};
 use sea_orm::prelude::*;
 
-use crate::{draw::{evaluation::{DrawConstructionEvaluationContext, DrawEvaluator, DrawEvaluatorConfig}, flow_optimization::{OptimizationOptions, OptimizationState}, preliminary::{DrawTeamInfo, RoundGenerationContext}, tab_draw::{assign_teams, pair_speakers, pair_teams, TeamPair}, PreliminariesDrawMode, PreliminaryRoundGenerator}, draw_view::{DrawAdjudicator, DrawBallot, DrawSpeaker, DrawTeam, SetDrawAdjudicator}, views, TournamentParticipantsInfo};
+use crate::{draw::{evaluation::{DrawConstructionEvaluationContext, DrawEvaluator, DrawEvaluatorConfig}, datastructures::{DebateInfo, RoundInfo}, flow_optimization::{OptimizationOptions, OptimizationState}, pins::{apply_pinned_adjudicators, apply_pinned_teams}, preliminary::{DrawTeamInfo, RoundGenerationContext}, seed::{choose_draw_seed, draw_rng}, swing_teams::select_draw_teams, tab_draw::{add_team_pairs_to_position_balance, assign_teams, compute_team_position_balance, pair_speakers, pair_teams, TeamPair}, venues::{allocate_venues, load_adjudicator_buildings, load_team_ranks, update_adjudicator_buildings, VenueDebateInfo}, PreliminariesDrawMode, PreliminaryRoundGenerator}, draw_view::{DrawAdjudicator, DrawBallot, DrawSpeaker, DrawTeam, SetDrawAdjudicator}, views, TournamentParticipantsInfo};
 use serde::{Serialize, Deserialize};
 
 use super::{ActionTrait, edit_tree::reindex_rounds};
@@ -18,7 +18,11 @@ use thiserror::Error;
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ExecutePlanNodeAction {
     pub tournament_id: Uuid,
-    pub plan_node: Uuid
+    pub plan_node: Uuid,
+    /// Seed for the random choices of the draw or break. If not set, a new seed is chosen.
+    /// The seed used is stored on the generated rounds.
+    #[serde(default)]
+    pub seed: Option<i64>
 }
 
 fn round_draw_from_team_and_speaker_pairs(team_pairs: Vec<TeamPair>, speaker_pairs: Vec<Vec<Uuid>>) -> Vec<DrawBallot> {
@@ -81,8 +85,11 @@ fn get_gov_opp_assignments_from_ballots(ballots: &Vec<Ballot>) -> HashMap<Uuid,
 }
 
 
-async fn generate_round_draw<C>(db: &C, tournament_id: Uuid, node_id: Uuid, config: &RoundGroupConfig, existing_rounds: &Vec<Uuid>) -> Result<EntityGroup, anyhow::Error> where C: sea_orm::ConnectionTrait {
+async fn generate_round_draw<C>(db: &C, tournament_id: Uuid, node_id: Uuid, config: &RoundGroupConfig, existing_rounds: &Vec<Uuid>, seed: Option<i64>) -> Result<EntityGroup, anyhow::Error> where C: sea_orm::ConnectionTrait {
     let mut changes = EntityGroup::new(tournament_id);
+    let seed = choose_draw_seed(seed);
+    let mut rng: StdRng = draw_rng(seed);
+    let draw_config = DrawConfig::load_for_tournament(db, tournament_id).await?;
 
     let all_nodes = TournamentPlanNode::get_all_in_tournament(db, tournament_id).await?;
     let edges = TournamentPlanEdge::get_all_for_sources(db, all_nodes.iter().map(|n| n.uuid).collect()).await?;
@@ -124,7 +131,14 @@ async fn generate_round_draw<C>(db: &C, tournament_id: Uuid, node_id: Uuid, conf
     let all_teams = Team::get_all_in_tournament(db, tournament_id).await?;
     let all_participants = Participant::get_all_in_tournament(db, tournament_id).await?;
 
-    let team_members = all_participants.iter().filter_map(
+    let mut participants_requiring_accessibility = all_participants.iter().filter(
+        |p| p.requires_accessible_venue
+    ).map(|p| p.uuid).collect::<HashSet<_>>();
+    let adjudicator_ids = all_participants.iter().filter(
+        |p| matches!(p.role, ParticipantRole::Adjudicator(_))
+    ).map(|p| p.uuid).collect::<HashSet<_>>();
+
+    let mut team_members = all_participants.iter().filter_map(
         |p| match &p.role {
             ParticipantRole::Speaker(s) if s.team_id.is_some() => Some((s.team_id.unwrap(), p.uuid)),
             _ => None
@@ -153,8 +167,25 @@ async fn generate_round_draw<C>(db: &C, tournament_id: Uuid, node_id: Uuid, conf
         }
     }
     else {
+        let selection = select_draw_teams(tournament_id, all_teams, &all_participants)?;
+        let mut all_participants = all_participants;
+        for member in selection.new_members {
+            if let ParticipantRole::Speaker(Speaker { team_id: Some(team_id) }) = &member.role {
+                team_members.entry(*team_id).or_default().push(member.uuid);
+            }
+            if member.requires_accessible_venue {
+                participants_requiring_accessibility.insert(member.uuid);
+            }
+            all_participants.retain(|p| p.uuid != member.uuid);
+            all_participants.push(member.clone());
+            changes.add(Entity::Participant(member));
+        }
+        for team in selection.new_teams {
+            changes.add(Entity::Team(team));
+        }
+
         (
-            all_teams,
+            selection.teams,
             all_participants,
             vec![]
         )
@@ -165,11 +196,37 @@ async fn generate_round_draw<C>(db: &C, tournament_id: Uuid, node_id: Uuid, conf
     ).collect_vec();
 
     let mut evaluation_context = DrawConstructionEvaluationContext::new_from_tournament(db, tournament_id).await?;
+    evaluation_context.remove_rounds(existing_rounds);
+
+    let mut team_institution_counts: HashMap<Uuid, HashMap<Uuid, usize>> = HashMap::new();
+    for participant in all_speakers.iter() {
+        if let ParticipantRole::Speaker(s) = &participant.role {
+            if let Some(team_id) = s.team_id {
+                let counts = team_institution_counts.entry(team_id).or_default();
+                for institution in participant.institutions.iter() {
+                    *counts.entry(institution.uuid).or_default() += 1;
+                }
+            }
+        }
+    }
+    let team_institutions = team_institution_counts.into_iter().map(
+        |(team_id, counts)| (team_id, counts.into_iter().sorted_by_key(|(institution, count)| (std::cmp::Reverse(*count), *institution)).map(|(institution, _)| institution).collect_vec())
+    ).collect::<HashMap<_, _>>();
+
+    // Also used to place the strongest debates in top rooms
+    let tab_team_ranks = load_team_ranks(db, tournament_id, other_rounds.iter().map(|r| r.uuid).collect(), &team_members).await?;
+    let team_ranks = match config {
+        RoundGroupConfig::Preliminaries { draw_mode: PreliminariesDrawMode::PowerPaired, .. } => tab_team_ranks.clone(),
+        _ => HashMap::new()
+    };
 
     let context = RoundGenerationContext {
         teams: all_teams.iter().map(|t| DrawTeamInfo {
             uuid: t.uuid,
-            member_ids: team_members.get(&t.uuid).cloned().unwrap_or_default()
+            member_ids: team_members.get(&t.uuid).cloned().unwrap_or_default(),
+            institution_ids: team_institutions.get(&t.uuid).cloned().unwrap_or_default(),
+            tab_rank: team_ranks.get(&t.uuid).cloned(),
+            is_swing: t.is_swing,
         }).collect(),
         adjudicators: all_speakers.iter().filter_map(
             |a| match a.role {
@@ -201,27 +258,33 @@ async fn generate_round_draw<C>(db: &C, tournament_id: Uuid, node_id: Uuid, conf
         }
     }
 
+    for round in rounds.iter_mut() {
+        round.draw_seed = Some(seed);
+    }
+
     let mut original_node = all_nodes.get(&node_id).expect("Guaranteed by db constraints").clone();
     original_node.config = PlanNodeType::Round { config: config.clone(), rounds: rounds.iter().map(|r| r.uuid).collect() };
     all_nodes.insert(node_id, original_node);
 
     let ballots = match config {
-        RoundGroupConfig::Preliminaries { num_roundtrips: _ } => {
+        RoundGroupConfig::Preliminaries { num_roundtrips: _, draw_mode } => {
             let generator = PreliminaryRoundGenerator {
-                draw_mode: PreliminariesDrawMode::AvoidClashes,
-                randomization_scale: 0.5
+                draw_mode: *draw_mode,
+                randomization_scale: 0.5,
+                evaluator_config: DrawEvaluatorConfig::from(&draw_config),
             };
 
             let ballots = generator.generate_draw_for_rounds(
                 &context,
                 rounds.iter().collect(),
                 other_rounds.iter().map(|r| r.uuid).collect(),
-                &mut evaluation_context
+                &mut evaluation_context,
+                &mut rng
             )?;
             ballots
         },
         RoundGroupConfig::FoldDraw { round_configs } => {
-            let team_pairs = round_configs.iter().map(|c| pair_teams(&all_teams.iter().map(|t| t.uuid).collect_vec(), &c.team_fold_method)).collect_vec();
+            let team_pairs = round_configs.iter().map(|c| pair_teams(&all_teams.iter().map(|t| t.uuid).collect_vec(), &c.team_fold_method, &mut rng)).collect_vec();
             
             let mut preceding_round_gov_opp_assignments = if let Some(immediately_preceding_round_id) = immediately_preceding_round_id {
                 let (_, round_ballots) = Ballot::get_all_in_rounds(db, vec![immediately_preceding_round_id]).await?.into_iter().next().expect("Round existence guaranteed by db constraints.");
@@ -232,11 +295,17 @@ async fn generate_round_draw<C>(db: &C, tournament_id: Uuid, node_id: Uuid, conf
                 None
             };
 
+            let previous_ballots = Ballot::get_all_in_rounds(db, other_rounds.iter().map(|r| r.uuid).collect()).await?.into_iter().map(
+                |(_, ballots)| ballots.iter().map(DrawBallot::from).collect_vec()
+            ).collect_vec();
+            let mut position_balance = compute_team_position_balance(&previous_ballots);
+
             let speakers = all_speakers.into_iter().map(|s| s.uuid).collect_vec();
 
             let team_and_speaker_pairs = izip!(team_pairs.into_iter(), round_configs.iter()).map(|(team_pairs, config)| {
-                let team_pairs = assign_teams(team_pairs, config, preceding_round_gov_opp_assignments.as_ref());
-                let speaker_pairs = pair_speakers(&speakers, &config.non_aligned_fold_method);
+                let team_pairs = assign_teams(team_pairs, config, preceding_round_gov_opp_assignments.as_ref(), &position_balance, &mut rng);
+                add_team_pairs_to_position_balance(&mut position_balance, &team_pairs);
+                let speaker_pairs = pair_speakers(&speakers, &config.non_aligned_fold_method, &mut rng);
 
                 if preceding_round_gov_opp_assignments.is_some() {
                     preceding_round_gov_opp_assignments.as_mut().unwrap().clear();
@@ -256,7 +325,32 @@ async fn generate_round_draw<C>(db: &C, tournament_id: Uuid, node_id: Uuid, conf
         },
     };
 
-    let mut optimization_state = OptimizationState::load_from_rounds_and_draw_ballots(db, tournament_id, rounds.iter().zip(ballots.iter()).collect(), OptimizationOptions::default()).await?;
+    // Pinned positions of an existing draw are kept and the rest of the draw is generated around them
+    let existing_debates = TournamentDebate::get_all_in_rounds(db, rounds.iter().map(|r| r.uuid).collect()).await?.into_iter().map(
+        |debates| debates.into_iter().sorted_by_key(|d| d.index).collect_vec()
+    ).collect_vec();
+    let mut ballots = ballots;
+    let mut round_pins = vec![];
+    let mut round_infos = vec![];
+    for (round, round_ballots, round_existing_debates) in izip![rounds.iter(), ballots.iter_mut(), existing_debates.iter()] {
+        let mut pins = round_existing_debates.iter().map(|d| d.pins.clone().unwrap_or_default()).collect_vec();
+        let previous_ballots = if pins.iter().any(|p| !p.is_empty()) {
+            Ballot::get_many(db, round_existing_debates.iter().map(|d| d.ballot_id).collect()).await?
+        }
+        else {
+            vec![]
+        };
+        apply_pinned_teams(round_ballots, &previous_ballots.iter().map(DrawBallot::from).collect_vec(), &mut pins, &team_members);
+
+        let mut debates = round_ballots.iter().map(DebateInfo::from).collect_vec();
+        for (debate, previous_ballot, pins) in izip![debates.iter_mut(), previous_ballots.into_iter(), pins.iter_mut()] {
+            apply_pinned_adjudicators(debate, &DebateInfo::from(previous_ballot), pins, &adjudicator_ids);
+        }
+        round_pins.push(pins);
+        round_infos.push(RoundInfo { id: round.uuid, debates, is_silent: round.is_silent });
+    }
+
+    let mut optimization_state = OptimizationState::load_from_rounds(db, tournament_id, round_infos, OptimizationOptions::from(&draw_config)).await?;
 
     let adjudicators_to_include = if all_adjudicator_ids.len() > 0 {
         Some(&all_adjudicator_ids)
@@ -264,7 +358,7 @@ async fn generate_round_draw<C>(db: &C, tournament_id: Uuid, node_id: Uuid, conf
     else {
         None
     };
-    let evaluator = DrawEvaluator::new(DrawEvaluatorConfig::default(), other_rounds.iter().map(|r| r.uuid).collect(), &evaluation_context);
+    let evaluator = DrawEvaluator::new(DrawEvaluatorConfig::from(&draw_config), other_rounds.iter().map(|r| r.uuid).collect(), &evaluation_context);
     optimization_state.update_state_by_assigning_adjudicators(
         adjudicators_to_include,
         &evaluator
@@ -282,17 +376,25 @@ async fn generate_round_draw<C>(db: &C, tournament_id: Uuid, node_id: Uuid, conf
                     }, ..Default::default()
                     }
                 ).collect();
+                ballot.president = debate.president.map(
+                    |president| DrawAdjudicator { uuid: president, ..Default::default() }.into()
+                );
+                ballot.trainees = debate.trainees.iter().map(
+                    |trainee| DrawAdjudicator { uuid: *trainee, ..Default::default() }.into()
+                ).collect();
                 ballot
             }
         ).collect_vec()
     }).collect_vec();
 
-    let existing_debates = TournamentDebate::get_all_in_rounds(db, rounds.iter().map(|r| r.uuid).collect()).await?;
-    let mut all_venues = TournamentVenue::get_all_in_tournament(db, tournament_id).await?;
-    all_venues.sort_by_key(|v| v.ordering_index);
+    let all_venues = TournamentVenue::get_all_in_tournament(db, tournament_id).await?;
+    let mut adjudicator_buildings = match immediately_preceding_round_id {
+        Some(round_id) => load_adjudicator_buildings(db, round_id, &all_venues).await?,
+        None => HashMap::new()
+    };
 
-    for (round, round_existing_debates, round_new_ballots) in izip![rounds.iter(), existing_debates.into_iter(), ballots.into_iter()] {
-        let debates = if round_existing_debates.len() < round_new_ballots.len() {
+    for (round, round_existing_debates, round_new_ballots, pins) in izip![rounds.iter(), existing_debates.into_iter(), ballots.into_iter(), round_pins.into_iter()] {
+        let mut debates = if round_existing_debates.len() < round_new_ballots.len() {
             let new_debates = (round_existing_debates.len()..round_new_ballots.len()).map(
                 |index| TournamentDebate::new(round.uuid, index as u64, Uuid::nil(), None)
             );
@@ -328,29 +430,26 @@ async fn generate_round_draw<C>(db: &C, tournament_id: Uuid, node_id: Uuid, conf
             round_existing_debates
         };
 
-        // Preserve any previously assigned venues
-        let used_venues = debates.iter().filter_map(|d| d.venue_id).collect::<HashSet<_>>();
-        let available_venues = all_venues.iter().filter(|v| !used_venues.contains(&v.uuid)).collect::<Vec<_>>();
-
-        let selected_venues = if available_venues.len() >= debates.iter().filter(|d| d.venue_id.is_none()).count() {
-            available_venues.iter().map(|v| Some(v.uuid)).collect::<Vec<_>>()
-        } else {
-            itertools::repeat_n(None, debates.len()).collect::<Vec<_>>()
-        };
-        let mut selected_venues = selected_venues.iter();
-
-        for (mut debate, ballot) in izip!(debates.into_iter(), round_new_ballots.into_iter()) {
+        let round_ballots = izip!(debates.iter_mut(), round_new_ballots.into_iter()).map(|(debate, ballot)| {
             let mut real_ballot : Ballot = ballot.into();
             if debate.ballot_id.is_nil() {
                 debate.ballot_id = Uuid::new_v4();
             }
             real_ballot.uuid = debate.ballot_id;
-            //real_ballot.uuid = Uuid::new_v4();
-            //debate.ballot_id = real_ballot.uuid;
+            real_ballot
+        }).collect_vec();
 
-            if debate.venue_id.is_none() {
-                selected_venues.next().map(|v| debate.venue_id = *v);
-            }
+        // Previously assigned venues are preserved by the allocation
+        let venue_infos = izip!(debates.iter(), round_ballots.iter()).map(
+            |(debate, ballot)| VenueDebateInfo::from_ballot(ballot, &team_members, debate.venue_id)
+        ).collect_vec();
+        let venue_ids = allocate_venues(&venue_infos, &all_venues, &participants_requiring_accessibility, &adjudicator_buildings, &tab_team_ranks);
+        update_adjudicator_buildings(&mut adjudicator_buildings, &venue_infos, &venue_ids, &all_venues);
+
+        for (mut debate, real_ballot, venue_id) in izip!(debates.into_iter(), round_ballots.into_iter(), venue_ids.into_iter()) {
+            debate.venue_id = venue_id;
+            // Pins that could not be kept in the new draw are removed
+            debate.pins = pins.get(debate.index as usize).filter(|p| !p.is_empty()).cloned();
 
             changes.add(Entity::Ballot(real_ballot));
             changes.add(Entity::TournamentDebate(debate));
@@ -359,11 +458,18 @@ async fn generate_round_draw<C>(db: &C, tournament_id: Uuid, node_id: Uuid, conf
 
     let all_edges = TournamentPlanEdge::get_all_for_sources(db, all_nodes.iter().map(|n| *n.0).collect()).await?;
 
+    let node_round_ids = rounds.iter().map(|r| r.uuid).collect::<HashSet<_>>();
     let all_rounds = itertools::chain(rounds, other_rounds).collect_vec();
 
     changes.add(Entity::TournamentPlanNode(all_nodes.get(&node_id).expect("Guaranteed by db constraints").clone()));
 
-    reindex_rounds(&all_nodes.into_values().collect(), &all_edges, &all_rounds).into_iter().for_each(
+    let reindexed_rounds = reindex_rounds(&all_nodes.into_values().collect(), &all_edges, &all_rounds);
+    let reindexed_round_ids = reindexed_rounds.iter().map(|r| r.uuid).collect::<HashSet<_>>();
+    // The rounds of this node have a new draw seed, so they are saved even if their index did not change
+    let seeded_rounds = all_rounds.into_iter().filter(
+        |r| node_round_ids.contains(&r.uuid) && !reindexed_round_ids.contains(&r.uuid)
+    ).collect_vec();
+    reindexed_rounds.into_iter().chain(seeded_rounds).for_each(
         |r| {
             changes.add(Entity::TournamentRound(r))
         }
@@ -625,8 +731,11 @@ pub fn get_eligiblity_info(
 }
 
 
-async fn generate_break<C>(db: &C, tournament_id: Uuid, node_id: Uuid, config: &BreakConfig, break_id: Option<Uuid>, eligible_categories: &Vec<TournamentEligibleBreakCategory>, suggested_award_title: &Option<String>, suggested_break_award_prestige: &Option<i32>, participants: &HashMap<Uuid, Participant>) -> Result<EntityGroup, anyhow::Error> where C: sea_orm::ConnectionTrait {
+async fn generate_break<C>(db: &C, tournament_id: Uuid, node_id: Uuid, config: &BreakConfig, break_id: Option<Uuid>, eligible_categories: &Vec<TournamentEligibleBreakCategory>, suggested_award_title: &Option<String>, suggested_break_award_prestige: &Option<i32>, participants: &HashMap<Uuid, Participant>, seed: Option<i64>) -> Result<EntityGroup, anyhow::Error> where C: sea_orm::ConnectionTrait {
     let mut groups = EntityGroup::new(tournament_id);
+    // Ties are broken randomly
+    let seed = choose_draw_seed(seed);
+    let mut rng: StdRng = draw_rng(seed);
 
     let break_background = BreakNodeBackgroundInfo::load_for_break_node(db, tournament_id, node_id).await?;
 
@@ -636,10 +745,13 @@ async fn generate_break<C>(db: &C, tournament_id: Uuid, node_id: Uuid, config: &
 
     let speaker_info = TournamentParticipantsInfo::load(db, tournament_id).await?;
 
+    let ranking = TabRankingConfig::load_for_tournament(db, tournament_id).await?;
+
     let tab = views::tab_view::TabView::load_from_rounds(
         db,
         preceding_rounds.clone(),
-        &speaker_info.team_members
+        &speaker_info.team_members,
+        &ranking
     ).await?;
 
     let eligibility_info = get_eligiblity_info(
@@ -650,22 +762,21 @@ async fn generate_break<C>(db: &C, tournament_id: Uuid, node_id: Uuid, config: &
     let team_ranking = tab.team_tab.iter()
         .filter(
             |t| {
-                eligibility_info.eligible_teams.contains(&t.team_uuid)
+                eligibility_info.eligible_teams.contains(&t.team_uuid) && ranking.team_ranking.is_break_eligible(t.rounds_missed)
             }
         )
-        .map(|t| (ordered_float::NotNan::new(t.total_score + thread_rng().gen_range(0.0..0.000001)).unwrap(), t))
+        .map(|t| ((t.rank, rng.gen::<u64>()), t))
         .sorted_by_key(|t| t.0)
-        .rev().map(|t| t.1.team_uuid).collect_vec();
+        .map(|t| t.1.team_uuid).collect_vec();
 
     let speaker_ranking = tab.speaker_tab.iter()
         .filter(
             |s| {
-                eligibility_info.eligible_speakers.contains(&s.speaker_uuid)
+                eligibility_info.eligible_speakers.contains(&s.speaker_uuid) && ranking.speaker_ranking.is_break_eligible(s.rounds_missed)
             }
         )
-        .map(|s| (ordered_float::NotNan::new(s.total_score + thread_rng().gen_range(0.0..0.000001)).unwrap(), s))
-        .sorted_by_key(|s| s.0 )
-        .rev()
+        .map(|s| ((s.rank, rng.gen::<u64>()), s))
+        .sorted_by_key(|s| s.0)
         .map(|s| s.1.speaker_uuid).collect_vec();
 
     let mut break_ = TournamentBreak::new(tournament_id);
@@ -708,7 +819,7 @@ async fn generate_break<C>(db: &C, tournament_id: Uuid, node_id: Uuid, config: &
                     (Some(gov_total), Some(opp_total)) => {
                         match gov_total.total_cmp(&opp_total) {
                             Ordering::Equal => {
-                                if thread_rng().gen() {
+                                if rng.gen() {
                                     SpeechRole::Government
                                 }
                                 else {
@@ -726,7 +837,7 @@ async fn generate_break<C>(db: &C, tournament_id: Uuid, node_id: Uuid, config: &
                     |s| s.role != winning_role
                 ).collect_vec();
 
-                let best_speech = remaining_speeches.into_iter().sorted_by_cached_key(|s| ordered_float::NotNan::new(s.speaker_score().unwrap_or(0.0)).unwrap() + thread_rng().gen_range(0.0..0.000001)).rev().next().ok_or(MakeBreakError::KORoundIncompleteRound)?;
+                let best_speech = remaining_speeches.into_iter().sorted_by_cached_key(|s| ordered_float::NotNan::new(s.speaker_score().unwrap_or(0.0)).unwrap() + rng.gen_range(0.0..0.000001)).rev().next().ok_or(MakeBreakError::KORoundIncompleteRound)?;
 
                 if winning_role == SpeechRole::Government {
                     let gov = ballot.government.team.ok_or(MakeBreakError::KORoundIncompleteRound)?;
@@ -746,7 +857,7 @@ async fn generate_break<C>(db: &C, tournament_id: Uuid, node_id: Uuid, config: &
             }
 
             let tab_breaking_speakers = tab.speaker_tab.iter()
-            .sorted_by_cached_key(|e| -ordered_float::NotNan::new(e.total_score + thread_rng().gen_range(0.0..0.000001)).unwrap())
+            .sorted_by_cached_key(|e| -ordered_float::NotNan::new(e.total_score + rng.gen_range(0.0..0.000001)).unwrap())
             .filter(
                 |e| {
                     !best_speaker_ids.contains(&e.speaker_uuid)
@@ -848,6 +959,7 @@ async fn generate_break<C>(db: &C, tournament_id: Uuid, node_id: Uuid, config: &
     let break_id = break_.uuid;
     break_.break_award_title = suggested_award_title.clone();
     break_.break_award_prestige = suggested_break_award_prestige.clone();
+    break_.draw_seed = Some(seed);
 
     groups.add(Entity::TournamentBreak(break_));
     let mut original_node = all_nodes.get(&node_id).expect("Guaranteed by db constraints").clone();
@@ -882,13 +994,13 @@ impl ActionTrait for ExecutePlanNodeAction {
 
         let changes = match &node.config {
             open_tab_entities::domain::tournament_plan_node::PlanNodeType::Round { config, rounds } => {
-                generate_round_draw(db, self.tournament_id, node.uuid, config, rounds).await?
+                generate_round_draw(db, self.tournament_id, node.uuid, config, rounds, self.seed).await?
             },
             open_tab_entities::domain::tournament_plan_node::PlanNodeType::Break { config, break_id, eligible_categories, suggested_award_title, suggested_break_award_prestige, .. } => {
                 let participants = Participant::get_all_in_tournament(db, self.tournament_id).await?.into_iter().map(
                     |p| (p.uuid, p)
                 ).collect::<HashMap<_, _>>();
-                generate_break(db, self.tournament_id, node.uuid, config, break_id.clone(), eligible_categories, suggested_award_title, suggested_break_award_prestige, &participants).await?
+                generate_break(db, self.tournament_id, node.uuid, config, break_id.clone(), eligible_categories, suggested_award_title, suggested_break_award_prestige, &participants, self.seed).await?
             },
         };
 
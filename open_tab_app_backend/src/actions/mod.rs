@@ -23,12 +23,20 @@ mod update_clashes_action;
 mod discard_ballot;
 mod update_feedback_system;
 mod create_break_category;
+mod apply_adjudicator_rating_suggestions;
+mod update_score_categories;
+mod update_tab_ranking;
+mod update_draw_config;
+mod update_ballot_validation_config;
+mod create_swing_team;
+mod update_speaker_substitutions;
+mod resolve_merge_conflict;
 
 pub use self::base::ActionTrait;
 pub use self::update_draw::UpdateDrawAction;
 pub use self::update_participant::UpdateParticipantsAction;
 pub use self::upload_participants_list::UploadParticipantsListAction;
-pub use self::update_scores::UpdateScoresAction;
+pub use self::update_scores::{UpdateScoresAction, ScoreUpdate};
 pub use self::edit_tree::EditTreeAction;
 pub use self::execute_plan_node::ExecutePlanNodeAction;
 pub use self::set_manual_break::SetManualBreakAction;
@@ -39,12 +47,20 @@ pub use self::import_feedback_system::ImportFeedbackSystemAction;
 pub use self::set_adjudicator_break::SetAdjudicatorBreakAction;
 pub use self::update_teams::UpdateTeamsAction;
 pub use self::set_break_release::SetBreakReleaseAction;
-pub use self::redraw_round::RedrawRoundAction;
+pub use self::redraw_round::{RedrawRoundAction, RedrawMode};
 pub use self::update_tournament::UpdateTournamentAction;
 pub use self::update_clashes_action::UpdateClashes;
 pub use self::discard_ballot::DiscardBallotAction;
 pub use self::update_feedback_system::UpdateFeedbackSystemAction;
 pub use self::create_break_category::CreateBreakCategoryAction;
+pub use self::apply_adjudicator_rating_suggestions::ApplyAdjudicatorRatingSuggestionsAction;
+pub use self::update_score_categories::UpdateScoreCategoriesAction;
+pub use self::update_tab_ranking::UpdateTabRankingAction;
+pub use self::update_draw_config::UpdateDrawConfigAction;
+pub use self::update_ballot_validation_config::UpdateBallotValidationConfigAction;
+pub use self::create_swing_team::CreateSwingTeamAction;
+pub use self::update_speaker_substitutions::{UpdateSpeakerSubstitutionsAction, NewSpeakerSubstitution};
+pub use self::resolve_merge_conflict::ResolveMergeConflictAction;
 
 pub(crate) use self::edit_tree::EditTreeActionType;
 
@@ -71,6 +87,14 @@ pub enum Action {
     DiscardBallot { action: DiscardBallotAction },
     UpdateFeedbackSystem { action: UpdateFeedbackSystemAction },
     CreateBreakCategory { action: CreateBreakCategoryAction },
+    ApplyAdjudicatorRatingSuggestions { action: ApplyAdjudicatorRatingSuggestionsAction },
+    UpdateScoreCategories { action: UpdateScoreCategoriesAction },
+    UpdateTabRanking { action: UpdateTabRankingAction },
+    UpdateDrawConfig { action: UpdateDrawConfigAction },
+    UpdateBallotValidationConfig { action: UpdateBallotValidationConfigAction },
+    CreateSwingTeam { action: CreateSwingTeamAction },
+    UpdateSpeakerSubstitutions { action: UpdateSpeakerSubstitutionsAction },
+    ResolveMergeConflict { action: ResolveMergeConflictAction },
 }
 
 impl Action {
@@ -96,6 +120,14 @@ impl Action {
             Action::DiscardBallot { action } => action.get_changes(db).await,
             Action::UpdateFeedbackSystem { action } => action.get_changes(db).await,
             Action::CreateBreakCategory { action } => action.get_changes(db).await,
+            Action::ApplyAdjudicatorRatingSuggestions { action } => action.get_changes(db).await,
+            Action::UpdateScoreCategories { action } => action.get_changes(db).await,
+            Action::UpdateTabRanking { action } => action.get_changes(db).await,
+            Action::UpdateDrawConfig { action } => action.get_changes(db).await,
+            Action::UpdateBallotValidationConfig { action } => action.get_changes(db).await,
+            Action::CreateSwingTeam { action } => action.get_changes(db).await,
+            Action::UpdateSpeakerSubstitutions { action } => action.get_changes(db).await,
+            Action::ResolveMergeConflict { action } => action.get_changes(db).await,
         }
     }
 }
@@ -2,12 +2,12 @@ use std::{sync::Arc, collections::{HashSet, HashMap}, cmp::Ordering};
 
 use itertools::{Itertools, izip, repeat_n};
 use async_trait::async_trait;
-use open_tab_entities::{prelude::*, domain::{tournament_break::TournamentBreak, tournament_venue::TournamentVenue, tournament_plan_node::{TournamentPlanNode, RoundGroupConfig, PlanNodeType, BreakConfig}, entity::LoadEntity, tournament_plan_edge::TournamentPlanEdge, self, ballot}, EntityTypeId, tab::TeamRoundRole, derived_models::{BreakNodeBackgroundInfo, NodeExecutionError}};
+use open_tab_entities::{prelude::*, domain::{tournament::DrawConfig, tournament_break::TournamentBreak, tournament_venue::TournamentVenue, tournament_plan_node::{TournamentPlanNode, RoundGroupConfig, PlanNodeType, BreakConfig}, entity::LoadEntity, tournament_plan_edge::TournamentPlanEdge, self, ballot}, EntityTypeId, tab::TeamRoundRole, derived_models::{BreakNodeBackgroundInfo, NodeExecutionError}};
 
-use rand::{seq::SliceRandom, thread_rng, Rng};
+use rand::seq::SliceRandom;
 use sea_orm::{prelude::*, QueryOrder};
 
-use crate::{draw::{PreliminaryRoundGenerator, PreliminariesDrawMode, evaluation::DrawEvaluator, preliminary::{RoundGenerationContext, DrawTeamInfo}, tab_draw::{pair_teams, pair_speakers, TeamPair, assign_teams}, flow_optimization::{OptimizationState, OptimizationOptions}}, TournamentParticipantsInfo, draw_view::{DrawBallot, DrawTeam, DrawSpeaker, DrawAdjudicator, SetDrawAdjudicator}, views};
+use crate::{draw::{PreliminaryRoundGenerator, PreliminariesDrawMode, evaluation::DrawEvaluator, preliminary::{RoundGenerationContext, DrawTeamInfo}, tab_draw::{pair_teams, pair_speakers, TeamPair, assign_teams}, flow_optimization::{OptimizationState, OptimizationOptions}, venues::{allocate_venues, load_adjudicator_buildings, load_team_ranks, VenueDebateInfo}, pins::{reassign_unpinned_teams, clear_unpinned_adjudicators}, repair::compute_draw_repair, seed::{choose_draw_seed, draw_rng}, evaluation::{DrawConstructionEvaluationContext, DrawEvaluatorConfig}, datastructures::{DebateInfo, RoundInfo}}, TournamentParticipantsInfo, draw_view::{DrawBallot, DrawTeam, DrawSpeaker, DrawAdjudicator, SetDrawAdjudicator}, views};
 use serde::{Serialize, Deserialize};
 
 use super::{ActionTrait, edit_tree::reindex_rounds};
@@ -18,7 +18,16 @@ use thiserror::Error;
 #[serde(tag = "mode")]
 pub enum RedrawMode {
     Venues,
-    MissingNonAligned
+    MissingNonAligned,
+    /// Re-runs team and adjudicator allocation, keeping all pinned positions
+    AroundPins,
+    /// Changes as few positions as possible to account for withdrawn or added teams and participants
+    RepairDraw {
+        #[serde(default)]
+        withdrawn_participants: Vec<Uuid>,
+        #[serde(default)]
+        added_participants: Vec<Uuid>
+    }
 }
 
 
@@ -26,7 +35,11 @@ pub enum RedrawMode {
 pub struct RedrawRoundAction {
     pub round_id: Uuid,
     #[serde(flatten)]
-    pub mode: RedrawMode
+    pub mode: RedrawMode,
+    /// Seed for modes that make random choices. If not set, a new seed is chosen
+    /// and stored on the round.
+    #[serde(default)]
+    pub seed: Option<i64>
 }
 
 
@@ -43,31 +56,138 @@ impl ActionTrait for RedrawRoundAction {
 
         match &self.mode {
             RedrawMode::Venues => {
+                let debates = TournamentDebate::get_all_in_rounds(db, vec![self.round_id]).await?.into_iter().next().unwrap_or_default()
+                    .into_iter().sorted_by_key(|d| d.index).collect_vec();
+                let ballots = domain::ballot::Ballot::get_many(db, debates.iter().map(|d| d.ballot_id).collect()).await?;
+                let venues = TournamentVenue::get_all_in_tournament(db, round.tournament_id).await?;
+                let info = TournamentParticipantsInfo::load(db, round.tournament_id).await?;
 
-                let debates = open_tab_entities::schema::tournament_debate::Entity::find()
-                    .filter(open_tab_entities::schema::tournament_debate::Column::RoundId.eq(self.round_id))
-                    .order_by_asc(open_tab_entities::schema::tournament_debate::Column::Index)
-                    .all(db)
-                    .await?;
+                let participants_requiring_accessibility = info.participants_by_id.values().filter(
+                    |p| p.requires_accessible_venue
+                ).map(|p| p.uuid).collect::<HashSet<_>>();
 
-                let venues = open_tab_entities::schema::tournament_venue::Entity::find()
-                    .filter(
-                        open_tab_entities::schema::tournament_venue::Column::TournamentId.eq(round.tournament_id)
-                    )
-                    .order_by_asc(
-                        open_tab_entities::schema::tournament_venue::Column::OrderingIndex
-                    )
-                    .all(db)
+                let previous_round = open_tab_entities::schema::tournament_round::Entity::find()
+                    .filter(open_tab_entities::schema::tournament_round::Column::TournamentId.eq(round.tournament_id))
+                    .filter(open_tab_entities::schema::tournament_round::Column::Index.eq(round.index - 1))
+                    .one(db)
                     .await?;
+                let previous_adjudicator_buildings = match previous_round {
+                    Some(previous_round) => load_adjudicator_buildings(db, previous_round.uuid, &venues).await?,
+                    None => HashMap::new()
+                };
+
+                let earlier_rounds = TournamentRound::get_all_in_tournament(db, round.tournament_id).await?.into_iter()
+                    .filter(|r| (r.index as i64) < round.index as i64).map(|r| r.uuid).collect_vec();
+                let team_ranks = load_team_ranks(db, round.tournament_id, earlier_rounds, &info.team_members).await?;
+
+                let debate_infos = ballots.iter().map(
+                    |b| VenueDebateInfo::from_ballot(b, &info.team_members, None)
+                ).collect_vec();
+                let venue_ids = allocate_venues(&debate_infos, &venues, &participants_requiring_accessibility, &previous_adjudicator_buildings, &team_ranks);
+
                 let mut g = EntityGroup::new(
                     round.tournament_id
                 );
-                let mut venue_iter = venues.into_iter();
-                for mut debate in debates.into_iter() {
-                    debate.venue_id = venue_iter.next().map(|v| v.uuid);
-                    g.add(Entity::TournamentDebate(
-                        domain::debate::TournamentDebate::from_model(debate)
-                    ));
+                for (mut debate, venue_id) in debates.into_iter().zip(venue_ids) {
+                    debate.venue_id = venue_id;
+                    g.add(Entity::TournamentDebate(debate));
+                }
+
+                Ok(g)
+            },
+            RedrawMode::AroundPins => {
+                let debates = TournamentDebate::get_all_in_rounds(db, vec![self.round_id]).await?.into_iter().next().unwrap_or_default()
+                    .into_iter().sorted_by_key(|d| d.index).collect_vec();
+                let ballots = domain::ballot::Ballot::get_many(db, debates.iter().map(|d| d.ballot_id).collect()).await?;
+                let mut pins = debates.iter().map(|d| d.pins.clone().unwrap_or_default()).collect_vec();
+
+                let other_rounds = TournamentRound::get_all_in_tournament(db, round.tournament_id).await?.into_iter()
+                    .map(|r| r.uuid).filter(|r| *r != self.round_id).collect_vec();
+                let evaluation_context = DrawConstructionEvaluationContext::new_from_tournament(db, round.tournament_id).await?;
+                let draw_config = DrawConfig::load_for_tournament(db, round.tournament_id).await?;
+                let evaluator = DrawEvaluator::new(DrawEvaluatorConfig::from(&draw_config), other_rounds, &evaluation_context);
+
+                // Adjudicators are allocated after the teams, so they should not influence the team allocation
+                let team_ballots = ballots.iter().map(|b| DrawBallot {
+                    adjudicators: vec![],
+                    president: None,
+                    trainees: vec![],
+                    ..DrawBallot::from(b)
+                }).collect_vec();
+                let seed = choose_draw_seed(self.seed);
+                let team_ballots = reassign_unpinned_teams(&team_ballots, &pins, &evaluator, 0.5, &mut draw_rng(seed))?;
+
+                let adjudicator_ids = Participant::get_all_adjudicators_in_tournament(db, round.tournament_id).await?.into_iter().map(|p| p.uuid).collect::<HashSet<_>>();
+                let debate_infos = izip![team_ballots.iter(), ballots.iter(), pins.iter_mut()].map(|(team_ballot, ballot, pins)| {
+                    let previous_info = DebateInfo::from(ballot.clone());
+                    let mut info = DebateInfo {
+                        chair: previous_info.chair,
+                        wings: previous_info.wings,
+                        president: previous_info.president,
+                        ..DebateInfo::from(team_ballot)
+                    };
+                    clear_unpinned_adjudicators(&mut info, pins, &adjudicator_ids);
+                    info
+                }).collect_vec();
+
+                let mut optimization_state = OptimizationState::load_from_rounds(
+                    db,
+                    round.tournament_id,
+                    vec![RoundInfo { id: round.uuid, debates: debate_infos, is_silent: round.is_silent }],
+                    OptimizationOptions::from(&draw_config)
+                ).await?;
+                optimization_state.update_state_by_assigning_adjudicators(None, &evaluator);
+                let debate_infos = optimization_state.rounds.into_iter().next().map(|r| r.debates).unwrap_or_default();
+
+                let mut g = EntityGroup::new(
+                    round.tournament_id
+                );
+                g.add(Entity::TournamentRound(TournamentRound {
+                    draw_seed: Some(seed),
+                    ..TournamentRound::from_model(round.clone())
+                }));
+                for (mut debate, mut ballot, team_ballot, info, pins) in izip![debates, ballots, team_ballots, debate_infos, pins] {
+                    let new_ballot = DrawBallot {
+                        adjudicators: info.chair.iter().chain(info.wings.iter()).map(
+                            |uuid| DrawAdjudicator { uuid: *uuid, ..Default::default() }.into()
+                        ).collect(),
+                        president: info.president.map(|uuid| DrawAdjudicator { uuid, ..Default::default() }.into()),
+                        trainees: info.trainees.iter().map(
+                            |uuid| DrawAdjudicator { uuid: *uuid, ..Default::default() }.into()
+                        ).collect(),
+                        ..team_ballot
+                    };
+                    update_ballot_positions(&mut ballot, &new_ballot);
+                    g.add(Entity::Ballot(ballot));
+
+                    let pins = if pins.is_empty() { None } else { Some(pins) };
+                    if debate.pins != pins {
+                        debate.pins = pins;
+                        g.add(Entity::TournamentDebate(debate));
+                    }
+                }
+
+                Ok(g)
+            },
+            RedrawMode::RepairDraw { withdrawn_participants, added_participants } => {
+                let (debates, ballots, repair) = compute_draw_repair(db, self.round_id, withdrawn_participants, added_participants).await?;
+
+                let mut g = EntityGroup::new(
+                    round.tournament_id
+                );
+                let changed_debates = repair.changes.iter().map(|c| c.debate_index).collect::<HashSet<_>>();
+                for (debate_idx, (mut ballot, new_ballot)) in ballots.into_iter().zip(repair.ballots.iter()).enumerate() {
+                    if changed_debates.contains(&debate_idx) {
+                        update_ballot_positions(&mut ballot, new_ballot);
+                        g.add(Entity::Ballot(ballot));
+                    }
+                }
+                for (mut debate, pins) in debates.into_iter().zip(repair.pins.into_iter()) {
+                    let pins = if pins.is_empty() { None } else { Some(pins) };
+                    if debate.pins.clone().unwrap_or_default() != pins.clone().unwrap_or_default() {
+                        debate.pins = pins;
+                        g.add(Entity::TournamentDebate(debate));
+                    }
                 }
 
                 Ok(g)
@@ -108,10 +228,15 @@ impl ActionTrait for RedrawRoundAction {
 
                 let desired_non_aligned_speakers = non_aligned_teams.iter().flat_map(|t| info.team_members[t].iter()).collect_vec();//.flatten().cloned().collect_vec();
 
-                let mut missing = desired_non_aligned_speakers.into_iter().filter(|s| !used_speakers.contains(s)).collect_vec();
+                // Sorted first, so that the shuffle only depends on the seed
+                let mut missing = desired_non_aligned_speakers.into_iter().filter(|s| !used_speakers.contains(s)).sorted().collect_vec();
 
-                let mut rng = thread_rng();
-                missing.shuffle(&mut rng);
+                let seed = choose_draw_seed(self.seed);
+                missing.shuffle(&mut draw_rng(seed));
+                g.add(Entity::TournamentRound(TournamentRound {
+                    draw_seed: Some(seed),
+                    ..TournamentRound::from_model(round.clone())
+                }));
 
                 let mut missing_iter = missing.into_iter();
 
@@ -141,4 +266,37 @@ impl ActionTrait for RedrawRoundAction {
             }
         }
     }
-}
\ No newline at end of file
+}
+
+/// Updates the teams, speakers and adjudicators of a ballot to match the draw ballot.
+/// Scores of adjudicators that are no longer on the panel are removed.
+fn update_ballot_positions(ballot: &mut Ballot, draw_ballot: &DrawBallot) {
+    ballot.government.team = draw_ballot.government.as_ref().map(|t| t.uuid);
+    ballot.opposition.team = draw_ballot.opposition.as_ref().map(|t| t.uuid);
+    for speech in ballot.speeches.iter_mut().filter(|s| s.role == SpeechRole::NonAligned) {
+        if let Some(speaker) = draw_ballot.non_aligned_speakers.get(speech.position as usize) {
+            speech.speaker = speaker.as_ref().map(|s| s.uuid);
+        }
+    }
+
+    let adjudicators = draw_ballot.adjudicators.iter().map(|a| a.adjudicator.uuid).collect_vec();
+    for removed_adjudicator in ballot.adjudicators.iter().filter(|a| !adjudicators.contains(a)) {
+        ballot.government.scores.remove(removed_adjudicator);
+        ballot.opposition.scores.remove(removed_adjudicator);
+        ballot.speeches.iter_mut().for_each(|speech| {
+            speech.scores.remove(removed_adjudicator);
+        });
+    }
+    ballot.adjudicators = adjudicators;
+    ballot.president = draw_ballot.president.as_ref().map(|p| p.adjudicator.uuid);
+
+    let trainees = draw_ballot.trainees.iter().map(|a| a.adjudicator.uuid).collect_vec();
+    for removed_trainee in ballot.trainees.iter().filter(|a| !trainees.contains(a)) {
+        ballot.government.trainee_scores.remove(removed_trainee);
+        ballot.opposition.trainee_scores.remove(removed_trainee);
+        ballot.speeches.iter_mut().for_each(|speech| {
+            speech.trainee_scores.remove(removed_trainee);
+        });
+    }
+    ballot.trainees = trainees;
+}
@@ -0,0 +1,82 @@
+use async_trait::async_trait;
+use open_tab_entities::{group::EntityGroupEntityTrait, schema, Entity, EntityGroup, EntityTypeId};
+use sea_orm::{prelude::Uuid, EntityTrait};
+use serde::{Serialize, Deserialize};
+
+use crate::actions::ActionTrait;
+
+/// Resolves a conflict reported by a three-way merge during sync.
+/// Conflicting fields keep their local value, so this only needs to be
+/// executed if the remote value should be used instead.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ResolveMergeConflictAction {
+    pub tournament_id: Uuid,
+    pub entity_type: EntityTypeId,
+    pub entity_uuid: Uuid,
+    /// JSON pointer into the serialized entity, as reported in the conflict.
+    /// Empty to replace the whole entity.
+    pub path: String,
+    /// The value to use. None removes the field, or deletes the entity if the path is empty.
+    pub value: Option<serde_json::Value>,
+}
+
+fn unescape_pointer_token(token: &str) -> String {
+    token.replace("~1", "/").replace("~0", "~")
+}
+
+fn set_value_at_pointer(target: &mut serde_json::Value, path: &str, value: Option<serde_json::Value>) -> Result<(), anyhow::Error> {
+    let (parent_path, key) = path.rsplit_once('/').ok_or_else(|| anyhow::anyhow!("Invalid path {}", path))?;
+    let key = unescape_pointer_token(key);
+
+    let parent = target.pointer_mut(parent_path).ok_or_else(|| anyhow::anyhow!("Path {} does not exist", parent_path))?;
+    let parent = parent.as_object_mut().ok_or_else(|| anyhow::anyhow!("Path {} is not an object", parent_path))?;
+
+    match value {
+        Some(value) => { parent.insert(key, value); },
+        None => { parent.remove(&key); }
+    }
+    Ok(())
+}
+
+#[async_trait]
+impl ActionTrait for ResolveMergeConflictAction {
+    async fn get_changes<C>(self, db: &C) -> Result<EntityGroup, anyhow::Error> where C: sea_orm::ConnectionTrait {
+        let mut g = EntityGroup::new(self.tournament_id);
+
+        let current = Entity::try_get_many_with_type(db, self.entity_type, vec![self.entity_uuid]).await?.pop().flatten();
+
+        // Deleted entities can only be found via the entity registry
+        let entity_tournament = match current {
+            Some(_) => EntityTypeId::try_get_tournaments_with_type(db, self.entity_type, vec![self.entity_uuid]).await?.pop().flatten(),
+            None => schema::tournament_entity::Entity::find_by_id(self.entity_uuid).one(db).await?.map(|e| e.tournament_id)
+        };
+        if entity_tournament != Some(self.tournament_id) {
+            return Err(anyhow::anyhow!("Entity {} is not part of the tournament", self.entity_uuid));
+        }
+
+        let new_value = if self.path.is_empty() {
+            self.value
+        }
+        else {
+            let current = current.ok_or_else(|| anyhow::anyhow!("Entity {} does not exist", self.entity_uuid))?;
+            let mut current_value = serde_json::to_value(current)?;
+            set_value_at_pointer(&mut current_value, &self.path, self.value)?;
+            Some(current_value)
+        };
+
+        match new_value {
+            Some(value) => {
+                let entity: Entity = serde_json::from_value(value)?;
+                if entity.get_type() != self.entity_type || entity.get_uuid() != self.entity_uuid {
+                    return Err(anyhow::anyhow!("Resolved value does not match the conflicting entity"));
+                }
+                g.add(entity);
+            },
+            None => {
+                g.delete(self.entity_type, self.entity_uuid);
+            }
+        }
+
+        Ok(g)
+    }
+}
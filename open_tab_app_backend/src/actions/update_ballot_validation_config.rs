@@ -0,0 +1,37 @@
+use async_trait::async_trait;
+use open_tab_entities::{domain::{entity::LoadEntity, ballot_validation::BallotValidationConfig}, prelude::Tournament, Entity, EntityGroup};
+use sea_orm::prelude::Uuid;
+use serde::{Serialize, Deserialize};
+
+use crate::ActionTrait;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UpdateBallotValidationConfigAction {
+    pub tournament_id: Uuid,
+    /// Setting this to None accepts every ballot
+    pub ballot_validation_config: Option<BallotValidationConfig>,
+}
+
+#[async_trait]
+impl ActionTrait for UpdateBallotValidationConfigAction {
+    async fn get_changes<C>(self, db: &C) -> Result<EntityGroup, anyhow::Error> where C: sea_orm::ConnectionTrait {
+        let mut g = EntityGroup::new(
+            self.tournament_id
+        );
+
+        let tournament = Tournament::get(db, self.tournament_id).await?;
+
+        g.add(
+            Entity::Tournament(
+                Tournament {
+                    ballot_validation_config: self.ballot_validation_config,
+                    ..tournament
+                }
+            )
+        );
+
+        Ok(
+            g
+        )
+    }
+}
@@ -65,6 +65,7 @@ impl ActionTrait for UpdateDrawAction {
                         position: i as u8,
                         scores: HashMap::new(),
                         is_opt_out: false,
+                        trainee_scores: HashMap::new(),
                     });
                 }
             }
@@ -94,6 +95,17 @@ impl ActionTrait for UpdateDrawAction {
 
             new_ballot.president = if let Some(president) = &debate.president {Some(president.adjudicator.uuid)} else {None};
 
+            let old_trainees = new_ballot.trainees.clone();
+            new_ballot.trainees = debate.trainees.iter().map(|t| t.adjudicator.uuid).collect_vec();
+
+            for deleted_trainee in old_trainees.iter().filter(|uuid| !new_ballot.trainees.contains(uuid)) {
+                new_ballot.government.trainee_scores.remove(deleted_trainee);
+                new_ballot.opposition.trainee_scores.remove(deleted_trainee);
+                new_ballot.speeches.iter_mut().for_each(|speech| {
+                    speech.trainee_scores.remove(deleted_trainee);
+                });
+            }
+
             groups.add(Entity::Ballot(new_ballot));
         }
 
@@ -142,6 +154,10 @@ impl ActionTrait for UpdateDrawAction {
                 }
             }
 
+            if let Some(pins) = debate.pins {
+                existing_debate.pins = if pins.is_empty() { None } else { Some(pins) };
+            }
+
             groups.add(Entity::TournamentDebate(existing_debate));
         }
         Ok(groups)
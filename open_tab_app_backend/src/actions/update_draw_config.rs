@@ -0,0 +1,37 @@
+use async_trait::async_trait;
+use open_tab_entities::{domain::{entity::LoadEntity, tournament::DrawConfig}, prelude::Tournament, Entity, EntityGroup};
+use sea_orm::prelude::Uuid;
+use serde::{Serialize, Deserialize};
+
+use crate::ActionTrait;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UpdateDrawConfigAction {
+    pub tournament_id: Uuid,
+    /// Setting this to None restores the default draw weights
+    pub draw_config: Option<DrawConfig>,
+}
+
+#[async_trait]
+impl ActionTrait for UpdateDrawConfigAction {
+    async fn get_changes<C>(self, db: &C) -> Result<EntityGroup, anyhow::Error> where C: sea_orm::ConnectionTrait {
+        let mut g = EntityGroup::new(
+            self.tournament_id
+        );
+
+        let tournament = Tournament::get(db, self.tournament_id).await?;
+
+        g.add(
+            Entity::Tournament(
+                Tournament {
+                    draw_config: self.draw_config,
+                    ..tournament
+                }
+            )
+        );
+
+        Ok(
+            g
+        )
+    }
+}
@@ -49,7 +49,8 @@ impl ActionTrait for UpdateParticipantsAction {
                             let new_team = domain::team::Team {
                                 uuid: Uuid::new_v4(),
                                 name: new_team_name.clone(),
-                                tournament_id: self.tournament_id
+                                tournament_id: self.tournament_id,
+                                is_swing: false,
                             };
                             let new_uuid = new_team.uuid;
                             new_teams_created.insert(new_team_name.clone(), new_uuid);
@@ -70,7 +71,7 @@ impl ActionTrait for UpdateParticipantsAction {
 
             let role = match participant_role {
                 crate::participants_list_view::ParticipantRole::Speaker { team_info: ParticipantTeamInfo::Existing { team_id } } => ParticipantRole::Speaker(Speaker { team_id: Some(team_id) }),
-                crate::participants_list_view::ParticipantRole::Adjudicator { chair_skill, panel_skill, unavailable_rounds } => ParticipantRole::Adjudicator(Adjudicator { chair_skill, panel_skill, unavailable_rounds }),
+                crate::participants_list_view::ParticipantRole::Adjudicator { chair_skill, panel_skill, unavailable_rounds, is_trainee } => ParticipantRole::Adjudicator(Adjudicator { chair_skill, panel_skill, unavailable_rounds, is_trainee }),
                 _ => unreachable!("Should not be possible to have a new team here")
             };
 
@@ -88,6 +89,7 @@ impl ActionTrait for UpdateParticipantsAction {
                     registration_key: Some(registration_key.to_vec()),
                     is_anonymous: participant.is_anonymous,
                     break_category_id: participant.break_category_id,
+                    requires_accessible_venue: participant.requires_accessible_venue,
                 }
             ));
         }
@@ -149,7 +151,8 @@ impl ActionTrait for UpdateParticipantsAction {
                             let new_team = domain::team::Team {
                                 uuid: Uuid::new_v4(),
                                 name: new_team_name.clone(),
-                                tournament_id: self.tournament_id
+                                tournament_id: self.tournament_id,
+                                is_swing: false,
                             };
                             let new_uuid = new_team.uuid;    
                             new_teams_created.insert(new_team_name.clone(), new_uuid);
@@ -170,7 +173,7 @@ impl ActionTrait for UpdateParticipantsAction {
 
             let role = match participant_role {
                 crate::participants_list_view::ParticipantRole::Speaker { team_info: ParticipantTeamInfo::Existing { team_id } } => ParticipantRole::Speaker(Speaker { team_id: Some(team_id) }),
-                crate::participants_list_view::ParticipantRole::Adjudicator { chair_skill, panel_skill, unavailable_rounds } => ParticipantRole::Adjudicator(Adjudicator { chair_skill, panel_skill, unavailable_rounds }),
+                crate::participants_list_view::ParticipantRole::Adjudicator { chair_skill, panel_skill, unavailable_rounds, is_trainee } => ParticipantRole::Adjudicator(Adjudicator { chair_skill, panel_skill, unavailable_rounds, is_trainee }),
                 _ => unreachable!("Should not be possible to have a new team here")
             };
 
@@ -187,6 +190,7 @@ impl ActionTrait for UpdateParticipantsAction {
                     registration_key: participant.registration_key.map(|r| general_purpose::URL_SAFE_NO_PAD.decode(r).map(|r| r[16..48].to_vec())).transpose()?,
                     is_anonymous: participant.is_anonymous,
                     break_category_id: participant.break_category_id,
+                    requires_accessible_venue: participant.requires_accessible_venue,
                 }
             ));
         }
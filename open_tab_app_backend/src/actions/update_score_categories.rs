@@ -0,0 +1,54 @@
+use std::collections::HashSet;
+
+use async_trait::async_trait;
+use open_tab_entities::{domain::{entity::LoadEntity, tournament::ScoreCategorySchema}, prelude::Tournament, Entity, EntityGroup};
+use sea_orm::prelude::Uuid;
+use serde::{Serialize, Deserialize};
+
+use crate::ActionTrait;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UpdateScoreCategoriesAction {
+    tournament_id: Uuid,
+    /// Setting this to None switches the tournament back to aggregate scores
+    score_categories: Option<ScoreCategorySchema>,
+}
+
+#[async_trait]
+impl ActionTrait for UpdateScoreCategoriesAction {
+    async fn get_changes<C>(self, db: &C) -> Result<EntityGroup, anyhow::Error> where C: sea_orm::ConnectionTrait {
+        let mut g = EntityGroup::new(
+            self.tournament_id
+        );
+
+        if let Some(schema) = &self.score_categories {
+            for categories in [&schema.speech_categories, &schema.team_categories] {
+                if categories.iter().map(|c| &c.key).collect::<HashSet<_>>().len() != categories.len() {
+                    anyhow::bail!("Score category keys must be unique");
+                }
+                if categories.iter().any(|c| c.max_score < 0) {
+                    anyhow::bail!("Maximum scores must not be negative");
+                }
+                // Totals of itemized scores are stored as i16
+                if categories.iter().try_fold(0i16, |total, c| total.checked_add(c.max_score)).is_none() {
+                    anyhow::bail!("The maximum scores of all categories are too large");
+                }
+            }
+        }
+
+        let tournament = Tournament::get(db, self.tournament_id).await?;
+
+        g.add(
+            Entity::Tournament(
+                Tournament {
+                    score_categories: self.score_categories,
+                    ..tournament
+                }
+            )
+        );
+
+        Ok(
+            g
+        )
+    }
+}
@@ -1,7 +1,7 @@
 
 
 use async_trait::async_trait;
-use open_tab_entities::{prelude::*, domain::debate_backup_ballot::DebateBackupBallot};
+use open_tab_entities::{prelude::*, domain::{ballot_validation::BallotValidationConfig, debate_backup_ballot::DebateBackupBallot}};
 
 use sea_orm::prelude::*;
 
@@ -30,9 +30,12 @@ impl ActionTrait for UpdateScoresAction {
         debate.is_complete = true;
         let round = open_tab_entities::domain::round::TournamentRound::get(db, debate.round_id).await?;
         let mut groups = EntityGroup::new(round.tournament_id);
+        let validation_config = BallotValidationConfig::load_for_tournament(db, round.tournament_id).await?;
         match self.update {
             ScoreUpdate::SetBallot(uuid) => {
                 let mut backup_ballot = DebateBackupBallot::get(db, uuid).await?;
+                let ballot = Ballot::get(db, backup_ballot.ballot_id).await?;
+                validation_config.validate(&ballot)?;
                 debate.ballot_id = backup_ballot.ballot_id;
                 backup_ballot.was_seen = true;
 
@@ -44,6 +47,7 @@ impl ActionTrait for UpdateScoresAction {
             },
             ScoreUpdate::NewBallot(display_ballot) => {
                 let mut ballot : Ballot = display_ballot.into();
+                validation_config.validate(&ballot)?;
                 ballot.uuid = Uuid::new_v4();
                 debate.ballot_id = ballot.uuid;
                 let backup_ballot = DebateBackupBallot {
@@ -51,7 +55,8 @@ impl ActionTrait for UpdateScoresAction {
                     debate_id: self.debate_id,
                     ballot_id: ballot.uuid,
                     timestamp: chrono::offset::Local::now().naive_local(),
-                    was_seen: true
+                    was_seen: true,
+                    author_participant_id: None,
                 };
                 groups.add(Entity::Ballot(ballot));
                 groups.add(Entity::TournamentDebate(debate));
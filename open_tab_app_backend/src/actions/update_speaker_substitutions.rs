@@ -0,0 +1,76 @@
+use async_trait::async_trait;
+use open_tab_entities::{domain::{entity::LoadEntity, speaker_substitution::SpeakerSubstitution}, prelude::*, EntityTypeId};
+use sea_orm::prelude::Uuid;
+use serde::{Serialize, Deserialize};
+
+use crate::actions::ActionTrait;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NewSpeakerSubstitution {
+    pub round_id: Uuid,
+    pub team_id: Uuid,
+    pub speaker_id: Uuid,
+    #[serde(default)]
+    pub replaced_speaker_id: Option<Uuid>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UpdateSpeakerSubstitutionsAction {
+    pub tournament_id: Uuid,
+    #[serde(default)]
+    pub added_substitutions: Vec<NewSpeakerSubstitution>,
+    #[serde(default)]
+    pub deleted_substitutions: Vec<Uuid>,
+}
+
+#[async_trait]
+impl ActionTrait for UpdateSpeakerSubstitutionsAction {
+    async fn get_changes<C>(self, db: &C) -> Result<EntityGroup, anyhow::Error> where C: sea_orm::ConnectionTrait {
+        let mut g = EntityGroup::new(self.tournament_id);
+
+        for substitution in self.added_substitutions {
+            let round = TournamentRound::get(db, substitution.round_id).await?;
+            if round.tournament_id != self.tournament_id {
+                return Err(anyhow::anyhow!("Round {} is not part of the tournament", round.uuid));
+            }
+            let team = Team::get(db, substitution.team_id).await?;
+            if team.tournament_id != self.tournament_id {
+                return Err(anyhow::anyhow!("{} is not part of the tournament", team.name));
+            }
+
+            let speaker = Participant::get(db, substitution.speaker_id).await?;
+            if speaker.tournament_id != self.tournament_id {
+                return Err(anyhow::anyhow!("{} is not part of the tournament", speaker.name));
+            }
+            if !matches!(speaker.role, ParticipantRole::Speaker(_)) {
+                return Err(anyhow::anyhow!("{} is not a speaker", speaker.name));
+            }
+
+            if let Some(replaced_speaker_id) = substitution.replaced_speaker_id {
+                let replaced_speaker = Participant::get(db, replaced_speaker_id).await?;
+                match replaced_speaker.role {
+                    ParticipantRole::Speaker(Speaker { team_id: Some(team_id) }) if team_id == substitution.team_id => {},
+                    _ => return Err(anyhow::anyhow!("{} is not a member of the team", replaced_speaker.name))
+                }
+            }
+
+            g.add(Entity::SpeakerSubstitution(SpeakerSubstitution::new(
+                substitution.round_id,
+                substitution.team_id,
+                substitution.speaker_id,
+                substitution.replaced_speaker_id
+            )));
+        }
+
+        let deleted_substitutions = SpeakerSubstitution::get_many(db, self.deleted_substitutions.clone()).await?;
+        let deleted_tournaments = SpeakerSubstitution::get_many_tournaments(db, &deleted_substitutions.iter().collect()).await?;
+        if deleted_tournaments.iter().any(|t| *t != Some(self.tournament_id)) {
+            return Err(anyhow::anyhow!("Substitution is not part of the tournament"));
+        }
+        for uuid in self.deleted_substitutions {
+            g.delete(EntityTypeId::SpeakerSubstitution, uuid);
+        }
+
+        Ok(g)
+    }
+}
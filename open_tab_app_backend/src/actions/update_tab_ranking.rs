@@ -0,0 +1,37 @@
+use async_trait::async_trait;
+use open_tab_entities::{domain::entity::LoadEntity, prelude::Tournament, tab::TabRankingConfig, Entity, EntityGroup};
+use sea_orm::prelude::Uuid;
+use serde::{Serialize, Deserialize};
+
+use crate::ActionTrait;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UpdateTabRankingAction {
+    tournament_id: Uuid,
+    /// Setting this to None restores ranking by total score
+    tab_ranking_config: Option<TabRankingConfig>,
+}
+
+#[async_trait]
+impl ActionTrait for UpdateTabRankingAction {
+    async fn get_changes<C>(self, db: &C) -> Result<EntityGroup, anyhow::Error> where C: sea_orm::ConnectionTrait {
+        let mut g = EntityGroup::new(
+            self.tournament_id
+        );
+
+        let tournament = Tournament::get(db, self.tournament_id).await?;
+
+        g.add(
+            Entity::Tournament(
+                Tournament {
+                    tab_ranking_config: self.tab_ranking_config,
+                    ..tournament
+                }
+            )
+        );
+
+        Ok(
+            g
+        )
+    }
+}
@@ -1,6 +1,6 @@
 
 
-use open_tab_entities::{EntityGroup, Entity, domain::team::Team};
+use open_tab_entities::{EntityGroup, Entity, domain::{entity::LoadEntity, team::Team}};
 use sea_orm::prelude::Uuid;
 use async_trait::async_trait;
 use serde::{Serialize, Deserialize};
@@ -20,22 +20,28 @@ pub struct UpdateTeamsAction {
 pub struct TeamUpdateRequest {
     uuid: Uuid,
     name: String,
+    /// Keeps the current swing status if not set
+    #[serde(default)]
+    is_swing: Option<bool>,
 }
 
 
 #[async_trait]
 impl ActionTrait for UpdateTeamsAction {
-    async fn get_changes<C>(self, _db: &C) -> Result<EntityGroup, anyhow::Error> where C: sea_orm::ConnectionTrait {
+    async fn get_changes<C>(self, db: &C) -> Result<EntityGroup, anyhow::Error> where C: sea_orm::ConnectionTrait {
         let mut g = EntityGroup::new(
             self.tournament_id
         );
 
-        for request in self.updates {
+        let existing_teams = Team::try_get_many(db, self.updates.iter().map(|r| r.uuid).collect()).await?;
+
+        for (request, existing_team) in self.updates.into_iter().zip(existing_teams.into_iter()) {
             g.add(
                 Entity::Team(Team {
                     uuid: request.uuid,
                     name: request.name,
-                    tournament_id: self.tournament_id
+                    tournament_id: self.tournament_id,
+                    is_swing: request.is_swing.unwrap_or_else(|| existing_team.map(|t| t.is_swing).unwrap_or(false)),
                 })
             );
         }
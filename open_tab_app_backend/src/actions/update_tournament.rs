@@ -12,6 +12,10 @@ pub struct UpdateTournamentAction {
     tournament_id: Uuid,
     allow_self_declared_clashes: bool,
     allow_speaker_self_declared_clashes: bool,
+    #[serde(default)]
+    allow_self_declared_availability: Option<bool>,
+    #[serde(default)]
+    availability_deadline_minutes: Option<i32>,
 }
 
 #[async_trait]
@@ -29,6 +33,8 @@ impl ActionTrait for UpdateTournamentAction {
                     uuid: self.tournament_id,
                     allow_self_declared_clashes: self.allow_self_declared_clashes,
                     allow_speaker_self_declared_clashes: self.allow_speaker_self_declared_clashes,
+                    allow_self_declared_availability: self.allow_self_declared_availability.unwrap_or(tournament.allow_self_declared_availability),
+                    availability_deadline_minutes: self.availability_deadline_minutes.unwrap_or(tournament.availability_deadline_minutes),
                     ..tournament
                 }
             )
@@ -35,6 +35,10 @@ impl ActionTrait for UpdateVenuesAction {
                         uuid: venue.uuid,
                         name: venue.name,
                         ordering_index: venue.ordering_index,
+                        capacity: venue.capacity,
+                        is_accessible: venue.is_accessible,
+                        building: venue.building,
+                        is_top_room: venue.is_top_room,
                         tournament_id: self.tournament_id
                     }
                 )
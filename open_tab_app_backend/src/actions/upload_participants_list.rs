@@ -52,6 +52,7 @@ impl ActionTrait for UploadParticipantsListAction {
                 uuid: team_uuid,
                 name: team.name,
                 tournament_id: self.tournament_id,
+                is_swing: false,
             };
             groups.add(Entity::Team(team_entity));
 
@@ -82,6 +83,7 @@ impl ActionTrait for UploadParticipantsListAction {
                         chair_skill,
                         panel_skill,
                         unavailable_rounds: Vec::new(),
+                        is_trainee: false,
                     }
                 ),
                 Uuid::new_v4()
@@ -210,7 +212,8 @@ impl UploadParticipantsListAction {
                 tournament_id: self.tournament_id,
                 registration_key: Some(registration_key.to_vec()),
                 is_anonymous: participant.is_anonymous.unwrap_or(false),
-                break_category_id: break_category
+                break_category_id: break_category,
+                requires_accessible_venue: false,
             };
 
             out_entities.push(Entity::Participant(out_participant_entity));
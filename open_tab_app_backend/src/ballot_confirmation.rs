@@ -0,0 +1,234 @@
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+use itertools::Itertools;
+use open_tab_entities::{domain::{ballot::SpeechRole, ballot_validation::{BallotConfirmationPolicy, BallotValidationConfig}, debate_backup_ballot::DebateBackupBallot, entity::LoadEntity}, prelude::*, EntityTypeId};
+use sea_orm::prelude::Uuid;
+use serde::{Serialize, Deserialize};
+
+
+/// A part of a ballot in which two submissions can disagree
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(tag="type")]
+pub enum BallotField {
+    Team { role: SpeechRole },
+    Speaker { role: SpeechRole, position: u8 },
+    SpeechScore { role: SpeechRole, position: u8, adjudicator_id: Uuid },
+    TeamScore { role: SpeechRole, adjudicator_id: Uuid },
+    Adjudicators,
+    President,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag="type")]
+pub enum BallotFieldValue {
+    Id { uuid: Option<Uuid> },
+    Ids { uuids: Vec<Uuid> },
+    Score { score: Score },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BallotFieldDifference {
+    pub field: BallotField,
+    /// Value of the field in each submission, keyed by the backup ballot id.
+    /// Submissions that leave the field empty are `None`.
+    pub values: HashMap<Uuid, Option<BallotFieldValue>>,
+}
+
+fn ballot_fields(ballot: &Ballot) -> BTreeMap<BallotField, BallotFieldValue> {
+    let mut fields = BTreeMap::new();
+
+    fields.insert(BallotField::Adjudicators, BallotFieldValue::Ids { uuids: ballot.adjudicators.clone() });
+    fields.insert(BallotField::President, BallotFieldValue::Id { uuid: ballot.president });
+
+    for (role, team) in [(SpeechRole::Government, &ballot.government), (SpeechRole::Opposition, &ballot.opposition)] {
+        fields.insert(BallotField::Team { role }, BallotFieldValue::Id { uuid: team.team });
+        for (adjudicator_id, score) in team.scores.iter() {
+            fields.insert(BallotField::TeamScore { role, adjudicator_id: *adjudicator_id }, BallotFieldValue::Score { score: score.total() });
+        }
+    }
+
+    for speech in ballot.speeches.iter() {
+        fields.insert(BallotField::Speaker { role: speech.role, position: speech.position }, BallotFieldValue::Id { uuid: speech.speaker });
+        for (adjudicator_id, score) in speech.scores.iter() {
+            fields.insert(
+                BallotField::SpeechScore { role: speech.role, position: speech.position, adjudicator_id: *adjudicator_id },
+                BallotFieldValue::Score { score: score.total() }
+            );
+        }
+    }
+
+    fields
+}
+
+/// Compares submissions field by field and returns every field in which they do not all agree.
+/// Trainee scores are not compared, since they do not count towards the result.
+pub fn find_ballot_differences(submissions: &[(Uuid, &Ballot)]) -> Vec<BallotFieldDifference> {
+    let submission_fields = submissions.iter().map(|(uuid, ballot)| (*uuid, ballot_fields(ballot))).collect_vec();
+    let all_fields : BTreeSet<&BallotField> = submission_fields.iter().flat_map(|(_, fields)| fields.keys()).collect();
+
+    all_fields.into_iter().filter_map(|field| {
+        let values : HashMap<Uuid, Option<BallotFieldValue>> = submission_fields.iter().map(
+            |(uuid, fields)| (*uuid, fields.get(field).cloned())
+        ).collect();
+
+        if values.values().all_equal() {
+            None
+        }
+        else {
+            Some(BallotFieldDifference { field: field.clone(), values })
+        }
+    }).collect()
+}
+
+/// Whether a submission was made for the current draw of the debate
+fn matches_draw(current_ballot: &Ballot, submitted_ballot: &Ballot) -> bool {
+    let non_aligned_speakers = |ballot: &Ballot| ballot.speeches.iter().filter(|s| s.role == SpeechRole::NonAligned).map(|s| s.speaker).collect_vec();
+
+    current_ballot.government.team == submitted_ballot.government.team
+        && current_ballot.opposition.team == submitted_ballot.opposition.team
+        && non_aligned_speakers(current_ballot) == non_aligned_speakers(submitted_ballot)
+}
+
+/// Picks the submissions that confirm a result according to the policy.
+/// The first returned submission becomes the ballot of the debate.
+fn select_confirmed_submissions<'a>(
+    policy: BallotConfirmationPolicy,
+    current_ballot: &Ballot,
+    submissions: &[(&'a DebateBackupBallot, &Ballot)]
+) -> Vec<&'a DebateBackupBallot> {
+    match policy {
+        BallotConfirmationPolicy::Manual => vec![],
+        BallotConfirmationPolicy::FirstSubmission => submissions.first().map(|(backup, _)| *backup).into_iter().collect(),
+        BallotConfirmationPolicy::MatchingSubmissions | BallotConfirmationPolicy::ChairOrMatchingSubmissions => {
+            if policy == BallotConfirmationPolicy::ChairOrMatchingSubmissions {
+                let chair = current_ballot.adjudicators.first();
+                if let Some((backup, _)) = submissions.iter().find(|(backup, _)| chair.is_some() && backup.author_participant_id.as_ref() == chair) {
+                    return vec![*backup];
+                }
+            }
+
+            submissions.iter().tuple_combinations().find(|((backup_1, ballot_1), (backup_2, ballot_2))| {
+                let is_independent = match (backup_1.author_participant_id, backup_2.author_participant_id) {
+                    (Some(author_1), Some(author_2)) => author_1 != author_2,
+                    _ => false
+                };
+                is_independent && find_ballot_differences(&[(backup_1.uuid, ballot_1), (backup_2.uuid, ballot_2)]).is_empty()
+            }).map(|((backup_1, _), (backup_2, _))| vec![*backup_1, *backup_2]).unwrap_or_default()
+        }
+    }
+}
+
+/// Confirms the results of debates that received new submissions, if the tournament's
+/// confirmation policy allows it. Debates that already have results are left to the tab director.
+pub async fn confirm_submitted_ballots<C>(changes: &EntityGroup, db: &C) -> Result<Option<EntityGroup>, anyhow::Error> where C: sea_orm::ConnectionTrait {
+    let groups = changes.as_group_map();
+    if groups.debate_backup_ballots.is_empty() {
+        return Ok(None);
+    }
+
+    let config = BallotValidationConfig::load_for_tournament(db, changes.tournament_id).await?;
+    if config.confirmation_policy == BallotConfirmationPolicy::Manual {
+        return Ok(None);
+    }
+
+    let debate_ids = groups.debate_backup_ballots.iter().map(|b| b.debate_id).unique().collect_vec();
+    let debates = TournamentDebate::get_many(db, debate_ids).await?;
+
+    let mut new_changes = EntityGroup::new(changes.tournament_id);
+    let mut has_changes = false;
+
+    for debate in debates.into_iter() {
+        let current_ballot = Ballot::get(db, debate.ballot_id).await?;
+        if current_ballot.is_scored() {
+            continue;
+        }
+
+        let pending_submissions = DebateBackupBallot::get_all_for_debate(db, debate.uuid).await?.into_iter()
+            .filter(|b| !b.was_seen && b.ballot_id != debate.ballot_id)
+            .sorted_by_key(|b| b.timestamp)
+            .collect_vec();
+        let ballots_by_id : HashMap<Uuid, Ballot> = Ballot::get_many(db, pending_submissions.iter().map(|b| b.ballot_id).collect()).await?
+            .into_iter().map(|b| (b.uuid, b)).collect();
+
+        let valid_submissions = pending_submissions.iter().filter_map(|backup| {
+            let ballot = ballots_by_id.get(&backup.ballot_id)?;
+            (config.validate(ballot).is_ok() && matches_draw(&current_ballot, ballot)).then_some((backup, ballot))
+        }).collect_vec();
+
+        let confirmed = select_confirmed_submissions(config.confirmation_policy, &current_ballot, &valid_submissions);
+        let Some(accepted) = confirmed.first() else {
+            continue;
+        };
+
+        new_changes.add(Entity::TournamentDebate(TournamentDebate {
+            ballot_id: accepted.ballot_id,
+            is_complete: true,
+            ..debate.clone()
+        }));
+        new_changes.delete(EntityTypeId::Ballot, current_ballot.uuid);
+
+        for backup in confirmed.into_iter() {
+            new_changes.add(Entity::DebateBackupBallot(DebateBackupBallot {
+                was_seen: true,
+                ..backup.clone()
+            }));
+        }
+        has_changes = true;
+    }
+
+    Ok(has_changes.then_some(new_changes))
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::HashMap;
+
+    use open_tab_entities::{domain::ballot::{BallotTeam, SpeakerScore, Speech, SpeechRole}, prelude::{Ballot, Score}};
+    use sea_orm::prelude::Uuid;
+
+    use super::{find_ballot_differences, BallotField, BallotFieldValue};
+
+    fn ballot(score: i16) -> Ballot {
+        let adjudicator_id = Uuid::from_u128(1);
+        Ballot {
+            adjudicators: vec![adjudicator_id],
+            government: BallotTeam { team: Some(Uuid::from_u128(10)), ..Default::default() },
+            opposition: BallotTeam { team: Some(Uuid::from_u128(11)), ..Default::default() },
+            speeches: vec![
+                Speech {
+                    speaker: Some(Uuid::from_u128(20)),
+                    role: SpeechRole::Government,
+                    position: 0,
+                    scores: HashMap::from([(adjudicator_id, SpeakerScore::new_aggregate(score.into()))]),
+                    is_opt_out: false,
+                    trainee_scores: HashMap::new(),
+                }
+            ],
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_identical_ballots_have_no_differences() {
+        let ballot_1 = ballot(70);
+        let mut ballot_2 = ballot(70);
+        ballot_2.uuid = Uuid::from_u128(100);
+
+        assert_eq!(find_ballot_differences(&[(Uuid::from_u128(1000), &ballot_1), (Uuid::from_u128(1001), &ballot_2)]), vec![]);
+    }
+
+    #[test]
+    fn test_differences_are_reported_per_field() {
+        let ballot_1 = ballot(70);
+        let mut ballot_2 = ballot(72);
+        ballot_2.speeches[0].speaker = None;
+
+        let differences = find_ballot_differences(&[(Uuid::from_u128(1000), &ballot_1), (Uuid::from_u128(1001), &ballot_2)]);
+        let fields = differences.iter().map(|d| d.field.clone()).collect::<Vec<_>>();
+        assert_eq!(fields, vec![
+            BallotField::Speaker { role: SpeechRole::Government, position: 0 },
+            BallotField::SpeechScore { role: SpeechRole::Government, position: 0, adjudicator_id: Uuid::from_u128(1) },
+        ]);
+        assert_eq!(differences[1].values.get(&Uuid::from_u128(1001)), Some(&Some(BallotFieldValue::Score { score: Score::from_points(72) })));
+    }
+}
@@ -0,0 +1,144 @@
+use std::collections::HashMap;
+
+use itertools::Itertools;
+use open_tab_entities::prelude::{Ballot, TournamentRound};
+use sea_orm::prelude::Uuid;
+use serde::{Serialize, Deserialize};
+
+/// Number of pseudo-observations of zero deviation that are added to every adjudicator.
+/// This keeps single outlier ballots from marking an adjudicator as extremely harsh or lenient.
+const PRIOR_WEIGHT: f64 = 3.0;
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct AdjudicatorScoringStatistics {
+    /// Average difference between the adjudicator's scores and the mean of their co-panelists.
+    /// Positive values indicate a lenient, negative values a harsh adjudicator.
+    pub bias: f64,
+    /// Mean squared spread of the adjudicator's deviations around their bias.
+    pub variance: f64,
+    /// Number of scores that could be compared to co-panelists.
+    pub num_compared_scores: usize,
+}
+
+impl AdjudicatorScoringStatistics {
+    fn from_deviations(deviations: &[f64]) -> Self {
+        let num_compared_scores = deviations.len();
+        let bias = deviations.iter().sum::<f64>() / (num_compared_scores as f64 + PRIOR_WEIGHT);
+        let variance = deviations.iter().map(|d| (d - bias).powi(2)).sum::<f64>() / (num_compared_scores as f64 + PRIOR_WEIGHT);
+
+        AdjudicatorScoringStatistics {
+            bias,
+            variance,
+            num_compared_scores
+        }
+    }
+
+    pub fn compute_from_ballots<'a, I>(ballots: I) -> HashMap<Uuid, AdjudicatorScoringStatistics> where I: IntoIterator<Item=&'a Ballot> {
+        let mut deviations : HashMap<Uuid, Vec<f64>> = HashMap::new();
+
+        for ballot in ballots {
+            let speech_scores = ballot.speeches.iter().filter(|s| !s.is_opt_out).map(
+                |s| s.scores.iter().map(|(adj, score)| (*adj, score.total().points())).collect_vec()
+            );
+            let team_scores = vec![&ballot.government, &ballot.opposition].into_iter().map(
+                |t| t.scores.iter().map(|(adj, score)| (*adj, score.total().points())).collect_vec()
+            );
+
+            for scores in speech_scores.chain(team_scores) {
+                if scores.len() < 2 {
+                    continue;
+                }
+
+                let total = scores.iter().map(|(_, s)| s).sum::<f64>();
+                for (adj, score) in scores.iter() {
+                    let co_panelist_mean = (total - score) / (scores.len() - 1) as f64;
+                    deviations.entry(*adj).or_default().push(score - co_panelist_mean);
+                }
+            }
+        }
+
+        deviations.into_iter().map(|(adj, deviations)| (adj, Self::from_deviations(&deviations))).collect()
+    }
+
+    pub async fn load_for_tournament<C>(db: &C, tournament_id: Uuid) -> Result<HashMap<Uuid, AdjudicatorScoringStatistics>, anyhow::Error> where C: sea_orm::ConnectionTrait {
+        let rounds = TournamentRound::get_all_in_tournament(db, tournament_id).await?;
+        let ballots = Ballot::get_all_in_rounds(db, rounds.into_iter().map(|r| r.uuid).collect()).await?;
+
+        Ok(Self::compute_from_ballots(ballots.iter().flat_map(|(_, ballots)| ballots.iter())))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::HashMap;
+
+    use open_tab_entities::prelude::{Ballot, BallotTeam, Speech, SpeechRole, SpeakerScore, TeamScore};
+    use sea_orm::prelude::Uuid;
+
+    use super::AdjudicatorScoringStatistics;
+
+    fn make_ballot(speech_scores: Vec<Vec<(u128, i16)>>, team_scores: Vec<(u128, i16)>) -> Ballot {
+        Ballot {
+            uuid: Uuid::new_v4(),
+            speeches: speech_scores.into_iter().enumerate().map(|(position, scores)| Speech {
+                speaker: None,
+                role: SpeechRole::Government,
+                position: position as u8,
+                scores: scores.into_iter().map(|(adj, score)| (Uuid::from_u128(adj), SpeakerScore::new_aggregate(score.into()))).collect(),
+                is_opt_out: false,
+                trainee_scores: HashMap::new()
+            }).collect(),
+            government: BallotTeam {
+                team: None,
+                scores: team_scores.into_iter().map(|(adj, score)| (Uuid::from_u128(adj), TeamScore::new_aggregate(score.into()))).collect::<HashMap<_, _>>(),
+                trainee_scores: HashMap::new()
+            },
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_single_adjudicator_has_no_statistics() {
+        let ballot = make_ballot(vec![vec![(1, 70)]], vec![(1, 35)]);
+        let statistics = AdjudicatorScoringStatistics::compute_from_ballots(vec![&ballot]);
+        assert!(statistics.is_empty());
+    }
+
+    #[test]
+    fn test_harsh_adjudicator_has_negative_bias() {
+        let ballots = (0..5).map(|_| make_ballot(
+            vec![vec![(1, 70), (2, 70), (3, 64)], vec![(1, 72), (2, 72), (3, 66)]],
+            vec![]
+        )).collect::<Vec<_>>();
+        let statistics = AdjudicatorScoringStatistics::compute_from_ballots(ballots.iter());
+
+        assert_eq!(statistics[&Uuid::from_u128(3)].num_compared_scores, 10);
+        assert!(statistics[&Uuid::from_u128(3)].bias < -4.0);
+        assert!(statistics[&Uuid::from_u128(1)].bias > 0.0);
+        assert!(statistics[&Uuid::from_u128(1)].bias < statistics[&Uuid::from_u128(3)].bias.abs());
+    }
+
+    #[test]
+    fn test_erratic_adjudicator_has_higher_variance() {
+        let ballots = vec![
+            make_ballot(vec![vec![(1, 70), (2, 70), (3, 60)]], vec![]),
+            make_ballot(vec![vec![(1, 70), (2, 71), (3, 80)]], vec![]),
+            make_ballot(vec![vec![(1, 70), (2, 70), (3, 62)]], vec![]),
+            make_ballot(vec![vec![(1, 70), (2, 69), (3, 78)]], vec![]),
+        ];
+        let statistics = AdjudicatorScoringStatistics::compute_from_ballots(ballots.iter());
+
+        assert!(statistics[&Uuid::from_u128(3)].variance > statistics[&Uuid::from_u128(2)].variance);
+        assert!(statistics[&Uuid::from_u128(3)].bias.abs() < 1.0);
+    }
+
+    #[test]
+    fn test_team_scores_are_compared() {
+        let ballot = make_ballot(vec![], vec![(1, 40), (2, 30)]);
+        let statistics = AdjudicatorScoringStatistics::compute_from_ballots(vec![&ballot]);
+
+        assert_eq!(statistics[&Uuid::from_u128(1)].num_compared_scores, 1);
+        assert!(statistics[&Uuid::from_u128(1)].bias > 0.0);
+        assert!(statistics[&Uuid::from_u128(2)].bias < 0.0);
+    }
+}
@@ -20,6 +20,8 @@ pub struct AdjudicatorInfo {
 
     pub bias: f32,
     pub variance: f32,
+
+    pub is_trainee: bool,
 }
 
 
@@ -31,6 +33,7 @@ pub struct DebateInfo {
     pub chair: Option<Uuid>,
     pub wings: Vec<Uuid>,
     pub president: Option<Uuid>,
+    pub trainees: Vec<Uuid>,
     pub non_aligned_speakers: Vec<Uuid>,
 }
 
@@ -46,7 +49,8 @@ impl From<Ballot> for DebateInfo {
                 domain::ballot::SpeechRole::NonAligned => Some(s.speaker).flatten(),
                 _ => None
             }).collect_vec(),
-            president: ballot.president
+            president: ballot.president,
+            trainees: ballot.trainees
         }
     }
 }
@@ -65,6 +69,7 @@ impl Into<Ballot> for DebateInfo {
                         position,
                         scores: HashMap::new(),
                         is_opt_out: false,
+                        trainee_scores: HashMap::new(),
                     }
                 )
             }
@@ -77,6 +82,7 @@ impl Into<Ballot> for DebateInfo {
                     position: idx as u8,
                     scores: HashMap::new(),
                     is_opt_out: false,
+                    trainee_scores: HashMap::new(),
                 }
             )
         );
@@ -94,7 +100,8 @@ impl Into<Ballot> for DebateInfo {
             adjudicators: self.chair.iter().chain(
                 self.wings.iter()
             ).cloned().collect_vec(),
-            president: self.president
+            president: self.president,
+            trainees: self.trainees
         }
     }
 }
@@ -108,7 +115,8 @@ impl From<&DrawBallot> for DebateInfo {
             chair: ballot.adjudicators.get(0).map(|a| a.adjudicator.uuid),
             wings: ballot.adjudicators.iter().skip(1).map(|a| a.adjudicator.uuid).collect_vec(),
             non_aligned_speakers: ballot.non_aligned_speakers.iter().filter_map(|s| s.as_ref().map(|s| s.uuid)).collect_vec(),
-            president: ballot.president.as_ref().map(|p| p.adjudicator.uuid)
+            president: ballot.president.as_ref().map(|p| p.adjudicator.uuid),
+            trainees: ballot.trainees.iter().map(|t| t.adjudicator.uuid).collect_vec()
         }
     }
 }
@@ -3,7 +3,7 @@ use std::collections::{HashMap, HashSet};
 use crate::{draw_view::DrawBallot, participants_list_view::Clash, TournamentParticipantsInfo};
 use itertools::{izip, Itertools};
 use open_tab_entities::{
-    domain::{ballot::{self, BallotParseError}, entity::LoadEntity, participant_clash::ParticipantClash},
+    domain::{ballot::{self, BallotParseError}, entity::LoadEntity, participant_clash::ParticipantClash, tournament::DrawConfig},
     prelude::{Ballot, Participant, ParticipantRole, SpeechRole, TournamentDebate, TournamentRound},
     schema::{self, adjudicator::Entity}, EntityGroup, EntityTypeId,
 };
@@ -42,19 +42,25 @@ pub struct DrawEvaluatorConfig {
 
 impl Default for DrawEvaluatorConfig {
     fn default() -> Self {
+        DrawEvaluatorConfig::from(&DrawConfig::default())
+    }
+}
+
+impl From<&DrawConfig> for DrawEvaluatorConfig {
+    fn from(config: &DrawConfig) -> Self {
         DrawEvaluatorConfig {
-            adj_adj_clash_factor: 0.3,
-            adj_team_clash_factor: 1.0,
-            adj_speaker_clash_factor: 0.5,
-            team_team_clash_factor: 0.2,
-            team_speaker_clash_factor: 0.1,
-            speaker_speaker_clash_factor: 0.1,
-            adj_adj_repeat_clash_severity: 40,
-            adj_team_repeat_clash_severity: 40,
-            adj_non_aligned_speaker_repeat_clash_severity: 40,
-            team_team_repeat_clash_severity: 10,
-            team_speaker_repeat_clash_severity: 10,
-            non_aligned_speakers_repeat_clash_severity: 10,
+            adj_adj_clash_factor: *config.adj_adj_clash_factor,
+            adj_team_clash_factor: *config.adj_team_clash_factor,
+            adj_speaker_clash_factor: *config.adj_speaker_clash_factor,
+            team_team_clash_factor: *config.team_team_clash_factor,
+            team_speaker_clash_factor: *config.team_speaker_clash_factor,
+            speaker_speaker_clash_factor: *config.speaker_speaker_clash_factor,
+            adj_adj_repeat_clash_severity: config.adj_adj_repeat_clash_severity,
+            adj_team_repeat_clash_severity: config.adj_team_repeat_clash_severity,
+            adj_non_aligned_speaker_repeat_clash_severity: config.adj_non_aligned_speaker_repeat_clash_severity,
+            team_team_repeat_clash_severity: config.team_team_repeat_clash_severity,
+            team_speaker_repeat_clash_severity: config.team_speaker_repeat_clash_severity,
+            non_aligned_speakers_repeat_clash_severity: config.non_aligned_speakers_repeat_clash_severity,
         }
     }
 }
@@ -184,6 +190,12 @@ impl ParticipantsDebateHistory {
         }
     }
 
+    fn remove_round_entries(&mut self, round_ids: &[Uuid]) {
+        for (_, round_activties) in self.participant_debates.iter_mut() {
+            round_activties.retain(|round_id, _| !round_ids.contains(round_id));
+        }
+    }
+
     fn add_from_ballot(
         &mut self,
         round_id: Uuid,
@@ -409,6 +421,12 @@ impl DrawConstructionEvaluationContext {
         )
     }
     
+    /// Forgets the existing debates of the given rounds, so that a redraw
+    /// of these rounds does not depend on their previous draw.
+    pub fn remove_rounds(&mut self, round_ids: &[Uuid]) {
+        self.history.remove_round_entries(round_ids);
+    }
+
     pub fn add_round_ballots(&mut self, round_id: Uuid, ballots: Vec<&DrawBallot>) {
         for ballot in ballots.into_iter() {
             //This is *not* the debate id that the debate will be saved as.
@@ -659,10 +677,11 @@ impl<'a, C> DrawEvaluator<'a, C> {
             ClashType::DeclaredClash { severity } => *severity,
             ClashType::InstitutionalClash { severity, .. } => *severity,
             ClashType::SameTeamClash => 1000,
-            ClashType::SpeakersHaveMetAsNonAligned { round } => 10,
-            ClashType::SpeakersHaveMetAsTeamAndNonAligned { round } => 10,
-            ClashType::SpeakersHaveMetAsTeam { round } => 10,
-            ClashType::JudgeHasSeenSpeaker { round, judge_was_chair, speaker_was_in_team } => 10,
+            ClashType::SpeakersHaveMetAsNonAligned { .. } => self.config.non_aligned_speakers_repeat_clash_severity,
+            ClashType::SpeakersHaveMetAsTeamAndNonAligned { .. } => self.config.team_speaker_repeat_clash_severity,
+            ClashType::SpeakersHaveMetAsTeam { .. } => self.config.team_team_repeat_clash_severity,
+            ClashType::JudgeHasSeenSpeaker { speaker_was_in_team: true, .. } => self.config.adj_team_repeat_clash_severity,
+            ClashType::JudgeHasSeenSpeaker { speaker_was_in_team: false, .. } => self.config.adj_non_aligned_speaker_repeat_clash_severity,
         }
     }
 
@@ -1720,4 +1739,30 @@ mod test {
         }
     }
 }
-*/
\ No newline at end of file
+*/
+
+#[cfg(test)]
+mod config_test {
+    use sea_orm::prelude::Uuid;
+
+    use crate::draw::clashes::ClashType;
+
+    use super::{DrawEvaluator, DrawEvaluatorConfig};
+
+    #[test]
+    fn test_repeat_severities_follow_config() {
+        let config = DrawEvaluatorConfig {
+            adj_team_repeat_clash_severity: 500,
+            adj_non_aligned_speaker_repeat_clash_severity: 300,
+            non_aligned_speakers_repeat_clash_severity: 0,
+            ..Default::default()
+        };
+        let evaluator = DrawEvaluator::new(config, vec![], &());
+        let round = Uuid::from_u128(1);
+
+        assert_eq!(evaluator.get_base_severity(&ClashType::JudgeHasSeenSpeaker { round, judge_was_chair: true, speaker_was_in_team: true }), 500);
+        assert_eq!(evaluator.get_base_severity(&ClashType::JudgeHasSeenSpeaker { round, judge_was_chair: false, speaker_was_in_team: false }), 300);
+        assert_eq!(evaluator.get_base_severity(&ClashType::SpeakersHaveMetAsNonAligned { round }), 0);
+        assert_eq!(evaluator.get_base_severity(&ClashType::SpeakersHaveMetAsTeam { round }), 10);
+    }
+}
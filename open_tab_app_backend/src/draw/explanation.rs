@@ -0,0 +1,221 @@
+use itertools::Itertools;
+use sea_orm::prelude::Uuid;
+use serde::{Deserialize, Serialize};
+
+use crate::draw_view::{DrawBallot, DrawSpeaker, DrawTeam};
+
+use super::{datastructures::DebateInfo, evaluation::{DrawConstructionEvaluationContext, DrawEvaluator, DrawIssue}, flow_optimization::{AdjudicatorCost, OptimizationState}, repair::DrawPosition};
+
+
+/// Number of runner-up options listed for each position
+const MAX_ALTERNATIVES: usize = 3;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DebateCostReport {
+    pub debate_index: usize,
+    pub positions: Vec<PositionCostReport>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PositionCostReport {
+    pub position: DrawPosition,
+    pub chosen: CostOption,
+    /// The cheapest other options for this position, cheapest first
+    pub alternatives: Vec<CostOption>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CostOption {
+    pub participant_id: Uuid,
+    /// Total cost of the option, `None` if it is ruled out by a hard clash
+    pub total: Option<i32>,
+    /// Clashes and repeated meetings that make up the cost of team and non-aligned positions
+    pub issues: Vec<DrawIssue>,
+    /// Cost terms of adjudicator positions
+    pub adjudicator_cost: Option<AdjudicatorCost>,
+    /// Whether the difference to the chosen option is small enough
+    /// that the random term of the allocation could have decided between them
+    pub within_randomization: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum TeamSlot {
+    Government,
+    Opposition,
+    NonAligned(usize),
+}
+
+impl TeamSlot {
+    fn position(&self) -> DrawPosition {
+        match self {
+            TeamSlot::Government => DrawPosition::Government,
+            TeamSlot::Opposition => DrawPosition::Opposition,
+            TeamSlot::NonAligned(position) => DrawPosition::NonAligned { position: *position },
+        }
+    }
+
+    fn get(&self, ballot: &DrawBallot) -> Option<Uuid> {
+        match self {
+            TeamSlot::Government => ballot.government.as_ref().map(|t| t.uuid),
+            TeamSlot::Opposition => ballot.opposition.as_ref().map(|t| t.uuid),
+            TeamSlot::NonAligned(position) => ballot.non_aligned_speakers.get(*position).cloned().flatten().map(|s| s.uuid),
+        }
+    }
+
+    fn with(&self, ballot: &DrawBallot, occupant: Uuid) -> DrawBallot {
+        let mut ballot = ballot.clone();
+        match self {
+            TeamSlot::Government => ballot.government = Some(DrawTeam { uuid: occupant, ..Default::default() }),
+            TeamSlot::Opposition => ballot.opposition = Some(DrawTeam { uuid: occupant, ..Default::default() }),
+            TeamSlot::NonAligned(position) => ballot.non_aligned_speakers[*position] = Some(DrawSpeaker { uuid: occupant, ..Default::default() }),
+        }
+        ballot
+    }
+
+    fn evaluate(&self, ballot: &DrawBallot, occupant: Uuid, evaluator: &DrawEvaluator<DrawConstructionEvaluationContext>) -> CostOption {
+        let ballot = self.with(ballot, occupant);
+        let result = evaluator.find_issues_in_ballot(&ballot);
+        let total = result.total_severity() as i32;
+        let issues = match self {
+            TeamSlot::Government => result.government_issues,
+            TeamSlot::Opposition => result.opposition_issues,
+            TeamSlot::NonAligned(_) => result.non_aligned_issues.get(&occupant).cloned().unwrap_or_default(),
+        };
+
+        CostOption {
+            participant_id: occupant,
+            total: Some(total),
+            issues,
+            adjudicator_cost: None,
+            within_randomization: false,
+        }
+    }
+}
+
+fn adjudicator_option(participant_id: Uuid, cost: Option<AdjudicatorCost>) -> CostOption {
+    CostOption {
+        participant_id,
+        total: cost.as_ref().map(|c| c.total()),
+        issues: vec![],
+        adjudicator_cost: cost,
+        within_randomization: false,
+    }
+}
+
+fn cheapest_alternatives(options: Vec<CostOption>) -> Vec<CostOption> {
+    // Options ruled out by a hard clash are listed last
+    options.into_iter().sorted_by_key(|o| (o.total.is_none(), o.total, o.participant_id)).take(MAX_ALTERNATIVES).collect()
+}
+
+/// Whether the random term of the allocation could have swapped the two options.
+/// Options that are ruled out by a hard clash never are.
+fn is_within_randomization(option_total: Option<i32>, chosen_total: i32, randomization_scale: f64) -> bool {
+    option_total.is_some_and(|total| ((total - chosen_total) as f64).abs() < randomization_scale)
+}
+
+/// Explains the cost of every position in a drawn round.
+///
+/// Teams and non-aligned speakers are compared with the occupants of the same position
+/// in the other debates, since the allocation only exchanges those.
+/// Adjudicators are compared with all adjudicators available in the round.
+/// All costs are evaluated against the final draw, so they can differ slightly
+/// from the costs at the time a position was filled.
+pub fn explain_round_draw(
+    ballots: &[DrawBallot],
+    optimization_state: &OptimizationState,
+    is_silent_round: bool,
+    evaluator: &DrawEvaluator<DrawConstructionEvaluationContext>,
+    randomization_scale: f64,
+) -> Vec<DebateCostReport> {
+    // Teams are allocated before adjudicators, so adjudicators do not influence their cost
+    let team_ballots = ballots.iter().map(|b| DrawBallot {
+        adjudicators: vec![],
+        president: None,
+        ..b.clone()
+    }).collect_vec();
+
+    let max_non_aligned = team_ballots.iter().map(|b| b.non_aligned_speakers.len()).max().unwrap_or(0);
+    let slots = vec![TeamSlot::Government, TeamSlot::Opposition].into_iter().chain(
+        (0..max_non_aligned).map(TeamSlot::NonAligned)
+    ).collect_vec();
+
+    let available_adjudicators = optimization_state.available_adjudicators(0);
+    let debate_infos = optimization_state.rounds.first().map(|r| r.debates.clone()).unwrap_or_default();
+
+    team_ballots.iter().enumerate().map(|(debate_index, ballot)| {
+        let mut positions = vec![];
+
+        for slot in slots.iter() {
+            let Some(occupant) = slot.get(ballot) else { continue };
+            let chosen = slot.evaluate(ballot, occupant, evaluator);
+            let chosen_total = chosen.total.unwrap_or(0);
+
+            let alternatives = team_ballots.iter().filter_map(|other| slot.get(other)).filter(|o| *o != occupant).map(
+                |alternative| {
+                    let mut option = slot.evaluate(ballot, alternative, evaluator);
+                    option.within_randomization = is_within_randomization(option.total, chosen_total, randomization_scale);
+                    option
+                }
+            ).collect_vec();
+
+            positions.push(PositionCostReport {
+                position: slot.position(),
+                chosen,
+                alternatives: cheapest_alternatives(alternatives),
+            });
+        }
+
+        if let Some(debate) = debate_infos.get(debate_index) {
+            if let Some(chair) = debate.chair {
+                // Chairs are allocated before wings
+                let chair_debate = DebateInfo { chair: None, wings: vec![], ..debate.clone() };
+                let alternatives = available_adjudicators.iter().filter(|a| **a != chair).map(
+                    |a| adjudicator_option(*a, optimization_state.explain_chair_cost(*a, &chair_debate, is_silent_round, evaluator))
+                ).collect_vec();
+
+                positions.push(PositionCostReport {
+                    position: DrawPosition::Chair,
+                    chosen: adjudicator_option(chair, optimization_state.explain_chair_cost(chair, &chair_debate, is_silent_round, evaluator)),
+                    alternatives: cheapest_alternatives(alternatives),
+                });
+            }
+
+            for (wing_idx, wing) in debate.wings.iter().enumerate() {
+                // Each wing is evaluated against the panel as it was before the wing was added
+                let wing_debate = DebateInfo { wings: debate.wings[..wing_idx].to_vec(), ..debate.clone() };
+                let alternatives = available_adjudicators.iter().filter(|a| *a != wing).map(
+                    |a| adjudicator_option(*a, optimization_state.explain_wing_cost(*a, &wing_debate, evaluator))
+                ).collect_vec();
+
+                positions.push(PositionCostReport {
+                    position: DrawPosition::Wing,
+                    chosen: adjudicator_option(*wing, optimization_state.explain_wing_cost(*wing, &wing_debate, evaluator)),
+                    alternatives: cheapest_alternatives(alternatives),
+                });
+            }
+        }
+
+        DebateCostReport {
+            debate_index,
+            positions,
+        }
+    }).collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::is_within_randomization;
+
+    #[test]
+    fn test_cheaper_alternatives_are_only_within_randomization_if_close() {
+        assert!(is_within_randomization(Some(95), 100, 10.0));
+        assert!(!is_within_randomization(Some(50), 100, 10.0));
+        assert!(is_within_randomization(Some(105), 100, 10.0));
+        assert!(!is_within_randomization(Some(150), 100, 10.0));
+    }
+
+    #[test]
+    fn test_clashing_alternatives_are_never_within_randomization() {
+        assert!(!is_within_randomization(None, 0, 10.0));
+    }
+}
@@ -1,14 +1,16 @@
-use std::{collections::{HashMap, HashSet}, sync::Arc};
+use std::{collections::{BTreeMap, BTreeSet, HashMap}, sync::Arc};
 
 use itertools::Itertools;
-use open_tab_entities::{domain::{self}, prelude::{Ballot, Participant, TournamentRound}};
+use open_tab_entities::{domain::{self, tournament::DrawConfig}, prelude::{Ballot, Participant, TournamentRound}};
 use sea_orm::{prelude::Uuid};
+use serde::{Deserialize, Serialize};
 
 use mcmf::{GraphBuilder, Capacity, Vertex, Cost};
 
 use crate::draw_view::DrawBallot;
 
 use super::evaluation::{DrawConstructionEvaluationContext, DrawEvaluator};
+use super::adjudicator_statistics::AdjudicatorScoringStatistics;
 
 use super::datastructures::{
     AdjudicatorInfo, RoundInfo, DebateInfo
@@ -19,6 +21,8 @@ enum AdjudicatorPosition {
     None,
     Chair{debate_idx: usize},
     Wing{debate_idx: usize, position: usize},
+    President{debate_idx: usize},
+    Trainee{debate_idx: usize},
     Unavailable
 }
 
@@ -31,30 +35,61 @@ pub struct OptimizationOptions {
     hard_clash_threshold: i32,
     bias_weight: f32,
     variance_weight: f32,
+
+    trainee_mentor_weight: f32,
 }
 
 impl Default for OptimizationOptions {
     fn default() -> Self {
+        OptimizationOptions::from(&DrawConfig::default())
+    }
+}
+
+impl From<&DrawConfig> for OptimizationOptions {
+    fn from(config: &DrawConfig) -> Self {
         Self {
-            feedback_weight: 10.0,
-            moderation_weight: 10.0,
-            max_discussion_improvement_weight: 1.0,
+            feedback_weight: *config.feedback_weight,
+            moderation_weight: *config.moderation_weight,
+            max_discussion_improvement_weight: *config.max_discussion_improvement_weight,
 
-            bias_weight: 1.0,
-            variance_weight: 1.0,
+            bias_weight: *config.bias_weight,
+            variance_weight: *config.variance_weight,
 
-            hard_clash_threshold: 75,
+            hard_clash_threshold: config.hard_clash_threshold,
+
+            trainee_mentor_weight: *config.trainee_mentor_weight,
         }
     }
 }
 
+/// The terms the cost of placing an adjudicator in a position is made up of.
+/// Lower costs are preferred, so skill terms are negative.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AdjudicatorCost {
+    pub clash: i32,
+    pub feedback_skill: i32,
+    pub moderation_skill: i32,
+    pub discussion_improvement: i32,
+    pub bias: i32,
+    pub variance: i32,
+    pub wing_offset: i32,
+    pub mentor_skill: i32,
+}
+
+impl AdjudicatorCost {
+    pub fn total(&self) -> i32 {
+        self.clash + self.feedback_skill + self.moderation_skill + self.discussion_improvement + self.bias + self.variance + self.wing_offset + self.mentor_skill
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct OptimizationState {
     pub rounds: Vec<RoundInfo>,
 
     options: OptimizationOptions,
 
-    adjudicator_assignments: HashMap<Uuid, Vec<AdjudicatorPosition>>,
+    // Ordered, so that the flow graphs and thus the allocation are reproducible
+    adjudicator_assignments: BTreeMap<Uuid, Vec<AdjudicatorPosition>>,
     adjudicator_info: HashMap<Uuid, AdjudicatorInfo>,
 }
 
@@ -105,10 +140,20 @@ impl OptimizationState {
         Self::load_from_rounds(db, tournament_id, rounds, options).await
     }
 
-    pub async fn load_from_rounds<C>(db: &C, tournament_id: Uuid, rounds: Vec<RoundInfo>, options: OptimizationOptions) -> Result<Self, anyhow::Error> where C: sea_orm::ConnectionTrait {
+    /// Participants in adjudicator positions that are no longer adjudicators in the tournament,
+    /// for example because they were pinned before their role changed, are removed from the debates.
+    pub async fn load_from_rounds<C>(db: &C, tournament_id: Uuid, mut rounds: Vec<RoundInfo>, options: OptimizationOptions) -> Result<Self, anyhow::Error> where C: sea_orm::ConnectionTrait {
         let adjudicators = Participant::get_all_adjudicators_in_tournament(db, tournament_id).await?;
 
-        let mut adjudicator_assignments = adjudicators.iter().map(|adj| (adj.uuid.clone(), vec![AdjudicatorPosition::None; rounds.len()])).collect::<HashMap<Uuid, Vec<AdjudicatorPosition>>>();
+        let adjudicator_ids = adjudicators.iter().map(|adj| adj.uuid).collect::<BTreeSet<_>>();
+        for debate in rounds.iter_mut().flat_map(|round| round.debates.iter_mut()) {
+            debate.chair = debate.chair.filter(|chair| adjudicator_ids.contains(chair));
+            debate.president = debate.president.filter(|president| adjudicator_ids.contains(president));
+            debate.wings.retain(|wing| adjudicator_ids.contains(wing));
+            debate.trainees.retain(|trainee| adjudicator_ids.contains(trainee));
+        }
+
+        let mut adjudicator_assignments = adjudicators.iter().map(|adj| (adj.uuid.clone(), vec![AdjudicatorPosition::None; rounds.len()])).collect::<BTreeMap<Uuid, Vec<AdjudicatorPosition>>>();
         rounds.iter().enumerate().for_each(|(round_idx, round)| {
             round.debates.iter().enumerate().flat_map(|(debate_idx, debate_info)| {
                 debate_info.chair.iter().map(
@@ -117,6 +162,14 @@ impl OptimizationState {
                     debate_info.wings.iter().enumerate().map(
                         move |(adj_pos, adj_id)| (adj_id, AdjudicatorPosition::Wing { debate_idx, position: adj_pos })
                     )
+                ).chain(
+                    debate_info.president.iter().map(
+                        move |president_uuid| (president_uuid, AdjudicatorPosition::President { debate_idx })
+                    )
+                ).chain(
+                    debate_info.trainees.iter().map(
+                        move |trainee_uuid| (trainee_uuid, AdjudicatorPosition::Trainee { debate_idx })
+                    )
                 )
             }).for_each(
                 |(adj_id, position)| {
@@ -144,7 +197,10 @@ impl OptimizationState {
             }
         }
 
+        let scoring_statistics = AdjudicatorScoringStatistics::load_for_tournament(db, tournament_id).await?;
+
         let adjudicator_info = adjudicators.iter().filter_map(|adj| {
+            let statistics = scoring_statistics.get(&adj.uuid);
             match &adj.role {
                 domain::participant::ParticipantRole::Adjudicator(info) => Some(
                     (adj.uuid, AdjudicatorInfo {
@@ -152,8 +208,9 @@ impl OptimizationState {
                         feedback_skill: info.chair_skill as i32,
                         moderation_skill: info.chair_skill as i32,
                         discussion_skill: info.panel_skill as i32,
-                        bias: 0.0,
-                        variance: 0.0,
+                        bias: statistics.map(|s| s.bias as f32).unwrap_or(0.0),
+                        variance: statistics.map(|s| s.variance as f32).unwrap_or(0.0),
+                        is_trainee: info.is_trainee,
                     }
                     )
                 ),
@@ -167,19 +224,21 @@ impl OptimizationState {
     }
 
     fn compute_adjudicator_chair_cost_in_debate(&self, adjudicator: Uuid, debate: &DebateInfo, is_silent_round: bool, evaluator: &DrawEvaluator<DrawConstructionEvaluationContext>) -> Option<i32> {
+        self.explain_chair_cost(adjudicator, debate, is_silent_round, evaluator).map(|c| c.total())
+    }
+
+    /// Cost of making the adjudicator the chair of the debate, split into its components.
+    /// Returns `None` if the adjudicator has a hard clash in the debate.
+    pub fn explain_chair_cost(&self, adjudicator: Uuid, debate: &DebateInfo, is_silent_round: bool, evaluator: &DrawEvaluator<DrawConstructionEvaluationContext>) -> Option<AdjudicatorCost> {
         let adj_info = self.adjudicator_info.get(&adjudicator).unwrap();
-        let cost = self.compute_clash_cost_in_debate(adj_info, debate, evaluator);
-        if let Some(mut cost) = cost {
-            if !is_silent_round {
-                cost -= (adj_info.feedback_skill as f32 * self.options.feedback_weight).round() as i32;
-            }
-            cost -= (adj_info.moderation_skill as f32 * self.options.moderation_weight).round() as i32;
-    
-            Some(cost)
-        }
-        else {
-            None
-        }
+        let clash = self.compute_clash_cost_in_debate(adj_info, debate, evaluator)?;
+
+        Some(AdjudicatorCost {
+            clash,
+            feedback_skill: if !is_silent_round { -(adj_info.feedback_skill as f32 * self.options.feedback_weight).round() as i32 } else { 0 },
+            moderation_skill: -(adj_info.moderation_skill as f32 * self.options.moderation_weight).round() as i32,
+            ..Default::default()
+        })
     }
 
     fn compute_clash_cost_in_debate(&self, adj_info: &AdjudicatorInfo, debate: &DebateInfo, evaluator: &DrawEvaluator<DrawConstructionEvaluationContext>) -> Option<i32> {
@@ -195,33 +254,67 @@ impl OptimizationState {
     }
 
     fn compute_wing_cost_in_debate(&self, adjudicator: Uuid, debate: &DebateInfo, evaluator: &DrawEvaluator<DrawConstructionEvaluationContext>) -> Option<i32> {
+        self.explain_wing_cost(adjudicator, debate, evaluator).map(|c| c.total())
+    }
+
+    /// Cost of adding the adjudicator as a wing to the debate, split into its components.
+    /// Returns `None` if the adjudicator has a hard clash in the debate.
+    pub fn explain_wing_cost(&self, adjudicator: Uuid, debate: &DebateInfo, evaluator: &DrawEvaluator<DrawConstructionEvaluationContext>) -> Option<AdjudicatorCost> {
         let adj_info = self.adjudicator_info.get(&adjudicator).unwrap();
-        let cost = self.compute_clash_cost_in_debate(adj_info, debate, evaluator);
+        let clash = self.compute_clash_cost_in_debate(adj_info, debate, evaluator)?;
 
-        if let Some(mut cost) = cost {
-            let chair_info = debate.chair.map(|c| self.adjudicator_info.get(&c).unwrap());
+        let mut cost = AdjudicatorCost {
+            clash,
+            wing_offset: -1,
+            ..Default::default()
+        };
 
-            let mut bias_sum = debate.wings.iter().map(|w| self.adjudicator_info.get(w).unwrap().bias).sum::<f32>();
-            let mut variance_sum = debate.wings.iter().map(|w| self.adjudicator_info.get(w).unwrap().variance).sum::<f32>();
+        let chair_info = debate.chair.map(|c| self.adjudicator_info.get(&c).unwrap());
 
-            if let Some(chair_info) = chair_info {
-                cost -= ((adj_info.moderation_skill - chair_info.discussion_skill) as f32 * self.options.max_discussion_improvement_weight).round() as i32;
+        let mut bias_sum = debate.wings.iter().map(|w| self.adjudicator_info.get(w).unwrap().bias).sum::<f32>();
+        let mut variance_sum = debate.wings.iter().map(|w| self.adjudicator_info.get(w).unwrap().variance).sum::<f32>();
 
-                bias_sum += chair_info.bias;
-                variance_sum += chair_info.variance;
-            }
+        if let Some(chair_info) = chair_info {
+            cost.discussion_improvement = -((adj_info.moderation_skill - chair_info.discussion_skill) as f32 * self.options.max_discussion_improvement_weight).round() as i32;
 
-            let avg_bias = bias_sum / (debate.wings.len() + chair_info.is_some() as usize) as f32;
-            let avg_variance = variance_sum / (debate.wings.len() + chair_info.is_some() as usize) as f32;
+            bias_sum += chair_info.bias;
+            variance_sum += chair_info.variance;
+        }
 
-            cost -= ((adj_info.bias - avg_bias).abs() as f32 * self.options.bias_weight) as i32;
-            cost -= ((avg_variance - adj_info.variance) as f32 * self.options.variance_weight) as i32;
+        let avg_bias = bias_sum / (debate.wings.len() + chair_info.is_some() as usize) as f32;
+        let avg_variance = variance_sum / (debate.wings.len() + chair_info.is_some() as usize) as f32;
 
-            Some(cost - 1)      
-        }
-        else {
-            None
-        }
+        cost.bias = -((adj_info.bias - avg_bias).abs() * self.options.bias_weight) as i32;
+        cost.variance = -((avg_variance - adj_info.variance) * self.options.variance_weight) as i32;
+
+        Some(cost)
+    }
+
+    /// Cost of adding the trainee to the debate, split into its components.
+    /// Trainees are preferably placed with chairs that are good at giving feedback.
+    /// Returns `None` if the trainee has a hard clash in the debate.
+    pub fn explain_trainee_cost(&self, adjudicator: Uuid, debate: &DebateInfo, evaluator: &DrawEvaluator<DrawConstructionEvaluationContext>) -> Option<AdjudicatorCost> {
+        let adj_info = self.adjudicator_info.get(&adjudicator).unwrap();
+        let clash = self.compute_clash_cost_in_debate(adj_info, debate, evaluator)?;
+
+        let chair_info = debate.chair.map(|c| self.adjudicator_info.get(&c).unwrap());
+
+        Some(AdjudicatorCost {
+            clash,
+            mentor_skill: chair_info.map(|c| -(c.feedback_skill as f32 * self.options.trainee_mentor_weight).round() as i32).unwrap_or(0),
+            ..Default::default()
+        })
+    }
+
+    fn is_trainee(&self, adjudicator: &Uuid) -> bool {
+        self.adjudicator_info.get(adjudicator).map(|i| i.is_trainee).unwrap_or(false)
+    }
+
+    /// Voting adjudicators that can be allocated in the round, regardless of whether they already are.
+    pub fn available_adjudicators(&self, round_idx: usize) -> Vec<Uuid> {
+        self.adjudicator_assignments.iter().filter(
+            |(adj, assignments)| assignments[round_idx] != AdjudicatorPosition::Unavailable && !self.is_trainee(adj)
+        ).map(|(adj, _)| *adj).collect()
     }
 
     pub fn update_state_by_assigning_chairs(&mut self, adjudicators: Option<&Vec<Uuid>>, evaluator: &DrawEvaluator<DrawConstructionEvaluationContext>) {
@@ -236,6 +329,9 @@ impl OptimizationState {
                 if adjudicators.is_some() && !adjudicators.unwrap().contains(adj) {
                     return;
                 }
+                if self.is_trainee(adj) {
+                    return;
+                }
                 for i in 0..self.rounds.len() {
                     graph_build.add_edge(
                         Vertex::Source,
@@ -337,6 +433,16 @@ impl OptimizationState {
     }
 
     pub fn update_state_by_assigning_wings(&mut self, adjudicators: Option<&Vec<Uuid>>, evaluator: &DrawEvaluator<DrawConstructionEvaluationContext>) {
+        self.update_state_by_assigning_panel_members(adjudicators, false, evaluator);
+    }
+
+    /// Spreads the trainees over the debates, filling up the debates with the fewest trainees first.
+    pub fn update_state_by_assigning_trainees(&mut self, adjudicators: Option<&Vec<Uuid>>, evaluator: &DrawEvaluator<DrawConstructionEvaluationContext>) {
+        self.update_state_by_assigning_panel_members(adjudicators, true, evaluator);
+    }
+
+    fn update_state_by_assigning_panel_members(&mut self, adjudicators: Option<&Vec<Uuid>>, trainees: bool, evaluator: &DrawEvaluator<DrawConstructionEvaluationContext>) {
+        let panel_size = |d: &DebateInfo| if trainees { d.trainees.len() } else { d.wings.len() };
         for round_id in 0..self.rounds.len() {
             // We stop iterating when we do not observe any changes in number of unassigned adjudicators
             // To stop this from happening on the first loop, we make sure the first test
@@ -344,11 +450,14 @@ impl OptimizationState {
             let mut previous_unassigned_cnt = self.adjudicator_assignments.len() + 1;
             loop {
                 let round_info = &self.rounds[round_id];
-                let mut unassigned_adjudicators : HashSet<Uuid> = self.adjudicator_assignments.iter().filter_map(
+                let mut unassigned_adjudicators : BTreeSet<Uuid> = self.adjudicator_assignments.iter().filter_map(
                     |(adj, assignments)| {
                         if adjudicators.is_some() && !adjudicators.unwrap().contains(adj) {
                             return None;
                         }
+                        if self.is_trainee(adj) != trainees {
+                            return None;
+                        }
                         if assignments[round_id] == AdjudicatorPosition::None {
                             Some(*adj)
                         }
@@ -364,8 +473,8 @@ impl OptimizationState {
 
                 previous_unassigned_cnt = unassigned_adjudicators.len();
 
-                let min_debate_wing_cnt: usize = round_info.debates.iter().map(|d| d.wings.len()).min().unwrap_or(0);
-                let debates_to_assign_wings = round_info.debates.iter().enumerate().filter(|(_d_idx, d)| d.wings.len() == min_debate_wing_cnt).collect_vec();
+                let min_debate_wing_cnt: usize = round_info.debates.iter().map(panel_size).min().unwrap_or(0);
+                let debates_to_assign_wings = round_info.debates.iter().enumerate().filter(|(_d_idx, d)| panel_size(d) == min_debate_wing_cnt).collect_vec();
 
                 let mut graph_build = GraphBuilder::new();
 
@@ -374,16 +483,13 @@ impl OptimizationState {
                         |adj| {
                             debates_to_assign_wings.iter().map(
                                 |(debate_idx, debate)| {
-                                    if let Some(cost) = self.compute_wing_cost_in_debate(
-                                        *adj,
-                                        debate,
-                                        evaluator
-                                    ) {
-                                        Some((*adj, *debate_idx, cost))
+                                    let cost = if trainees {
+                                        self.explain_trainee_cost(*adj, debate, evaluator).map(|c| c.total())
                                     }
                                     else {
-                                        None
-                                    }
+                                        self.compute_wing_cost_in_debate(*adj, debate, evaluator)
+                                    };
+                                    cost.map(|cost| (*adj, *debate_idx, cost))
                                 }
                             )
                         }
@@ -447,8 +553,14 @@ impl OptimizationState {
 
                 assignments.into_iter().for_each(
                     |(adj, debate_id)| {
-                        self.adjudicator_assignments.get_mut(adj).unwrap()[round_id] = AdjudicatorPosition::Wing{debate_idx: *debate_id, position: self.rounds[round_id].debates[*debate_id].wings.len()};
-                        self.rounds[round_id].debates[*debate_id].wings.push(*adj);
+                        if trainees {
+                            self.adjudicator_assignments.get_mut(adj).unwrap()[round_id] = AdjudicatorPosition::Trainee{debate_idx: *debate_id};
+                            self.rounds[round_id].debates[*debate_id].trainees.push(*adj);
+                        }
+                        else {
+                            self.adjudicator_assignments.get_mut(adj).unwrap()[round_id] = AdjudicatorPosition::Wing{debate_idx: *debate_id, position: self.rounds[round_id].debates[*debate_id].wings.len()};
+                            self.rounds[round_id].debates[*debate_id].wings.push(*adj);
+                        }
                         unassigned_adjudicators.remove(adj);
                     }
                 );
@@ -459,5 +571,6 @@ impl OptimizationState {
     pub fn update_state_by_assigning_adjudicators(&mut self, adjudicators: Option<&Vec<Uuid>>, evaluator: &DrawEvaluator<DrawConstructionEvaluationContext>) {
         self.update_state_by_assigning_chairs(adjudicators.clone(), evaluator);
         self.update_state_by_assigning_wings(adjudicators.clone(), evaluator);
+        self.update_state_by_assigning_trainees(adjudicators, evaluator);
     }
 }
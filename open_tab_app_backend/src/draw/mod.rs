@@ -5,5 +5,12 @@ pub mod optimization;
 pub mod tab_draw;
 pub mod flow_optimization;
 pub mod datastructures;
+pub mod adjudicator_statistics;
+pub mod swing_teams;
+pub mod venues;
+pub mod pins;
+pub mod repair;
+pub mod seed;
+pub mod explanation;
 
 pub use preliminary::{PreliminaryRoundGenerator, PreliminariesDrawMode};
\ No newline at end of file
@@ -1,7 +1,7 @@
 use std::{collections::VecDeque, iter::zip};
 
 
-use rand::{thread_rng, Rng};
+use rand::{rngs::StdRng, Rng};
 
 
 use crate::draw_view::DrawBallot;
@@ -74,8 +74,7 @@ fn auction_algorithm(
     owners.into_iter().enumerate().map(|(obj, owner)| (owner, obj)).collect()
 }
 
-pub(crate) fn find_best_ballot_assignments(ballots: &Vec<Vec<DrawBallot>>, evaluator: &DrawEvaluator<DrawConstructionEvaluationContext>, randomization_scale: f64) -> Result<Vec<DrawBallot>, anyhow::Error> {
-    let mut rng = thread_rng();
+pub(crate) fn find_best_ballot_assignments(ballots: &Vec<Vec<DrawBallot>>, evaluator: &DrawEvaluator<DrawConstructionEvaluationContext>, randomization_scale: f64, rng: &mut StdRng) -> Result<Vec<DrawBallot>, anyhow::Error> {
     let mut matrix = Matrix::new(ballots.len(), ballots[0].len());
     for (option_idx, ballot_options) in ballots.iter().enumerate() {
         for (ballot_idx, ballot) in ballot_options.iter().enumerate() {
@@ -0,0 +1,370 @@
+use std::collections::{HashMap, HashSet};
+
+use itertools::Itertools;
+use rand::rngs::StdRng;
+use open_tab_entities::domain::debate::DebatePins;
+use sea_orm::prelude::Uuid;
+
+use crate::draw_view::{DrawBallot, DrawSpeaker, DrawTeam};
+
+use super::{datastructures::DebateInfo, evaluation::{DrawConstructionEvaluationContext, DrawEvaluator}, optimization::find_best_ballot_assignments};
+
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Slot {
+    Government,
+    Opposition,
+    NonAligned(usize),
+}
+
+impl Slot {
+    fn is_free(&self, ballot: &DrawBallot, pins: &DebatePins) -> bool {
+        match self {
+            Slot::Government => !pins.government,
+            Slot::Opposition => !pins.opposition,
+            Slot::NonAligned(position) => *position < ballot.non_aligned_speakers.len() && !pins.non_aligned_speakers.contains(position),
+        }
+    }
+
+    fn get(&self, ballot: &DrawBallot) -> Option<Uuid> {
+        match self {
+            Slot::Government => ballot.government.as_ref().map(|t| t.uuid),
+            Slot::Opposition => ballot.opposition.as_ref().map(|t| t.uuid),
+            Slot::NonAligned(position) => ballot.non_aligned_speakers.get(*position).cloned().flatten().map(|s| s.uuid),
+        }
+    }
+
+    fn holds_team(&self) -> bool {
+        matches!(self, Slot::Government | Slot::Opposition)
+    }
+
+    fn take(&self, ballot: &mut DrawBallot) -> Option<Uuid> {
+        match self {
+            Slot::Government => ballot.government.take().map(|t| t.uuid),
+            Slot::Opposition => ballot.opposition.take().map(|t| t.uuid),
+            Slot::NonAligned(position) => ballot.non_aligned_speakers[*position].take().map(|s| s.uuid),
+        }
+    }
+
+    fn set(&self, ballot: &mut DrawBallot, occupant: Option<Uuid>) {
+        match self {
+            Slot::Government => ballot.government = occupant.map(|uuid| DrawTeam { uuid, ..Default::default() }),
+            Slot::Opposition => ballot.opposition = occupant.map(|uuid| DrawTeam { uuid, ..Default::default() }),
+            Slot::NonAligned(position) => ballot.non_aligned_speakers[*position] = occupant.map(|uuid| DrawSpeaker { uuid, ..Default::default() }),
+        }
+    }
+}
+
+/// Reassigns teams and non-aligned speakers that are not pinned.
+/// Teams keep their role and speakers keep their position, only the debates they are in change.
+/// Pinned positions are left untouched and the rest is filled around them.
+pub fn reassign_unpinned_teams(
+    ballots: &[DrawBallot],
+    pins: &[DebatePins],
+    evaluator: &DrawEvaluator<DrawConstructionEvaluationContext>,
+    randomization_scale: f64,
+    rng: &mut StdRng
+) -> Result<Vec<DrawBallot>, anyhow::Error> {
+    let mut ballots = ballots.to_vec();
+    let max_non_aligned = ballots.iter().map(|b| b.non_aligned_speakers.len()).max().unwrap_or(0);
+    let slots = vec![Slot::Government, Slot::Opposition].into_iter().chain(
+        (0..max_non_aligned).map(Slot::NonAligned)
+    ).collect_vec();
+
+    // All free positions are cleared first, so that the evaluation
+    // is not influenced by the previous draw.
+    let slot_options = slots.into_iter().map(|slot| {
+        let free_ballots = ballots.iter().zip(pins.iter()).enumerate().filter(
+            |(_, (ballot, pins))| slot.is_free(ballot, pins)
+        ).map(|(idx, _)| idx).collect_vec();
+        let occupants = free_ballots.iter().map(|idx| slot.take(&mut ballots[*idx])).collect_vec();
+        (slot, free_ballots, occupants)
+    }).collect_vec();
+
+    for (slot, free_ballots, occupants) in slot_options {
+        if free_ballots.is_empty() {
+            continue;
+        }
+
+        let possible_ballots = occupants.iter().map(
+            |occupant| free_ballots.iter().map(|idx| {
+                let mut ballot = ballots[*idx].clone();
+                slot.set(&mut ballot, *occupant);
+                ballot
+            }).collect_vec()
+        ).collect_vec();
+
+        let assigned_ballots = find_best_ballot_assignments(&possible_ballots, evaluator, randomization_scale, rng)?;
+        for (idx, ballot) in free_ballots.into_iter().zip(assigned_ballots) {
+            ballots[idx] = ballot;
+        }
+    }
+
+    Ok(ballots)
+}
+
+fn find_position(ballots: &[DrawBallot], occupant: Uuid, is_team: bool) -> Option<(usize, Slot)> {
+    ballots.iter().enumerate().flat_map(|(idx, ballot)| {
+        [Slot::Government, Slot::Opposition].into_iter()
+            .chain((0..ballot.non_aligned_speakers.len()).map(Slot::NonAligned))
+            .map(move |slot| (idx, slot))
+    }).find(|(idx, slot)| slot.holds_team() == is_team && slot.get(&ballots[*idx]) == Some(occupant))
+}
+
+/// Moves the occupant into the target position by swapping it with the current occupant.
+/// A team that is non-aligned in the generated draw swaps roles with the team it displaces,
+/// whose members take over the non-aligned positions.
+/// Returns false if the occupant is not part of the draw or would have to leave a fixed position.
+fn move_into_position(
+    ballots: &mut [DrawBallot],
+    (target_idx, target_slot): (usize, Slot),
+    occupant: Uuid,
+    team_members: &HashMap<Uuid, Vec<Uuid>>,
+    fixed_positions: &[(usize, Slot)]
+) -> bool {
+    let displaced = target_slot.get(&ballots[target_idx]);
+    if displaced == Some(occupant) {
+        return true;
+    }
+
+    if let Some((source_idx, source_slot)) = find_position(ballots, occupant, target_slot.holds_team()) {
+        if fixed_positions.contains(&(source_idx, source_slot)) {
+            return false;
+        }
+        source_slot.set(&mut ballots[source_idx], displaced);
+        target_slot.set(&mut ballots[target_idx], Some(occupant));
+        return true;
+    }
+
+    if !target_slot.holds_team() {
+        return false;
+    }
+    let member_positions = team_members.get(&occupant).into_iter().flatten().filter_map(
+        |member| find_position(ballots, *member, false)
+    ).collect_vec();
+    if member_positions.is_empty() || member_positions.iter().any(|position| fixed_positions.contains(position)) {
+        return false;
+    }
+    let displaced_members = displaced.and_then(|team| team_members.get(&team)).cloned().unwrap_or_default();
+    for (member_idx, (ballot_idx, slot)) in member_positions.into_iter().enumerate() {
+        slot.set(&mut ballots[ballot_idx], displaced_members.get(member_idx).cloned());
+    }
+    target_slot.set(&mut ballots[target_idx], Some(occupant));
+    true
+}
+
+/// Moves the pinned teams and non-aligned speakers of the previous draw into their positions
+/// in a newly generated draw. Whoever held such a position in the generated draw takes the
+/// previous place of the pinned occupant, so every team and speaker still appears exactly once.
+/// Pins that can not be kept, because the debate or the pinned occupant is no longer part of the draw,
+/// are removed.
+pub fn apply_pinned_teams(ballots: &mut [DrawBallot], previous_ballots: &[DrawBallot], pins: &mut [DebatePins], team_members: &HashMap<Uuid, Vec<Uuid>>) {
+    let mut fixed_positions = vec![];
+    for (debate_idx, (previous_ballot, pins)) in previous_ballots.iter().zip(pins.iter_mut()).enumerate() {
+        let pinned_slots = pins.government.then_some(Slot::Government).into_iter()
+            .chain(pins.opposition.then_some(Slot::Opposition))
+            .chain(pins.non_aligned_speakers.iter().map(|position| Slot::NonAligned(*position)))
+            .collect_vec();
+
+        let mut kept_slots = vec![];
+        for slot in pinned_slots {
+            let target_exists = debate_idx < ballots.len() && match slot {
+                Slot::NonAligned(position) => position < ballots[debate_idx].non_aligned_speakers.len(),
+                _ => true
+            };
+            let is_kept = target_exists && slot.get(previous_ballot).map(
+                |occupant| move_into_position(ballots, (debate_idx, slot), occupant, team_members, &fixed_positions)
+            ).unwrap_or(false);
+
+            if is_kept {
+                fixed_positions.push((debate_idx, slot));
+                kept_slots.push(slot);
+            }
+        }
+
+        pins.government = kept_slots.contains(&Slot::Government);
+        pins.opposition = kept_slots.contains(&Slot::Opposition);
+        pins.non_aligned_speakers.retain(|position| kept_slots.contains(&Slot::NonAligned(*position)));
+    }
+}
+
+/// Places the pinned adjudicators of the previous debate in a newly generated debate,
+/// so that the allocation only fills the remaining positions.
+/// Pinned wings are moved to the front of the panel and their pins are updated accordingly.
+/// Pins of participants that are no longer adjudicators in the tournament are removed.
+pub fn apply_pinned_adjudicators(debate: &mut DebateInfo, previous_debate: &DebateInfo, pins: &mut DebatePins, adjudicators: &HashSet<Uuid>) {
+    debate.chair = previous_debate.chair.filter(|chair| pins.chair && adjudicators.contains(chair));
+    debate.president = previous_debate.president.filter(|president| pins.president && adjudicators.contains(president));
+    debate.wings = previous_debate.wings.iter().enumerate().filter(
+        |(idx, wing)| pins.wings.contains(idx) && adjudicators.contains(wing)
+    ).map(|(_, w)| *w).collect();
+    debate.trainees = vec![];
+    pins.chair = debate.chair.is_some();
+    pins.president = debate.president.is_some();
+    pins.wings = (0..debate.wings.len()).collect();
+}
+
+/// Removes adjudicators from all unpinned positions, so that the allocation can fill them again.
+/// Pinned wings are moved to the front of the panel and their pins are updated accordingly.
+/// Presidents are kept, since the allocation does not assign them.
+/// Participants that are no longer adjudicators in the tournament are removed even if they are pinned.
+pub fn clear_unpinned_adjudicators(debate: &mut DebateInfo, pins: &mut DebatePins, adjudicators: &HashSet<Uuid>) {
+    debate.chair = debate.chair.filter(|chair| pins.chair && adjudicators.contains(chair));
+    debate.president = debate.president.filter(|president| adjudicators.contains(president));
+    debate.trainees = vec![];
+    debate.wings = debate.wings.iter().enumerate().filter(
+        |(idx, wing)| pins.wings.contains(idx) && adjudicators.contains(wing)
+    ).map(|(_, w)| *w).collect();
+    pins.chair = debate.chair.is_some();
+    pins.president = pins.president && debate.president.is_some();
+    pins.wings = (0..debate.wings.len()).collect();
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::{HashMap, HashSet};
+
+    use open_tab_entities::domain::debate::DebatePins;
+    use sea_orm::prelude::Uuid;
+
+    use crate::{draw::datastructures::DebateInfo, draw_view::{DrawBallot, DrawSpeaker, DrawTeam}};
+
+    use super::{apply_pinned_adjudicators, apply_pinned_teams, clear_unpinned_adjudicators};
+
+    fn make_ballot(government: u128, opposition: u128, speaker: u128) -> DrawBallot {
+        DrawBallot {
+            government: Some(DrawTeam { uuid: Uuid::from_u128(government), ..Default::default() }),
+            opposition: Some(DrawTeam { uuid: Uuid::from_u128(opposition), ..Default::default() }),
+            non_aligned_speakers: vec![Some(DrawSpeaker { uuid: Uuid::from_u128(speaker), ..Default::default() })],
+            ..Default::default()
+        }
+    }
+
+    fn make_debate() -> DebateInfo {
+        DebateInfo {
+            id: Uuid::from_u128(1),
+            government: None,
+            opposition: None,
+            chair: Some(Uuid::from_u128(10)),
+            wings: vec![Uuid::from_u128(11), Uuid::from_u128(12), Uuid::from_u128(13)],
+            president: Some(Uuid::from_u128(14)),
+            trainees: vec![],
+            non_aligned_speakers: vec![],
+        }
+    }
+
+    fn make_adjudicators() -> HashSet<Uuid> {
+        (10..15).map(Uuid::from_u128).collect()
+    }
+
+    #[test]
+    fn test_unpinned_adjudicators_are_cleared() {
+        let mut debate = make_debate();
+        let mut pins = DebatePins::default();
+        clear_unpinned_adjudicators(&mut debate, &mut pins, &make_adjudicators());
+        assert_eq!(debate.chair, None);
+        assert!(debate.wings.is_empty());
+    }
+
+    #[test]
+    fn test_unpinned_president_is_kept() {
+        let mut debate = make_debate();
+        let mut pins = DebatePins::default();
+        clear_unpinned_adjudicators(&mut debate, &mut pins, &make_adjudicators());
+        assert_eq!(debate.president, Some(Uuid::from_u128(14)));
+    }
+
+    #[test]
+    fn test_pinned_teams_are_moved_into_generated_draw() {
+        let previous_ballots = vec![make_ballot(1, 2, 10), make_ballot(3, 4, 11)];
+        let mut ballots = vec![make_ballot(4, 1, 11), make_ballot(2, 3, 10)];
+        let mut pins = vec![
+            DebatePins { government: true, non_aligned_speakers: vec![0], ..Default::default() },
+            DebatePins::default()
+        ];
+        apply_pinned_teams(&mut ballots, &previous_ballots, &mut pins, &HashMap::new());
+
+        assert_eq!(ballots[0].government.as_ref().map(|t| t.uuid), Some(Uuid::from_u128(1)));
+        assert_eq!(ballots[0].opposition.as_ref().map(|t| t.uuid), Some(Uuid::from_u128(4)));
+        assert_eq!(ballots[0].non_aligned_speakers[0].as_ref().map(|s| s.uuid), Some(Uuid::from_u128(10)));
+        assert_eq!(ballots[1].non_aligned_speakers[0].as_ref().map(|s| s.uuid), Some(Uuid::from_u128(11)));
+        assert!(pins[0].government);
+        assert_eq!(pins[0].non_aligned_speakers, vec![0]);
+    }
+
+    #[test]
+    fn test_pinned_team_swaps_roles_with_displaced_team() {
+        let previous_ballots = vec![make_ballot(1, 2, 10)];
+        let mut ballots = vec![make_ballot(3, 2, 10)];
+        let team_members = HashMap::from_iter(vec![
+            (Uuid::from_u128(1), vec![Uuid::from_u128(10)]),
+            (Uuid::from_u128(3), vec![Uuid::from_u128(30)]),
+        ]);
+        let mut pins = vec![DebatePins { government: true, ..Default::default() }];
+        apply_pinned_teams(&mut ballots, &previous_ballots, &mut pins, &team_members);
+
+        assert_eq!(ballots[0].government.as_ref().map(|t| t.uuid), Some(Uuid::from_u128(1)));
+        assert_eq!(ballots[0].non_aligned_speakers[0].as_ref().map(|s| s.uuid), Some(Uuid::from_u128(30)));
+        assert!(pins[0].government);
+    }
+
+    #[test]
+    fn test_pins_of_removed_teams_are_dropped() {
+        let previous_ballots = vec![make_ballot(1, 2, 10)];
+        let mut ballots = vec![make_ballot(5, 2, 10)];
+        let mut pins = vec![DebatePins { government: true, ..Default::default() }];
+        apply_pinned_teams(&mut ballots, &previous_ballots, &mut pins, &HashMap::new());
+
+        assert_eq!(ballots[0].government.as_ref().map(|t| t.uuid), Some(Uuid::from_u128(5)));
+        assert!(pins[0].is_empty());
+    }
+
+    #[test]
+    fn test_pinned_wings_move_to_the_front() {
+        let mut debate = make_debate();
+        let mut pins = DebatePins {
+            chair: true,
+            wings: vec![2],
+            ..Default::default()
+        };
+        clear_unpinned_adjudicators(&mut debate, &mut pins, &make_adjudicators());
+        assert_eq!(debate.chair, Some(Uuid::from_u128(10)));
+        assert_eq!(debate.wings, vec![Uuid::from_u128(13)]);
+        assert_eq!(pins.wings, vec![0]);
+    }
+
+    #[test]
+    fn test_pins_of_removed_adjudicators_are_dropped() {
+        let mut debate = make_debate();
+        let mut pins = DebatePins {
+            chair: true,
+            president: true,
+            wings: vec![0, 2],
+            ..Default::default()
+        };
+        let adjudicators = HashSet::from_iter(vec![Uuid::from_u128(11)]);
+        clear_unpinned_adjudicators(&mut debate, &mut pins, &adjudicators);
+        assert_eq!(debate.chair, None);
+        assert_eq!(debate.president, None);
+        assert_eq!(debate.wings, vec![Uuid::from_u128(11)]);
+        assert_eq!(pins, DebatePins { wings: vec![0], ..Default::default() });
+    }
+
+    #[test]
+    fn test_pinned_removed_adjudicators_are_not_applied() {
+        let previous_debate = make_debate();
+        let mut debate = DebateInfo { chair: None, wings: vec![], president: None, ..make_debate() };
+        let mut pins = DebatePins {
+            chair: true,
+            president: true,
+            wings: vec![1, 2],
+            ..Default::default()
+        };
+        let adjudicators = HashSet::from_iter(vec![Uuid::from_u128(10), Uuid::from_u128(13)]);
+        apply_pinned_adjudicators(&mut debate, &previous_debate, &mut pins, &adjudicators);
+        assert_eq!(debate.chair, Some(Uuid::from_u128(10)));
+        assert_eq!(debate.president, None);
+        assert_eq!(debate.wings, vec![Uuid::from_u128(13)]);
+        assert_eq!(pins, DebatePins { chair: true, wings: vec![0], ..Default::default() });
+    }
+}
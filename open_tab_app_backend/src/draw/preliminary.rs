@@ -1,7 +1,7 @@
 use std::collections::HashMap;
 
 use open_tab_entities::prelude::{Ballot, BallotTeam, Speech, SpeechRole, TournamentRound};
-use rand::{rngs::StdRng, seq::SliceRandom, thread_rng, SeedableRng};
+use rand::{rngs::StdRng, seq::SliceRandom, Rng};
 use sea_orm::prelude::Uuid;
 
 use thiserror::Error;
@@ -24,17 +24,21 @@ pub struct RoundGenerationContext {
 pub struct DrawTeamInfo {
     pub uuid: Uuid,
     pub member_ids: Vec<Uuid>,
+    /// Institutions of the team members, most common first
+    pub institution_ids: Vec<Uuid>,
+    /// Rank of the team in the tab preceding the generated rounds, if there is one
+    pub tab_rank: Option<u32>,
+    /// Swing teams may have fewer than three members, but at least one. Their missing speeches stay empty
+    pub is_swing: bool,
 }
 
-pub enum PreliminariesDrawMode {
-    //Random,
-    AvoidClashes,
-}
+pub use open_tab_entities::domain::tournament_plan_node::PreliminariesDrawMode;
 
 
 pub struct PreliminaryRoundGenerator {
     pub draw_mode: PreliminariesDrawMode,
     pub randomization_scale: f64,
+    pub evaluator_config: DrawEvaluatorConfig,
 }
 
 impl Default for PreliminaryRoundGenerator {
@@ -42,6 +46,7 @@ impl Default for PreliminaryRoundGenerator {
         PreliminaryRoundGenerator {
             draw_mode: PreliminariesDrawMode::AvoidClashes,
             randomization_scale: 0.5,
+            evaluator_config: DrawEvaluatorConfig::default(),
         }
     }
 }
@@ -69,6 +74,7 @@ impl PreliminaryRoundGenerator {
         rounds: Vec<&TournamentRound>,
         other_rounds: Vec<Uuid>,
         evaluation_context: &mut DrawConstructionEvaluationContext,
+        rng: &mut StdRng,
     ) -> Result<Vec<Vec<DrawBallot>>, PreliminaryDrawError> {
         if rounds.len() % 3 != 0 {
             return Err(PreliminaryDrawError::IncorrectRoundCount(rounds.len()));
@@ -81,7 +87,7 @@ impl PreliminaryRoundGenerator {
         }
 
         for team in &context.teams {
-            if team.member_ids.len() != 3 {
+            if team.member_ids.len() > 3 || team.member_ids.is_empty() || (team.member_ids.len() < 3 && !team.is_swing) {
                 return Err(PreliminaryDrawError::IncorrectTeamSize {
                     is: team.member_ids.len(),
                     team_id: team.uuid,
@@ -93,21 +99,27 @@ impl PreliminaryRoundGenerator {
 
         let num_debates = context.teams.len() / 3;
 
-        //let mut rng = thread_rng();
-        let mut rng: StdRng = SeedableRng::from_seed([0; 32]);
-        let mut shuffled_teams = context.teams.iter().collect::<Vec<_>>();
-        shuffled_teams.shuffle(&mut rng);
+        let draw_mode = self.effective_draw_mode(context);
+
+        let buckets = match draw_mode {
+            PreliminariesDrawMode::PowerPaired => power_paired_buckets(&context.teams, rng),
+            PreliminariesDrawMode::InstitutionSeeded => institution_seeded_buckets(&context.teams, rng),
+            PreliminariesDrawMode::Random | PreliminariesDrawMode::AvoidClashes => {
+                let mut shuffled_teams = context.teams.iter().collect::<Vec<_>>();
+                shuffled_teams.shuffle(rng);
 
-        let buckets = shuffled_teams
-            .chunks(context.teams.len() / 3)
-            .map(|v| Vec::from(v))
-            .collect::<Vec<_>>();
+                shuffled_teams
+                    .chunks(context.teams.len() / 3)
+                    .map(Vec::from)
+                    .collect::<Vec<_>>()
+            }
+        };
         let mut role_sequence = [
             TeamRoundRole::Government,
             TeamRoundRole::Opposition,
             TeamRoundRole::NonAligned,
         ];
-        role_sequence.shuffle(&mut rng);
+        role_sequence.shuffle(rng);
 
         let teams = context
             .teams
@@ -169,17 +181,19 @@ impl PreliminaryRoundGenerator {
                     };
 
                 let evaluator = DrawEvaluator::new(
-                    DrawEvaluatorConfig::default(),
+                    self.evaluator_config.clone(),
                     relevant_rounds.clone(),
                     evaluation_context
                 );            
 
                 let ballots = self.assign_teams_to_ballots(
+                    draw_mode,
                     &ballots,
                     gov_bucket,
                     opp_bucket,
                     non_aligned_bucket,
                     &evaluator,
+                    rng,
                 );
 
                 drop(evaluator);
@@ -197,22 +211,31 @@ impl PreliminaryRoundGenerator {
         Ok(round_ballots?)
     }
 
+    /// Power pairing requires a preceding tab. Without one, clashes are avoided instead.
+    fn effective_draw_mode(&self, context: &RoundGenerationContext) -> PreliminariesDrawMode {
+        match self.draw_mode {
+            PreliminariesDrawMode::PowerPaired if context.teams.iter().any(|t| t.tab_rank.is_none()) => PreliminariesDrawMode::AvoidClashes,
+            mode => mode
+        }
+    }
+
     fn assign_teams_to_ballots(
         &self,
+        draw_mode: PreliminariesDrawMode,
         ballots: &Vec<DrawBallot>,
         gov_bucket: &Vec<&DrawTeamInfo>,
         opp_bucket: &Vec<&DrawTeamInfo>,
         non_aligned_bucket: &Vec<&DrawTeamInfo>,
         evaluator: &DrawEvaluator<DrawConstructionEvaluationContext>,
+        rng: &mut StdRng,
     ) -> Result<Vec<DrawBallot>, anyhow::Error> {
         let mut out_ballots = ballots.clone();
-        let mut rng = thread_rng();
 
         let mut non_aligned_bucket_position_buckets = (0..3).map(|_| Vec::new()).collect_vec();
 
         non_aligned_bucket.iter().for_each(|team| {
-            let mut member_ids = team.member_ids.clone();
-            member_ids.shuffle(&mut rng);
+            let mut member_ids = team.member_ids.iter().cloned().map(Some).pad_using(3, |_| None).collect_vec();
+            member_ids.shuffle(rng);
 
             for i in 0..3 {
                 non_aligned_bucket_position_buckets[i].push(member_ids[i]);
@@ -220,7 +243,10 @@ impl PreliminaryRoundGenerator {
         });
 
         let mut gov_bucket = gov_bucket.clone();
-        gov_bucket.shuffle(&mut rng);
+        // Power-paired buckets are in tab order, which must be kept
+        if draw_mode != PreliminariesDrawMode::PowerPaired {
+            gov_bucket.shuffle(rng);
+        }
         for (ballot_idx, ballot) in out_ballots.iter_mut().enumerate() {
             ballot.government = Some(DrawTeam {
                 uuid: gov_bucket[ballot_idx].uuid,
@@ -228,8 +254,46 @@ impl PreliminaryRoundGenerator {
             });
         }
 
-        match self.draw_mode {
-            PreliminariesDrawMode::AvoidClashes => {
+        match draw_mode {
+            PreliminariesDrawMode::Random => {
+                let mut opp_bucket = opp_bucket.clone();
+                opp_bucket.shuffle(rng);
+                for (ballot, team) in out_ballots.iter_mut().zip(opp_bucket.iter()) {
+                    ballot.opposition = Some(DrawTeam {
+                        uuid: team.uuid,
+                        ..Default::default()
+                    });
+                }
+
+                for position_bucket in non_aligned_bucket_position_buckets.iter_mut() {
+                    position_bucket.shuffle(rng);
+                    for (ballot, speaker_id) in out_ballots.iter_mut().zip(position_bucket.iter()) {
+                        ballot.non_aligned_speakers.push(speaker_id.map(|uuid| DrawSpeaker {
+                            uuid,
+                            ..Default::default()
+                        }));
+                    }
+                }
+            }
+            PreliminariesDrawMode::PowerPaired => {
+                // The i-th team of every bucket debates in the i-th room.
+                // Non-aligned speakers are rotated by their position, so that
+                // a team does not fill all non-aligned positions in a room.
+                for (ballot_idx, ballot) in out_ballots.iter_mut().enumerate() {
+                    ballot.opposition = Some(DrawTeam {
+                        uuid: opp_bucket[ballot_idx].uuid,
+                        ..Default::default()
+                    });
+
+                    for (position, position_bucket) in non_aligned_bucket_position_buckets.iter().enumerate() {
+                        ballot.non_aligned_speakers.push(position_bucket[(ballot_idx + position) % position_bucket.len()].map(|uuid| DrawSpeaker {
+                            uuid,
+                            ..Default::default()
+                        }));
+                    }
+                }
+            }
+            PreliminariesDrawMode::AvoidClashes | PreliminariesDrawMode::InstitutionSeeded => {
                 let possible_ballots = opp_bucket
                     .iter()
                     .map(|team| {
@@ -250,6 +314,7 @@ impl PreliminaryRoundGenerator {
                     &possible_ballots,
                     evaluator,
                     self.randomization_scale,
+                    rng,
                 )?;
 
                 for non_aligned_position in 0..3 {
@@ -261,8 +326,8 @@ impl PreliminaryRoundGenerator {
                                 .iter()
                                 .map(|ballot| {
                                     let mut new_non_aligned = ballot.non_aligned_speakers.clone();
-                                    new_non_aligned.push(Some(DrawSpeaker {
-                                        uuid: speaker_id.clone(),
+                                    new_non_aligned.push(speaker_id.map(|uuid| DrawSpeaker {
+                                        uuid,
                                         ..Default::default()
                                     }));
                                     DrawBallot {
@@ -278,6 +343,7 @@ impl PreliminaryRoundGenerator {
                         &possible_ballots,
                         evaluator,
                         self.randomization_scale,
+                        rng,
                     )?;
                 }
             }
@@ -287,6 +353,52 @@ impl PreliminaryRoundGenerator {
     }
 }
 
+/// Splits the teams into rooms of three by tab rank and distributes each room over the buckets.
+/// The buckets are sorted by rank, so that over a roundtrip every team faces the other teams of its room.
+fn power_paired_buckets<'a>(teams: &'a [DrawTeamInfo], rng: &mut StdRng) -> Vec<Vec<&'a DrawTeamInfo>> {
+    let ranked_teams = teams.iter().map(|t| ((t.tab_rank, rng.gen::<u64>()), t)).sorted_by_key(|(key, _)| *key).map(|(_, t)| t).collect_vec();
+
+    let mut buckets = vec![Vec::new(); 3];
+    for room in ranked_teams.chunks(3) {
+        let mut room = room.to_vec();
+        room.shuffle(rng);
+        for (bucket, team) in buckets.iter_mut().zip(room) {
+            bucket.push(team);
+        }
+    }
+
+    buckets
+}
+
+/// Teams in the same bucket always share a role, so they never face each other.
+/// We place teams of the same institution in the same bucket, largest institutions first,
+/// and only split an institution if it does not fit into a single bucket.
+fn institution_seeded_buckets<'a>(teams: &'a [DrawTeamInfo], rng: &mut StdRng) -> Vec<Vec<&'a DrawTeamInfo>> {
+    let bucket_size = teams.len() / 3;
+    let mut shuffled_teams = teams.iter().collect_vec();
+    shuffled_teams.shuffle(rng);
+
+    let institution_groups = shuffled_teams
+        .into_iter()
+        .into_group_map_by(|t| t.institution_ids.first().cloned().unwrap_or(t.uuid))
+        .into_values()
+        .sorted_by_key(|group| (std::cmp::Reverse(group.len()), group[0].uuid))
+        .collect_vec();
+
+    let mut buckets: Vec<Vec<&DrawTeamInfo>> = vec![Vec::new(); 3];
+    for group in institution_groups {
+        let mut remaining = group.as_slice();
+        while !remaining.is_empty() {
+            let bucket = buckets.iter_mut().max_by_key(|b| bucket_size - b.len()).expect("There are always three buckets");
+            let num_added = remaining.len().min(bucket_size - bucket.len());
+            bucket.extend_from_slice(&remaining[..num_added]);
+            remaining = &remaining[num_added..];
+        }
+    }
+
+    buckets
+}
+
 /* 
 #[cfg(test)]
 mod test {
@@ -0,0 +1,502 @@
+use std::collections::{HashMap, HashSet};
+
+use itertools::Itertools;
+use open_tab_entities::{domain::{debate::DebatePins, entity::LoadEntity, tournament::DrawConfig}, info::TournamentParticipantsInfo, prelude::{Ballot, ParticipantRole, TournamentDebate, TournamentRound}};
+use sea_orm::prelude::Uuid;
+use serde::{Deserialize, Serialize};
+
+use crate::draw_view::DrawBallot;
+
+use super::{datastructures::DebateInfo, evaluation::{DrawConstructionEvaluationContext, DrawEvaluationContext, DrawEvaluator, DrawEvaluatorConfig}};
+
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type")]
+pub enum DrawPosition {
+    Government,
+    Opposition,
+    NonAligned { position: usize },
+    Chair,
+    Wing,
+    President,
+}
+
+/// A single position in the draw that is changed by a repair.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DrawRepairChange {
+    pub debate_index: usize,
+    pub position: DrawPosition,
+    pub previous: Option<Uuid>,
+    pub new: Option<Uuid>,
+}
+
+/// A position that is still empty after a repair.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DrawVacancy {
+    pub debate_index: usize,
+    pub position: DrawPosition,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DrawRepair {
+    #[serde(skip)]
+    pub ballots: Vec<DrawBallot>,
+    pub changes: Vec<DrawRepairChange>,
+    /// Added participants that could not be placed in the draw
+    pub unplaced: Vec<Uuid>,
+    /// Added teams that did not get a government or opposition position.
+    /// Their members are placed as non-aligned speakers where possible.
+    pub unplaced_teams: Vec<Uuid>,
+    /// Team, non-aligned speaker and chair positions that are empty after the repair
+    pub vacancies: Vec<DrawVacancy>,
+    /// The pins of the debates after the repair. Wing pins follow their adjudicators
+    /// and pins of withdrawn participants are removed.
+    #[serde(skip)]
+    pub pins: Vec<DebatePins>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct DrawRepairRequest {
+    /// Withdrawn teams, speakers and adjudicators
+    pub withdrawn: HashSet<Uuid>,
+    pub added_teams: Vec<Uuid>,
+    pub added_team_members: HashMap<Uuid, Vec<Uuid>>,
+    pub added_adjudicators: Vec<Uuid>,
+    pub added_speakers: Vec<Uuid>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct RepairDebate {
+    government: Option<Uuid>,
+    opposition: Option<Uuid>,
+    non_aligned_speakers: Vec<Option<Uuid>>,
+    chair: Option<Uuid>,
+    wings: Vec<Uuid>,
+    president: Option<Uuid>,
+}
+
+impl RepairDebate {
+    fn from_ballot(ballot: &DrawBallot) -> Self {
+        let info = DebateInfo::from(ballot);
+        RepairDebate {
+            government: info.government,
+            opposition: info.opposition,
+            non_aligned_speakers: ballot.non_aligned_speakers.iter().map(|s| s.as_ref().map(|s| s.uuid)).collect(),
+            chair: info.chair,
+            wings: info.wings,
+            president: info.president,
+        }
+    }
+
+    fn severity<C>(&self, evaluator: &DrawEvaluator<C>) -> i64 where C: DrawEvaluationContext {
+        let info = DebateInfo {
+            id: Uuid::nil(),
+            government: self.government,
+            opposition: self.opposition,
+            chair: self.chair,
+            wings: self.wings.clone(),
+            president: self.president,
+            trainees: vec![],
+            non_aligned_speakers: self.non_aligned_speakers.iter().flatten().cloned().collect(),
+        };
+        evaluator.find_issues_in_debate(&info).total_severity() as i64
+    }
+
+    fn get(&self, slot: &Slot) -> Option<Uuid> {
+        match slot {
+            Slot::Government => self.government,
+            Slot::Opposition => self.opposition,
+            Slot::NonAligned(position) => self.non_aligned_speakers[*position],
+            Slot::Chair => self.chair,
+            Slot::Wing(position) => self.wings.get(*position).cloned(),
+        }
+    }
+
+    fn set(&mut self, slot: &Slot, occupant: Option<Uuid>) {
+        match slot {
+            Slot::Government => self.government = occupant,
+            Slot::Opposition => self.opposition = occupant,
+            Slot::NonAligned(position) => self.non_aligned_speakers[*position] = occupant,
+            Slot::Chair => self.chair = occupant,
+            Slot::Wing(position) => {
+                match occupant {
+                    Some(occupant) if *position < self.wings.len() => self.wings[*position] = occupant,
+                    Some(occupant) => self.wings.push(occupant),
+                    None => { self.wings.remove(*position); }
+                }
+            }
+        }
+    }
+
+    fn slots(&self) -> Vec<Slot> {
+        vec![Slot::Government, Slot::Opposition, Slot::Chair].into_iter()
+            .chain((0..self.non_aligned_speakers.len()).map(Slot::NonAligned))
+            .chain((0..self.wings.len()).map(Slot::Wing))
+            .collect()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Slot {
+    Government,
+    Opposition,
+    NonAligned(usize),
+    Chair,
+    Wing(usize),
+}
+
+impl Slot {
+    fn can_swap_with(&self, other: &Slot) -> bool {
+        match (self, other) {
+            (Slot::Wing(_), Slot::Wing(_)) => true,
+            _ => self == other
+        }
+    }
+
+    fn is_pinned(&self, pins: &DebatePins) -> bool {
+        match self {
+            Slot::Government => pins.government,
+            Slot::Opposition => pins.opposition,
+            Slot::NonAligned(position) => pins.non_aligned_speakers.contains(position),
+            Slot::Chair => pins.chair,
+            Slot::Wing(position) => pins.wings.contains(position),
+        }
+    }
+}
+
+struct DrawRepairState<'a, C> {
+    debates: Vec<RepairDebate>,
+    pins: Vec<DebatePins>,
+    evaluator: &'a DrawEvaluator<'a, C>,
+    placed: Vec<(Uuid, usize)>,
+}
+
+impl<'a, C> DrawRepairState<'a, C> where C: DrawEvaluationContext {
+    fn placement_cost(&self, debate_idx: usize, slot: &Slot, occupant: Uuid) -> i64 {
+        let debate = &self.debates[debate_idx];
+        let mut new_debate = debate.clone();
+        new_debate.set(slot, Some(occupant));
+        new_debate.severity(self.evaluator) - debate.severity(self.evaluator)
+    }
+
+    fn place(&mut self, debate_idx: usize, slot: &Slot, occupant: Uuid) {
+        self.debates[debate_idx].set(slot, Some(occupant));
+        self.placed.push((occupant, debate_idx));
+    }
+
+    /// Removes a wing from a panel, keeping the pins of the remaining wings on their adjudicators
+    fn remove_wing(&mut self, debate_idx: usize, wing_idx: usize) -> Uuid {
+        let wing = self.debates[debate_idx].wings.remove(wing_idx);
+        let pinned_wings = &mut self.pins[debate_idx].wings;
+        pinned_wings.retain(|w| *w != wing_idx);
+        for w in pinned_wings.iter_mut() {
+            if *w > wing_idx {
+                *w -= 1;
+            }
+        }
+        wing
+    }
+
+    /// Repeatedly fills the vacancy-candidate pair with the lowest cost.
+    /// Returns the candidates that could not be placed.
+    fn fill_vacancies(&mut self, mut vacancies: Vec<(usize, Slot)>, mut candidates: Vec<Uuid>) -> Vec<Uuid> {
+        while !vacancies.is_empty() && !candidates.is_empty() {
+            let (vacancy_idx, candidate_idx, _) = vacancies.iter().enumerate().cartesian_product(candidates.iter().enumerate()).map(
+                |((vacancy_idx, (debate_idx, slot)), (candidate_idx, candidate))| {
+                    (vacancy_idx, candidate_idx, self.placement_cost(*debate_idx, slot, *candidate))
+                }
+            ).min_by_key(|(_, _, cost)| *cost).expect("Vacancies and candidates are not empty");
+
+            let (debate_idx, slot) = vacancies.remove(vacancy_idx);
+            let candidate = candidates.remove(candidate_idx);
+            self.place(debate_idx, &slot, candidate);
+        }
+
+        candidates
+    }
+
+    fn fill_chair_vacancies(&mut self, vacancies: Vec<usize>, mut candidates: Vec<Uuid>, chair_skills: &HashMap<Uuid, i16>) -> Vec<Uuid> {
+        for debate_idx in vacancies {
+            if !candidates.is_empty() {
+                let (candidate_idx, _) = candidates.iter().enumerate().min_by_key(
+                    |(_, c)| (self.placement_cost(debate_idx, &Slot::Chair, **c), -chair_skills.get(*c).cloned().unwrap_or(0))
+                ).expect("Candidates are not empty");
+                let candidate = candidates.remove(candidate_idx);
+                self.place(debate_idx, &Slot::Chair, candidate);
+            }
+            else if let Some(wing_idx) = self.debates[debate_idx].wings.iter().enumerate().filter(
+                |(idx, _)| !self.pins[debate_idx].wings.contains(idx)
+            ).max_by_key(
+                |(_, w)| chair_skills.get(w).cloned().unwrap_or(0)
+            ).map(|(idx, _)| idx) {
+                // Promoting a wing of the same panel keeps all other debates unchanged
+                let wing = self.remove_wing(debate_idx, wing_idx);
+                self.place(debate_idx, &Slot::Chair, wing);
+            }
+            else {
+                // Otherwise a wing is taken from one of the largest panels
+                let max_wings = self.debates.iter().map(|d| d.wings.len()).max().unwrap_or(0);
+                let donor_wings = (0..self.debates.len()).filter(
+                    |idx| *idx != debate_idx && self.debates[*idx].chair.is_some() && self.debates[*idx].wings.len() == max_wings
+                ).flat_map(
+                    |idx| (0..max_wings).filter(|w| !self.pins[idx].wings.contains(w)).map(|w| (idx, w)).collect_vec()
+                ).collect_vec();
+                let donor = donor_wings.into_iter().min_by_key(
+                    |(idx, w)| self.placement_cost(debate_idx, &Slot::Chair, self.debates[*idx].wings[*w])
+                );
+
+                if let Some((donor_idx, wing_idx)) = donor {
+                    let wing = self.remove_wing(donor_idx, wing_idx);
+                    self.place(debate_idx, &Slot::Chair, wing);
+                }
+                else if let Some(wing_idx) = (0..self.debates[debate_idx].wings.len()).max_by_key(
+                    |idx| chair_skills.get(&self.debates[debate_idx].wings[*idx]).cloned().unwrap_or(0)
+                ) {
+                    // Only pinned wings are left. A panel with wings always needs a chair,
+                    // so one of them is promoted and stays pinned as the chair.
+                    let wing = self.remove_wing(debate_idx, wing_idx);
+                    self.place(debate_idx, &Slot::Chair, wing);
+                    self.pins[debate_idx].chair = true;
+                }
+            }
+        }
+
+        candidates
+    }
+
+    fn add_wings(&mut self, candidates: Vec<Uuid>) {
+        for candidate in candidates {
+            let debate_idx = (0..self.debates.len()).filter(|idx| self.debates[*idx].chair.is_some()).min_by_key(
+                |idx| (self.debates[*idx].wings.len(), self.placement_cost(*idx, &Slot::Wing(usize::MAX), candidate))
+            );
+
+            if let Some(debate_idx) = debate_idx {
+                self.place(debate_idx, &Slot::Wing(usize::MAX), candidate);
+            }
+        }
+    }
+
+    /// Swaps each newly placed participant with a participant in the same position of another debate,
+    /// if that strictly reduces the total severity of the two debates.
+    fn improve_by_swaps(&mut self) {
+        for (participant, debate_idx) in self.placed.clone() {
+            let Some(slot) = self.debates[debate_idx].slots().into_iter().find(|s| self.debates[debate_idx].get(s) == Some(participant)) else {
+                continue;
+            };
+            if slot.is_pinned(&self.pins[debate_idx]) {
+                continue;
+            }
+
+            let current_cost = |state: &Self, other_idx: usize| state.debates[debate_idx].severity(state.evaluator) + state.debates[other_idx].severity(state.evaluator);
+
+            let best_swap = (0..self.debates.len()).filter(|idx| *idx != debate_idx).flat_map(
+                |other_idx| self.debates[other_idx].slots().into_iter().filter(
+                    |other_slot| slot.can_swap_with(other_slot) && !other_slot.is_pinned(&self.pins[other_idx]) && self.debates[other_idx].get(other_slot).is_some()
+                ).map(|other_slot| (other_idx, other_slot)).collect_vec()
+            ).map(|(other_idx, other_slot)| {
+                let mut debate = self.debates[debate_idx].clone();
+                let mut other_debate = self.debates[other_idx].clone();
+                let other_participant = other_debate.get(&other_slot);
+                debate.set(&slot, other_participant);
+                other_debate.set(&other_slot, Some(participant));
+                let improvement = current_cost(self, other_idx) - debate.severity(self.evaluator) - other_debate.severity(self.evaluator);
+                (other_idx, other_slot, improvement)
+            }).max_by_key(|(_, _, improvement)| *improvement);
+
+            if let Some((other_idx, other_slot, improvement)) = best_swap {
+                if improvement > 0 {
+                    let other_participant = self.debates[other_idx].get(&other_slot);
+                    self.debates[debate_idx].set(&slot, other_participant);
+                    self.debates[other_idx].set(&other_slot, Some(participant));
+                }
+            }
+        }
+    }
+}
+
+fn diff_debates(debate_index: usize, previous: &RepairDebate, new: &RepairDebate) -> Vec<DrawRepairChange> {
+    let mut changes = vec![];
+    let mut push_if_changed = |position, previous: Option<Uuid>, new: Option<Uuid>| {
+        if previous != new {
+            changes.push(DrawRepairChange { debate_index, position, previous, new });
+        }
+    };
+
+    push_if_changed(DrawPosition::Government, previous.government, new.government);
+    push_if_changed(DrawPosition::Opposition, previous.opposition, new.opposition);
+    for (position, (previous, new)) in previous.non_aligned_speakers.iter().zip(new.non_aligned_speakers.iter()).enumerate() {
+        push_if_changed(DrawPosition::NonAligned { position }, *previous, *new);
+    }
+    push_if_changed(DrawPosition::Chair, previous.chair, new.chair);
+
+    let removed_wings = previous.wings.iter().filter(|w| !new.wings.contains(w)).cloned().collect_vec();
+    let added_wings = new.wings.iter().filter(|w| !previous.wings.contains(w)).cloned().collect_vec();
+    for pair in removed_wings.into_iter().zip_longest(added_wings) {
+        let (previous, new) = pair.left_and_right();
+        push_if_changed(DrawPosition::Wing, previous, new);
+    }
+
+    push_if_changed(DrawPosition::President, previous.president, new.president);
+
+    changes
+}
+
+/// Repairs a draw after participants have withdrawn or were added.
+/// Withdrawn participants are removed, and their positions are filled with the added participants
+/// at the lowest cost according to the evaluator. Afterwards, newly placed participants are swapped
+/// into other debates only where that reduces the severity of issues, so that the draw changes as little as possible.
+/// Pinned positions are never swapped.
+/// Added teams without a team position and positions that stay empty are reported in the repair.
+pub fn repair_draw<C>(
+    ballots: &[DrawBallot],
+    pins: &[DebatePins],
+    request: &DrawRepairRequest,
+    chair_skills: &HashMap<Uuid, i16>,
+    evaluator: &DrawEvaluator<C>
+) -> DrawRepair where C: DrawEvaluationContext {
+    let previous_debates = ballots.iter().map(RepairDebate::from_ballot).collect_vec();
+
+    let mut state = DrawRepairState {
+        debates: previous_debates.clone(),
+        pins: pins.to_vec(),
+        evaluator,
+        placed: vec![],
+    };
+
+    let mut team_vacancies = vec![];
+    let mut non_aligned_vacancies = vec![];
+    let mut chair_vacancies = vec![];
+
+    // Positions of withdrawn participants are no longer pinned, since their replacements are chosen by the repair
+    for debate_idx in 0..state.debates.len() {
+        let debate = &mut state.debates[debate_idx];
+        let pins = &mut state.pins[debate_idx];
+        if debate.government.map(|t| request.withdrawn.contains(&t)).unwrap_or(false) {
+            debate.government = None;
+            pins.government = false;
+            team_vacancies.push((debate_idx, Slot::Government));
+        }
+        if debate.opposition.map(|t| request.withdrawn.contains(&t)).unwrap_or(false) {
+            debate.opposition = None;
+            pins.opposition = false;
+            team_vacancies.push((debate_idx, Slot::Opposition));
+        }
+        for (position, speaker) in debate.non_aligned_speakers.iter_mut().enumerate() {
+            if speaker.map(|s| request.withdrawn.contains(&s)).unwrap_or(false) {
+                *speaker = None;
+                pins.non_aligned_speakers.retain(|p| *p != position);
+                non_aligned_vacancies.push((debate_idx, Slot::NonAligned(position)));
+            }
+        }
+        if debate.chair.map(|c| request.withdrawn.contains(&c)).unwrap_or(false) {
+            debate.chair = None;
+            pins.chair = false;
+            chair_vacancies.push(debate_idx);
+        }
+        if debate.president.map(|p| request.withdrawn.contains(&p)).unwrap_or(false) {
+            debate.president = None;
+            pins.president = false;
+        }
+
+        let withdrawn_wings = state.debates[debate_idx].wings.iter().enumerate().filter(
+            |(_, w)| request.withdrawn.contains(w)
+        ).map(|(idx, _)| idx).collect_vec();
+        for wing_idx in withdrawn_wings.into_iter().rev() {
+            state.remove_wing(debate_idx, wing_idx);
+        }
+    }
+
+    let unplaced_teams = state.fill_vacancies(team_vacancies, request.added_teams.clone());
+    let speaker_candidates = unplaced_teams.iter().flat_map(
+        |t| request.added_team_members.get(t).cloned().unwrap_or_default()
+    ).chain(request.added_speakers.iter().cloned()).collect_vec();
+    let unplaced_speakers = state.fill_vacancies(non_aligned_vacancies, speaker_candidates);
+
+    let remaining_adjudicators = state.fill_chair_vacancies(chair_vacancies, request.added_adjudicators.clone(), chair_skills);
+    state.add_wings(remaining_adjudicators);
+
+    state.improve_by_swaps();
+
+    let changes = previous_debates.iter().zip(state.debates.iter()).enumerate().flat_map(
+        |(debate_idx, (previous, new))| diff_debates(debate_idx, previous, new)
+    ).collect_vec();
+
+    let new_ballots = ballots.iter().zip(state.debates.iter()).map(|(ballot, debate)| {
+        let mut ballot = ballot.clone();
+        ballot.government = debate.government.map(|uuid| crate::draw_view::DrawTeam { uuid, ..Default::default() });
+        ballot.opposition = debate.opposition.map(|uuid| crate::draw_view::DrawTeam { uuid, ..Default::default() });
+        ballot.non_aligned_speakers = debate.non_aligned_speakers.iter().map(
+            |s| s.map(|uuid| crate::draw_view::DrawSpeaker { uuid, ..Default::default() })
+        ).collect();
+        ballot.adjudicators = debate.chair.iter().chain(debate.wings.iter()).map(
+            |uuid| crate::draw_view::DrawAdjudicator { uuid: *uuid, ..Default::default() }.into()
+        ).collect();
+        ballot.president = debate.president.map(|uuid| crate::draw_view::DrawAdjudicator { uuid, ..Default::default() }.into());
+        ballot.trainees.retain(|t| !request.withdrawn.contains(&t.adjudicator.uuid));
+        ballot
+    }).collect_vec();
+
+    let placed = state.placed.iter().map(|(p, _)| *p).collect::<HashSet<_>>();
+    let unplaced = unplaced_speakers.into_iter().chain(
+        request.added_adjudicators.iter().filter(|a| !placed.contains(a)).cloned()
+    ).collect_vec();
+
+    let vacancies = state.debates.iter().enumerate().flat_map(|(debate_index, debate)| {
+        debate.government.is_none().then_some(DrawPosition::Government).into_iter()
+            .chain(debate.opposition.is_none().then_some(DrawPosition::Opposition))
+            .chain(debate.non_aligned_speakers.iter().positions(|s| s.is_none()).map(|position| DrawPosition::NonAligned { position }))
+            .chain(debate.chair.is_none().then_some(DrawPosition::Chair))
+            .map(move |position| DrawVacancy { debate_index, position })
+    }).collect_vec();
+
+    DrawRepair {
+        ballots: new_ballots,
+        changes,
+        unplaced,
+        unplaced_teams,
+        vacancies,
+        pins: state.pins,
+    }
+}
+
+/// Computes a repair for the draw of a round from the database.
+/// Ids can be team or participant ids, withdrawing a team also withdraws its members.
+pub async fn compute_draw_repair<C>(
+    db: &C,
+    round_id: Uuid,
+    withdrawn_ids: &[Uuid],
+    added_ids: &[Uuid]
+) -> Result<(Vec<TournamentDebate>, Vec<Ballot>, DrawRepair), anyhow::Error> where C: sea_orm::ConnectionTrait {
+    let round = TournamentRound::get(db, round_id).await?;
+    let debates = TournamentDebate::get_all_in_rounds(db, vec![round_id]).await?.into_iter().next().unwrap_or_default()
+        .into_iter().sorted_by_key(|d| d.index).collect_vec();
+    let ballots = Ballot::get_many(db, debates.iter().map(|d| d.ballot_id).collect()).await?;
+    let pins = debates.iter().map(|d| d.pins.clone().unwrap_or_default()).collect_vec();
+    let info = TournamentParticipantsInfo::load(db, round.tournament_id).await?;
+
+    let added_participants = added_ids.iter().filter_map(|id| info.participants_by_id.get(id)).collect_vec();
+    let request = DrawRepairRequest {
+        withdrawn: withdrawn_ids.iter().flat_map(
+            |id| std::iter::once(*id).chain(info.team_members.get(id).cloned().unwrap_or_default())
+        ).collect(),
+        added_teams: added_ids.iter().filter(|id| info.teams_by_id.contains_key(id)).cloned().collect(),
+        added_team_members: info.team_members.clone(),
+        added_adjudicators: added_participants.iter().filter(|p| matches!(p.role, ParticipantRole::Adjudicator(_))).map(|p| p.uuid).collect(),
+        added_speakers: added_participants.iter().filter(|p| matches!(p.role, ParticipantRole::Speaker(_))).map(|p| p.uuid).collect(),
+    };
+    let chair_skills = info.participants_by_id.values().filter_map(|p| match &p.role {
+        ParticipantRole::Adjudicator(adj) => Some((p.uuid, adj.chair_skill)),
+        _ => None
+    }).collect::<HashMap<_, _>>();
+
+    let other_rounds = TournamentRound::get_all_in_tournament(db, round.tournament_id).await?.into_iter()
+        .map(|r| r.uuid).filter(|r| *r != round_id).collect_vec();
+    let evaluation_context = DrawConstructionEvaluationContext::new_from_tournament(db, round.tournament_id).await?;
+    let draw_config = DrawConfig::load_for_tournament(db, round.tournament_id).await?;
+    let evaluator = DrawEvaluator::new(DrawEvaluatorConfig::from(&draw_config), other_rounds, &evaluation_context);
+
+    let draw_ballots = ballots.iter().map(DrawBallot::from).collect_vec();
+    let repair = repair_draw(&draw_ballots, &pins, &request, &chair_skills, &evaluator);
+
+    Ok((debates, ballots, repair))
+}
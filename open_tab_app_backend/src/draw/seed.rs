@@ -0,0 +1,34 @@
+use rand::{rngs::StdRng, thread_rng, Rng, SeedableRng};
+
+
+/// Returns the given seed, or a new random one if none was given.
+/// New seeds are kept below 2^32, so they can be displayed and entered
+/// in the frontend without losing precision.
+pub fn choose_draw_seed(seed: Option<i64>) -> i64 {
+    seed.unwrap_or_else(|| thread_rng().gen::<u32>() as i64)
+}
+
+/// Creates the random number generator for a draw.
+/// The same seed always produces the same sequence of random choices.
+pub fn draw_rng(seed: i64) -> StdRng {
+    StdRng::seed_from_u64(seed as u64)
+}
+
+#[cfg(test)]
+mod test {
+    use rand::Rng;
+
+    use super::{choose_draw_seed, draw_rng};
+
+    #[test]
+    fn test_given_seed_is_kept() {
+        assert_eq!(choose_draw_seed(Some(42)), 42);
+    }
+
+    #[test]
+    fn test_same_seed_gives_same_sequence() {
+        let a = draw_rng(42).gen::<[u64; 4]>();
+        let b = draw_rng(42).gen::<[u64; 4]>();
+        assert_eq!(a, b);
+    }
+}
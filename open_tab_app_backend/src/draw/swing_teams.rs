@@ -0,0 +1,263 @@
+use std::collections::{HashMap, HashSet};
+
+use itertools::Itertools;
+use open_tab_entities::prelude::{Participant, ParticipantRole, Speaker, Team};
+use sea_orm::prelude::Uuid;
+use thiserror::Error;
+
+pub const SWING_TEAM_SIZE: usize = 3;
+
+#[derive(Error, Debug)]
+pub enum SwingTeamError {
+    #[error("Swing teams can have at most {SWING_TEAM_SIZE} members, got {0}")]
+    TooManyMembers(usize),
+    #[error("Swing teams need at least one member")]
+    NoMembers,
+    #[error("Speaker {0} is already in a regular team")]
+    SpeakerHasTeam(Uuid),
+    #[error("Adjudicator {0} can only swing in specific rounds")]
+    MissingSwingRounds(Uuid),
+    #[error("{required} swing teams are needed, but there are only {available} speakers without a team")]
+    NotEnoughSpareSpeakers { required: usize, available: usize },
+}
+
+/// Number of swing teams that have to be added so that the teams can be split into debates.
+pub fn num_required_swing_teams(num_teams: usize) -> usize {
+    (SWING_TEAM_SIZE - num_teams % SWING_TEAM_SIZE) % SWING_TEAM_SIZE
+}
+
+/// Picks the first name of the form "Swing N" that is not taken by another team.
+pub fn next_swing_team_name<'a, I>(existing_names: I) -> String where I: IntoIterator<Item=&'a String> {
+    let existing_names = existing_names.into_iter().collect::<HashSet<_>>();
+    (1..).map(|idx| format!("Swing {}", idx)).find(|name| !existing_names.contains(name)).expect("Names are unbounded")
+}
+
+/// Forms a swing team from spare participants.
+/// Speakers without a team (or from another swing team) join the new team directly.
+/// Adjudicators remain adjudicators, instead a speaker with their name and institutions is created.
+/// Since they speak in the rounds the team swings in, they are unavailable for adjudication in these rounds.
+/// Swing teams may have fewer than three members, but at least one. The missing speeches stay empty.
+/// Returns the team and all participants that need to be saved.
+pub fn make_swing_team(tournament_id: Uuid, name: String, members: Vec<&Participant>, round_ids: &[Uuid], teams_by_id: &HashMap<Uuid, Team>) -> Result<(Team, Vec<Participant>), SwingTeamError> {
+    if members.len() > SWING_TEAM_SIZE {
+        return Err(SwingTeamError::TooManyMembers(members.len()));
+    }
+    if members.is_empty() {
+        return Err(SwingTeamError::NoMembers);
+    }
+
+    let team = Team {
+        uuid: Uuid::new_v4(),
+        name,
+        tournament_id,
+        is_swing: true,
+    };
+
+    let mut unavailable_adjudicators = vec![];
+    let team_members = members.into_iter().map(|member| {
+        match &member.role {
+            ParticipantRole::Speaker(speaker) => {
+                if let Some(team_id) = speaker.team_id {
+                    if !teams_by_id.get(&team_id).map(|t| t.is_swing).unwrap_or(false) {
+                        return Err(SwingTeamError::SpeakerHasTeam(member.uuid));
+                    }
+                }
+                Ok(Participant {
+                    role: ParticipantRole::Speaker(Speaker { team_id: Some(team.uuid) }),
+                    ..member.clone()
+                })
+            },
+            ParticipantRole::Adjudicator(adjudicator) => {
+                if round_ids.is_empty() {
+                    return Err(SwingTeamError::MissingSwingRounds(member.uuid));
+                }
+                let mut adjudicator = adjudicator.clone();
+                adjudicator.unavailable_rounds.extend(round_ids.iter().filter(|r| !adjudicator.unavailable_rounds.contains(r)).cloned().collect_vec());
+                unavailable_adjudicators.push(Participant {
+                    role: ParticipantRole::Adjudicator(adjudicator),
+                    ..member.clone()
+                });
+                Ok(Participant {
+                    institutions: member.institutions.clone(),
+                    requires_accessible_venue: member.requires_accessible_venue,
+                    ..Participant::new_with_uuid(Uuid::new_v4(), member.name.clone(), ParticipantRole::Speaker(Speaker { team_id: Some(team.uuid) }), tournament_id)
+                })
+            }
+        }
+    }).collect::<Result<Vec<_>, _>>()?;
+
+    Ok((team, team_members.into_iter().chain(unavailable_adjudicators).collect()))
+}
+
+pub struct DrawTeamSelection {
+    /// Teams that take part in the draw
+    pub teams: Vec<Team>,
+    /// Swing teams that were formed for this draw and still need to be saved
+    pub new_teams: Vec<Team>,
+    /// Members of the new swing teams, and spare speakers that joined them
+    pub new_members: Vec<Participant>,
+}
+
+/// Selects the teams for a draw.
+/// Regular teams always take part. Swing teams are only drawn as far as they are
+/// needed to reach a multiple of three, with complete swing teams preferred.
+/// If there are not enough swing teams, new ones are formed from speakers without a team.
+/// Every new swing team gets at least one speaker, otherwise an error is returned.
+pub fn select_draw_teams(tournament_id: Uuid, teams: Vec<Team>, participants: &[Participant]) -> Result<DrawTeamSelection, SwingTeamError> {
+    let team_sizes = participants.iter().filter_map(|p| match &p.role {
+        ParticipantRole::Speaker(Speaker { team_id: Some(team_id) }) => Some(*team_id),
+        _ => None
+    }).counts();
+    let teams_by_id = teams.iter().map(|t| (t.uuid, t.clone())).collect::<HashMap<_, _>>();
+    let mut team_names = teams.iter().map(|t| t.name.clone()).collect_vec();
+
+    let (swing_teams, mut draw_teams): (Vec<_>, Vec<_>) = teams.into_iter().partition(|t| t.is_swing);
+    let num_required = num_required_swing_teams(draw_teams.len());
+
+    let mut swing_teams = swing_teams.into_iter().sorted_by_key(
+        |t| (team_sizes.get(&t.uuid).cloned().unwrap_or(0) != SWING_TEAM_SIZE, t.name.clone())
+    ).collect_vec();
+    // Without regular teams the swing teams only have to be drawn in groups of three
+    let num_drawn_swing_teams = if draw_teams.is_empty() {
+        swing_teams.len() - swing_teams.len() % SWING_TEAM_SIZE
+    }
+    else if swing_teams.len() >= num_required {
+        num_required
+    }
+    else {
+        swing_teams.len()
+    };
+    draw_teams.extend(swing_teams.drain(..num_drawn_swing_teams));
+
+    let mut spare_speakers = participants.iter().filter(|p| matches!(p.role, ParticipantRole::Speaker(Speaker { team_id: None }))).collect_vec();
+
+    let mut new_teams = vec![];
+    let mut new_members = vec![];
+    if !draw_teams.is_empty() {
+        let num_new_teams = num_required_swing_teams(draw_teams.len());
+        if spare_speakers.len() < num_new_teams {
+            return Err(SwingTeamError::NotEnoughSpareSpeakers { required: num_new_teams, available: spare_speakers.len() });
+        }
+        for num_remaining_teams in (1..=num_new_teams).rev() {
+            let name = next_swing_team_name(team_names.iter());
+            // Leave one speaker for each of the remaining teams
+            let num_members = (spare_speakers.len() - (num_remaining_teams - 1)).min(SWING_TEAM_SIZE);
+            let members = spare_speakers.drain(..num_members).collect_vec();
+            let (team, members) = make_swing_team(tournament_id, name, members, &[], &teams_by_id)?;
+            team_names.push(team.name.clone());
+            draw_teams.push(team.clone());
+            new_teams.push(team);
+            new_members.extend(members);
+        }
+    }
+
+    Ok(DrawTeamSelection {
+        teams: draw_teams,
+        new_teams,
+        new_members,
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::HashMap;
+
+    use open_tab_entities::prelude::{Adjudicator, Participant, ParticipantRole, Speaker, Team};
+    use sea_orm::prelude::Uuid;
+
+    use super::{make_swing_team, num_required_swing_teams, select_draw_teams};
+
+    fn make_team(id: u128, is_swing: bool) -> Team {
+        Team { uuid: Uuid::from_u128(id), name: format!("Team {}", id), tournament_id: Uuid::from_u128(1), is_swing }
+    }
+
+    fn make_speaker(id: u128, team_id: Option<u128>) -> Participant {
+        Participant::new_with_uuid(Uuid::from_u128(id), format!("Speaker {}", id), ParticipantRole::Speaker(Speaker { team_id: team_id.map(Uuid::from_u128) }), Uuid::from_u128(1))
+    }
+
+    #[test]
+    fn test_num_required_swing_teams() {
+        assert_eq!(num_required_swing_teams(9), 0);
+        assert_eq!(num_required_swing_teams(10), 2);
+        assert_eq!(num_required_swing_teams(11), 1);
+    }
+
+    #[test]
+    fn test_adjudicators_are_copied_into_swing_team() {
+        let adjudicator = Participant::new_with_uuid(Uuid::from_u128(3000), "Adj".into(), ParticipantRole::Adjudicator(Adjudicator::default()), Uuid::from_u128(1));
+        let speaker = make_speaker(2000, None);
+        let round_id = Uuid::from_u128(100);
+        let (team, members) = make_swing_team(Uuid::from_u128(1), "Swing 1".into(), vec![&adjudicator, &speaker], &[round_id], &HashMap::new()).unwrap();
+
+        assert!(team.is_swing);
+        assert_eq!(members.len(), 3);
+        assert!(members[..2].iter().all(|m| m.role == ParticipantRole::Speaker(Speaker { team_id: Some(team.uuid) })));
+        assert!(members[..2].iter().all(|m| m.uuid != adjudicator.uuid));
+        assert_eq!(members[0].name, "Adj");
+        assert_eq!(members[1].uuid, speaker.uuid);
+
+        // The adjudicator can not judge while speaking in the swing team
+        assert_eq!(members[2].uuid, adjudicator.uuid);
+        match &members[2].role {
+            ParticipantRole::Adjudicator(adj) => assert_eq!(adj.unavailable_rounds, vec![round_id]),
+            _ => panic!("Expected an adjudicator")
+        }
+    }
+
+    #[test]
+    fn test_adjudicators_need_swing_rounds() {
+        let adjudicator = Participant::new_with_uuid(Uuid::from_u128(3000), "Adj".into(), ParticipantRole::Adjudicator(Adjudicator::default()), Uuid::from_u128(1));
+        assert!(make_swing_team(Uuid::from_u128(1), "Swing 1".into(), vec![&adjudicator], &[], &HashMap::new()).is_err());
+    }
+
+    #[test]
+    fn test_swing_teams_need_members() {
+        assert!(make_swing_team(Uuid::from_u128(1), "Swing 1".into(), vec![], &[], &HashMap::new()).is_err());
+    }
+
+    #[test]
+    fn test_speakers_of_regular_teams_can_not_swing() {
+        let speaker = make_speaker(2000, Some(1000));
+        let teams = vec![(Uuid::from_u128(1000), make_team(1000, false))].into_iter().collect();
+        assert!(make_swing_team(Uuid::from_u128(1), "Swing 1".into(), vec![&speaker], &[], &teams).is_err());
+    }
+
+    #[test]
+    fn test_only_needed_swing_teams_are_drawn() {
+        let teams = (0..6).map(|i| make_team(1000 + i, false)).chain((0..2).map(|i| make_team(1100 + i, true))).collect();
+        let selection = select_draw_teams(Uuid::from_u128(1), teams, &[]).unwrap();
+
+        assert_eq!(selection.teams.len(), 6);
+        assert!(selection.new_teams.is_empty());
+    }
+
+    #[test]
+    fn test_missing_swing_teams_are_formed_from_spare_speakers() {
+        let teams = (0..4).map(|i| make_team(1000 + i, false)).collect();
+        let spare_speakers = (0..4).map(|i| make_speaker(2000 + i, None)).collect::<Vec<_>>();
+        let selection = select_draw_teams(Uuid::from_u128(1), teams, &spare_speakers).unwrap();
+
+        assert_eq!(selection.teams.len(), 6);
+        assert_eq!(selection.new_teams.len(), 2);
+        assert_eq!(selection.new_teams[0].name, "Swing 1");
+        assert_eq!(selection.new_teams[1].name, "Swing 2");
+        // No placeholder speakers are created for the missing members
+        assert_eq!(selection.new_members.len(), 4);
+        assert!(selection.new_members.iter().all(|m| m.uuid.as_u128() >= 2000 && m.uuid.as_u128() < 2004));
+    }
+
+    #[test]
+    fn test_every_new_swing_team_gets_a_speaker() {
+        let teams = (0..4).map(|i| make_team(1000 + i, false)).collect::<Vec<_>>();
+        let spare_speakers = (0..2).map(|i| make_speaker(2000 + i, None)).collect::<Vec<_>>();
+        let selection = select_draw_teams(Uuid::from_u128(1), teams.clone(), &spare_speakers).unwrap();
+
+        let new_team_sizes = selection.new_teams.iter().map(
+            |t| selection.new_members.iter().filter(|m| m.role == ParticipantRole::Speaker(Speaker { team_id: Some(t.uuid) })).count()
+        ).collect::<Vec<_>>();
+        assert_eq!(new_team_sizes, vec![1, 1]);
+
+        assert!(select_draw_teams(Uuid::from_u128(1), teams.clone(), &spare_speakers[..1]).is_err());
+        assert!(select_draw_teams(Uuid::from_u128(1), teams, &[]).is_err());
+    }
+}
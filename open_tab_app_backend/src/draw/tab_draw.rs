@@ -1,8 +1,10 @@
-use std::collections::HashMap;
+use std::collections::{BTreeSet, HashMap};
 
 use itertools::Itertools;
 use open_tab_entities::{domain::tournament_plan_node::{TeamFoldMethod, NonAlignedFoldMethod}, tab::TeamRoundRole};
-use rand::{thread_rng, seq::SliceRandom, Rng};
+
+use crate::draw_view::DrawBallot;
+use rand::{rngs::StdRng, seq::SliceRandom, Rng};
 use sea_orm::prelude::Uuid;
 
 
@@ -14,8 +16,7 @@ pub struct TeamPair {
 }
 
 impl TeamPair {
-    pub fn shuffled(self) -> Self {
-        let mut rng = thread_rng();
+    pub fn shuffled(self, rng: &mut StdRng) -> Self {
         if rng.gen_bool(0.5) {
             Self {
                 government_id: self.opposition_id,
@@ -34,6 +35,66 @@ impl TeamPair {
     }
 }
 
+/// Number of government and opposition appearances of a team in earlier rounds
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TeamPositionBalance {
+    pub government: u32,
+    pub opposition: u32,
+}
+
+impl TeamPositionBalance {
+    fn government_surplus(&self) -> i64 {
+        self.government as i64 - self.opposition as i64
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum TeamPosition {
+    Government,
+    Opposition,
+    NonAligned,
+}
+
+/// Positions each team had in each of the given rounds.
+/// Non-aligned speakers are attributed to their team via `team_members`, speakers of other teams are ignored.
+/// A well-formed draw has exactly one position per team and round.
+pub fn compute_team_position_history(round_ballots: &[Vec<DrawBallot>], team_members: &HashMap<Uuid, Vec<Uuid>>) -> HashMap<Uuid, Vec<BTreeSet<TeamPosition>>> {
+    let member_teams = team_members.iter().flat_map(
+        |(team_id, members)| members.iter().map(|member_id| (*member_id, *team_id))
+    ).collect::<HashMap<_, _>>();
+
+    let mut history: HashMap<Uuid, Vec<BTreeSet<TeamPosition>>> = HashMap::new();
+    for (round_idx, ballots) in round_ballots.iter().enumerate() {
+        for ballot in ballots.iter() {
+            let positions = ballot.government.iter().map(|t| (t.uuid, TeamPosition::Government))
+                .chain(ballot.opposition.iter().map(|t| (t.uuid, TeamPosition::Opposition)))
+                .chain(ballot.non_aligned_speakers.iter().flatten().filter_map(|s| member_teams.get(&s.uuid).map(|t| (*t, TeamPosition::NonAligned))));
+
+            for (team_id, position) in positions {
+                history.entry(team_id).or_insert_with(|| vec![BTreeSet::new(); round_ballots.len()])[round_idx].insert(position);
+            }
+        }
+    }
+    history
+}
+
+pub fn compute_team_position_balance(round_ballots: &[Vec<DrawBallot>]) -> HashMap<Uuid, TeamPositionBalance> {
+    compute_team_position_history(round_ballots, &HashMap::new()).into_iter().map(|(team_id, history)| {
+        let balance = TeamPositionBalance {
+            government: history.iter().filter(|p| p.contains(&TeamPosition::Government)).count() as u32,
+            opposition: history.iter().filter(|p| p.contains(&TeamPosition::Opposition)).count() as u32,
+        };
+        (team_id, balance)
+    }).collect()
+}
+
+pub fn add_team_pairs_to_position_balance(balance: &mut HashMap<Uuid, TeamPositionBalance>, team_pairs: &[TeamPair]) {
+    for pair in team_pairs {
+        balance.entry(pair.government_id).or_default().government += 1;
+        balance.entry(pair.opposition_id).or_default().opposition += 1;
+    }
+}
+
 pub fn reverse_fold(
     items: &Vec<Uuid>,
 ) -> Vec<TeamPair> {
@@ -48,8 +109,8 @@ pub fn reverse_fold(
 pub fn pair_teams(
     breaking_teams: &Vec<Uuid>,
     team_draw_mode: &TeamFoldMethod,
+    rng: &mut StdRng,
 ) -> Vec<TeamPair> {
-    let mut rng = thread_rng();
     let pairs = match team_draw_mode {
         TeamFoldMethod::PowerPaired => {
             let gov_iter = breaking_teams.iter().step_by(2);
@@ -95,7 +156,7 @@ pub fn pair_teams(
         }
         TeamFoldMethod::Random => {
             let mut teams = breaking_teams.clone();
-            teams.shuffle(&mut rng);
+            teams.shuffle(rng);
             teams.iter().step_by(2).zip(teams.iter().skip(1).step_by(2)).map(|(gov, opp)| TeamPair {
                 government_id: *gov,
                 opposition_id: *opp,
@@ -120,8 +181,8 @@ pub fn pair_teams(
                 vec![]
             };
 
-            upper_half.shuffle(&mut rng);
-            lower_half.shuffle(&mut rng);
+            upper_half.shuffle(rng);
+            lower_half.shuffle(rng);
 
             dbg!(&upper_half.iter().step_by(2).zip(upper_half.iter().skip(1).step_by(2)).collect_vec());
             let upper_half = upper_half.iter().step_by(2).zip(upper_half.iter().skip(1).step_by(2)).map(|(gov, opp)| TeamPair {
@@ -163,15 +224,14 @@ pub fn pair_consequtive_speakers(speakers: &Vec<Uuid>) -> Vec<Vec<Uuid>> {
 
 }
 
-pub fn pair_speakers(breaking_speakers: &Vec<Uuid>, speaker_draw_mode: &NonAlignedFoldMethod) -> Vec<Vec<Uuid>> {
-    let mut rng = thread_rng();
+pub fn pair_speakers(breaking_speakers: &Vec<Uuid>, speaker_draw_mode: &NonAlignedFoldMethod, rng: &mut StdRng) -> Vec<Vec<Uuid>> {
     let pairs = match speaker_draw_mode {
         NonAlignedFoldMethod::TabOrder => {
             pair_consequtive_speakers(breaking_speakers)
         }
         NonAlignedFoldMethod::Random => {
             let mut speakers = breaking_speakers.clone();
-            speakers.shuffle(&mut rng);
+            speakers.shuffle(rng);
             pair_consequtive_speakers(&speakers)
         }
     };
@@ -182,19 +242,21 @@ pub fn assign_teams(
     team_pairs: Vec<TeamPair>,
     config: &open_tab_entities::domain::tournament_plan_node::FoldDrawConfig,
     preceding_round_gov_opp_assignments: Option<&HashMap<Uuid, TeamRoundRole>>,
+    position_balance: &HashMap<Uuid, TeamPositionBalance>,
+    rng: &mut StdRng,
 ) -> Vec<TeamPair> {
     match config.team_assignment_rule {
         open_tab_entities::domain::tournament_plan_node::TeamAssignmentRule::Random => {
-            team_pairs.into_iter().map(|p| p.shuffled()).collect_vec()
+            team_pairs.into_iter().map(|p| p.shuffled(rng)).collect_vec()
         },
         open_tab_entities::domain::tournament_plan_node::TeamAssignmentRule::InvertPrevious => {
             team_pairs.into_iter().map(|p| {
-                let p = p.shuffled();
+                let p = p.shuffled(rng);
                 if let Some(preceding_round_gov_opp_assignments) = &preceding_round_gov_opp_assignments {
                     let prev_gov_role = preceding_round_gov_opp_assignments.get(&p.government_id);
                     let prev_opp_role = preceding_round_gov_opp_assignments.get(&p.opposition_id);
                     if prev_gov_role == prev_gov_role  {
-                        p.shuffled()
+                        p.shuffled(rng)
                     }
                     else {
                         if prev_gov_role == Some(&TeamRoundRole::Government) || prev_opp_role == Some(&TeamRoundRole::Opposition) {
@@ -207,7 +269,20 @@ pub fn assign_teams(
                     }
                 }
                 else {
-                    p.shuffled()
+                    p.shuffled(rng)
+                }
+            }).collect_vec()
+        },
+        open_tab_entities::domain::tournament_plan_node::TeamAssignmentRule::BalanceHistory => {
+            // On ties, the order of the fold decides, so the assignment does not depend on the seed
+            team_pairs.into_iter().map(|p| {
+                let gov_surplus = position_balance.get(&p.government_id).copied().unwrap_or_default().government_surplus();
+                let opp_surplus = position_balance.get(&p.opposition_id).copied().unwrap_or_default().government_surplus();
+                if gov_surplus > opp_surplus {
+                    p.inverted()
+                }
+                else {
+                    p
                 }
             }).collect_vec()
         },
@@ -217,10 +292,15 @@ pub fn assign_teams(
 #[cfg(test)]
 mod test {
     use itertools::Itertools;
-    use open_tab_entities::domain::tournament_plan_node::TeamFoldMethod;
+    use std::collections::HashMap;
+
+    use open_tab_entities::domain::tournament_plan_node::{FoldDrawConfig, TeamAssignmentRule, TeamFoldMethod};
+    use rand::{rngs::StdRng, SeedableRng};
     use sea_orm::prelude::Uuid;
 
-    use super::TeamPair;
+    use crate::draw_view::{DrawBallot, DrawTeam};
+
+    use super::{TeamPair, TeamPositionBalance};
 
     fn get_teams(n: usize) -> Vec<Uuid> {
         (0..n).map(|i| Uuid::from_u128(i as u128)).collect()
@@ -230,10 +310,58 @@ mod test {
         pairs.iter().flat_map(|p| vec![TeamPair { government_id: Uuid::from_u128(p.0 as u128), opposition_id: Uuid::from_u128(p.1 as u128) } ]).collect()
     }
 
+    #[test]
+    fn test_random_pairing_is_reproducible() {
+        let teams = get_teams(12);
+        let pairs = super::pair_teams(&teams, &TeamFoldMethod::Random, &mut StdRng::seed_from_u64(7));
+        let repeated_pairs = super::pair_teams(&teams, &TeamFoldMethod::Random, &mut StdRng::seed_from_u64(7));
+        assert_eq!(pairs, repeated_pairs);
+    }
+
+    #[test]
+    fn test_balance_history_sends_teams_with_more_government_rounds_to_opposition() {
+        let mut balance = HashMap::new();
+        balance.insert(Uuid::from_u128(0), TeamPositionBalance { government: 2, opposition: 0 });
+        balance.insert(Uuid::from_u128(1), TeamPositionBalance { government: 1, opposition: 1 });
+        balance.insert(Uuid::from_u128(2), TeamPositionBalance { government: 0, opposition: 1 });
+        balance.insert(Uuid::from_u128(3), TeamPositionBalance { government: 0, opposition: 1 });
+
+        let config = FoldDrawConfig {
+            team_assignment_rule: TeamAssignmentRule::BalanceHistory,
+            ..FoldDrawConfig::default_ko_fold()
+        };
+        let pairs = super::assign_teams(pairs_to_team_pairs(vec![(0, 1), (2, 3), (4, 5)]), &config, None, &balance, &mut StdRng::seed_from_u64(0));
+
+        // Ties keep the order of the fold
+        assert_eq!(pairs, pairs_to_team_pairs(vec![(1, 0), (2, 3), (4, 5)]));
+    }
+
+    #[test]
+    fn test_balance_history_counts_team_pairs() {
+        let mut balance = HashMap::new();
+        super::add_team_pairs_to_position_balance(&mut balance, &pairs_to_team_pairs(vec![(0, 1)]));
+        super::add_team_pairs_to_position_balance(&mut balance, &pairs_to_team_pairs(vec![(0, 1)]));
+        assert_eq!(balance.get(&Uuid::from_u128(0)), Some(&TeamPositionBalance { government: 2, opposition: 0 }));
+        assert_eq!(balance.get(&Uuid::from_u128(1)), Some(&TeamPositionBalance { government: 0, opposition: 2 }));
+    }
+
+    #[test]
+    fn test_position_balance_counts_previous_rounds() {
+        let ballot = |gov: u128, opp: u128| DrawBallot {
+            government: Some(DrawTeam { uuid: Uuid::from_u128(gov), ..Default::default() }),
+            opposition: Some(DrawTeam { uuid: Uuid::from_u128(opp), ..Default::default() }),
+            ..Default::default()
+        };
+        let balance = super::compute_team_position_balance(&[vec![ballot(0, 1)], vec![ballot(0, 2)]]);
+        assert_eq!(balance.get(&Uuid::from_u128(0)), Some(&TeamPositionBalance { government: 2, opposition: 0 }));
+        assert_eq!(balance.get(&Uuid::from_u128(1)), Some(&TeamPositionBalance { government: 0, opposition: 1 }));
+        assert_eq!(balance.get(&Uuid::from_u128(2)), Some(&TeamPositionBalance { government: 0, opposition: 1 }));
+    }
+
     #[test]
     fn test_power_pairing() {
         let teams = get_teams(12);
-        let pairs = super::pair_teams(&teams, &TeamFoldMethod::PowerPaired);
+        let pairs = super::pair_teams(&teams, &TeamFoldMethod::PowerPaired, &mut StdRng::seed_from_u64(0));
         assert_eq!(pairs, pairs_to_team_pairs(
             vec![
                 (0, 1),
@@ -249,7 +377,7 @@ mod test {
     #[test]
     fn test_inverse_power_pairing() {
         let teams = get_teams(12);
-        let pairs = super::pair_teams(&teams, &TeamFoldMethod::InversePowerPaired);
+        let pairs = super::pair_teams(&teams, &TeamFoldMethod::InversePowerPaired, &mut StdRng::seed_from_u64(0));
         assert_eq!(pairs, pairs_to_team_pairs(
             vec![
                 (0, 11),
@@ -265,7 +393,7 @@ mod test {
     #[test]
     fn test_balanced_power_pairing_even_rooms() {
         let teams = get_teams(12);
-        let pairs = super::pair_teams(&teams, &TeamFoldMethod::BalancedPowerPaired);
+        let pairs = super::pair_teams(&teams, &TeamFoldMethod::BalancedPowerPaired, &mut StdRng::seed_from_u64(0));
         assert_eq!(pairs, pairs_to_team_pairs(
             vec![
                 (0, 5),
@@ -281,7 +409,7 @@ mod test {
     #[test]
     fn test_balanced_power_pairing_uneven_rooms() {
         let teams = get_teams(14);
-        let pairs = super::pair_teams(&teams, &TeamFoldMethod::BalancedPowerPaired);
+        let pairs = super::pair_teams(&teams, &TeamFoldMethod::BalancedPowerPaired, &mut StdRng::seed_from_u64(0));
         assert_eq!(pairs, pairs_to_team_pairs(
             vec![
                 (0, 5),
@@ -298,7 +426,7 @@ mod test {
     #[test]
     fn test_half_random_even_rooms() {
         let teams = get_teams(12);
-        let pairs = super::pair_teams(&teams, &TeamFoldMethod::HalfRandom);
+        let pairs = super::pair_teams(&teams, &TeamFoldMethod::HalfRandom, &mut StdRng::seed_from_u64(0));
         let mut upper_half_pairs_teams : Vec<_> = pairs.iter().take(3).flat_map(|p| vec![p.government_id, p.opposition_id].into_iter()).collect();
         let mut lower_half_pairs_teams : Vec<_> = pairs.iter().skip(3).take(3).flat_map(|p| vec![p.government_id, p.opposition_id].into_iter()).collect();
         upper_half_pairs_teams.sort();
@@ -311,7 +439,7 @@ mod test {
     #[test]
     fn test_half_random_uneven_rooms() {
         let teams = get_teams(14);
-        let pairs = super::pair_teams(&teams, &TeamFoldMethod::HalfRandom);
+        let pairs = super::pair_teams(&teams, &TeamFoldMethod::HalfRandom, &mut StdRng::seed_from_u64(0));
         let mut upper_half_pairs_teams : Vec<_> = pairs.iter().take(3).flat_map(|p| vec![p.government_id, p.opposition_id].into_iter()).collect();
         let mut lower_half_pairs_teams : Vec<_> = pairs.iter().rev().take(3).flat_map(|p| vec![p.government_id, p.opposition_id].into_iter()).collect();
 
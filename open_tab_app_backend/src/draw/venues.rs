@@ -0,0 +1,276 @@
+use std::collections::{HashMap, HashSet};
+
+use itertools::Itertools;
+use mcmf::{Capacity, Cost, GraphBuilder, Vertex};
+use open_tab_entities::{domain::{entity::LoadEntity, tournament_venue::TournamentVenue}, prelude::{Ballot, TournamentDebate}, tab::{TabRankingConfig, TabView}};
+use sea_orm::prelude::Uuid;
+
+const INACCESSIBLE_VENUE_COST: i32 = 1_000_000;
+const OVER_CAPACITY_COST: i32 = 100_000;
+const TOP_ROOM_COST: i32 = 10_000;
+const BUILDING_CHANGE_COST: i32 = 1_000;
+
+#[derive(Debug, Clone, Default)]
+pub struct VenueDebateInfo {
+    /// Everyone who is in the room, including adjudicators
+    pub participant_ids: Vec<Uuid>,
+    pub adjudicator_ids: Vec<Uuid>,
+    pub team_ids: Vec<Uuid>,
+    /// Venues that are already assigned are kept
+    pub venue_id: Option<Uuid>,
+}
+
+impl VenueDebateInfo {
+    pub fn from_ballot(ballot: &Ballot, team_members: &HashMap<Uuid, Vec<Uuid>>, venue_id: Option<Uuid>) -> Self {
+        let adjudicator_ids = ballot.adjudicators.iter().chain(ballot.president.iter()).chain(ballot.trainees.iter()).cloned().collect_vec();
+        let team_ids = [&ballot.government, &ballot.opposition].into_iter()
+            .filter_map(|team| team.team)
+            .collect_vec();
+        let participant_ids = team_ids.iter()
+            .flat_map(|team_id| team_members.get(team_id).cloned().unwrap_or_default())
+            .chain(ballot.speeches.iter().filter_map(|s| s.speaker))
+            .chain(adjudicator_ids.iter().cloned())
+            .unique()
+            .collect_vec();
+
+        VenueDebateInfo {
+            participant_ids,
+            adjudicator_ids,
+            team_ids,
+            venue_id,
+        }
+    }
+
+    /// Average tab rank of the teams in the debate, if any of them are ranked
+    fn average_team_rank(&self, team_ranks: &HashMap<Uuid, u32>) -> Option<f64> {
+        let ranks = self.team_ids.iter().filter_map(|t| team_ranks.get(t)).collect_vec();
+        if ranks.is_empty() {
+            None
+        }
+        else {
+            Some(ranks.iter().map(|r| **r as f64).sum::<f64>() / ranks.len() as f64)
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+enum NodeType {
+    Debate(usize),
+    Venue(usize),
+}
+
+/// Assigns venues to the debates of a round.
+/// Debates between the teams ranked highest on the tab are placed in the available venues
+/// marked as top rooms, and stronger debates get venues earlier in the venue ordering.
+/// Debates without ranked teams, e.g. in the first round, keep the order of their index
+/// and have no preference for top rooms.
+/// Debates with participants that need accessible rooms are placed in accessible venues,
+/// and adjudicators are kept in the building they were in during the previous round where possible.
+/// Constraints are soft: if they can not be met, the allocation with the fewest violations is chosen.
+/// Debates stay without a venue if there are not enough venues.
+pub fn allocate_venues(
+    debates: &[VenueDebateInfo],
+    venues: &[TournamentVenue],
+    participants_requiring_accessibility: &HashSet<Uuid>,
+    previous_adjudicator_buildings: &HashMap<Uuid, String>,
+    team_ranks: &HashMap<Uuid, u32>
+) -> Vec<Option<Uuid>> {
+    let used_venues = debates.iter().filter_map(|d| d.venue_id).collect::<HashSet<_>>();
+    let available_venues = venues.iter()
+        .filter(|v| !used_venues.contains(&v.uuid))
+        .sorted_by_key(|v| (v.ordering_index, v.name.clone()))
+        .collect_vec();
+    let num_top_rooms = available_venues.iter().filter(|v| v.is_top_room).count();
+
+    let average_ranks = debates.iter().map(|d| d.average_team_rank(team_ranks)).collect_vec();
+    // Unranked debates are not moved ahead of ranked ones
+    let debate_order = debates.iter().enumerate()
+        .filter(|(_, d)| d.venue_id.is_none())
+        .map(|(debate_idx, _)| debate_idx)
+        .sorted_by(|a, b| {
+            let rank_a = average_ranks[*a].unwrap_or(f64::INFINITY);
+            let rank_b = average_ranks[*b].unwrap_or(f64::INFINITY);
+            rank_a.total_cmp(&rank_b).then(a.cmp(b))
+        })
+        .collect_vec();
+
+    let mut graph_build = GraphBuilder::new();
+    for (strength_idx, debate_idx) in debate_order.iter().enumerate() {
+        let debate_idx = *debate_idx;
+        let debate = &debates[debate_idx];
+        graph_build.add_edge(Vertex::Source, NodeType::Debate(debate_idx), Capacity(1), Cost(0));
+
+        let requires_accessibility = debate.participant_ids.iter().any(|p| participants_requiring_accessibility.contains(p));
+        let is_top_debate = average_ranks[debate_idx].map(|_| strength_idx < num_top_rooms);
+
+        for (venue_idx, venue) in available_venues.iter().enumerate() {
+            let mut cost = (strength_idx as i32 - venue_idx as i32).abs();
+            if requires_accessibility && !venue.is_accessible {
+                cost += INACCESSIBLE_VENUE_COST;
+            }
+            if venue.capacity.map(|c| (c as usize) < debate.participant_ids.len()).unwrap_or(false) {
+                cost += OVER_CAPACITY_COST;
+            }
+            if is_top_debate.map(|is_top_debate| is_top_debate != venue.is_top_room).unwrap_or(false) {
+                cost += TOP_ROOM_COST;
+            }
+            if let Some(building) = &venue.building {
+                cost += debate.adjudicator_ids.iter().filter(
+                    |adj| previous_adjudicator_buildings.get(adj).map(|b| b != building).unwrap_or(false)
+                ).count() as i32 * BUILDING_CHANGE_COST;
+            }
+
+            graph_build.add_edge(NodeType::Debate(debate_idx), NodeType::Venue(venue_idx), Capacity(1), Cost(cost));
+        }
+    }
+    for venue_idx in 0..available_venues.len() {
+        graph_build.add_edge(NodeType::Venue(venue_idx), Vertex::Sink, Capacity(1), Cost(0));
+    }
+
+    let (_cost, paths) = graph_build.mcmf();
+    let assignments = paths.iter().flat_map(|path| path.edges()).filter_map(|edge| {
+        if edge.amount > 0 {
+            match (&edge.a, &edge.b) {
+                (Vertex::Node(NodeType::Debate(debate_idx)), Vertex::Node(NodeType::Venue(venue_idx))) => Some((*debate_idx, available_venues[*venue_idx].uuid)),
+                _ => None
+            }
+        }
+        else {
+            None
+        }
+    }).collect::<HashMap<_, _>>();
+
+    debates.iter().enumerate().map(|(debate_idx, debate)| debate.venue_id.or(assignments.get(&debate_idx).cloned())).collect()
+}
+
+/// Records the building each adjudicator is in, based on the venues of the given debates.
+pub fn update_adjudicator_buildings(
+    adjudicator_buildings: &mut HashMap<Uuid, String>,
+    debates: &[VenueDebateInfo],
+    venue_ids: &[Option<Uuid>],
+    venues: &[TournamentVenue]
+) {
+    let venue_buildings = venues.iter().filter_map(|v| v.building.clone().map(|b| (v.uuid, b))).collect::<HashMap<_, _>>();
+    for (debate, venue_id) in debates.iter().zip(venue_ids.iter()) {
+        let building = venue_id.and_then(|v| venue_buildings.get(&v));
+        for adjudicator in debate.adjudicator_ids.iter() {
+            match building {
+                Some(building) => { adjudicator_buildings.insert(*adjudicator, building.clone()); },
+                None => { adjudicator_buildings.remove(adjudicator); }
+            }
+        }
+    }
+}
+
+/// Loads the tab ranks of teams after the given rounds, which decide the top rooms.
+pub async fn load_team_ranks<C>(db: &C, tournament_id: Uuid, round_ids: Vec<Uuid>, team_members: &HashMap<Uuid, Vec<Uuid>>) -> Result<HashMap<Uuid, u32>, anyhow::Error> where C: sea_orm::ConnectionTrait {
+    if round_ids.is_empty() {
+        return Ok(HashMap::new());
+    }
+    let ranking = TabRankingConfig::load_for_tournament(db, tournament_id).await?;
+    let tab = TabView::load_from_rounds(db, round_ids, team_members, &ranking).await?;
+    Ok(tab.team_tab.iter().map(|t| (t.team_uuid, t.rank)).collect())
+}
+
+/// Loads the buildings adjudicators were in during a round.
+pub async fn load_adjudicator_buildings<C>(db: &C, round_id: Uuid, venues: &[TournamentVenue]) -> Result<HashMap<Uuid, String>, anyhow::Error> where C: sea_orm::ConnectionTrait {
+    let debates = TournamentDebate::get_all_in_rounds(db, vec![round_id]).await?.into_iter().next().unwrap_or_default();
+    let ballots = Ballot::get_many(db, debates.iter().map(|d| d.ballot_id).collect()).await?;
+
+    let infos = ballots.iter().map(|b| VenueDebateInfo::from_ballot(b, &HashMap::new(), None)).collect_vec();
+    let mut buildings = HashMap::new();
+    update_adjudicator_buildings(&mut buildings, &infos, &debates.iter().map(|d| d.venue_id).collect_vec(), venues);
+    Ok(buildings)
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::{HashMap, HashSet};
+
+    use open_tab_entities::domain::tournament_venue::TournamentVenue;
+    use sea_orm::prelude::Uuid;
+
+    use super::{allocate_venues, VenueDebateInfo};
+
+    fn make_venue(id: u128, ordering_index: i32) -> TournamentVenue {
+        TournamentVenue {
+            uuid: Uuid::from_u128(id),
+            name: format!("Venue {}", id),
+            ordering_index,
+            ..Default::default()
+        }
+    }
+
+    fn make_debate(participants: Vec<u128>, adjudicators: Vec<u128>) -> VenueDebateInfo {
+        VenueDebateInfo {
+            participant_ids: participants.into_iter().chain(adjudicators.iter().cloned()).map(Uuid::from_u128).collect(),
+            adjudicator_ids: adjudicators.into_iter().map(Uuid::from_u128).collect(),
+            team_ids: vec![],
+            venue_id: None,
+        }
+    }
+
+    #[test]
+    fn test_venues_follow_ordering_without_constraints() {
+        let venues = vec![make_venue(2, 1), make_venue(1, 0), make_venue(3, 2)];
+        let debates = vec![make_debate(vec![10], vec![]), make_debate(vec![20], vec![])];
+        let allocation = allocate_venues(&debates, &venues, &HashSet::new(), &HashMap::new(), &HashMap::new());
+        assert_eq!(allocation, vec![Some(Uuid::from_u128(1)), Some(Uuid::from_u128(2))]);
+    }
+
+    #[test]
+    fn test_accessibility_needs_are_respected() {
+        let mut venues = vec![make_venue(1, 0), make_venue(2, 1), make_venue(3, 2)];
+        venues[2].is_accessible = true;
+        let debates = vec![make_debate(vec![10], vec![]), make_debate(vec![20], vec![]), make_debate(vec![30], vec![])];
+        let allocation = allocate_venues(&debates, &venues, &vec![Uuid::from_u128(10)].into_iter().collect(), &HashMap::new(), &HashMap::new());
+        assert_eq!(allocation[0], Some(Uuid::from_u128(3)));
+    }
+
+    #[test]
+    fn test_top_rooms_are_kept_for_top_debates() {
+        let mut venues = vec![make_venue(1, 0), make_venue(2, 1)];
+        venues[1].is_top_room = true;
+        let mut debates = vec![make_debate(vec![10], vec![]), make_debate(vec![20], vec![])];
+        debates[0].team_ids = vec![Uuid::from_u128(100)];
+        debates[1].team_ids = vec![Uuid::from_u128(101)];
+        let team_ranks = vec![(Uuid::from_u128(100), 1), (Uuid::from_u128(101), 2)].into_iter().collect();
+        let allocation = allocate_venues(&debates, &venues, &HashSet::new(), &HashMap::new(), &team_ranks);
+        assert_eq!(allocation, vec![Some(Uuid::from_u128(2)), Some(Uuid::from_u128(1))]);
+    }
+
+    #[test]
+    fn test_top_rooms_follow_team_ranks() {
+        let mut venues = vec![make_venue(1, 0), make_venue(2, 1), make_venue(3, 2)];
+        venues[0].is_top_room = true;
+        venues[2].is_top_room = true;
+        let mut debates = vec![make_debate(vec![10], vec![]), make_debate(vec![20], vec![]), make_debate(vec![30], vec![])];
+        debates[0].team_ids = vec![Uuid::from_u128(100), Uuid::from_u128(101)];
+        debates[1].team_ids = vec![Uuid::from_u128(102), Uuid::from_u128(103)];
+        // The first top room is already taken, so only one is left
+        debates[2].venue_id = Some(Uuid::from_u128(1));
+        let team_ranks = vec![(100, 3), (101, 4), (102, 1), (103, 2)].into_iter().map(|(t, r)| (Uuid::from_u128(t), r)).collect();
+        let allocation = allocate_venues(&debates, &venues, &HashSet::new(), &HashMap::new(), &team_ranks);
+        assert_eq!(allocation, vec![Some(Uuid::from_u128(2)), Some(Uuid::from_u128(3)), Some(Uuid::from_u128(1))]);
+    }
+
+    #[test]
+    fn test_adjudicators_stay_in_building() {
+        let mut venues = vec![make_venue(1, 0), make_venue(2, 1)];
+        venues[0].building = Some("A".into());
+        venues[1].building = Some("B".into());
+        let debates = vec![make_debate(vec![10], vec![100]), make_debate(vec![20], vec![200])];
+        let previous_buildings = vec![(Uuid::from_u128(100), "B".to_string()), (Uuid::from_u128(200), "A".to_string())].into_iter().collect();
+        let allocation = allocate_venues(&debates, &venues, &HashSet::new(), &previous_buildings, &HashMap::new());
+        assert_eq!(allocation, vec![Some(Uuid::from_u128(2)), Some(Uuid::from_u128(1))]);
+    }
+
+    #[test]
+    fn test_existing_venues_are_kept() {
+        let venues = vec![make_venue(1, 0), make_venue(2, 1)];
+        let mut debates = vec![make_debate(vec![10], vec![]), make_debate(vec![20], vec![])];
+        debates[1].venue_id = Some(Uuid::from_u128(1));
+        let allocation = allocate_venues(&debates, &venues, &HashSet::new(), &HashMap::new(), &HashMap::new());
+        assert_eq!(allocation, vec![Some(Uuid::from_u128(2)), Some(Uuid::from_u128(1))]);
+    }
+}
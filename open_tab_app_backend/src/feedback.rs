@@ -169,6 +169,8 @@ pub struct FormTemplate {
     #[serde(default)]
     shared_questions: HashMap<String, QuestionInfo>,
     chairs_for_wings: Option<FormKeyOrInline>,
+    #[serde(default)]
+    chairs_for_trainees: Option<FormKeyOrInline>,
 
     wings_for_chairs: Option<FormKeyOrInline>,
     wings_for_presidents: Option<FormKeyOrInline>,
@@ -281,6 +283,10 @@ impl FormTemplate {
             out.push(("chairs_for_wings".into(), form.clone()));
         }
 
+        if let Some(form) = &self.chairs_for_trainees {
+            out.push(("chairs_for_trainees".into(), form.clone()));
+        }
+
         if let Some(form) = &self.wings_for_chairs {
             out.push(("wings_for_chairs".into(), form.clone()));
         }
@@ -335,6 +341,9 @@ impl FormTemplate {
             "chairs_for_wings" => {
                 visbility.show_chairs_for_wings = true;
             },
+            "chairs_for_trainees" => {
+                visbility.show_chairs_for_trainees = true;
+            },
             "wings_for_chairs" => {
                 visbility.show_wings_for_chairs = true;
             },
@@ -0,0 +1,333 @@
+use std::collections::HashMap;
+
+use itertools::Itertools;
+use open_tab_entities::{domain::{entity::LoadEntity, feedback_form::{FeedbackSourceRole, FeedbackTargetRole}, feedback_question::{FeedbackQuestion, QuestionType, RangeQuestionConfig, RangeQuestionOrientation}, feedback_response::{FeedbackResponse, FeedbackResponseValue}}, prelude::*};
+use sea_orm::prelude::Uuid;
+use serde::{Serialize, Deserialize};
+
+/// Number of pseudo-responses at the global mean that are added to every author
+/// before estimating their leniency.
+const LENIENCY_PRIOR_WEIGHT: f64 = 3.0;
+
+/// Weight of the currently entered skill, measured in units of feedback weight.
+/// A proposal only moves away from the hand-entered value once enough feedback has been collected.
+const SKILL_PRIOR_WEIGHT: f64 = 3.0;
+
+const MAX_SKILL: f64 = 100.0;
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct FeedbackRatingConfig {
+    pub chair_weight: f64,
+    pub wing_weight: f64,
+    pub president_weight: f64,
+    pub team_weight: f64,
+    pub non_aligned_weight: f64,
+}
+
+impl Default for FeedbackRatingConfig {
+    fn default() -> Self {
+        FeedbackRatingConfig {
+            chair_weight: 1.0,
+            wing_weight: 0.75,
+            president_weight: 1.0,
+            team_weight: 0.5,
+            non_aligned_weight: 0.25,
+        }
+    }
+}
+
+impl FeedbackRatingConfig {
+    fn weight_for_role(&self, role: FeedbackSourceRole) -> f64 {
+        match role {
+            FeedbackSourceRole::Chair => self.chair_weight,
+            FeedbackSourceRole::Wing => self.wing_weight,
+            FeedbackSourceRole::President => self.president_weight,
+            FeedbackSourceRole::Team => self.team_weight,
+            FeedbackSourceRole::NonAligned => self.non_aligned_weight,
+        }
+    }
+}
+
+/// A single feedback response reduced to a score between 0 (worst) and 1 (best).
+#[derive(Debug, Clone, PartialEq)]
+pub struct RatedFeedback {
+    pub author_id: Uuid,
+    pub target_id: Uuid,
+    pub source_role: FeedbackSourceRole,
+    pub target_role: FeedbackTargetRole,
+    pub score: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct AdjudicatorRatingSuggestion {
+    pub adjudicator_id: Uuid,
+    pub current_chair_skill: i16,
+    pub current_panel_skill: i16,
+    pub proposed_chair_skill: i16,
+    pub proposed_panel_skill: i16,
+    /// Summed source weight of the feedback received as chair
+    pub chair_feedback_weight: f64,
+    /// Summed source weight of the feedback received as wing
+    pub panel_feedback_weight: f64,
+    pub num_responses: usize,
+}
+
+impl AdjudicatorRatingSuggestion {
+    pub fn has_changes(&self) -> bool {
+        self.current_chair_skill != self.proposed_chair_skill || self.current_panel_skill != self.proposed_panel_skill
+    }
+}
+
+fn normalize_range_answer(config: &RangeQuestionConfig, val: i32) -> Option<f64> {
+    if config.max <= config.min {
+        return None;
+    }
+    let val = val.clamp(config.min, config.max) as f64;
+    let min = config.min as f64;
+    let max = config.max as f64;
+
+    Some(match config.orientation {
+        RangeQuestionOrientation::HighIsGood => (val - min) / (max - min),
+        RangeQuestionOrientation::LowIsGood => (max - val) / (max - min),
+        RangeQuestionOrientation::MeanIsGood => {
+            let mid = (max + min) / 2.0;
+            1.0 - (val - mid).abs() / (mid - min)
+        }
+    })
+}
+
+/// Averages all range answers of a response. Returns None if the response
+/// contains no range answers.
+pub fn score_response(response: &FeedbackResponse, questions: &HashMap<Uuid, FeedbackQuestion>) -> Option<f64> {
+    let answers = response.values.iter().filter_map(|(question_id, value)| {
+        match (questions.get(question_id).map(|q| &q.question_config), value) {
+            (Some(QuestionType::RangeQuestion { config }), FeedbackResponseValue::Int { val }) => normalize_range_answer(config, *val),
+            _ => None
+        }
+    }).collect_vec();
+
+    if answers.is_empty() {
+        None
+    }
+    else {
+        Some(answers.iter().sum::<f64>() / answers.len() as f64)
+    }
+}
+
+/// Reconstructs source and target role of a response from the ballot of the debate it was submitted for.
+/// Returns None if the target was not adjudicating in that debate.
+pub fn infer_roles(response: &FeedbackResponse, ballot: &Ballot) -> Option<(FeedbackSourceRole, FeedbackTargetRole)> {
+    let target_role = if ballot.adjudicators.first() == Some(&response.target_participant_id) {
+        FeedbackTargetRole::Chair
+    }
+    else if ballot.adjudicators.contains(&response.target_participant_id) {
+        FeedbackTargetRole::Wing
+    }
+    else if ballot.president == Some(response.target_participant_id) {
+        FeedbackTargetRole::President
+    }
+    else if ballot.trainees.contains(&response.target_participant_id) {
+        FeedbackTargetRole::Trainee
+    }
+    else {
+        return None;
+    };
+
+    let source_role = match (response.source_team_id, response.source_participant_id) {
+        (Some(_), _) => FeedbackSourceRole::Team,
+        (None, Some(source)) if ballot.adjudicators.first() == Some(&source) => FeedbackSourceRole::Chair,
+        (None, Some(source)) if ballot.adjudicators.contains(&source) => FeedbackSourceRole::Wing,
+        (None, Some(source)) if ballot.president == Some(source) => FeedbackSourceRole::President,
+        _ => FeedbackSourceRole::NonAligned,
+    };
+
+    Some((source_role, target_role))
+}
+
+/// Subtracts each author's (shrunk) deviation from the mean score across all authors.
+fn correct_for_leniency(feedback: &[RatedFeedback]) -> Vec<f64> {
+    if feedback.is_empty() {
+        return vec![];
+    }
+    let global_mean = feedback.iter().map(|f| f.score).sum::<f64>() / feedback.len() as f64;
+
+    let leniency = feedback.iter().into_grouping_map_by(|f| f.author_id).fold(
+        (0.0, 0usize),
+        |(sum, count), _, f| (sum + f.score - global_mean, count + 1)
+    ).into_iter().map(
+        |(author, (sum, count))| (author, sum / (count as f64 + LENIENCY_PRIOR_WEIGHT))
+    ).collect::<HashMap<_, _>>();
+
+    feedback.iter().map(|f| (f.score - leniency[&f.author_id]).clamp(0.0, 1.0)).collect()
+}
+
+fn propose_skill(current: i16, weighted_scores: &[(f64, f64)]) -> (i16, f64) {
+    let total_weight = weighted_scores.iter().map(|(w, _)| w).sum::<f64>();
+    let feedback_sum = weighted_scores.iter().map(|(w, s)| w * s * MAX_SKILL).sum::<f64>();
+
+    let proposal = (SKILL_PRIOR_WEIGHT * current as f64 + feedback_sum) / (SKILL_PRIOR_WEIGHT + total_weight);
+    (proposal.round().clamp(0.0, MAX_SKILL) as i16, total_weight)
+}
+
+pub fn compute_rating_suggestions(adjudicators: &[Participant], feedback: &[RatedFeedback], config: &FeedbackRatingConfig) -> Vec<AdjudicatorRatingSuggestion> {
+    let corrected_scores = correct_for_leniency(feedback);
+
+    let mut scores_by_target : HashMap<(Uuid, FeedbackTargetRole), Vec<(f64, f64)>> = HashMap::new();
+    for (f, score) in feedback.iter().zip(corrected_scores) {
+        let weight = config.weight_for_role(f.source_role);
+        if weight > 0.0 {
+            scores_by_target.entry((f.target_id, f.target_role)).or_default().push((weight, score));
+        }
+    }
+    let num_responses = feedback.iter().counts_by(|f| f.target_id);
+
+    let empty = vec![];
+    adjudicators.iter().filter_map(|participant| {
+        match &participant.role {
+            ParticipantRole::Adjudicator(adj) => {
+                let (proposed_chair_skill, chair_feedback_weight) = propose_skill(
+                    adj.chair_skill,
+                    scores_by_target.get(&(participant.uuid, FeedbackTargetRole::Chair)).unwrap_or(&empty)
+                );
+                let (proposed_panel_skill, panel_feedback_weight) = propose_skill(
+                    adj.panel_skill,
+                    scores_by_target.get(&(participant.uuid, FeedbackTargetRole::Wing)).unwrap_or(&empty)
+                );
+                Some(AdjudicatorRatingSuggestion {
+                    adjudicator_id: participant.uuid,
+                    current_chair_skill: adj.chair_skill,
+                    current_panel_skill: adj.panel_skill,
+                    proposed_chair_skill,
+                    proposed_panel_skill,
+                    chair_feedback_weight,
+                    panel_feedback_weight,
+                    num_responses: num_responses.get(&participant.uuid).cloned().unwrap_or(0),
+                })
+            },
+            _ => None
+        }
+    }).collect()
+}
+
+pub async fn load_rating_suggestions<C>(db: &C, tournament_id: Uuid, config: &FeedbackRatingConfig) -> Result<Vec<AdjudicatorRatingSuggestion>, anyhow::Error> where C: sea_orm::ConnectionTrait {
+    let adjudicators = Participant::get_all_adjudicators_in_tournament(db, tournament_id).await?;
+    let questions = FeedbackQuestion::get_all_in_tournament(db, tournament_id).await?.into_iter().map(|q| (q.uuid, q)).collect::<HashMap<_, _>>();
+
+    let rounds = TournamentRound::get_all_in_tournament(db, tournament_id).await?;
+    let debates = TournamentDebate::get_all_in_rounds(db, rounds.iter().map(|r| r.uuid).collect()).await?.into_iter().flatten().collect_vec();
+    let ballots = Ballot::get_many(db, debates.iter().map(|d| d.ballot_id).collect()).await?;
+    let ballots_by_debate = debates.iter().map(|d| d.uuid).zip(ballots.iter()).collect::<HashMap<_, _>>();
+
+    let responses = FeedbackResponse::get_all_in_debates(db, debates.iter().map(|d| d.uuid).collect()).await?;
+
+    let feedback = responses.iter().filter_map(|response| {
+        let ballot = ballots_by_debate.get(&response.source_debate_id)?;
+        let (source_role, target_role) = infer_roles(response, ballot)?;
+        let score = score_response(response, &questions)?;
+
+        Some(RatedFeedback {
+            author_id: response.author_participant_id,
+            target_id: response.target_participant_id,
+            source_role,
+            target_role,
+            score
+        })
+    }).collect_vec();
+
+    Ok(compute_rating_suggestions(&adjudicators, &feedback, config))
+}
+
+#[cfg(test)]
+mod test {
+    use open_tab_entities::{domain::feedback_form::{FeedbackSourceRole, FeedbackTargetRole}, prelude::*};
+    use sea_orm::prelude::Uuid;
+
+    use super::{compute_rating_suggestions, normalize_range_answer, FeedbackRatingConfig, RatedFeedback};
+    use open_tab_entities::domain::feedback_question::{RangeQuestionConfig, RangeQuestionOrientation};
+
+    fn make_adjudicator(uuid: u128, skill: i16) -> Participant {
+        Participant {
+            uuid: Uuid::from_u128(uuid),
+            name: format!("Adj {}", uuid),
+            role: ParticipantRole::Adjudicator(Adjudicator { chair_skill: skill, panel_skill: skill, ..Default::default() }),
+            tournament_id: Uuid::nil(),
+            institutions: vec![],
+            registration_key: None,
+            is_anonymous: false,
+            break_category_id: None,
+            requires_accessible_venue: false,
+        }
+    }
+
+    fn make_feedback(author: u128, target: u128, source_role: FeedbackSourceRole, score: f64) -> RatedFeedback {
+        RatedFeedback {
+            author_id: Uuid::from_u128(author),
+            target_id: Uuid::from_u128(target),
+            source_role,
+            target_role: FeedbackTargetRole::Chair,
+            score
+        }
+    }
+
+    #[test]
+    fn test_normalize_orientations() {
+        let mut config = RangeQuestionConfig { min: 1, max: 5, orientation: RangeQuestionOrientation::HighIsGood, labels: vec![] };
+        assert_eq!(normalize_range_answer(&config, 5), Some(1.0));
+        config.orientation = RangeQuestionOrientation::LowIsGood;
+        assert_eq!(normalize_range_answer(&config, 5), Some(0.0));
+        config.orientation = RangeQuestionOrientation::MeanIsGood;
+        assert_eq!(normalize_range_answer(&config, 3), Some(1.0));
+        assert_eq!(normalize_range_answer(&config, 1), Some(0.0));
+    }
+
+    #[test]
+    fn test_no_feedback_keeps_skill() {
+        let suggestions = compute_rating_suggestions(&[make_adjudicator(1, 40)], &[], &FeedbackRatingConfig::default());
+        assert_eq!(suggestions[0].proposed_chair_skill, 40);
+        assert!(!suggestions[0].has_changes());
+    }
+
+    #[test]
+    fn test_good_feedback_raises_skill() {
+        let adjudicators = vec![make_adjudicator(1, 50), make_adjudicator(2, 50)];
+        let feedback = (0..6).flat_map(|author| vec![
+            make_feedback(10 + author, 1, FeedbackSourceRole::Team, 1.0),
+            make_feedback(10 + author, 2, FeedbackSourceRole::Team, 0.2),
+        ]).collect::<Vec<_>>();
+
+        let suggestions = compute_rating_suggestions(&adjudicators, &feedback, &FeedbackRatingConfig::default());
+        assert!(suggestions[0].proposed_chair_skill > 50);
+        assert!(suggestions[1].proposed_chair_skill < 50);
+        assert_eq!(suggestions[0].proposed_panel_skill, 50);
+    }
+
+    #[test]
+    fn test_lenient_author_counts_less() {
+        let adjudicators = vec![make_adjudicator(1, 50), make_adjudicator(2, 50)];
+        let mut feedback = vec![
+            make_feedback(10, 1, FeedbackSourceRole::Wing, 1.0),
+            make_feedback(11, 2, FeedbackSourceRole::Wing, 1.0),
+        ];
+        // Author 10 rates everyone highly, author 11 is usually critical
+        for target in 3..8 {
+            feedback.push(make_feedback(10, target, FeedbackSourceRole::Wing, 1.0));
+            feedback.push(make_feedback(11, target, FeedbackSourceRole::Wing, 0.3));
+        }
+
+        let suggestions = compute_rating_suggestions(&adjudicators, &feedback, &FeedbackRatingConfig::default());
+        assert!(suggestions[1].proposed_chair_skill > suggestions[0].proposed_chair_skill);
+    }
+
+    #[test]
+    fn test_source_weights() {
+        let adjudicators = vec![make_adjudicator(1, 50)];
+        let feedback = vec![
+            make_feedback(10, 1, FeedbackSourceRole::Chair, 1.0),
+            make_feedback(11, 1, FeedbackSourceRole::NonAligned, 0.0),
+        ];
+
+        let suggestions = compute_rating_suggestions(&adjudicators, &feedback, &FeedbackRatingConfig::default());
+        assert!(suggestions[0].proposed_chair_skill > 50);
+        assert_eq!(suggestions[0].chair_feedback_weight, 1.25);
+    }
+}
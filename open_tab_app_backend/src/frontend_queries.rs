@@ -1,8 +1,15 @@
 
 
-use crate::import::CSVReaderConfig;
+use sea_orm::prelude::Uuid;
+
+use crate::{draw::repair::{compute_draw_repair, DrawRepair}, import::CSVReaderConfig};
 
 pub async fn query_participant_csv_config_proposal(path: String) -> Result<CSVReaderConfig, anyhow::Error> {
     let file = std::fs::File::open(path.clone())?;
     Ok(CSVReaderConfig::default_from_file(&file)?)
 }
+
+pub async fn query_draw_repair_proposal<C>(db: &C, round_id: Uuid, withdrawn_participants: Vec<Uuid>, added_participants: Vec<Uuid>) -> Result<DrawRepair, anyhow::Error> where C: sea_orm::ConnectionTrait {
+    let (_, _, repair) = compute_draw_repair(db, round_id, &withdrawn_participants, &added_participants).await?;
+    Ok(repair)
+}
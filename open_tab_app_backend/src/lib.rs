@@ -8,4 +8,7 @@ pub use actions::*;
 pub use views::*;
 
 pub mod feedback;
-pub mod institutions;
\ No newline at end of file
+pub mod feedback_ratings;
+pub mod undo;
+pub mod institutions;
+pub mod ballot_confirmation;
\ No newline at end of file
@@ -0,0 +1,155 @@
+use std::collections::HashMap;
+
+use itertools::Itertools;
+use open_tab_entities::{Entity, EntityGroup, EntityState, EntityTypeId, LogAuthor};
+use sea_orm::prelude::Uuid;
+use thiserror::Error;
+
+/// Number of actions per tournament that can be undone
+pub const MAX_UNDO_DEPTH: usize = 50;
+
+#[derive(Debug, Error)]
+pub enum UndoError {
+    #[error("There is nothing to undo")]
+    NothingToUndo,
+    #[error("There is nothing to redo")]
+    NothingToRedo,
+    #[error("The affected entries were changed in the meantime and can not be restored")]
+    StateChanged,
+}
+
+/// The state of all entities touched by an action, before and after it was applied.
+#[derive(Debug, Clone)]
+pub struct UndoEntry {
+    pub tournament_id: Uuid,
+    before: Vec<EntityState<Entity, EntityTypeId>>,
+    after: Vec<EntityState<Entity, EntityTypeId>>,
+    /// Undoing and redoing is attributed to the author of the original action
+    author: Option<LogAuthor>,
+}
+
+pub async fn load_entity_states<C>(db: &C, keys: &[(EntityTypeId, Uuid)]) -> Result<Vec<EntityState<Entity, EntityTypeId>>, anyhow::Error> where C: sea_orm::ConnectionTrait {
+    let mut states = HashMap::new();
+
+    for (entity_type, uuids) in keys.iter().into_group_map_by(|(entity_type, _)| *entity_type) {
+        let uuids = uuids.into_iter().map(|(_, uuid)| *uuid).collect_vec();
+        let entities = Entity::try_get_many_with_type(db, entity_type, uuids.clone()).await?;
+
+        for (uuid, entity) in uuids.into_iter().zip(entities) {
+            let state = match entity {
+                Some(entity) => EntityState::Exists(entity),
+                None => EntityState::Deleted { uuid, type_: entity_type }
+            };
+            states.insert((entity_type, uuid), state);
+        }
+    }
+
+    Ok(keys.iter().filter_map(|key| states.remove(key)).collect())
+}
+
+fn group_from_states(tournament_id: Uuid, states: &[EntityState<Entity, EntityTypeId>]) -> EntityGroup {
+    let mut group = EntityGroup::new(tournament_id);
+    for state in states {
+        match state {
+            EntityState::Exists(entity) => group.add(entity.clone()),
+            EntityState::Deleted { uuid, type_ } => group.delete(*type_, *uuid),
+        }
+    }
+    group
+}
+
+impl UndoEntry {
+    /// Saves and logs the changes, recording the prior state of every touched entity.
+    pub async fn save_changes<C>(db: &C, changes: &EntityGroup) -> Result<UndoEntry, anyhow::Error> where C: sea_orm::ConnectionTrait {
+        let keys = changes.entity_states.keys().cloned().sorted().collect_vec();
+
+        let before = load_entity_states(db, &keys).await?;
+        changes.save_all_and_log(db).await?;
+        // Reloading ensures the comparison on undo is not affected by normalization during saving
+        let after = load_entity_states(db, &keys).await?;
+
+        Ok(UndoEntry {
+            tournament_id: changes.tournament_id,
+            before,
+            after,
+            author: changes.author.clone(),
+        })
+    }
+
+    async fn restore<C>(&self, db: &C, expected: &[EntityState<Entity, EntityTypeId>], target: &[EntityState<Entity, EntityTypeId>]) -> Result<EntityGroup, anyhow::Error> where C: sea_orm::ConnectionTrait {
+        let keys = expected.iter().map(|state| (state.get_type(), state.get_uuid())).collect_vec();
+        let current = load_entity_states(db, &keys).await?;
+
+        if current != expected {
+            return Err(UndoError::StateChanged.into());
+        }
+
+        // Restoring is a regular change, so the log stays append-only and sync is unaffected
+        let mut group = group_from_states(self.tournament_id, target);
+        group.author = self.author.clone();
+        group.save_all_and_log(db).await?;
+        Ok(group)
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct UndoStack {
+    stacks: HashMap<Uuid, (Vec<UndoEntry>, Vec<UndoEntry>)>,
+}
+
+impl UndoStack {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records a newly applied action. This discards all actions that could be redone.
+    pub fn push(&mut self, entry: UndoEntry) {
+        let (undo, redo) = self.stacks.entry(entry.tournament_id).or_default();
+        redo.clear();
+        undo.push(entry);
+        if undo.len() > MAX_UNDO_DEPTH {
+            undo.remove(0);
+        }
+    }
+
+    pub fn can_undo(&self, tournament_id: Uuid) -> bool {
+        self.stacks.get(&tournament_id).map(|(undo, _)| !undo.is_empty()).unwrap_or(false)
+    }
+
+    pub fn can_redo(&self, tournament_id: Uuid) -> bool {
+        self.stacks.get(&tournament_id).map(|(_, redo)| !redo.is_empty()).unwrap_or(false)
+    }
+
+    /// Reverts the last action and saves the reverting changes.
+    /// Fails if any of the affected entities was changed after the action.
+    /// The action only moves to the redo stack with `confirm_undo`, once the changes are committed.
+    pub async fn undo<C>(&self, db: &C, tournament_id: Uuid) -> Result<EntityGroup, anyhow::Error> where C: sea_orm::ConnectionTrait {
+        let entry = self.stacks.get(&tournament_id).and_then(|(undo, _)| undo.last()).ok_or(UndoError::NothingToUndo)?;
+        entry.restore(db, &entry.after, &entry.before).await
+    }
+
+    /// Reapplies the last undone action and saves the changes.
+    /// The action only moves back to the undo stack with `confirm_redo`, once the changes are committed.
+    pub async fn redo<C>(&self, db: &C, tournament_id: Uuid) -> Result<EntityGroup, anyhow::Error> where C: sea_orm::ConnectionTrait {
+        let entry = self.stacks.get(&tournament_id).and_then(|(_, redo)| redo.last()).ok_or(UndoError::NothingToRedo)?;
+        entry.restore(db, &entry.before, &entry.after).await
+    }
+
+    /// Records that the changes of the last `undo` were committed
+    pub fn confirm_undo(&mut self, tournament_id: Uuid) {
+        if let Some((undo, redo)) = self.stacks.get_mut(&tournament_id) {
+            if let Some(entry) = undo.pop() {
+                redo.push(entry);
+            }
+        }
+    }
+
+    /// Records that the changes of the last `redo` were committed
+    pub fn confirm_redo(&mut self, tournament_id: Uuid) {
+        if let Some((undo, redo)) = self.stacks.get_mut(&tournament_id) {
+            if let Some(entry) = redo.pop() {
+                undo.push(entry);
+            }
+        }
+    }
+}
@@ -1,12 +1,12 @@
 use std::{collections::HashMap};
 
 use itertools::Itertools;
-use open_tab_entities::{EntityGroup, derived_models::BreakNodeBackgroundInfo, domain::entity::LoadEntity, EntityTypeId};
+use open_tab_entities::{EntityGroup, derived_models::BreakNodeBackgroundInfo, domain::{entity::LoadEntity, tournament::DrawConfig}, EntityTypeId};
 use sea_orm::{prelude::Uuid, EntityTrait, QueryFilter, ColumnTrait};
 use serde::Serialize;
 
 
-use crate::{draw::{clashes::ClashType, evaluation::{DrawEvaluator, TournamentObservingDrawEvaluationContext}}, LoadedView};
+use crate::{draw::{clashes::ClashType, evaluation::{DrawEvaluator, DrawEvaluatorConfig, TournamentObservingDrawEvaluationContext}}, LoadedView};
 
 
 pub struct LoadedAdjudicatorBreakCandidatesView {
@@ -114,8 +114,9 @@ impl AdjudicatorBreakCandidatesView {
         };
 
         let evaluation_context = TournamentObservingDrawEvaluationContext::new_from_tournament(db, target_node.tournament_id).await?;
+        let draw_config = DrawConfig::load_for_tournament(db, target_node.tournament_id).await?;
         let evaluator = DrawEvaluator::new(
-            Default::default(),
+            DrawEvaluatorConfig::from(&draw_config),
             vec![],
             &evaluation_context
         );
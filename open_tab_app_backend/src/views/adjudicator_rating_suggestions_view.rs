@@ -0,0 +1,84 @@
+use std::collections::HashMap;
+
+use async_trait::async_trait;
+use itertools::Itertools;
+use open_tab_entities::{prelude::*, EntityTypeId};
+use sea_orm::prelude::*;
+use serde::{Serialize, Deserialize};
+
+use crate::{feedback_ratings::{load_rating_suggestions, AdjudicatorRatingSuggestion, FeedbackRatingConfig}, LoadedView};
+
+
+pub struct LoadedAdjudicatorRatingSuggestionsView {
+    pub view: AdjudicatorRatingSuggestionsView,
+    pub tournament_id: Uuid
+}
+
+impl LoadedAdjudicatorRatingSuggestionsView {
+    pub async fn load<C>(db: &C, tournament_uuid: Uuid) -> Result<Self, anyhow::Error> where C: sea_orm::ConnectionTrait {
+        Ok(
+            Self {
+                tournament_id: tournament_uuid,
+                view: AdjudicatorRatingSuggestionsView::load_from_tournament(db, tournament_uuid).await?,
+            }
+        )
+    }
+}
+
+#[async_trait]
+impl LoadedView for LoadedAdjudicatorRatingSuggestionsView {
+    async fn update_and_get_changes(&mut self, db: &sea_orm::DatabaseTransaction, changes: &EntityGroup) -> Result<Option<HashMap<String, serde_json::Value>>, anyhow::Error> {
+        if changes.has_changes_for_types(vec![
+            EntityTypeId::FeedbackResponse,
+            EntityTypeId::FeedbackQuestion,
+            EntityTypeId::Ballot,
+            EntityTypeId::Participant
+        ]) {
+            self.view = AdjudicatorRatingSuggestionsView::load_from_tournament(db, self.tournament_id).await?;
+
+            let mut out = HashMap::new();
+            out.insert(".".to_string(), serde_json::to_value(&self.view)?);
+
+            Ok(Some(out))
+        }
+        else {
+            Ok(None)
+        }
+    }
+
+    async fn view_string(&self) -> Result<String, anyhow::Error> {
+        Ok(serde_json::to_string(&self.view)?)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AdjudicatorRatingSuggestionsView {
+    pub adjudicators: Vec<AdjudicatorRatingSuggestionEntry>
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AdjudicatorRatingSuggestionEntry {
+    pub name: String,
+    #[serde(flatten)]
+    pub suggestion: AdjudicatorRatingSuggestion,
+}
+
+impl AdjudicatorRatingSuggestionsView {
+    pub async fn load_from_tournament<C>(db: &C, tournament_uuid: Uuid) -> Result<Self, anyhow::Error> where C: sea_orm::ConnectionTrait {
+        let names = Participant::get_all_adjudicators_in_tournament(db, tournament_uuid).await?.into_iter().map(|p| (p.uuid, p.name)).collect::<HashMap<_, _>>();
+        let suggestions = load_rating_suggestions(db, tournament_uuid, &FeedbackRatingConfig::default()).await?;
+
+        let adjudicators = suggestions.into_iter().map(|suggestion| {
+            AdjudicatorRatingSuggestionEntry {
+                name: names.get(&suggestion.adjudicator_id).cloned().unwrap_or_default(),
+                suggestion
+            }
+        }).sorted_by(|a, b| a.name.cmp(&b.name)).collect();
+
+        Ok(
+            AdjudicatorRatingSuggestionsView {
+                adjudicators
+            }
+        )
+    }
+}
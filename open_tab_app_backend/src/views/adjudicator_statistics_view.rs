@@ -0,0 +1,80 @@
+use std::collections::HashMap;
+
+use async_trait::async_trait;
+use itertools::Itertools;
+use open_tab_entities::{prelude::*, EntityTypeId};
+use sea_orm::prelude::*;
+use serde::{Serialize, Deserialize};
+
+use crate::{draw::adjudicator_statistics::AdjudicatorScoringStatistics, LoadedView};
+
+
+pub struct LoadedAdjudicatorStatisticsView {
+    pub view: AdjudicatorStatisticsView,
+    pub tournament_id: Uuid
+}
+
+impl LoadedAdjudicatorStatisticsView {
+    pub async fn load<C>(db: &C, tournament_uuid: Uuid) -> Result<Self, anyhow::Error> where C: sea_orm::ConnectionTrait {
+        Ok(
+            Self {
+                tournament_id: tournament_uuid,
+                view: AdjudicatorStatisticsView::load_from_tournament(db, tournament_uuid).await?,
+            }
+        )
+    }
+}
+
+#[async_trait]
+impl LoadedView for LoadedAdjudicatorStatisticsView {
+    async fn update_and_get_changes(&mut self, db: &sea_orm::DatabaseTransaction, changes: &EntityGroup) -> Result<Option<HashMap<String, serde_json::Value>>, anyhow::Error> {
+        if changes.has_changes_for_types(vec![EntityTypeId::Ballot, EntityTypeId::TournamentDebate, EntityTypeId::Participant]) {
+            self.view = AdjudicatorStatisticsView::load_from_tournament(db, self.tournament_id).await?;
+
+            let mut out = HashMap::new();
+            out.insert(".".to_string(), serde_json::to_value(&self.view)?);
+
+            Ok(Some(out))
+        }
+        else {
+            Ok(None)
+        }
+    }
+
+    async fn view_string(&self) -> Result<String, anyhow::Error> {
+        Ok(serde_json::to_string(&self.view)?)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AdjudicatorStatisticsView {
+    pub adjudicators: Vec<AdjudicatorStatisticsEntry>
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AdjudicatorStatisticsEntry {
+    pub uuid: Uuid,
+    pub name: String,
+    pub statistics: Option<AdjudicatorScoringStatistics>,
+}
+
+impl AdjudicatorStatisticsView {
+    pub async fn load_from_tournament<C>(db: &C, tournament_uuid: Uuid) -> Result<Self, anyhow::Error> where C: sea_orm::ConnectionTrait {
+        let adjudicators = Participant::get_all_adjudicators_in_tournament(db, tournament_uuid).await?;
+        let mut statistics = AdjudicatorScoringStatistics::load_for_tournament(db, tournament_uuid).await?;
+
+        let adjudicators = adjudicators.into_iter().map(|adj| {
+            AdjudicatorStatisticsEntry {
+                statistics: statistics.remove(&adj.uuid),
+                uuid: adj.uuid,
+                name: adj.name,
+            }
+        }).sorted_by(|a, b| a.name.cmp(&b.name)).collect();
+
+        Ok(
+            AdjudicatorStatisticsView {
+                adjudicators
+            }
+        )
+    }
+}
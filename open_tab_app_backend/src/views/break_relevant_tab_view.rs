@@ -36,7 +36,8 @@ impl LoadedView for LoadedBreakRelevantTabView {
             EntityTypeId::TournamentRound,
             EntityTypeId::Participant,
             EntityTypeId::Ballot,
-            EntityTypeId::TournamentBreak
+            EntityTypeId::TournamentBreak,
+            EntityTypeId::SpeakerSubstitution
         ]) {
             self.view = AugmentedBreakRelevantTabView::load_from_node(db, self.node_uuid).await?;
 
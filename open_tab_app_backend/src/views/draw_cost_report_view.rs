@@ -0,0 +1,106 @@
+use std::collections::HashMap;
+
+use async_trait::async_trait;
+use itertools::Itertools;
+use open_tab_entities::{domain::{entity::LoadEntity, tournament::DrawConfig}, prelude::*, EntityTypeId};
+use sea_orm::prelude::*;
+use serde::{Serialize, Deserialize};
+
+use crate::{draw::{datastructures::RoundInfo, evaluation::{DrawConstructionEvaluationContext, DrawEvaluator, DrawEvaluatorConfig}, explanation::{explain_round_draw, DebateCostReport}, flow_optimization::{OptimizationOptions, OptimizationState}, PreliminaryRoundGenerator}, draw_view::DrawBallot, LoadedView, TournamentParticipantsInfo};
+
+
+pub struct LoadedDrawCostReportView {
+    pub view: DrawCostReportView,
+    pub round_id: Uuid
+}
+
+impl LoadedDrawCostReportView {
+    pub async fn load<C>(db: &C, round_uuid: Uuid) -> Result<Self, anyhow::Error> where C: sea_orm::ConnectionTrait {
+        Ok(
+            LoadedDrawCostReportView {
+                round_id: round_uuid,
+                view: DrawCostReportView::load(db, round_uuid).await?,
+            }
+        )
+    }
+}
+
+#[async_trait]
+impl LoadedView for LoadedDrawCostReportView {
+    async fn update_and_get_changes(&mut self, db: &sea_orm::DatabaseTransaction, changes: &EntityGroup) -> Result<Option<HashMap<String, serde_json::Value>>, anyhow::Error> {
+        if changes.has_changes_for_types(vec![
+            EntityTypeId::TournamentRound,
+            EntityTypeId::TournamentDebate,
+            EntityTypeId::Ballot,
+            EntityTypeId::Team,
+            EntityTypeId::Participant,
+            EntityTypeId::ParticipantClash,
+            EntityTypeId::Tournament,
+        ]) {
+            self.view = DrawCostReportView::load(db, self.round_id).await?;
+
+            let mut out = HashMap::new();
+            out.insert(".".to_string(), serde_json::to_value(&self.view)?);
+
+            Ok(Some(out))
+        }
+        else {
+            Ok(None)
+        }
+    }
+
+    async fn view_string(&self) -> Result<String, anyhow::Error> {
+        Ok(serde_json::to_string(&self.view)?)
+    }
+}
+
+/// Explains which costs led to the allocation of each position in the draw of a round,
+/// and how the closest alternatives compare.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DrawCostReportView {
+    pub round_id: Uuid,
+    pub draw_seed: Option<i64>,
+    pub randomization_scale: f64,
+    pub debates: Vec<DebateCostReport>,
+    /// Names of all teams and participants mentioned in the report
+    pub names: HashMap<Uuid, String>,
+}
+
+impl DrawCostReportView {
+    pub async fn load<C>(db: &C, round_uuid: Uuid) -> Result<Self, anyhow::Error> where C: sea_orm::ConnectionTrait {
+        let round = TournamentRound::get(db, round_uuid).await?;
+        let debates = TournamentDebate::get_all_in_rounds(db, vec![round_uuid]).await?.into_iter().next().unwrap_or_default()
+            .into_iter().sorted_by_key(|d| d.index).collect_vec();
+        let ballots = Ballot::get_many(db, debates.iter().map(|d| d.ballot_id).collect()).await?;
+
+        let other_rounds = TournamentRound::get_all_in_tournament(db, round.tournament_id).await?.into_iter()
+            .map(|r| r.uuid).filter(|r| *r != round_uuid).collect_vec();
+        let evaluation_context = DrawConstructionEvaluationContext::new_from_tournament(db, round.tournament_id).await?;
+        let draw_config = DrawConfig::load_for_tournament(db, round.tournament_id).await?;
+        let evaluator = DrawEvaluator::new(DrawEvaluatorConfig::from(&draw_config), other_rounds, &evaluation_context);
+
+        let optimization_state = OptimizationState::load_from_rounds(
+            db,
+            round.tournament_id,
+            vec![RoundInfo { id: round.uuid, debates: ballots.iter().cloned().map(Into::into).collect(), is_silent: round.is_silent }],
+            OptimizationOptions::from(&draw_config)
+        ).await?;
+
+        let randomization_scale = PreliminaryRoundGenerator::default().randomization_scale;
+        let draw_ballots = ballots.iter().map(DrawBallot::from).collect_vec();
+        let debates = explain_round_draw(&draw_ballots, &optimization_state, round.is_silent, &evaluator, randomization_scale);
+
+        let info = TournamentParticipantsInfo::load(db, round.tournament_id).await?;
+        let names = info.participants_by_id.values().map(|p| (p.uuid, p.name.clone())).chain(
+            info.teams_by_id.values().map(|t| (t.uuid, t.name.clone()))
+        ).collect();
+
+        Ok(DrawCostReportView {
+            round_id: round_uuid,
+            draw_seed: round.draw_seed,
+            randomization_scale,
+            debates,
+            names,
+        })
+    }
+}
@@ -3,6 +3,8 @@ use std::collections::{HashMap, HashSet};
 use async_trait::async_trait;
 use open_tab_entities::domain::entity::LoadEntity;
 use open_tab_entities::domain::tournament_venue::TournamentVenue;
+use open_tab_entities::domain::debate::DebatePins;
+use open_tab_entities::domain::tournament::DrawConfig;
 use serde::{Serialize, Deserialize};
 
 use sea_orm::prelude::*;
@@ -15,7 +17,7 @@ use itertools::Itertools;
 
 
 
-use crate::draw::evaluation::{DrawEvaluator, DrawIssue, TournamentObservingDrawEvaluationContext};
+use crate::draw::evaluation::{DrawEvaluator, DrawEvaluatorConfig, DrawIssue, TournamentObservingDrawEvaluationContext};
 use crate::tab_view::TeamRoundRole;
 
 use super::base::{LoadedView, TournamentParticipantsInfo};
@@ -29,7 +31,9 @@ pub struct LoadedDrawView {
     pub team_uuid_to_index: HashMap<Uuid, usize>,
     pub adjudicator_uuid_to_index: HashMap<Uuid, usize>,
 
-    pub evaluation_context: TournamentObservingDrawEvaluationContext
+    pub evaluation_context: TournamentObservingDrawEvaluationContext,
+    /// The clash weights of the tournament, reloaded when the tournament changes
+    pub evaluator_config: DrawEvaluatorConfig,
     //TODO: Use this to cache team and participant names
     //to avoid a full reload every time
     //Alternatively, it would be interesting to try to implement
@@ -47,7 +51,8 @@ impl LoadedDrawView {
         let all_rounds = schema::tournament_round::Entity::find().filter(schema::tournament_round::Column::TournamentId.eq(tournament_id)).all(db).await?;
         let relevant_rounds = all_rounds.iter().map(|r| r.uuid).filter(|u| *u != round_uuid).collect_vec();
 
-        let evaluator = DrawEvaluator::new(Default::default(), relevant_rounds, &evaluation_context);
+        let evaluator_config = DrawEvaluatorConfig::from(&DrawConfig::load_for_tournament(db, tournament_id).await?);
+        let evaluator = DrawEvaluator::new(evaluator_config.clone(), relevant_rounds, &evaluation_context);
         let view = DrawView::load_from_round(db, round, &participant_info, &evaluator).await?;
 
         let team_uuid_to_index = view.team_index.iter().enumerate().map(|(idx, entry)| (entry.team.uuid, idx)).collect();
@@ -58,6 +63,7 @@ impl LoadedDrawView {
                 tournament_id,
                 view,
                 evaluation_context,
+                evaluator_config,
                 participant_info,
                 team_uuid_to_index,
                 adjudicator_uuid_to_index
@@ -85,8 +91,13 @@ impl LoadedView for LoadedDrawView {
 
         let changed_evaluation_debates : HashSet<_> = self.evaluation_context.update_from_changes(db, changes).await?.into_iter().collect();
 
+        if changes.has_changes_for_types(vec![EntityTypeId::Tournament]) {
+            self.evaluator_config = DrawEvaluatorConfig::from(&DrawConfig::load_for_tournament(db, self.tournament_id).await?);
+        }
+
         // TODO: Reloads could be much more efficient
         if has_new_debate || changes.has_changes_for_types(vec![
+                EntityTypeId::Tournament,
                 EntityTypeId::TournamentVenue,
                 EntityTypeId::TournamentDebate,
                 EntityTypeId::Participant,
@@ -97,7 +108,7 @@ impl LoadedView for LoadedDrawView {
             let mut out: HashMap<String, Json> = HashMap::new();
             let round = schema::tournament_round::Entity::find_by_id(self.view.round_uuid).one(db).await?.ok_or(DrawViewError::MissingDebate)?;
             self.participant_info = TournamentParticipantsInfo::load(db, round.tournament_id).await?;
-            let evaluator = DrawEvaluator::new(Default::default(), relevant_rounds, &self.evaluation_context);
+            let evaluator = DrawEvaluator::new(self.evaluator_config.clone(), relevant_rounds, &self.evaluation_context);
             self.view = DrawView::load_from_round(db, round, &self.participant_info, &evaluator).await?;
             out.insert(".".to_string(), serde_json::to_value(&self.view)?);
 
@@ -118,7 +129,7 @@ impl LoadedView for LoadedDrawView {
             }
         }
         if indices_to_reload.len() > 0 {
-            let evaluator = DrawEvaluator::new(Default::default(), relevant_rounds, &self.evaluation_context);
+            let evaluator = DrawEvaluator::new(self.evaluator_config.clone(), relevant_rounds, &self.evaluation_context);
             let mut out : HashMap<String, serde_json::Value> = HashMap::new();
             let ballot_uuids = indices_to_reload.iter().map(|idx| {self.view.debates[*idx].ballot.uuid}).collect_vec();
             let ballots = Ballot::get_many(db, ballot_uuids).await?;
@@ -134,7 +145,7 @@ impl LoadedView for LoadedDrawView {
 
                 let mut removed_adjudicators = HashSet::new();
 
-                for adj in prev_value.adjudicators.iter() {
+                for adj in prev_value.adjudicators.iter().chain(prev_value.trainees.iter()) {
                     removed_adjudicators.insert(adj.adjudicator.uuid);
                 }
 
@@ -146,6 +157,14 @@ impl LoadedView for LoadedDrawView {
                         position: AdjudicatorPositionRole::Panel { position: pos_idx }
                     });
                 }
+                for (pos_idx, trainee) in ballot.trainees.iter().enumerate() {
+                    removed_adjudicators.remove(trainee);
+                    adj_index_updates.insert(*trainee, AdjudictorPosition::Set {
+                        debate_uuid: debate.uuid,
+                        debate_index: idx,
+                        position: AdjudicatorPositionRole::Trainee { position: pos_idx }
+                    });
+                }
                 for removed_adjudicator in removed_adjudicators {
                     if !adj_index_updates.contains_key(&removed_adjudicator) {
                         adj_index_updates.insert(removed_adjudicator, AdjudictorPosition::NotSet);
@@ -245,7 +264,8 @@ struct SpeakerPosition {
 #[serde(tag = "type")]
 enum AdjudicatorPositionRole {
     President,
-    Panel {position: usize}
+    Panel {position: usize},
+    Trainee {position: usize}
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -266,6 +286,9 @@ pub struct DrawDebate {
     pub index: usize,
     pub ballot: DrawBallot,
     pub venue: Option<DrawVenue>,
+    /// If not set, the pins of the debate are not changed when updating the draw
+    #[serde(default)]
+    pub pins: Option<DebatePins>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
@@ -276,6 +299,8 @@ pub struct DrawBallot {
     pub non_aligned_speakers: Vec<Option<DrawSpeaker>>,
     pub adjudicators: Vec<SetDrawAdjudicator>,
     pub president: Option<SetDrawAdjudicator>,
+    #[serde(default)]
+    pub trainees: Vec<SetDrawAdjudicator>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -307,6 +332,7 @@ impl Into<Ballot> for DrawBallot {
                         position,
                         scores: HashMap::new(),
                         is_opt_out: false,
+                        trainee_scores: HashMap::new(),
                     }
                 )
             }
@@ -319,16 +345,41 @@ impl Into<Ballot> for DrawBallot {
                     position: idx as u8,
                     scores: HashMap::new(),
                     is_opt_out: false,
+                    trainee_scores: HashMap::new(),
                 }
             )
         );
         Ballot {
             uuid: self.uuid,
-            government: BallotTeam { team: self.government.map(|t| t.uuid), scores: HashMap::new() },
-            opposition: BallotTeam { team: self.opposition.map(|t| t.uuid), scores: HashMap::new() },
+            government: BallotTeam { team: self.government.map(|t| t.uuid), scores: HashMap::new(), trainee_scores: HashMap::new() },
+            opposition: BallotTeam { team: self.opposition.map(|t| t.uuid), scores: HashMap::new(), trainee_scores: HashMap::new() },
             speeches,
             adjudicators: self.adjudicators.into_iter().map(|a| a.adjudicator.uuid).collect(),
             president: None,
+            trainees: self.trainees.into_iter().map(|a| a.adjudicator.uuid).collect(),
+        }
+    }
+}
+
+/// Only the ids are filled in, names and issues are left empty.
+impl From<&Ballot> for DrawBallot {
+    fn from(ballot: &Ballot) -> Self {
+        DrawBallot {
+            uuid: ballot.uuid,
+            government: ballot.government.team.map(|uuid| DrawTeam { uuid, ..Default::default() }),
+            opposition: ballot.opposition.team.map(|uuid| DrawTeam { uuid, ..Default::default() }),
+            non_aligned_speakers: ballot.speeches.iter().filter(
+                |speech| speech.role == SpeechRole::NonAligned
+            ).sorted_by_key(|speech| speech.position).map(
+                |speech| speech.speaker.map(|uuid| DrawSpeaker { uuid, ..Default::default() })
+            ).collect(),
+            adjudicators: ballot.adjudicators.iter().map(
+                |uuid| DrawAdjudicator { uuid: *uuid, ..Default::default() }.into()
+            ).collect(),
+            president: ballot.president.map(|uuid| DrawAdjudicator { uuid, ..Default::default() }.into()),
+            trainees: ballot.trainees.iter().map(
+                |uuid| DrawAdjudicator { uuid: *uuid, ..Default::default() }.into()
+            ).collect(),
         }
     }
 }
@@ -468,6 +519,26 @@ impl DrawView {
         }
     }
 
+    fn set_draw_adjudicator_from_uuid(uuid: Uuid, info: &TournamentParticipantsInfo, round_id: Uuid) -> SetDrawAdjudicator {
+        let mut adj : SetDrawAdjudicator = Self::draw_adjudicator_from_uuid(uuid, info).into();
+        adj.is_available = match info.participants_by_id.get(&uuid) {
+            Some(participant) => {
+                match &participant.role {
+                    ParticipantRole::Adjudicator (Adjudicator{ unavailable_rounds, .. }) => {
+                        !unavailable_rounds.contains(&round_id)
+                    },
+                    _ => {
+                        true
+                    }
+                }
+            },
+            None => {
+                true
+            }
+        };
+        adj
+    }
+
     fn draw_ballot_from_debate_ballot(
         ballot: &Ballot,
         info: &TournamentParticipantsInfo,
@@ -512,44 +583,9 @@ impl DrawView {
                     None
                 }
             }).collect(),
-            adjudicators: ballot.adjudicators.iter().map(|adjudicator_uuid| {
-                let mut adj : SetDrawAdjudicator = Self::draw_adjudicator_from_uuid(*adjudicator_uuid, info).into();
-                adj.is_available = match info.participants_by_id.get(adjudicator_uuid) {
-                    Some(participant) => {
-                        match &participant.role {
-                            ParticipantRole::Adjudicator (Adjudicator{ unavailable_rounds, .. }) => {
-                                !unavailable_rounds.contains(&round_id)
-                            },
-                            _ => {
-                                true
-                            }
-                        }
-                    },
-                    None => {
-                        true
-                    }
-                };
-                adj
-            }).collect(),
-            president: ballot.president.map(|president_uuid| {
-                let mut adj : SetDrawAdjudicator = Self::draw_adjudicator_from_uuid(president_uuid, info).into();
-                adj.is_available = match info.participants_by_id.get(&president_uuid) {
-                    Some(participant) => {
-                        match &participant.role {
-                            ParticipantRole::Adjudicator (Adjudicator{ unavailable_rounds, .. }) => {
-                                !unavailable_rounds.contains(&round_id)
-                            },
-                            _ => {
-                                true
-                            }
-                        }
-                    },
-                    None => {
-                        true
-                    }
-                };
-                adj
-            })
+            adjudicators: ballot.adjudicators.iter().map(|adjudicator_uuid| Self::set_draw_adjudicator_from_uuid(*adjudicator_uuid, info, round_id)).collect(),
+            president: ballot.president.map(|president_uuid| Self::set_draw_adjudicator_from_uuid(president_uuid, info, round_id)),
+            trainees: ballot.trainees.iter().map(|trainee_uuid| Self::set_draw_adjudicator_from_uuid(*trainee_uuid, info, round_id)).collect(),
         };
         let ballot_evaluation = evaluator.find_issues_in_ballot(&ballot);
 
@@ -562,7 +598,7 @@ impl DrawView {
             opp.issues = ballot_evaluation.opposition_issues.clone();
         }
 
-        ballot.adjudicators.iter_mut().for_each(|adjudicator| {
+        ballot.adjudicators.iter_mut().chain(ballot.trainees.iter_mut()).for_each(|adjudicator| {
             adjudicator.issues = ballot_evaluation.adjudicator_issues.get(&adjudicator.adjudicator.uuid).unwrap_or(&vec![]).clone();
         });
         ballot.non_aligned_speakers.iter_mut().filter_map(|s| s.as_mut()).for_each(|speaker| {
@@ -597,6 +633,14 @@ impl DrawView {
                     position: AdjudicatorPositionRole::President
                 });
             }
+
+            debate.ballot.trainees.iter().enumerate().for_each(|(trainee_idx, trainee)| {
+                adj_positions.insert(trainee.adjudicator.uuid, AdjudictorPosition::Set {
+                    debate_uuid: debate.uuid,
+                    debate_index: debate.index,
+                    position: AdjudicatorPositionRole::Trainee { position: trainee_idx }
+                });
+            });
         });
 
         adjudicators.into_iter().map(
@@ -726,6 +770,7 @@ impl DrawView {
                     index: debate.index as usize,
                     ballot: Self::draw_ballot_from_debate_ballot(&debate_ballot, &participant_info, &evaluator, round.uuid),
                     venue: debate.venue_id.map(|id| debate_venues.get(&id).cloned()).flatten(),
+                    pins: Some(TournamentDebate::from_model(debate).pins.unwrap_or_default()),
                 }
             }
         ).sorted_by_key(|d| d.index).collect();
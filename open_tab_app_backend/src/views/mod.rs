@@ -18,6 +18,9 @@ pub mod clashes_view;
 pub mod pending_ballots_view;
 pub mod feedback_forms_view;
 pub mod break_categories_view;
+pub mod adjudicator_statistics_view;
+pub mod adjudicator_rating_suggestions_view;
+pub mod draw_cost_report_view;
 mod base;
 
 pub use self::base::{LoadedView, TournamentParticipantsInfo};
@@ -41,6 +44,9 @@ use self::clashes_view::LoadedClashesView;
 use self::pending_ballots_view::LoadedPendingBallotsView;
 use self::feedback_forms_view::LoadedFeedbackFormsView;
 use self::break_categories_view::LoadedBreakCategoriesView;
+use self::adjudicator_statistics_view::LoadedAdjudicatorStatisticsView;
+use self::adjudicator_rating_suggestions_view::LoadedAdjudicatorRatingSuggestionsView;
+use self::draw_cost_report_view::LoadedDrawCostReportView;
 
 use self::draw_view::LoadedDrawView;
 
@@ -75,6 +81,9 @@ pub enum View {
     PendingBallots{tournament_id: Uuid},
     FeedbackForms{tournament_id: Uuid},
     BreakCategories{tournament_uuid: Uuid},
+    AdjudicatorStatistics{tournament_uuid: Uuid},
+    AdjudicatorRatingSuggestions{tournament_uuid: Uuid},
+    DrawCostReport{round_uuid: Uuid},
 }
 
 impl View {
@@ -147,6 +156,15 @@ impl View {
             },
             View::BreakCategories { tournament_uuid } => {
                 Box::new(LoadedBreakCategoriesView::load(db, *tournament_uuid).await?)
+            },
+            View::AdjudicatorStatistics { tournament_uuid } => {
+                Box::new(LoadedAdjudicatorStatisticsView::load(db, *tournament_uuid).await?)
+            },
+            View::AdjudicatorRatingSuggestions { tournament_uuid } => {
+                Box::new(LoadedAdjudicatorRatingSuggestionsView::load(db, *tournament_uuid).await?)
+            },
+            View::DrawCostReport { round_uuid } => {
+                Box::new(LoadedDrawCostReportView::load(db, *round_uuid).await?)
             }
         })
     }
@@ -80,6 +80,15 @@ pub struct ParticipantsListView {
     pub teams: HashMap<Uuid, TeamEntry>,
     pub institutions: HashMap<Uuid, Institution>,
     pub break_categories: HashMap<Uuid, BreakCategory>,
+    /// Reserve speakers, who can substitute for team members
+    #[serde(default)]
+    pub speakers_without_team: HashMap<Uuid, SpeakerWithoutTeamEntry>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SpeakerWithoutTeamEntry {
+    pub uuid: Uuid,
+    pub name: String,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -102,6 +111,8 @@ pub struct ParticipantEntry {
     pub registration_key: Option<String>,
     pub is_anonymous: bool,
     pub break_category_id: Option<Uuid>,
+    #[serde(default)]
+    pub requires_accessible_venue: bool,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -162,6 +173,8 @@ pub enum ParticipantRole {
         chair_skill: i16,
         panel_skill: i16,
         unavailable_rounds: Vec<Uuid>,
+        #[serde(default)]
+        is_trainee: bool,
     }
 }
 
@@ -178,6 +191,7 @@ impl ParticipantsListView {
             registration_key: None,
             is_anonymous: p.is_anonymous,
             break_category_id: None,
+            requires_accessible_venue: false,
         }).collect_vec().load_many(schema::participant_tournament_institution::Entity, db).await?;
 
         let all_clashes = schema::participant_clash::Entity::find()
@@ -202,6 +216,9 @@ impl ParticipantsListView {
             ).all(db).await?.into_iter().map(|i| (i.uuid, i.name)).collect::<HashMap<_, _>>();
 
         let participant_names = participants.iter().map(|p| (p.uuid, p.name.clone())).collect::<HashMap<_, _>>();
+        let speakers_without_team = participants.iter().filter(
+            |p| matches!(p.role, domain::participant::ParticipantRole::Speaker(Speaker { team_id: None }))
+        ).map(|p| (p.uuid, SpeakerWithoutTeamEntry { uuid: p.uuid, name: p.name.clone() })).collect::<HashMap<_, _>>();
         
         let participants = izip![participants, all_institutions];
 
@@ -228,14 +245,15 @@ impl ParticipantsListView {
             ).collect_vec();
             match p.role {
                 domain::participant::ParticipantRole::Adjudicator(
-                    Adjudicator { chair_skill, panel_skill, unavailable_rounds}
+                    Adjudicator { chair_skill, panel_skill, unavailable_rounds, is_trainee }
                 ) => Some(ParticipantEntry {
                     uuid: p.uuid,
                     name: p.name,
                     role: ParticipantRole::Adjudicator {
                         chair_skill,
                         panel_skill,
-                        unavailable_rounds
+                        unavailable_rounds,
+                        is_trainee
                     },
                     institutions,
                     clashes,
@@ -244,6 +262,7 @@ impl ParticipantsListView {
                     }),
                     is_anonymous: p.is_anonymous,
                     break_category_id: p.break_category_id,
+                    requires_accessible_venue: p.requires_accessible_venue,
                 }),
                 domain::participant::ParticipantRole::Speaker(
                     Speaker { team_id }
@@ -258,6 +277,7 @@ impl ParticipantsListView {
                             registration_key: p.registration_key.map(|k| Participant::encode_registration_key(p.uuid, &k)),
                             is_anonymous: p.is_anonymous,
                             break_category_id: p.break_category_id,
+                            requires_accessible_venue: p.requires_accessible_venue,
                         })    
                     }
                     else {
@@ -297,6 +317,7 @@ impl ParticipantsListView {
                 teams: teams.into_iter().map(|t| (t.uuid, t)).collect(),
                 institutions: institution_names.into_iter().map(|(i, n)| (i, Institution { uuid: i, name: n })).collect(),
                 break_categories: domain::tournament_break_category::TournamentBreakCategory::get_all_in_tournament(db, tournament_uuid).await?.into_iter().map(|b| (b.uuid, BreakCategory { uuid: b.uuid, name: b.name })).collect(),
+                speakers_without_team,
             }
         )
     }
@@ -5,10 +5,13 @@ use std::collections::HashMap;
 use async_trait::async_trait;
 use serde::{Serialize, Deserialize};
 
+use itertools::Itertools;
 use sea_orm::{prelude::*, DbBackend, QueryOrder, QuerySelect, QueryTrait};
-use open_tab_entities::{prelude::*, EntityTypeId};
+use open_tab_entities::{prelude::*, domain::entity::LoadEntity, EntityTypeId};
 
-use open_tab_entities::schema::{debate_backup_ballot, tournament, tournament_debate, tournament_round};
+use open_tab_entities::schema::{debate_backup_ballot, participant, team, tournament_debate, tournament_round};
+
+use crate::ballot_confirmation::{find_ballot_differences, BallotField, BallotFieldDifference, BallotFieldValue};
 
 
 
@@ -34,7 +37,7 @@ impl LoadedPendingBallotsView {
 #[async_trait]
 impl LoadedView for LoadedPendingBallotsView {
     async fn update_and_get_changes(&mut self, db: &sea_orm::DatabaseTransaction, changes: &EntityGroup) -> Result<Option<HashMap<String, serde_json::Value>>, anyhow::Error> {
-        if changes.has_changes_for_type(EntityTypeId::DebateBackupBallot) {
+        if changes.has_changes_for_type(EntityTypeId::DebateBackupBallot) || changes.has_changes_for_type(EntityTypeId::TournamentDebate) || changes.has_changes_for_type(EntityTypeId::Ballot) {
             self.view = PendingBallotsView::load_from_tournament(db, self.tournament_id).await?;
 
             let mut out = HashMap::new();
@@ -54,7 +57,30 @@ impl LoadedView for LoadedPendingBallotsView {
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PendingBallotsView {
-    pending_ballot_counts: HashMap<Uuid, i32>
+    pending_ballot_counts: HashMap<Uuid, i32>,
+    pending_debates: Vec<PendingDebate>,
+    /// Names of the teams and participants that appear in the differences
+    names: HashMap<Uuid, String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PendingDebate {
+    debate_id: Uuid,
+    round_id: Uuid,
+    debate_index: u64,
+    /// The current ballot of the debate, if it already has results
+    current_ballot_id: Option<Uuid>,
+    submissions: Vec<PendingSubmission>,
+    /// Fields in which the submissions and the current results disagree.
+    /// Values are keyed by backup ballot id, or by `current_ballot_id` for the current results.
+    differences: Vec<BallotFieldDifference>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PendingSubmission {
+    backup_ballot_id: Uuid,
+    author_name: Option<String>,
+    timestamp: chrono::NaiveDateTime,
 }
 
 
@@ -73,9 +99,84 @@ impl PendingBallotsView {
             .all(db)
             .await?;
 
+        let pending_backup_ballots = debate_backup_ballot::Entity::find()
+            .inner_join(tournament_debate::Entity)
+            .join(sea_orm::JoinType::InnerJoin, tournament_debate::Relation::TournamentRound.def())
+            .filter(tournament_round::Column::TournamentId.eq(tournament_id))
+            .filter(debate_backup_ballot::Column::WasSeen.eq(false))
+            .order_by_asc(debate_backup_ballot::Column::Timestamp)
+            .all(db)
+            .await?
+            .into_iter()
+            .into_group_map_by(|b| b.debate_id);
+
+        let debates = TournamentDebate::get_many(db, pending_backup_ballots.keys().cloned().collect()).await?;
+        let ballots_by_id : HashMap<Uuid, Ballot> = Ballot::get_many(
+            db,
+            debates.iter().map(|d| d.ballot_id).chain(pending_backup_ballots.values().flatten().map(|b| b.ballot_id)).collect()
+        ).await?.into_iter().map(|b| (b.uuid, b)).collect();
+        let author_names : HashMap<Uuid, String> = participant::Entity::find()
+            .filter(participant::Column::Uuid.is_in(pending_backup_ballots.values().flatten().filter_map(|b| b.author_participant_id)))
+            .all(db)
+            .await?
+            .into_iter()
+            .map(|p| (p.uuid, p.name))
+            .collect();
+
+        let pending_debates = debates.into_iter().map(|debate| {
+            let backup_ballots = pending_backup_ballots.get(&debate.uuid).map(|b| b.as_slice()).unwrap_or_default();
+            let current_ballot = ballots_by_id.get(&debate.ballot_id).filter(|b| b.is_scored());
+
+            let compared_ballots = current_ballot.map(|b| (b.uuid, b)).into_iter().chain(
+                backup_ballots.iter().filter_map(|backup| ballots_by_id.get(&backup.ballot_id).map(|ballot| (backup.uuid, ballot)))
+            ).collect_vec();
+
+            PendingDebate {
+                debate_id: debate.uuid,
+                round_id: debate.round_id,
+                debate_index: debate.index,
+                current_ballot_id: current_ballot.map(|b| b.uuid),
+                submissions: backup_ballots.iter().map(|backup| PendingSubmission {
+                    backup_ballot_id: backup.uuid,
+                    author_name: backup.author_participant_id.and_then(|author| author_names.get(&author).cloned()),
+                    timestamp: backup.timestamp,
+                }).collect(),
+                differences: find_ballot_differences(&compared_ballots),
+            }
+        }).sorted_by_key(|d| (d.round_id, d.debate_index)).collect_vec();
+
+        let named_ids = pending_debates.iter().flat_map(|d| d.differences.iter()).flat_map(|d| {
+            let adjudicator_id = match d.field {
+                BallotField::SpeechScore { adjudicator_id, .. } | BallotField::TeamScore { adjudicator_id, .. } => Some(adjudicator_id),
+                _ => None
+            };
+            d.values.values().flat_map(|value| match value {
+                Some(BallotFieldValue::Id { uuid: Some(uuid) }) => vec![*uuid],
+                Some(BallotFieldValue::Ids { uuids }) => uuids.clone(),
+                _ => vec![]
+            }).chain(adjudicator_id)
+        }).unique().collect_vec();
+        let mut names : HashMap<Uuid, String> = participant::Entity::find()
+            .filter(participant::Column::Uuid.is_in(named_ids.clone()))
+            .all(db)
+            .await?
+            .into_iter()
+            .map(|p| (p.uuid, p.name))
+            .collect();
+        names.extend(
+            team::Entity::find()
+                .filter(team::Column::Uuid.is_in(named_ids))
+                .all(db)
+                .await?
+                .into_iter()
+                .map(|t| (t.uuid, t.name))
+        );
+
         Ok(
             PendingBallotsView {
-                pending_ballot_counts: num_pending_ballots.into_iter().collect()
+                pending_ballot_counts: num_pending_ballots.into_iter().collect(),
+                pending_debates,
+                names,
             }
         )
     }
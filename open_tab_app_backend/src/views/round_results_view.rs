@@ -1,5 +1,5 @@
 
-use open_tab_entities::{derived_models::ResultDebate, EntityTypeId};
+use open_tab_entities::{derived_models::ResultDebate, domain::speaker_substitution::SpeakerSubstitution, EntityTypeId};
 
 
 
@@ -46,7 +46,8 @@ impl LoadedView for LoadedRoundResultsView {
             EntityTypeId::Ballot,
             EntityTypeId::Team,
             EntityTypeId::Participant,
-            EntityTypeId::DebateBackupBallot
+            EntityTypeId::DebateBackupBallot,
+            EntityTypeId::SpeakerSubstitution
         ]) {
             self.view = RoundResultsView::load(db, self.round_id).await?;
 
@@ -67,7 +68,8 @@ impl LoadedView for LoadedRoundResultsView {
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RoundResultsView {
-    debates: Vec<ResultDebate>
+    debates: Vec<ResultDebate>,
+    substitutions: Vec<SpeakerSubstitution>,
 }
 
 
@@ -75,7 +77,8 @@ impl RoundResultsView {
     async fn load<C>(db: &C, round_uuid: Uuid) -> Result<Self, anyhow::Error> where C: sea_orm::ConnectionTrait {
 
         Ok(RoundResultsView {
-            debates: ResultDebate::load_all_from_round(db, round_uuid).await?
+            debates: ResultDebate::load_all_from_round(db, round_uuid).await?,
+            substitutions: SpeakerSubstitution::get_all_in_rounds(db, vec![round_uuid]).await?,
         })
     }
 }
\ No newline at end of file
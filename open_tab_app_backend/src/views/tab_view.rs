@@ -36,7 +36,7 @@ impl LoadedView for LoadedTabView {
         db: &sea_orm::DatabaseTransaction,
         changes: &EntityGroup,
     ) -> Result<Option<HashMap<String, serde_json::Value>>, anyhow::Error> {
-        if changes.has_changes_for_type(EntityTypeId::Ballot) {
+        if changes.has_changes_for_types(vec![EntityTypeId::Ballot, EntityTypeId::Tournament, EntityTypeId::SpeakerSubstitution]) {
             self.view = AugmentedTabView::load_from_tournament(db, self.tournament_uuid).await?;
 
             let mut out = HashMap::new();
@@ -1,7 +1,7 @@
 
 use sea_orm::{ColumnTrait, EntityTrait, QueryFilter};
 
-use open_tab_entities::{domain::entity::LoadEntity, EntityTypeId};
+use open_tab_entities::{domain::{ballot_validation::BallotValidationConfig, entity::LoadEntity, tournament::DrawConfig}, EntityTypeId};
 use open_tab_entities::schema::tournament_remote;
 
 
@@ -63,7 +63,11 @@ pub struct TournamentStatusView {
     annoucements_password: Option<String>,
     remote_url: Option<String>,
     allow_self_declared_clashes: bool,
-    allow_speaker_self_declared_clashes: bool
+    allow_speaker_self_declared_clashes: bool,
+    allow_self_declared_availability: bool,
+    availability_deadline_minutes: i32,
+    draw_config: DrawConfig,
+    ballot_validation_config: BallotValidationConfig,
 }
 
 impl TournamentStatusView {
@@ -82,6 +86,10 @@ impl TournamentStatusView {
             remote_url: remote.map(|r| r.url),
             allow_self_declared_clashes: tournament.allow_self_declared_clashes,
             allow_speaker_self_declared_clashes: tournament.allow_speaker_self_declared_clashes,
+            allow_self_declared_availability: tournament.allow_self_declared_availability,
+            availability_deadline_minutes: tournament.availability_deadline_minutes,
+            draw_config: tournament.draw_config.unwrap_or_default(),
+            ballot_validation_config: tournament.ballot_validation_config.unwrap_or_default(),
         })
     }
 }
\ No newline at end of file
@@ -60,7 +60,15 @@ pub struct VenueListView {
 pub struct VenueOverview {
     pub uuid: Uuid,
     pub name: String,
-    pub ordering_index: i32
+    pub ordering_index: i32,
+    #[serde(default)]
+    pub capacity: Option<i32>,
+    #[serde(default)]
+    pub is_accessible: bool,
+    #[serde(default)]
+    pub building: Option<String>,
+    #[serde(default)]
+    pub is_top_room: bool,
 }
 
 
@@ -72,7 +80,11 @@ impl VenueListView {
             VenueOverview {
                 uuid: venue.uuid,
                 name: venue.name,
-                ordering_index: venue.ordering_index
+                ordering_index: venue.ordering_index,
+                capacity: venue.capacity,
+                is_accessible: venue.is_accessible,
+                building: venue.building,
+                is_top_room: venue.is_top_room,
             }
         }).collect();
 
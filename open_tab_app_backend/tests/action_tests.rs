@@ -2,11 +2,11 @@ use std::collections::HashMap;
 
 use itertools::Itertools;
 use migration::MigratorTrait;
-use open_tab_entities::{prelude::*, mock::{make_mock_tournament_with_options, MockOption}, domain::entity::LoadEntity};
-use sea_orm::{prelude::*, Database, Statement};
+use open_tab_entities::{prelude::*, Entity, EntityGroup, EntityTypeId, mock::{make_mock_tournament_with_options, MockOption}, domain::{ballot_validation::{BallotConfirmationPolicy, BallotValidationConfig, BallotValidationError, BallotValidationErrors}, debate_backup_ballot::DebateBackupBallot, entity::LoadEntity, tournament::DrawConfig}};
+use sea_orm::{prelude::*, Database, TransactionTrait};
 
 
-use open_tab_app_backend::{actions::UpdateDrawAction, draw_view::{DrawBallot, DrawTeam, DrawAdjudicator, DrawSpeaker}, actions::ActionTrait};
+use open_tab_app_backend::{actions::UpdateDrawAction, draw::{datastructures::{DebateInfo, RoundInfo}, repair::{DrawPosition, DrawRepair, DrawVacancy}, evaluation::{DrawConstructionEvaluationContext, DrawEvaluator, DrawEvaluatorConfig}, flow_optimization::{OptimizationOptions, OptimizationState}}, draw_view::{DrawBallot, DrawTeam, DrawAdjudicator, DrawSpeaker}, actions::{ActionTrait, NewSpeakerSubstitution, ResolveMergeConflictAction, UpdateSpeakerSubstitutionsAction}, undo::{UndoEntry, UndoError, UndoStack}, ballot_confirmation::confirm_submitted_ballots, views::{LoadedView, pending_ballots_view::LoadedPendingBallotsView}};
 
 
 pub async fn set_up_db(with_mock_env: bool) -> Result<DatabaseConnection, anyhow::Error> {
@@ -36,7 +36,8 @@ async fn test_insert_new_empty_ballot() -> Result<(), anyhow::Error> {
             opposition: None,
             non_aligned_speakers: vec![],
             adjudicators: vec![],
-            president: None
+            president: None,
+            trainees: vec![]
         }],
         ..Default::default()
     };
@@ -79,6 +80,7 @@ async fn test_update_ballot_saves_team() -> Result<(), anyhow::Error> {
             non_aligned_speakers: vec![],
             adjudicators: vec![],
             president: None,
+            trainees: vec![],
         }],
         ..Default::default()
     };
@@ -119,6 +121,7 @@ async fn test_update_ballot_saves_adjudicators() -> Result<(), anyhow::Error> {
                 DrawAdjudicator { uuid: Uuid::from_u128(3002), ..Default::default() }.into(),
             ],
             president: Some(DrawAdjudicator { uuid: Uuid::from_u128(3006), ..Default::default()}.into()),
+            trainees: vec![],
         }],
         ..Default::default()
     };
@@ -167,6 +170,7 @@ async fn test_update_ballot_saves_non_aligned() -> Result<(), anyhow::Error> {
             ],
             adjudicators: vec![],
             president: None,
+            trainees: vec![],
         }],
         ..Default::default()
     };
@@ -193,6 +197,7 @@ async fn test_update_ballot_saves_non_aligned() -> Result<(), anyhow::Error> {
             is_opt_out: false,
             position: idx as u8,
             scores: HashMap::new(),
+            trainee_scores: HashMap::new(),
         }}).collect_vec()
     );
     assert_eq!(ballot.president, None);
@@ -207,7 +212,7 @@ async fn test_changing_adjudicator_order_does_not_delete_scores() -> Result<(),
 
     let mut prev_ballot = Ballot::get_many(&db, vec![Uuid::from_u128(421)]).await?.pop().unwrap();
     prev_ballot.adjudicators = vec![3003, 3001, 3002].into_iter().map(Uuid::from_u128).collect_vec();
-    prev_ballot.government.scores.insert(Uuid::from_u128(3003), TeamScore::Aggregate{total: 123});
+    prev_ballot.government.scores.insert(Uuid::from_u128(3003), TeamScore::Aggregate { total: Score::from_points(123) });
     
     prev_ballot.speeches = vec![
         Speech {
@@ -215,7 +220,8 @@ async fn test_changing_adjudicator_order_does_not_delete_scores() -> Result<(),
             role: open_tab_entities::prelude::SpeechRole::Government,
             is_opt_out: false,
             position: 0,
-            scores: HashMap::from_iter(vec![(Uuid::from_u128(3003), SpeakerScore::Aggregate { total: 61 })].into_iter()),
+            scores: HashMap::from_iter(vec![(Uuid::from_u128(3003), SpeakerScore::Aggregate { total: Score::from_points(61) })].into_iter()),
+            trainee_scores: HashMap::new(),
         }
     ];
     prev_ballot.save(&db, false).await?;
@@ -228,6 +234,7 @@ async fn test_changing_adjudicator_order_does_not_delete_scores() -> Result<(),
             non_aligned_speakers: vec![],
             adjudicators: vec![3002, 3001, 3003].into_iter().map(|uuid| DrawAdjudicator { uuid: Uuid::from_u128(uuid), ..Default::default() }.into()).collect_vec(),
             president: None,
+            trainees: vec![],
         }],
         ..Default::default()
     };
@@ -237,8 +244,8 @@ async fn test_changing_adjudicator_order_does_not_delete_scores() -> Result<(),
 
     let ballot = &Ballot::get_many(&db, vec![Uuid::from_u128(421)]).await?[0];
 
-    assert_eq!(ballot.speeches[0].scores.get(&Uuid::from_u128(3003)), Some(&SpeakerScore::Aggregate { total: 61 }));
-    assert_eq!(ballot.government.scores.get(&Uuid::from_u128(3003)), Some(&TeamScore::Aggregate{total: 123}));
+    assert_eq!(ballot.speeches[0].scores.get(&Uuid::from_u128(3003)), Some(&SpeakerScore::Aggregate { total: Score::from_points(61) }));
+    assert_eq!(ballot.government.scores.get(&Uuid::from_u128(3003)), Some(&TeamScore::Aggregate { total: Score::from_points(123) }));
     assert_eq!(ballot.president, None);
 
     Ok(())
@@ -251,7 +258,7 @@ async fn test_delete_adjudicator_with_scores_deletes_both() -> Result<(), anyhow
 
     let mut prev_ballot = Ballot::get_many(&db, vec![Uuid::from_u128(421)]).await?.pop().unwrap();
     prev_ballot.adjudicators = vec![3003, 3001, 3002].into_iter().map(Uuid::from_u128).collect_vec();
-    prev_ballot.government.scores.insert(Uuid::from_u128(3003), TeamScore::Aggregate{total: 123});
+    prev_ballot.government.scores.insert(Uuid::from_u128(3003), TeamScore::Aggregate { total: Score::from_points(123) });
     
     prev_ballot.speeches = vec![
         Speech {
@@ -259,7 +266,8 @@ async fn test_delete_adjudicator_with_scores_deletes_both() -> Result<(), anyhow
             role: open_tab_entities::prelude::SpeechRole::Government,
             position: 0,
             is_opt_out: false,
-            scores: HashMap::new() //HashMap::from_iter(vec![(Uuid::from_u128(3003), SpeakerScore::Aggregate { total: 61 })].into_iter()),
+            scores: HashMap::new(), //HashMap::from_iter(vec![(Uuid::from_u128(3003), SpeakerScore::Aggregate { total: Score::from_points(61) })].into_iter()),
+            trainee_scores: HashMap::new(),
         }
     ];
     prev_ballot.save(&db, false).await?;
@@ -303,6 +311,7 @@ async fn test_change_non_aligned_with_additional() -> Result<(), anyhow::Error>
             position: 0,
             is_opt_out: false,
             scores: HashMap::new(),
+            trainee_scores: HashMap::new(),
         },
         Speech {
             speaker: Some(Uuid::from_u128(2051)),
@@ -310,6 +319,7 @@ async fn test_change_non_aligned_with_additional() -> Result<(), anyhow::Error>
             position: 1,
             is_opt_out: false,
             scores: HashMap::new(),
+            trainee_scores: HashMap::new(),
         }
     ];
     
@@ -327,6 +337,7 @@ async fn test_change_non_aligned_with_additional() -> Result<(), anyhow::Error>
             ],
             adjudicators: vec![],
             president: None,
+            trainees: vec![],
         }],
         ..Default::default()
     };
@@ -360,6 +371,7 @@ async fn test_change_non_aligned_with_fewer() -> Result<(), anyhow::Error> {
             position: 0,
             is_opt_out: false,
             scores: HashMap::new(),
+            trainee_scores: HashMap::new(),
         },
         Speech {
             speaker: Some(Uuid::from_u128(2051)),
@@ -367,6 +379,7 @@ async fn test_change_non_aligned_with_fewer() -> Result<(), anyhow::Error> {
             position: 1,
             is_opt_out: false,
             scores: HashMap::new(),
+            trainee_scores: HashMap::new(),
         },
         Speech {
             speaker: Some(Uuid::from_u128(2070)),
@@ -374,6 +387,7 @@ async fn test_change_non_aligned_with_fewer() -> Result<(), anyhow::Error> {
             position: 2,
             is_opt_out: false,
             scores: HashMap::new(),
+            trainee_scores: HashMap::new(),
         }
     ];
     
@@ -390,6 +404,7 @@ async fn test_change_non_aligned_with_fewer() -> Result<(), anyhow::Error> {
             ],
             adjudicators: vec![],
             president: None,
+            trainees: vec![],
         }],
         ..Default::default()
     };
@@ -408,3 +423,858 @@ async fn test_change_non_aligned_with_fewer() -> Result<(), anyhow::Error> {
 
     Ok(())
 }
+
+fn make_swap_teams_action() -> UpdateDrawAction {
+    UpdateDrawAction {
+        tournament_id: Uuid::from_u128(1),
+        updated_ballots: vec![DrawBallot {
+            uuid: Uuid::from_u128(421),
+            government: Some(DrawTeam {
+                uuid: Uuid::from_u128(1002),
+                ..Default::default()
+            }),
+            opposition: Some(DrawTeam {
+                uuid: Uuid::from_u128(1001),
+                ..Default::default()
+            }),
+            non_aligned_speakers: vec![],
+            adjudicators: vec![],
+            president: None,
+            trainees: vec![],
+        }],
+        ..Default::default()
+    }
+}
+
+#[tokio::test]
+async fn test_undo_and_redo_restore_ballot() -> Result<(), anyhow::Error> {
+    let db = set_up_db(true).await?;
+    let original_ballot = Ballot::get(&db, Uuid::from_u128(421)).await?;
+
+    let changes = make_swap_teams_action().get_changes(&db).await?;
+    let mut undo_stack = UndoStack::new();
+    undo_stack.push(UndoEntry::save_changes(&db, &changes).await?);
+    let changed_ballot = Ballot::get(&db, Uuid::from_u128(421)).await?;
+
+    let undo_changes = undo_stack.undo(&db, changes.tournament_id).await?;
+    assert!(undo_changes.has_changes_for_type(open_tab_entities::EntityTypeId::Ballot));
+    assert_eq!(Ballot::get(&db, Uuid::from_u128(421)).await?, original_ballot);
+    undo_stack.confirm_undo(changes.tournament_id);
+    assert!(!undo_stack.can_undo(changes.tournament_id));
+
+    undo_stack.redo(&db, changes.tournament_id).await?;
+    undo_stack.confirm_redo(changes.tournament_id);
+    assert_eq!(Ballot::get(&db, Uuid::from_u128(421)).await?, changed_ballot);
+    assert!(undo_stack.can_undo(changes.tournament_id));
+    assert!(!undo_stack.can_redo(changes.tournament_id));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_undo_stays_available_if_not_committed() -> Result<(), anyhow::Error> {
+    let db = set_up_db(true).await?;
+    let changes = make_swap_teams_action().get_changes(&db).await?;
+    let mut undo_stack = UndoStack::new();
+    undo_stack.push(UndoEntry::save_changes(&db, &changes).await?);
+    let changed_ballot = Ballot::get(&db, Uuid::from_u128(421)).await?;
+
+    let transaction = db.begin().await?;
+    undo_stack.undo(&transaction, changes.tournament_id).await?;
+    transaction.rollback().await?;
+
+    assert_eq!(Ballot::get(&db, Uuid::from_u128(421)).await?, changed_ballot);
+    assert!(undo_stack.can_undo(changes.tournament_id));
+    assert!(!undo_stack.can_redo(changes.tournament_id));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_undo_fails_after_later_change() -> Result<(), anyhow::Error> {
+    let db = set_up_db(true).await?;
+
+    let changes = make_swap_teams_action().get_changes(&db).await?;
+    let mut undo_stack = UndoStack::new();
+    undo_stack.push(UndoEntry::save_changes(&db, &changes).await?);
+
+    let mut ballot = Ballot::get(&db, Uuid::from_u128(421)).await?;
+    ballot.president = Some(Uuid::from_u128(3000));
+    let mut later_changes = EntityGroup::new(changes.tournament_id);
+    later_changes.add(Entity::Ballot(ballot.clone()));
+    later_changes.save_all_and_log(&db).await?;
+
+    let result = undo_stack.undo(&db, changes.tournament_id).await;
+    let err = result.err().expect("Expected undo to fail");
+    assert!(matches!(err.downcast_ref::<UndoError>(), Some(UndoError::StateChanged)));
+    assert_eq!(Ballot::get(&db, Uuid::from_u128(421)).await?, ballot);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_venue_redraw_respects_accessibility_needs() -> Result<(), anyhow::Error> {
+    let db = set_up_db(true).await?;
+
+    let debates = TournamentDebate::get_all_in_rounds(&db, vec![Uuid::from_u128(100)]).await?.into_iter().next().unwrap();
+    let last_debate = debates.iter().max_by_key(|d| d.index).unwrap().clone();
+    let ballot = Ballot::get(&db, last_debate.ballot_id).await?;
+
+    let mut participant = Participant::get(&db, ballot.adjudicators[0]).await?;
+    participant.requires_accessible_venue = true;
+
+    let mut venues = open_tab_entities::domain::tournament_venue::TournamentVenue::get_all_in_tournament(&db, Uuid::from_u128(1)).await?;
+    venues.sort_by_key(|v| v.name.clone());
+    let mut accessible_venue = venues[0].clone();
+    accessible_venue.is_accessible = true;
+
+    let mut changes = EntityGroup::new(Uuid::from_u128(1));
+    changes.add(Entity::Participant(participant));
+    changes.add(Entity::TournamentVenue(accessible_venue.clone()));
+    changes.save_all(&db).await?;
+
+    let action = open_tab_app_backend::actions::RedrawRoundAction {
+        round_id: Uuid::from_u128(100),
+        mode: open_tab_app_backend::actions::RedrawMode::Venues,
+        seed: None
+    };
+    action.get_changes(&db).await?.save_all(&db).await?;
+
+    let last_debate = TournamentDebate::get(&db, last_debate.uuid).await?;
+    assert_eq!(last_debate.venue_id, Some(accessible_venue.uuid));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_redraw_around_pins_keeps_pinned_positions() -> Result<(), anyhow::Error> {
+    let db = set_up_db(true).await?;
+
+    let debates = TournamentDebate::get_all_in_rounds(&db, vec![Uuid::from_u128(100)]).await?.into_iter().next().unwrap()
+        .into_iter().sorted_by_key(|d| d.index).collect_vec();
+    let ballots = Ballot::get_many(&db, debates.iter().map(|d| d.ballot_id).collect()).await?;
+
+    let mut pinned_team_debate = debates[0].clone();
+    pinned_team_debate.pins = Some(open_tab_entities::domain::debate::DebatePins { government: true, ..Default::default() });
+    let mut pinned_chair_debate = debates[1].clone();
+    pinned_chair_debate.pins = Some(open_tab_entities::domain::debate::DebatePins { chair: true, ..Default::default() });
+
+    let mut changes = EntityGroup::new(Uuid::from_u128(1));
+    changes.add(Entity::TournamentDebate(pinned_team_debate));
+    changes.add(Entity::TournamentDebate(pinned_chair_debate));
+    changes.save_all(&db).await?;
+
+    let action = open_tab_app_backend::actions::RedrawRoundAction {
+        round_id: Uuid::from_u128(100),
+        mode: open_tab_app_backend::actions::RedrawMode::AroundPins,
+        seed: None
+    };
+    action.get_changes(&db).await?.save_all(&db).await?;
+
+    let new_ballots = Ballot::get_many(&db, debates.iter().map(|d| d.ballot_id).collect()).await?;
+    assert_eq!(new_ballots[0].government.team, ballots[0].government.team);
+    assert_eq!(new_ballots[1].adjudicators[0], ballots[1].adjudicators[0]);
+
+    let previous_government_teams = ballots.iter().filter_map(|b| b.government.team).sorted().collect_vec();
+    let new_government_teams = new_ballots.iter().filter_map(|b| b.government.team).sorted().collect_vec();
+    assert_eq!(previous_government_teams, new_government_teams);
+
+    let previous_opposition_teams = ballots.iter().filter_map(|b| b.opposition.team).sorted().collect_vec();
+    let new_opposition_teams = new_ballots.iter().filter_map(|b| b.opposition.team).sorted().collect_vec();
+    assert_eq!(previous_opposition_teams, new_opposition_teams);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_allocation_removes_participants_that_are_no_longer_adjudicators() -> Result<(), anyhow::Error> {
+    let db = set_up_db(true).await?;
+
+    let round = TournamentRound::get(&db, Uuid::from_u128(100)).await?;
+    let speaker = Participant::get_all_in_tournament(&db, Uuid::from_u128(1)).await?.into_iter().find(
+        |p| matches!(p.role, ParticipantRole::Speaker(_))
+    ).unwrap();
+    let debate = DebateInfo {
+        id: Uuid::from_u128(1),
+        government: None,
+        opposition: None,
+        chair: Some(speaker.uuid),
+        wings: vec![],
+        president: None,
+        trainees: vec![],
+        non_aligned_speakers: vec![],
+    };
+
+    let mut state = OptimizationState::load_from_rounds(
+        &db,
+        Uuid::from_u128(1),
+        vec![RoundInfo { id: round.uuid, debates: vec![debate], is_silent: round.is_silent }],
+        OptimizationOptions::from(&DrawConfig::default())
+    ).await?;
+    let evaluation_context = DrawConstructionEvaluationContext::new_from_tournament(&db, Uuid::from_u128(1)).await?;
+    let evaluator = DrawEvaluator::new(DrawEvaluatorConfig::from(&DrawConfig::default()), vec![], &evaluation_context);
+    state.update_state_by_assigning_adjudicators(None, &evaluator);
+
+    let adjudicators = Participant::get_all_adjudicators_in_tournament(&db, Uuid::from_u128(1)).await?.into_iter().map(|p| p.uuid).collect_vec();
+    assert_ne!(state.rounds[0].debates[0].chair, Some(speaker.uuid));
+    assert!(!state.rounds[0].debates[0].wings.is_empty());
+    assert!(state.rounds[0].debates[0].wings.iter().all(|wing| adjudicators.contains(wing)));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_redraw_around_pins_keeps_unpinned_president() -> Result<(), anyhow::Error> {
+    let db = set_up_db(true).await?;
+
+    let debates = TournamentDebate::get_all_in_rounds(&db, vec![Uuid::from_u128(100)]).await?.into_iter().next().unwrap()
+        .into_iter().sorted_by_key(|d| d.index).collect_vec();
+    let mut ballot = Ballot::get(&db, debates[0].ballot_id).await?;
+    let president = ballot.adjudicators.pop().unwrap();
+    ballot.president = Some(president);
+
+    let mut changes = EntityGroup::new(Uuid::from_u128(1));
+    changes.add(Entity::Ballot(ballot.clone()));
+    changes.save_all(&db).await?;
+
+    let action = open_tab_app_backend::actions::RedrawRoundAction {
+        round_id: Uuid::from_u128(100),
+        mode: open_tab_app_backend::actions::RedrawMode::AroundPins,
+        seed: None
+    };
+    action.get_changes(&db).await?.save_all(&db).await?;
+
+    let new_ballots = Ballot::get_many(&db, debates.iter().map(|d| d.ballot_id).collect()).await?;
+    assert_eq!(new_ballots[0].president, Some(president));
+    assert!(new_ballots.iter().all(|b| !b.adjudicators.contains(&president)));
+
+    Ok(())
+}
+
+fn assert_every_position_filled_or_reported(repair: &DrawRepair) {
+    for (debate_index, ballot) in repair.ballots.iter().enumerate() {
+        let is_reported = |position| repair.vacancies.contains(&DrawVacancy { debate_index, position });
+        assert!(ballot.government.is_some() || is_reported(DrawPosition::Government));
+        assert!(ballot.opposition.is_some() || is_reported(DrawPosition::Opposition));
+        for (position, speaker) in ballot.non_aligned_speakers.iter().enumerate() {
+            assert!(speaker.is_some() || is_reported(DrawPosition::NonAligned { position }));
+        }
+        assert!(!ballot.adjudicators.is_empty() || is_reported(DrawPosition::Chair));
+    }
+}
+
+async fn add_late_team(db: &DatabaseConnection, team_id: Uuid, first_member_id: u128) -> Result<(), anyhow::Error> {
+    let mut changes = EntityGroup::new(Uuid::from_u128(1));
+    changes.add(Entity::Team(open_tab_entities::domain::team::Team {
+        uuid: team_id,
+        name: "Late Team".into(),
+        tournament_id: Uuid::from_u128(1),
+        is_swing: false,
+    }));
+    for i in 0..3 {
+        changes.add(Entity::Participant(Participant::new_with_uuid(
+            Uuid::from_u128(first_member_id + i),
+            format!("Late Speaker {}", i),
+            ParticipantRole::Speaker(Speaker { team_id: Some(team_id) }),
+            Uuid::from_u128(1)
+        )));
+    }
+    changes.save_all(db).await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_repair_draw_reports_empty_positions() -> Result<(), anyhow::Error> {
+    let db = set_up_db(true).await?;
+
+    let debates = TournamentDebate::get_all_in_rounds(&db, vec![Uuid::from_u128(100)]).await?.into_iter().next().unwrap()
+        .into_iter().sorted_by_key(|d| d.index).collect_vec();
+    let ballots = Ballot::get_many(&db, debates.iter().map(|d| d.ballot_id).collect()).await?;
+    let withdrawn_teams = vec![ballots[0].government.team.unwrap(), ballots[1].opposition.team.unwrap()];
+    add_late_team(&db, Uuid::from_u128(9000), 9001).await?;
+
+    let proposal = open_tab_app_backend::frontend_queries::query_draw_repair_proposal(
+        &db,
+        Uuid::from_u128(100),
+        withdrawn_teams,
+        vec![Uuid::from_u128(9000)]
+    ).await?;
+    assert!(proposal.unplaced_teams.is_empty());
+    assert_eq!(proposal.vacancies.len(), 1);
+    assert!(proposal.ballots.iter().any(|b| b.government.as_ref().map(|t| t.uuid) == Some(Uuid::from_u128(9000)) || b.opposition.as_ref().map(|t| t.uuid) == Some(Uuid::from_u128(9000))));
+    assert_every_position_filled_or_reported(&proposal);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_repair_draw_reports_unplaced_teams() -> Result<(), anyhow::Error> {
+    let db = set_up_db(true).await?;
+
+    add_late_team(&db, Uuid::from_u128(9000), 9001).await?;
+
+    let proposal = open_tab_app_backend::frontend_queries::query_draw_repair_proposal(
+        &db,
+        Uuid::from_u128(100),
+        vec![],
+        vec![Uuid::from_u128(9000)]
+    ).await?;
+    assert_eq!(proposal.unplaced_teams, vec![Uuid::from_u128(9000)]);
+    assert_eq!(proposal.unplaced.len(), 3);
+    assert!(proposal.changes.is_empty());
+    assert_every_position_filled_or_reported(&proposal);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_repair_draw_replaces_withdrawn_participants() -> Result<(), anyhow::Error> {
+    let db = set_up_db(true).await?;
+
+    let debates = TournamentDebate::get_all_in_rounds(&db, vec![Uuid::from_u128(100)]).await?.into_iter().next().unwrap()
+        .into_iter().sorted_by_key(|d| d.index).collect_vec();
+    let ballots = Ballot::get_many(&db, debates.iter().map(|d| d.ballot_id).collect()).await?;
+
+    let withdrawn_team = ballots[0].government.team.unwrap();
+    let withdrawn_chair = ballots[1].adjudicators[0];
+
+    add_late_team(&db, Uuid::from_u128(9000), 9001).await?;
+    let new_adjudicator = Participant::new_with_uuid(
+        Uuid::from_u128(9100),
+        "Late Adjudicator".into(),
+        ParticipantRole::Adjudicator(Adjudicator { chair_skill: 90, panel_skill: 90, unavailable_rounds: vec![], is_trainee: false }),
+        Uuid::from_u128(1)
+    );
+    let mut changes = EntityGroup::new(Uuid::from_u128(1));
+    changes.add(Entity::Participant(new_adjudicator));
+    changes.save_all(&db).await?;
+
+    let proposal = open_tab_app_backend::frontend_queries::query_draw_repair_proposal(
+        &db,
+        Uuid::from_u128(100),
+        vec![withdrawn_team, withdrawn_chair],
+        vec![Uuid::from_u128(9000), Uuid::from_u128(9100)]
+    ).await?;
+    assert!(proposal.unplaced.is_empty());
+    assert!(proposal.unplaced_teams.is_empty());
+    assert_every_position_filled_or_reported(&proposal);
+    assert!(proposal.changes.iter().any(|c| c.new == Some(Uuid::from_u128(9000))));
+    assert!(proposal.changes.iter().any(|c| c.new == Some(Uuid::from_u128(9100))));
+
+    // The proposal does not change the draw
+    let unchanged_ballots = Ballot::get_many(&db, debates.iter().map(|d| d.ballot_id).collect()).await?;
+    assert_eq!(unchanged_ballots[0].government.team, Some(withdrawn_team));
+
+    let action = open_tab_app_backend::actions::RedrawRoundAction {
+        round_id: Uuid::from_u128(100),
+        mode: open_tab_app_backend::actions::RedrawMode::RepairDraw {
+            withdrawn_participants: vec![withdrawn_team, withdrawn_chair],
+            added_participants: vec![Uuid::from_u128(9000), Uuid::from_u128(9100)]
+        },
+        seed: None
+    };
+    action.get_changes(&db).await?.save_all(&db).await?;
+
+    let new_ballots = Ballot::get_many(&db, debates.iter().map(|d| d.ballot_id).collect()).await?;
+    assert!(new_ballots.iter().all(|b| b.government.team != Some(withdrawn_team) && b.opposition.team != Some(withdrawn_team)));
+    assert!(new_ballots.iter().all(|b| !b.adjudicators.contains(&withdrawn_chair)));
+    assert!(new_ballots.iter().any(|b| b.government.team == Some(Uuid::from_u128(9000))));
+    assert!(new_ballots.iter().any(|b| b.adjudicators.first() == Some(&Uuid::from_u128(9100))));
+
+    // Only the debates affected by the withdrawals, and at most one swap partner each, change
+    let num_changed = ballots.iter().zip(new_ballots.iter()).filter(|(b1, b2)| b1 != b2).count();
+    assert!(num_changed <= 4);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_repair_draw_keeps_pinned_wings() -> Result<(), anyhow::Error> {
+    let db = set_up_db(true).await?;
+
+    let debates = TournamentDebate::get_all_in_rounds(&db, vec![Uuid::from_u128(100)]).await?.into_iter().next().unwrap()
+        .into_iter().sorted_by_key(|d| d.index).collect_vec();
+    let ballot = Ballot::get(&db, debates[0].ballot_id).await?;
+    let withdrawn_chair = ballot.adjudicators[0];
+    let withdrawn_wing = ballot.adjudicators[1];
+    let pinned_wing = ballot.adjudicators[2];
+
+    let mut pinned_debate = debates[0].clone();
+    pinned_debate.pins = Some(open_tab_entities::domain::debate::DebatePins { wings: vec![1], ..Default::default() });
+    let mut changes = EntityGroup::new(Uuid::from_u128(1));
+    changes.add(Entity::TournamentDebate(pinned_debate.clone()));
+    changes.save_all(&db).await?;
+
+    let action = open_tab_app_backend::actions::RedrawRoundAction {
+        round_id: Uuid::from_u128(100),
+        mode: open_tab_app_backend::actions::RedrawMode::RepairDraw {
+            withdrawn_participants: vec![withdrawn_chair, withdrawn_wing],
+            added_participants: vec![]
+        },
+        seed: None
+    };
+    action.get_changes(&db).await?.save_all(&db).await?;
+
+    // The pinned wing is not promoted to chair and its pin follows it to its new index
+    let new_ballot = Ballot::get(&db, debates[0].ballot_id).await?;
+    assert!(new_ballot.adjudicators[0] != pinned_wing);
+    assert_eq!(new_ballot.adjudicators[1], pinned_wing);
+    let new_pins = TournamentDebate::get(&db, pinned_debate.uuid).await?.pins.unwrap();
+    assert_eq!(new_pins.wings, vec![0]);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_repair_draw_promotes_pinned_wing_without_donor() -> Result<(), anyhow::Error> {
+    let db = set_up_db(true).await?;
+
+    let debates = TournamentDebate::get_all_in_rounds(&db, vec![Uuid::from_u128(100)]).await?.into_iter().next().unwrap()
+        .into_iter().sorted_by_key(|d| d.index).collect_vec();
+    let ballots = Ballot::get_many(&db, debates.iter().map(|d| d.ballot_id).collect()).await?;
+    let withdrawn_chair = ballots[0].adjudicators[0];
+    let pinned_wings = ballots[0].adjudicators[1..].to_vec();
+    assert!(pinned_wings.len() >= 2);
+
+    // No panel can give up a wing, since every wing is pinned
+    let mut changes = EntityGroup::new(Uuid::from_u128(1));
+    for (debate, ballot) in debates.iter().zip(ballots.iter()) {
+        let mut pinned_debate = debate.clone();
+        pinned_debate.pins = Some(open_tab_entities::domain::debate::DebatePins { wings: (0..ballot.adjudicators.len().saturating_sub(1)).collect(), ..Default::default() });
+        changes.add(Entity::TournamentDebate(pinned_debate));
+    }
+    changes.save_all(&db).await?;
+
+    let proposal = open_tab_app_backend::frontend_queries::query_draw_repair_proposal(
+        &db,
+        Uuid::from_u128(100),
+        vec![withdrawn_chair],
+        vec![]
+    ).await?;
+    assert!(!proposal.vacancies.contains(&DrawVacancy { debate_index: 0, position: DrawPosition::Chair }));
+
+    let action = open_tab_app_backend::actions::RedrawRoundAction {
+        round_id: Uuid::from_u128(100),
+        mode: open_tab_app_backend::actions::RedrawMode::RepairDraw {
+            withdrawn_participants: vec![withdrawn_chair],
+            added_participants: vec![]
+        },
+        seed: None
+    };
+    action.get_changes(&db).await?.save_all(&db).await?;
+
+    // One of the pinned wings becomes the chair and takes its pin along, the pins of the other wings follow them
+    let new_ballot = Ballot::get(&db, debates[0].ballot_id).await?;
+    assert_eq!(new_ballot.adjudicators.iter().sorted().collect_vec(), pinned_wings.iter().sorted().collect_vec());
+    let new_pins = TournamentDebate::get(&db, debates[0].uuid).await?.pins.unwrap();
+    assert!(new_pins.chair);
+    assert_eq!(new_pins.wings, (0..pinned_wings.len() - 1).collect_vec());
+
+    Ok(())
+}
+
+async fn execute_preliminaries_with_seed(db: &DatabaseConnection, seed: Option<i64>) -> Result<Vec<Ballot>, anyhow::Error> {
+    let node = open_tab_entities::domain::tournament_plan_node::TournamentPlanNode::get_all_in_tournament(db, Uuid::from_u128(1)).await?.into_iter().find(
+        |n| matches!(n.config, open_tab_entities::domain::tournament_plan_node::PlanNodeType::Round { config: open_tab_entities::domain::tournament_plan_node::RoundGroupConfig::Preliminaries { .. }, .. })
+    ).unwrap();
+
+    let action = open_tab_app_backend::actions::ExecutePlanNodeAction {
+        tournament_id: Uuid::from_u128(1),
+        plan_node: node.uuid,
+        seed
+    };
+    action.get_changes(db).await?.save_all(db).await?;
+
+    let round_ids = vec![Uuid::from_u128(100), Uuid::from_u128(101), Uuid::from_u128(102)];
+    let debates = TournamentDebate::get_all_in_rounds(db, round_ids).await?.into_iter().flat_map(
+        |debates| debates.into_iter().sorted_by_key(|d| d.index)
+    ).collect_vec();
+    Ok(Ballot::get_many(db, debates.iter().map(|d| d.ballot_id).collect()).await?)
+}
+
+#[tokio::test]
+async fn test_executing_plan_node_with_same_seed_reproduces_draw() -> Result<(), anyhow::Error> {
+    let db = set_up_db(true).await?;
+
+    let first_ballots = execute_preliminaries_with_seed(&db, Some(42)).await?;
+    let round = TournamentRound::get(&db, Uuid::from_u128(100)).await?;
+    assert_eq!(round.draw_seed, Some(42));
+
+    let second_ballots = execute_preliminaries_with_seed(&db, Some(42)).await?;
+    assert_eq!(first_ballots, second_ballots);
+
+    Ok(())
+}
+
+async fn execute_break_with_seed(db: &DatabaseConnection, seed: Option<i64>) -> Result<open_tab_entities::domain::tournament_break::TournamentBreak, anyhow::Error> {
+    let node = open_tab_entities::domain::tournament_plan_node::TournamentPlanNode::get_all_in_tournament(db, Uuid::from_u128(1)).await?.into_iter().find(
+        |n| matches!(n.config, open_tab_entities::domain::tournament_plan_node::PlanNodeType::Break { .. })
+    ).unwrap();
+
+    let action = open_tab_app_backend::actions::ExecutePlanNodeAction {
+        tournament_id: Uuid::from_u128(1),
+        plan_node: node.uuid,
+        seed
+    };
+    let changes = action.get_changes(db).await?;
+    Ok(changes.as_group_map().tournament_breaks.pop().unwrap())
+}
+
+#[tokio::test]
+async fn test_executing_break_with_same_seed_reproduces_break() -> Result<(), anyhow::Error> {
+    let db = set_up_db(true).await?;
+    // Without scores, every team and speaker is tied
+    let ballots = execute_preliminaries_with_seed(&db, Some(1)).await?;
+    assert!(ballots.iter().all(|b| b.government_total().is_none()));
+
+    let first_break = execute_break_with_seed(&db, Some(42)).await?;
+    let second_break = execute_break_with_seed(&db, Some(42)).await?;
+    assert_eq!(first_break.breaking_teams, second_break.breaking_teams);
+    assert_eq!(first_break.breaking_speakers, second_break.breaking_speakers);
+    assert_eq!(first_break.draw_seed, Some(42));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_executing_break_without_seed_stores_chosen_seed() -> Result<(), anyhow::Error> {
+    let db = set_up_db(true).await?;
+    execute_preliminaries_with_seed(&db, Some(1)).await?;
+
+    let first_break = execute_break_with_seed(&db, None).await?;
+    let mut changes = EntityGroup::new(Uuid::from_u128(1));
+    changes.add(Entity::TournamentBreak(first_break.clone()));
+    changes.save_all(&db).await?;
+    let seed = open_tab_entities::domain::tournament_break::TournamentBreak::get(&db, first_break.uuid).await?.draw_seed;
+    assert!(seed.is_some());
+
+    let second_break = execute_break_with_seed(&db, seed).await?;
+    assert_eq!(first_break.breaking_teams, second_break.breaking_teams);
+    assert_eq!(first_break.breaking_speakers, second_break.breaking_speakers);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_executing_plan_node_keeps_pinned_positions() -> Result<(), anyhow::Error> {
+    let db = set_up_db(true).await?;
+    execute_preliminaries_with_seed(&db, Some(1)).await?;
+
+    let debates = TournamentDebate::get_all_in_rounds(&db, vec![Uuid::from_u128(100)]).await?.into_iter().next().unwrap()
+        .into_iter().sorted_by_key(|d| d.index).collect_vec();
+    let ballots = Ballot::get_many(&db, debates.iter().map(|d| d.ballot_id).collect()).await?;
+
+    let mut pinned_debate = debates[1].clone();
+    pinned_debate.pins = Some(open_tab_entities::domain::debate::DebatePins { opposition: true, chair: true, ..Default::default() });
+    let mut changes = EntityGroup::new(Uuid::from_u128(1));
+    changes.add(Entity::TournamentDebate(pinned_debate.clone()));
+    changes.save_all(&db).await?;
+
+    execute_preliminaries_with_seed(&db, Some(2)).await?;
+
+    let new_ballot = Ballot::get(&db, pinned_debate.ballot_id).await?;
+    assert_eq!(new_ballot.opposition.team, ballots[1].opposition.team);
+    assert_eq!(new_ballot.adjudicators.first(), ballots[1].adjudicators.first());
+    assert_eq!(TournamentDebate::get(&db, pinned_debate.uuid).await?.pins, pinned_debate.pins);
+
+    let new_ballots = Ballot::get_many(&db, debates.iter().map(|d| d.ballot_id).collect()).await?;
+    let teams = new_ballots.iter().flat_map(|b| b.government.team.into_iter().chain(b.opposition.team)).collect_vec();
+    assert_eq!(teams.iter().unique().count(), teams.len());
+    let adjudicators = new_ballots.iter().flat_map(|b| b.adjudicators.iter()).collect_vec();
+    assert_eq!(adjudicators.iter().unique().count(), adjudicators.len());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_executing_plan_node_without_seed_stores_chosen_seed() -> Result<(), anyhow::Error> {
+    let db = set_up_db(true).await?;
+
+    let first_ballots = execute_preliminaries_with_seed(&db, None).await?;
+    let seed = TournamentRound::get(&db, Uuid::from_u128(101)).await?.draw_seed;
+    assert!(seed.is_some());
+
+    let second_ballots = execute_preliminaries_with_seed(&db, seed).await?;
+    assert_eq!(first_ballots, second_ballots);
+
+    Ok(())
+}
+
+async fn execute_preliminaries_with_draw_config(draw_config: DrawConfig) -> Result<Vec<Ballot>, anyhow::Error> {
+    let db = set_up_db(true).await?;
+
+    let action = open_tab_app_backend::actions::UpdateDrawConfigAction {
+        tournament_id: Uuid::from_u128(1),
+        draw_config: Some(draw_config.clone()),
+    };
+    action.get_changes(&db).await?.save_all(&db).await?;
+    assert_eq!(Tournament::get(&db, Uuid::from_u128(1)).await?.draw_config, Some(draw_config));
+
+    execute_preliminaries_with_seed(&db, Some(42)).await
+}
+
+fn count_repeated_team_meetings(ballots: &[Ballot]) -> usize {
+    ballots.iter().filter_map(|b| match (b.government.team, b.opposition.team) {
+        (Some(gov), Some(opp)) => Some(if gov < opp { (gov, opp) } else { (opp, gov) }),
+        _ => None
+    }).counts().values().map(|count| count - 1).sum()
+}
+
+#[tokio::test]
+async fn test_draw_config_changes_allocation() -> Result<(), anyhow::Error> {
+    let ignore_clashes = DrawConfig {
+        adj_adj_clash_factor: 0.0.into(),
+        adj_team_clash_factor: 0.0.into(),
+        adj_speaker_clash_factor: 0.0.into(),
+        team_team_clash_factor: 0.0.into(),
+        team_speaker_clash_factor: 0.0.into(),
+        speaker_speaker_clash_factor: 0.0.into(),
+        ..Default::default()
+    };
+    let avoid_repeats = DrawConfig {
+        team_team_clash_factor: 1.0.into(),
+        team_team_repeat_clash_severity: 1000,
+        ..Default::default()
+    };
+
+    let ignoring_ballots = execute_preliminaries_with_draw_config(ignore_clashes).await?;
+    let avoiding_ballots = execute_preliminaries_with_draw_config(avoid_repeats).await?;
+
+    assert_ne!(ignoring_ballots, avoiding_ballots);
+    assert_eq!(count_repeated_team_meetings(&avoiding_ballots), 0);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_trainees_are_allocated_without_vote() -> Result<(), anyhow::Error> {
+    let db = set_up_db(true).await?;
+
+    let adjudicators = Participant::get_all_adjudicators_in_tournament(&db, Uuid::from_u128(1)).await?;
+    let mut changes = EntityGroup::new(Uuid::from_u128(1));
+    let trainees = adjudicators.into_iter().sorted_by_key(|a| a.uuid).take(3).map(|mut adj| {
+        if let ParticipantRole::Adjudicator(info) = &mut adj.role {
+            info.is_trainee = true;
+        }
+        let uuid = adj.uuid;
+        changes.add(Entity::Participant(adj));
+        uuid
+    }).collect_vec();
+    changes.save_all(&db).await?;
+
+    let ballots = execute_preliminaries_with_seed(&db, Some(42)).await?;
+
+    for ballot in ballots.iter() {
+        assert!(ballot.adjudicators.iter().chain(ballot.president.iter()).all(|a| !trainees.contains(a)));
+        assert!(ballot.trainees.len() <= 1);
+        assert!(ballot.trainees.iter().all(|t| trainees.contains(t)));
+    }
+    assert_eq!(ballots.iter().map(|b| b.trainees.len()).sum::<usize>(), 3 * trainees.len());
+
+    Ok(())
+}
+
+async fn submit_scores(db: &DatabaseConnection, score: i16) -> Result<EntityGroup, anyhow::Error> {
+    let mut ballot = Ballot::get(db, Uuid::from_u128(400)).await?;
+    for speech in ballot.speeches.iter_mut() {
+        speech.scores = ballot.adjudicators.iter().map(|adj| (*adj, SpeakerScore::new_aggregate(score.into()))).collect();
+    }
+    let info = open_tab_entities::info::TournamentParticipantsInfo::load(db, Uuid::from_u128(1)).await?;
+
+    let action = open_tab_app_backend::actions::UpdateScoresAction {
+        debate_id: Uuid::from_u128(200),
+        update: open_tab_app_backend::actions::ScoreUpdate::NewBallot(
+            open_tab_entities::derived_models::DisplayBallot::from_ballot_and_info(ballot, &info)
+        ),
+    };
+    action.get_changes(db).await
+}
+
+#[tokio::test]
+async fn test_update_scores_rejects_invalid_ballots() -> Result<(), anyhow::Error> {
+    let db = set_up_db(true).await?;
+
+    assert!(submit_scores(&db, 200).await.is_ok());
+
+    let action = open_tab_app_backend::actions::UpdateBallotValidationConfigAction {
+        tournament_id: Uuid::from_u128(1),
+        ballot_validation_config: Some(BallotValidationConfig {
+            min_speech_score: Some(Score::from_points(60)),
+            max_speech_score: Some(Score::from_points(80)),
+            ..Default::default()
+        }),
+    };
+    action.get_changes(&db).await?.save_all(&db).await?;
+
+    let Err(err) = submit_scores(&db, 200).await else {
+        panic!("Expected the ballot to be rejected");
+    };
+    let validation_errors = err.downcast_ref::<BallotValidationErrors>().expect("Expected validation errors");
+    // 9 speeches scored by 3 adjudicators each
+    assert_eq!(validation_errors.errors.len(), 27);
+    assert!(validation_errors.errors.iter().all(|e| matches!(e, BallotValidationError::ScoreOutOfRange { score, .. } if *score == Score::from_points(200))));
+
+    assert!(submit_scores(&db, 75).await.is_ok());
+
+    Ok(())
+}
+
+async fn submit_backup_ballot(db: &DatabaseConnection, author: Uuid, score: i16, minute: u32) -> Result<EntityGroup, anyhow::Error> {
+    let mut ballot = Ballot::get(db, Uuid::from_u128(400)).await?;
+    ballot.uuid = Uuid::new_v4();
+    for speech in ballot.speeches.iter_mut() {
+        speech.scores = ballot.adjudicators.iter().map(|adj| (*adj, SpeakerScore::new_aggregate(score.into()))).collect();
+    }
+    let backup_ballot = DebateBackupBallot {
+        uuid: Uuid::new_v4(),
+        debate_id: Uuid::from_u128(200),
+        ballot_id: ballot.uuid,
+        timestamp: chrono::NaiveDate::from_ymd_opt(2024, 1, 1).unwrap().and_hms_opt(12, minute, 0).unwrap(),
+        was_seen: false,
+        author_participant_id: Some(author),
+    };
+
+    let group = EntityGroup::new_from_entities(Uuid::from_u128(1), vec![Entity::Ballot(ballot), Entity::DebateBackupBallot(backup_ballot)]);
+    group.save_all(db).await?;
+    Ok(group)
+}
+
+async fn set_up_confirmation_policy(policy: BallotConfirmationPolicy) -> Result<(DatabaseConnection, Vec<Uuid>), anyhow::Error> {
+    let db = set_up_db(true).await?;
+    let action = open_tab_app_backend::actions::UpdateBallotValidationConfigAction {
+        tournament_id: Uuid::from_u128(1),
+        ballot_validation_config: Some(BallotValidationConfig {
+            confirmation_policy: policy,
+            ..Default::default()
+        }),
+    };
+    action.get_changes(&db).await?.save_all(&db).await?;
+    let adjudicators = Ballot::get(&db, Uuid::from_u128(400)).await?.adjudicators;
+    Ok((db, adjudicators))
+}
+
+#[tokio::test]
+async fn test_matching_submissions_confirm_debate() -> Result<(), anyhow::Error> {
+    let (db, adjudicators) = set_up_confirmation_policy(BallotConfirmationPolicy::MatchingSubmissions).await?;
+
+    let first = submit_backup_ballot(&db, adjudicators[1], 75, 0).await?;
+    assert!(confirm_submitted_ballots(&first, &db).await?.is_none());
+
+    // A second submission by the same participant is not independent
+    let repeated = submit_backup_ballot(&db, adjudicators[1], 75, 1).await?;
+    assert!(confirm_submitted_ballots(&repeated, &db).await?.is_none());
+
+    let second = submit_backup_ballot(&db, adjudicators[2], 75, 2).await?;
+    let changes = confirm_submitted_ballots(&second, &db).await?.expect("Expected matching submissions to be confirmed");
+    changes.save_all(&db).await?;
+
+    let debate = TournamentDebate::get(&db, Uuid::from_u128(200)).await?;
+    assert!(debate.is_complete);
+    assert_eq!(debate.ballot_id, first.as_group_map().ballots[0].uuid);
+    assert_eq!(Ballot::get(&db, debate.ballot_id).await?.speeches[0].speaker_score(), Some(75.0));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_disagreeing_submissions_are_shown_as_pending() -> Result<(), anyhow::Error> {
+    let (db, adjudicators) = set_up_confirmation_policy(BallotConfirmationPolicy::ChairOrMatchingSubmissions).await?;
+
+    let first = submit_backup_ballot(&db, adjudicators[1], 75, 0).await?;
+    assert!(confirm_submitted_ballots(&first, &db).await?.is_none());
+    let second = submit_backup_ballot(&db, adjudicators[2], 76, 1).await?;
+    assert!(confirm_submitted_ballots(&second, &db).await?.is_none());
+
+    let view = LoadedPendingBallotsView::load(&db, Uuid::from_u128(1)).await?;
+    let view : serde_json::Value = serde_json::from_str(&view.view_string().await?)?;
+    let pending_debates = view["pending_debates"].as_array().unwrap();
+    assert_eq!(pending_debates.len(), 1);
+    assert_eq!(pending_debates[0]["submissions"].as_array().unwrap().len(), 2);
+    // Every speech score of each adjudicator differs
+    let num_scores = Ballot::get(&db, Uuid::from_u128(400)).await?.speeches.len() * adjudicators.len();
+    assert_eq!(pending_debates[0]["differences"].as_array().unwrap().len(), num_scores);
+    assert!(adjudicators.iter().all(|adj| view["names"].get(adj.to_string()).is_some()));
+
+    // The chair's submission is accepted without a second submission
+    let chair = submit_backup_ballot(&db, adjudicators[0], 77, 2).await?;
+    let changes = confirm_submitted_ballots(&chair, &db).await?.expect("Expected chair submission to be confirmed");
+    changes.save_all(&db).await?;
+    assert_eq!(TournamentDebate::get(&db, Uuid::from_u128(200)).await?.ballot_id, chair.as_group_map().ballots[0].uuid);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_resolve_merge_conflict_uses_remote_value() -> Result<(), anyhow::Error> {
+    let db = set_up_db(true).await?;
+    let participant = Participant::get_all_in_tournament(&db, Uuid::from_u128(1)).await?.into_iter().next().unwrap();
+
+    let action = ResolveMergeConflictAction {
+        tournament_id: Uuid::from_u128(1),
+        entity_type: EntityTypeId::Participant,
+        entity_uuid: participant.uuid,
+        path: "/Participant/name".into(),
+        value: Some(serde_json::json!("Remote Name")),
+    };
+    action.get_changes(&db).await?.save_all(&db).await?;
+
+    let resolved = Participant::get(&db, participant.uuid).await?;
+    assert_eq!(resolved.name, "Remote Name");
+    assert_eq!(resolved.role, participant.role);
+
+    let action = ResolveMergeConflictAction {
+        tournament_id: Uuid::from_u128(1),
+        entity_type: EntityTypeId::Participant,
+        entity_uuid: participant.uuid,
+        path: "".into(),
+        value: None,
+    };
+    action.get_changes(&db).await?.save_all(&db).await?;
+    assert!(Participant::try_get(&db, participant.uuid).await?.is_none());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_resolve_merge_conflict_rejects_other_tournaments() -> Result<(), anyhow::Error> {
+    let db = set_up_db(true).await?;
+    let participant = Participant::get_all_in_tournament(&db, Uuid::from_u128(1)).await?.into_iter().next().unwrap();
+
+    let action = ResolveMergeConflictAction {
+        tournament_id: Uuid::from_u128(2),
+        entity_type: EntityTypeId::Participant,
+        entity_uuid: participant.uuid,
+        path: "/Participant/name".into(),
+        value: Some(serde_json::json!("Remote Name")),
+    };
+    assert!(action.get_changes(&db).await.is_err());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_speaker_substitutions_reject_other_tournaments() -> Result<(), anyhow::Error> {
+    let db = set_up_db(true).await?;
+    let round = TournamentRound::get_all_in_tournament(&db, Uuid::from_u128(1)).await?.into_iter().next().unwrap();
+    let team = Team::get_all_in_tournament(&db, Uuid::from_u128(1)).await?.into_iter().next().unwrap();
+    let speaker = Participant::get_all_in_tournament(&db, Uuid::from_u128(1)).await?.into_iter().find(
+        |p| matches!(p.role, ParticipantRole::Speaker(_))
+    ).unwrap();
+
+    let action = |tournament_id| UpdateSpeakerSubstitutionsAction {
+        tournament_id,
+        added_substitutions: vec![NewSpeakerSubstitution {
+            round_id: round.uuid,
+            team_id: team.uuid,
+            speaker_id: speaker.uuid,
+            replaced_speaker_id: None,
+        }],
+        deleted_substitutions: vec![],
+    };
+    assert!(action(Uuid::from_u128(2)).get_changes(&db).await.is_err());
+
+    let changes = action(Uuid::from_u128(1)).get_changes(&db).await?;
+    assert_eq!(changes.as_group_map().speaker_substitutions.len(), 1);
+
+    Ok(())
+}
@@ -6,7 +6,8 @@ use open_tab_entities::{prelude::*, EntityGroup, Entity, mock::{make_mock_tourna
 use sea_orm::{prelude::*, Database, Statement, TransactionTrait};
 
 
-use open_tab_app_backend::{views::LoadedView, draw_view::LoadedDrawView};
+use open_tab_app_backend::{views::{LoadedView, draw_cost_report_view::DrawCostReportView}, draw_view::LoadedDrawView, draw::repair::DrawPosition};
+use open_tab_entities::domain::entity::LoadEntity;
 
 
 pub async fn set_up_db(with_mock_env: bool) -> Result<DatabaseConnection, anyhow::Error> {
@@ -55,3 +56,34 @@ async fn test_view_updates_when_ballot_updates() -> Result<(), anyhow::Error> {
 
     Ok(())
 }
+
+#[tokio::test]
+async fn test_draw_cost_report_explains_every_position() -> Result<(), anyhow::Error> {
+    let db = set_up_db(true).await?;
+
+    let debates = TournamentDebate::get_all_in_rounds(&db, vec![Uuid::from_u128(100)]).await?.into_iter().next().unwrap();
+    let report = DrawCostReportView::load(&db, Uuid::from_u128(100)).await?;
+    assert_eq!(report.debates.len(), debates.len());
+
+    for debate in debates {
+        let ballot = Ballot::get(&db, debate.ballot_id).await?;
+        let debate_report = &report.debates[debate.index as usize];
+
+        let government = debate_report.positions.iter().find(|p| p.position == DrawPosition::Government).unwrap();
+        assert_eq!(Some(government.chosen.participant_id), ballot.government.team);
+        assert!(report.names.contains_key(&government.chosen.participant_id));
+
+        let chair = debate_report.positions.iter().find(|p| p.position == DrawPosition::Chair).unwrap();
+        assert_eq!(Some(&chair.chosen.participant_id), ballot.adjudicators.first());
+        assert!(chair.chosen.adjudicator_cost.is_some() || chair.chosen.total.is_none());
+
+        for position in debate_report.positions.iter() {
+            assert!(position.alternatives.len() <= 3);
+            assert!(position.alternatives.iter().all(|a| a.participant_id != position.chosen.participant_id));
+            let feasible_totals = position.alternatives.iter().filter_map(|a| a.total).collect::<Vec<_>>();
+            assert!(feasible_totals.windows(2).all(|w| w[0] <= w[1]), "Alternatives must be sorted by cost");
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,155 @@
+use std::collections::HashMap;
+
+use itertools::Itertools;
+use migration::MigratorTrait;
+use open_tab_entities::{prelude::*, mock::{make_mock_tournament_with_options, MockOption}};
+use sea_orm::{prelude::*, Database};
+
+use open_tab_app_backend::{draw::{evaluation::DrawConstructionEvaluationContext, preliminary::{DrawTeamInfo, RoundGenerationContext}, seed::draw_rng, tab_draw::compute_team_position_history, PreliminariesDrawMode, PreliminaryRoundGenerator}, draw_view::DrawBallot};
+
+
+async fn set_up_db() -> Result<DatabaseConnection, anyhow::Error> {
+    let db = Database::connect("sqlite::memory:").await?;
+    migration::Migrator::up(&db, None).await.unwrap();
+
+    let entities = make_mock_tournament_with_options(MockOption {deterministic_uuids: true, num_teams: 12, num_adjudicators: 12, draw_debates: false, ..Default::default()});
+    entities.save_all(&db).await?;
+    Ok(db)
+}
+
+fn make_context(institution_of_team: impl Fn(u128) -> u128, tab_rank_of_team: impl Fn(u128) -> Option<u32>) -> RoundGenerationContext {
+    RoundGenerationContext {
+        teams: (0..12).map(|idx| DrawTeamInfo {
+            uuid: Uuid::from_u128(1000 + idx),
+            member_ids: (0..3).map(|i| Uuid::from_u128(2000 + idx * 10 + i)).collect(),
+            institution_ids: vec![Uuid::from_u128(institution_of_team(idx))],
+            tab_rank: tab_rank_of_team(idx),
+            is_swing: false,
+        }).collect(),
+        speakers: vec![],
+        adjudicators: vec![],
+    }
+}
+
+async fn generate_draw(draw_mode: PreliminariesDrawMode, context: &RoundGenerationContext) -> Result<Vec<Vec<DrawBallot>>, anyhow::Error> {
+    let db = set_up_db().await?;
+    let mut evaluation_context = DrawConstructionEvaluationContext::new_from_tournament(&db, Uuid::from_u128(1)).await?;
+
+    let rounds = (0..3).map(|idx| TournamentRound {
+        uuid: Uuid::from_u128(10 + idx),
+        tournament_id: Uuid::from_u128(1),
+        index: idx as u64,
+        ..Default::default()
+    }).collect_vec();
+
+    let generator = PreliminaryRoundGenerator {
+        draw_mode,
+        ..Default::default()
+    };
+
+    Ok(generator.generate_draw_for_rounds(context, rounds.iter().collect(), vec![], &mut evaluation_context, &mut draw_rng(0))?)
+}
+
+fn assert_draw_has_correct_statistics(round_ballots: &Vec<Vec<DrawBallot>>, context: &RoundGenerationContext) {
+    assert!(round_ballots.iter().all(|ballots| ballots.len() == 4));
+    for ballot in round_ballots.iter().flatten() {
+        assert_eq!(ballot.non_aligned_speakers.iter().flatten().count(), 3);
+    }
+
+    let team_members = context.teams.iter().map(|t| (t.uuid, t.member_ids.clone())).collect::<HashMap<_, _>>();
+    let stats = compute_team_position_history(round_ballots, &team_members);
+    assert_eq!(stats.len(), 12, "All teams should have a statistics entry");
+
+    for team_stats in stats.values() {
+        assert!(team_stats.iter().all(|p| p.len() == 1), "Team must have exactly one role per round");
+        assert_eq!(team_stats.iter().unique().count(), 3, "Team must see all three roles");
+    }
+}
+
+#[tokio::test]
+async fn test_random_draw_has_correct_statistics() -> Result<(), anyhow::Error> {
+    let context = make_context(|idx| 500 + idx, |_| None);
+    let ballots = generate_draw(PreliminariesDrawMode::Random, &context).await?;
+    assert_draw_has_correct_statistics(&ballots, &context);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_avoid_clashes_draw_has_correct_statistics() -> Result<(), anyhow::Error> {
+    let context = make_context(|idx| 500 + idx, |_| None);
+    let ballots = generate_draw(PreliminariesDrawMode::AvoidClashes, &context).await?;
+    assert_draw_has_correct_statistics(&ballots, &context);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_power_paired_draw_has_correct_statistics() -> Result<(), anyhow::Error> {
+    let context = make_context(|idx| 500 + idx, |idx| Some(idx as u32));
+    let ballots = generate_draw(PreliminariesDrawMode::PowerPaired, &context).await?;
+    assert_draw_has_correct_statistics(&ballots, &context);
+
+    let rank_of = |team_id: Uuid| (team_id.as_u128() - 1000) as u32;
+    for ballot in ballots.iter().flatten() {
+        let gov_rank = rank_of(ballot.government.as_ref().unwrap().uuid);
+        let opp_rank = rank_of(ballot.opposition.as_ref().unwrap().uuid);
+        assert_eq!(gov_rank / 3, opp_rank / 3, "Teams must face teams of similar rank");
+    }
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_power_paired_draw_without_tab_has_correct_statistics() -> Result<(), anyhow::Error> {
+    let context = make_context(|idx| 500 + idx, |_| None);
+    let ballots = generate_draw(PreliminariesDrawMode::PowerPaired, &context).await?;
+    assert_draw_has_correct_statistics(&ballots, &context);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_institution_seeded_draw_has_correct_statistics() -> Result<(), anyhow::Error> {
+    let institution_of_team = |idx| 600 + idx % 6;
+    let context = make_context(institution_of_team, |_| None);
+    let ballots = generate_draw(PreliminariesDrawMode::InstitutionSeeded, &context).await?;
+    assert_draw_has_correct_statistics(&ballots, &context);
+
+    let institution_of = |team_id: Uuid| institution_of_team(team_id.as_u128() - 1000);
+    let institution_of_speaker = |speaker_id: Uuid| institution_of_team((speaker_id.as_u128() - 2000) / 10);
+    for ballot in ballots.iter().flatten() {
+        let gov_institution = institution_of(ballot.government.as_ref().unwrap().uuid);
+        let opp_institution = institution_of(ballot.opposition.as_ref().unwrap().uuid);
+        assert_ne!(gov_institution, opp_institution);
+        for speaker in ballot.non_aligned_speakers.iter().flatten() {
+            assert_ne!(institution_of_speaker(speaker.uuid), gov_institution);
+            assert_ne!(institution_of_speaker(speaker.uuid), opp_institution);
+        }
+    }
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_incomplete_swing_team_leaves_speeches_empty() -> Result<(), anyhow::Error> {
+    let mut context = make_context(|idx| 500 + idx, |_| None);
+    let swing_team = context.teams.last_mut().unwrap();
+    swing_team.is_swing = true;
+    swing_team.member_ids.truncate(2);
+
+    let ballots = generate_draw(PreliminariesDrawMode::AvoidClashes, &context).await?;
+    let num_non_aligned_speeches = ballots.iter().flatten().map(|b| b.non_aligned_speakers.len()).sum::<usize>();
+    let num_non_aligned_speakers = ballots.iter().flatten().map(|b| b.non_aligned_speakers.iter().flatten().count()).sum::<usize>();
+    assert_eq!(num_non_aligned_speeches - num_non_aligned_speakers, 1);
+
+    context.teams[0].member_ids.truncate(2);
+    assert!(generate_draw(PreliminariesDrawMode::AvoidClashes, &context).await.is_err());
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_empty_swing_team_is_rejected() -> Result<(), anyhow::Error> {
+    let mut context = make_context(|idx| 500 + idx, |_| None);
+    let swing_team = context.teams.last_mut().unwrap();
+    swing_team.is_swing = true;
+    swing_team.member_ids.clear();
+
+    assert!(generate_draw(PreliminariesDrawMode::AvoidClashes, &context).await.is_err());
+    Ok(())
+}
@@ -2,7 +2,7 @@
 
 
 use migration::MigratorTrait;
-use open_tab_entities::{prelude::*, Entity, mock::{make_mock_tournament_with_options, MockOption}, domain::entity::LoadEntity};
+use open_tab_entities::{prelude::*, Entity, mock::{make_mock_tournament_with_options, MockOption}, domain::{entity::LoadEntity, speaker_substitution::SpeakerSubstitution}};
 use sea_orm::{prelude::*, Database, Statement};
 
 use open_tab_app_backend::views::tab_view::LoadedTabView;
@@ -27,18 +27,18 @@ pub async fn set_up_db(with_mock_env: bool) -> Result<DatabaseConnection, anyhow
             government: BallotTeam {
                 team: Some(Uuid::from_u128(1000)),
                 scores: vec![
-                    (Uuid::from_u128(3000), TeamScore::Aggregate { total: 120 }),
-                    (Uuid::from_u128(3001), TeamScore::Aggregate { total: 20 }),
-                    (Uuid::from_u128(3002), TeamScore::Aggregate { total: 100 }),
+                    (Uuid::from_u128(3000), TeamScore::Aggregate { total: Score::from_points(120) }),
+                    (Uuid::from_u128(3001), TeamScore::Aggregate { total: Score::from_points(20) }),
+                    (Uuid::from_u128(3002), TeamScore::Aggregate { total: Score::from_points(100) }),
                 ].into_iter().collect(),
                 ..Default::default()
             },
             opposition: BallotTeam {
                 team: Some(Uuid::from_u128(1001)),
                 scores: vec![
-                    (Uuid::from_u128(3000), TeamScore::Aggregate { total: 100 }),
-                    (Uuid::from_u128(3001), TeamScore::Aggregate { total: 100 }),
-                    (Uuid::from_u128(3002), TeamScore::Aggregate { total: 100 }),
+                    (Uuid::from_u128(3000), TeamScore::Aggregate { total: Score::from_points(100) }),
+                    (Uuid::from_u128(3001), TeamScore::Aggregate { total: Score::from_points(100) }),
+                    (Uuid::from_u128(3002), TeamScore::Aggregate { total: Score::from_points(100) }),
                 ].into_iter().collect(),
                 ..Default::default()
             },
@@ -49,10 +49,11 @@ pub async fn set_up_db(with_mock_env: bool) -> Result<DatabaseConnection, anyhow
                     is_opt_out: false,
                     position: 0,
                     scores: vec![
-                        (Uuid::from_u128(3000), SpeakerScore::Aggregate { total: 53 }),
-                        (Uuid::from_u128(3001), SpeakerScore::Aggregate { total: 60 }),
-                        (Uuid::from_u128(3002), SpeakerScore::Aggregate { total: 70 }),
-                    ].into_iter().collect()
+                        (Uuid::from_u128(3000), SpeakerScore::Aggregate { total: Score::from_points(53) }),
+                        (Uuid::from_u128(3001), SpeakerScore::Aggregate { total: Score::from_points(60) }),
+                        (Uuid::from_u128(3002), SpeakerScore::Aggregate { total: Score::from_points(70) }),
+                    ].into_iter().collect(),
+                    trainee_scores: Default::default()
                 },
                 Speech {
                     speaker: Some(Uuid::from_u128(2010)),
@@ -60,10 +61,11 @@ pub async fn set_up_db(with_mock_env: bool) -> Result<DatabaseConnection, anyhow
                     is_opt_out: false,
                     position: 0,
                     scores: vec![
-                        (Uuid::from_u128(3000), SpeakerScore::Aggregate { total: 50 }),
-                        (Uuid::from_u128(3001), SpeakerScore::Aggregate { total: 50 }),
-                        (Uuid::from_u128(3002), SpeakerScore::Aggregate { total: 50 }),
-                    ].into_iter().collect()
+                        (Uuid::from_u128(3000), SpeakerScore::Aggregate { total: Score::from_points(50) }),
+                        (Uuid::from_u128(3001), SpeakerScore::Aggregate { total: Score::from_points(50) }),
+                        (Uuid::from_u128(3002), SpeakerScore::Aggregate { total: Score::from_points(50) }),
+                    ].into_iter().collect(),
+                    trainee_scores: Default::default()
                 },
                 Speech {
                     speaker: Some(Uuid::from_u128(2001)),
@@ -71,10 +73,11 @@ pub async fn set_up_db(with_mock_env: bool) -> Result<DatabaseConnection, anyhow
                     position: 1,
                     is_opt_out: false,
                     scores: vec![
-                        (Uuid::from_u128(3000), SpeakerScore::Aggregate { total: 20 }),
-                        (Uuid::from_u128(3001), SpeakerScore::Aggregate { total: 21 }),
-                        (Uuid::from_u128(3002), SpeakerScore::Aggregate { total: 20 }),
-                    ].into_iter().collect()
+                        (Uuid::from_u128(3000), SpeakerScore::Aggregate { total: Score::from_points(20) }),
+                        (Uuid::from_u128(3001), SpeakerScore::Aggregate { total: Score::from_points(21) }),
+                        (Uuid::from_u128(3002), SpeakerScore::Aggregate { total: Score::from_points(20) }),
+                    ].into_iter().collect(),
+                    trainee_scores: Default::default()
                 },
                 Speech {
                     speaker: Some(Uuid::from_u128(2011)),
@@ -82,10 +85,11 @@ pub async fn set_up_db(with_mock_env: bool) -> Result<DatabaseConnection, anyhow
                     position: 1,
                     is_opt_out: false,
                     scores: vec![
-                        (Uuid::from_u128(3000), SpeakerScore::Aggregate { total: 50 }),
-                        (Uuid::from_u128(3001), SpeakerScore::Aggregate { total: 50 }),
-                        (Uuid::from_u128(3002), SpeakerScore::Aggregate { total: 50 }),
-                    ].into_iter().collect()
+                        (Uuid::from_u128(3000), SpeakerScore::Aggregate { total: Score::from_points(50) }),
+                        (Uuid::from_u128(3001), SpeakerScore::Aggregate { total: Score::from_points(50) }),
+                        (Uuid::from_u128(3002), SpeakerScore::Aggregate { total: Score::from_points(50) }),
+                    ].into_iter().collect(),
+                    trainee_scores: Default::default()
                 },
                 Speech {
                     speaker: Some(Uuid::from_u128(2050)),
@@ -93,10 +97,11 @@ pub async fn set_up_db(with_mock_env: bool) -> Result<DatabaseConnection, anyhow
                     position: 0,
                     is_opt_out: false,
                     scores: vec![
-                        (Uuid::from_u128(3000), SpeakerScore::Aggregate { total: 80 }),
-                        (Uuid::from_u128(3001), SpeakerScore::Aggregate { total: 70 }),
-                        (Uuid::from_u128(3002), SpeakerScore::Aggregate { total: 70 }),
-                    ].into_iter().collect()
+                        (Uuid::from_u128(3000), SpeakerScore::Aggregate { total: Score::from_points(80) }),
+                        (Uuid::from_u128(3001), SpeakerScore::Aggregate { total: Score::from_points(70) }),
+                        (Uuid::from_u128(3002), SpeakerScore::Aggregate { total: Score::from_points(70) }),
+                    ].into_iter().collect(),
+                    trainee_scores: Default::default()
                 },
                 Speech {
                     speaker: Some(Uuid::from_u128(2051)),
@@ -104,10 +109,11 @@ pub async fn set_up_db(with_mock_env: bool) -> Result<DatabaseConnection, anyhow
                     position: 1,
                     is_opt_out: false,
                     scores: vec![
-                        (Uuid::from_u128(3000), SpeakerScore::Aggregate { total: 80 }),
-                        (Uuid::from_u128(3001), SpeakerScore::Aggregate { total: 70 }),
-                        (Uuid::from_u128(3002), SpeakerScore::Aggregate { total: 71 }),
-                    ].into_iter().collect()
+                        (Uuid::from_u128(3000), SpeakerScore::Aggregate { total: Score::from_points(80) }),
+                        (Uuid::from_u128(3001), SpeakerScore::Aggregate { total: Score::from_points(70) }),
+                        (Uuid::from_u128(3002), SpeakerScore::Aggregate { total: Score::from_points(71) }),
+                    ].into_iter().collect(),
+                    trainee_scores: Default::default()
                 },
                 Speech {
                     speaker: Some(Uuid::from_u128(2052)),
@@ -115,10 +121,11 @@ pub async fn set_up_db(with_mock_env: bool) -> Result<DatabaseConnection, anyhow
                     position: 2,
                     is_opt_out: false,
                     scores: vec![
-                        (Uuid::from_u128(3000), SpeakerScore::Aggregate { total: 51 }),
-                        (Uuid::from_u128(3001), SpeakerScore::Aggregate { total: 50 }),
-                        (Uuid::from_u128(3002), SpeakerScore::Aggregate { total: 50 }),
-                    ].into_iter().collect()
+                        (Uuid::from_u128(3000), SpeakerScore::Aggregate { total: Score::from_points(51) }),
+                        (Uuid::from_u128(3001), SpeakerScore::Aggregate { total: Score::from_points(50) }),
+                        (Uuid::from_u128(3002), SpeakerScore::Aggregate { total: Score::from_points(50) }),
+                    ].into_iter().collect(),
+                    trainee_scores: Default::default()
                 },
                 Speech {
                     speaker: Some(Uuid::from_u128(2012)),
@@ -126,10 +133,11 @@ pub async fn set_up_db(with_mock_env: bool) -> Result<DatabaseConnection, anyhow
                     position: 2,
                     is_opt_out: false,
                     scores: vec![
-                        (Uuid::from_u128(3000), SpeakerScore::Aggregate { total: 50 }),
-                        (Uuid::from_u128(3001), SpeakerScore::Aggregate { total: 50 }),
-                        (Uuid::from_u128(3002), SpeakerScore::Aggregate { total: 50 }),
-                    ].into_iter().collect()
+                        (Uuid::from_u128(3000), SpeakerScore::Aggregate { total: Score::from_points(50) }),
+                        (Uuid::from_u128(3001), SpeakerScore::Aggregate { total: Score::from_points(50) }),
+                        (Uuid::from_u128(3002), SpeakerScore::Aggregate { total: Score::from_points(50) }),
+                    ].into_iter().collect(),
+                    trainee_scores: Default::default()
                 },
                 Speech {
                     speaker: Some(Uuid::from_u128(2002)),
@@ -137,10 +145,11 @@ pub async fn set_up_db(with_mock_env: bool) -> Result<DatabaseConnection, anyhow
                     position: 2,
                     is_opt_out: false,
                     scores: vec![
-                        (Uuid::from_u128(3000), SpeakerScore::Aggregate { total: 50 }),
-                        (Uuid::from_u128(3001), SpeakerScore::Aggregate { total: 50 }),
-                        (Uuid::from_u128(3002), SpeakerScore::Aggregate { total: 50 }),
-                    ].into_iter().collect()
+                        (Uuid::from_u128(3000), SpeakerScore::Aggregate { total: Score::from_points(50) }),
+                        (Uuid::from_u128(3001), SpeakerScore::Aggregate { total: Score::from_points(50) }),
+                        (Uuid::from_u128(3002), SpeakerScore::Aggregate { total: Score::from_points(50) }),
+                    ].into_iter().collect(),
+                    trainee_scores: Default::default()
                 },
             ],
             ..Default::default()
@@ -152,16 +161,16 @@ pub async fn set_up_db(with_mock_env: bool) -> Result<DatabaseConnection, anyhow
             government: BallotTeam {
                 team: Some(Uuid::from_u128(1003)),
                 scores: vec![
-                    (Uuid::from_u128(3000), TeamScore::Aggregate { total: 100 }),
-                    (Uuid::from_u128(3001), TeamScore::Aggregate { total: 100 }),
+                    (Uuid::from_u128(3000), TeamScore::Aggregate { total: Score::from_points(100) }),
+                    (Uuid::from_u128(3001), TeamScore::Aggregate { total: Score::from_points(100) }),
                 ].into_iter().collect(),
                 ..Default::default()
             },
             opposition: BallotTeam {
                 team: Some(Uuid::from_u128(1000)),
                 scores: vec![
-                    (Uuid::from_u128(3000), TeamScore::Aggregate { total: 120 }),
-                    (Uuid::from_u128(3001), TeamScore::Aggregate { total: 121 }),
+                    (Uuid::from_u128(3000), TeamScore::Aggregate { total: Score::from_points(120) }),
+                    (Uuid::from_u128(3001), TeamScore::Aggregate { total: Score::from_points(121) }),
                 ].into_iter().collect(),
                 ..Default::default()
             },
@@ -172,9 +181,10 @@ pub async fn set_up_db(with_mock_env: bool) -> Result<DatabaseConnection, anyhow
                     position: 0,
                     is_opt_out: false,
                     scores: vec![
-                        (Uuid::from_u128(3000), SpeakerScore::Aggregate { total: 50 }),
-                        (Uuid::from_u128(3001), SpeakerScore::Aggregate { total: 50 }),
-                    ].into_iter().collect()
+                        (Uuid::from_u128(3000), SpeakerScore::Aggregate { total: Score::from_points(50) }),
+                        (Uuid::from_u128(3001), SpeakerScore::Aggregate { total: Score::from_points(50) }),
+                    ].into_iter().collect(),
+                    trainee_scores: Default::default()
                 },
                 Speech {
                     speaker: Some(Uuid::from_u128(2000)),
@@ -182,9 +192,10 @@ pub async fn set_up_db(with_mock_env: bool) -> Result<DatabaseConnection, anyhow
                     position: 0,
                     is_opt_out: false,
                     scores: vec![
-                        (Uuid::from_u128(3000), SpeakerScore::Aggregate { total: 50 }),
-                        (Uuid::from_u128(3001), SpeakerScore::Aggregate { total: 50 }),
-                    ].into_iter().collect()
+                        (Uuid::from_u128(3000), SpeakerScore::Aggregate { total: Score::from_points(50) }),
+                        (Uuid::from_u128(3001), SpeakerScore::Aggregate { total: Score::from_points(50) }),
+                    ].into_iter().collect(),
+                    trainee_scores: Default::default()
                 },
                 Speech {
                     speaker: Some(Uuid::from_u128(2031)),
@@ -192,9 +203,10 @@ pub async fn set_up_db(with_mock_env: bool) -> Result<DatabaseConnection, anyhow
                     position: 1,
                     is_opt_out: false,
                     scores: vec![
-                        (Uuid::from_u128(3000), SpeakerScore::Aggregate { total: 50 }),
-                        (Uuid::from_u128(3001), SpeakerScore::Aggregate { total: 50 }),
-                    ].into_iter().collect()
+                        (Uuid::from_u128(3000), SpeakerScore::Aggregate { total: Score::from_points(50) }),
+                        (Uuid::from_u128(3001), SpeakerScore::Aggregate { total: Score::from_points(50) }),
+                    ].into_iter().collect(),
+                    trainee_scores: Default::default()
                 },
                 Speech {
                     speaker: Some(Uuid::from_u128(2001)),
@@ -202,9 +214,10 @@ pub async fn set_up_db(with_mock_env: bool) -> Result<DatabaseConnection, anyhow
                     position: 1,
                     is_opt_out: false,
                     scores: vec![
-                        (Uuid::from_u128(3000), SpeakerScore::Aggregate { total: 50 }),
-                        (Uuid::from_u128(3001), SpeakerScore::Aggregate { total: 50 }),
-                    ].into_iter().collect()
+                        (Uuid::from_u128(3000), SpeakerScore::Aggregate { total: Score::from_points(50) }),
+                        (Uuid::from_u128(3001), SpeakerScore::Aggregate { total: Score::from_points(50) }),
+                    ].into_iter().collect(),
+                    trainee_scores: Default::default()
                 },
                 Speech {
                     speaker: Some(Uuid::from_u128(2050)),
@@ -212,9 +225,10 @@ pub async fn set_up_db(with_mock_env: bool) -> Result<DatabaseConnection, anyhow
                     position: 0,
                     is_opt_out: false,
                     scores: vec![
-                        (Uuid::from_u128(3000), SpeakerScore::Aggregate { total: 50 }),
-                        (Uuid::from_u128(3001), SpeakerScore::Aggregate { total: 50 }),
-                    ].into_iter().collect()
+                        (Uuid::from_u128(3000), SpeakerScore::Aggregate { total: Score::from_points(50) }),
+                        (Uuid::from_u128(3001), SpeakerScore::Aggregate { total: Score::from_points(50) }),
+                    ].into_iter().collect(),
+                    trainee_scores: Default::default()
                 },
                 Speech {
                     speaker: Some(Uuid::from_u128(2051)),
@@ -222,9 +236,10 @@ pub async fn set_up_db(with_mock_env: bool) -> Result<DatabaseConnection, anyhow
                     position: 1,
                     is_opt_out: false,
                     scores: vec![
-                        (Uuid::from_u128(3000), SpeakerScore::Aggregate { total: 50 }),
-                        (Uuid::from_u128(3001), SpeakerScore::Aggregate { total: 50 }),
-                    ].into_iter().collect()
+                        (Uuid::from_u128(3000), SpeakerScore::Aggregate { total: Score::from_points(50) }),
+                        (Uuid::from_u128(3001), SpeakerScore::Aggregate { total: Score::from_points(50) }),
+                    ].into_iter().collect(),
+                    trainee_scores: Default::default()
                 },
                 Speech {
                     speaker: Some(Uuid::from_u128(2052)),
@@ -232,9 +247,10 @@ pub async fn set_up_db(with_mock_env: bool) -> Result<DatabaseConnection, anyhow
                     position: 2,
                     is_opt_out: false,
                     scores: vec![
-                        (Uuid::from_u128(3000), SpeakerScore::Aggregate { total: 50 }),
-                        (Uuid::from_u128(3001), SpeakerScore::Aggregate { total: 50 }),
-                    ].into_iter().collect()
+                        (Uuid::from_u128(3000), SpeakerScore::Aggregate { total: Score::from_points(50) }),
+                        (Uuid::from_u128(3001), SpeakerScore::Aggregate { total: Score::from_points(50) }),
+                    ].into_iter().collect(),
+                    trainee_scores: Default::default()
                 },
                 Speech {
                     speaker: Some(Uuid::from_u128(2002)),
@@ -242,9 +258,10 @@ pub async fn set_up_db(with_mock_env: bool) -> Result<DatabaseConnection, anyhow
                     position: 2,
                     is_opt_out: false,
                     scores: vec![
-                        (Uuid::from_u128(3000), SpeakerScore::Aggregate { total: 50 }),
-                        (Uuid::from_u128(3001), SpeakerScore::Aggregate { total: 50 }),
-                    ].into_iter().collect()
+                        (Uuid::from_u128(3000), SpeakerScore::Aggregate { total: Score::from_points(50) }),
+                        (Uuid::from_u128(3001), SpeakerScore::Aggregate { total: Score::from_points(50) }),
+                    ].into_iter().collect(),
+                    trainee_scores: Default::default()
                 },
                 Speech {
                     speaker: Some(Uuid::from_u128(2032)),
@@ -252,9 +269,10 @@ pub async fn set_up_db(with_mock_env: bool) -> Result<DatabaseConnection, anyhow
                     position: 2,
                     is_opt_out: false,
                     scores: vec![
-                        (Uuid::from_u128(3000), SpeakerScore::Aggregate { total: 50 }),
-                        (Uuid::from_u128(3001), SpeakerScore::Aggregate { total: 50 }),
-                    ].into_iter().collect()
+                        (Uuid::from_u128(3000), SpeakerScore::Aggregate { total: Score::from_points(50) }),
+                        (Uuid::from_u128(3001), SpeakerScore::Aggregate { total: Score::from_points(50) }),
+                    ].into_iter().collect(),
+                    trainee_scores: Default::default()
                 },
             ],
             ..Default::default()
@@ -424,3 +442,161 @@ async fn test_opt_out_adds_smallest_score_for_non_aligned() -> Result<(), anyhow
 
     Ok(())
 }
+
+#[tokio::test]
+async fn test_itemized_scores_are_broken_down_by_category() -> Result<(), anyhow::Error> {
+    let db = set_up_db(true).await?;
+
+    let mut ballot = Ballot::get(&db, Uuid::from_u128(400)).await?;
+    ballot.speeches[0].scores.insert(Uuid::from_u128(3000), SpeakerScore::new_itemized(vec![("content".to_string(), Score::from_points(30)), ("style".to_string(), Score::from_points(23))].into_iter().collect()));
+    ballot.speeches[0].scores.insert(Uuid::from_u128(3001), SpeakerScore::new_itemized(vec![("content".to_string(), Score::from_points(20)), ("style".to_string(), Score::from_points(27))].into_iter().collect()));
+    ballot.government.scores.insert(Uuid::from_u128(3000), TeamScore::new_itemized(vec![("cooperation".to_string(), Score::from_points(120))].into_iter().collect()));
+    ballot.save(&db, false).await.unwrap();
+    let speaker = ballot.speeches[0].speaker.expect("Expected speaker");
+
+    let loaded_view = LoadedTabView::load(&db, Uuid::from_u128(1)).await?;
+
+    let view = loaded_view.view;
+
+    let speaker_entry = view.speaker_tab.iter().find(|e| e.speaker_uuid == speaker).expect("Expected to find speaker");
+    assert_eq!(speaker_entry.category_totals.get("content"), Some(&25.0));
+    assert_eq!(speaker_entry.category_totals.get("style"), Some(&25.0));
+
+    let team_entry = view.team_tab.iter().find(|e| e.team_uuid == Uuid::from_u128(1000)).expect("Expected to find team");
+    assert_eq!(team_entry.team_category_totals.get("cooperation"), Some(&120.0));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_dropped_rounds_are_excluded_from_team_total() -> Result<(), anyhow::Error> {
+    let db = set_up_db(true).await?;
+
+    let mut tournament = Tournament::get(&db, Uuid::from_u128(1)).await?;
+    tournament.tab_ranking_config = Some(open_tab_entities::tab::TabRankingConfig {
+        team_ranking: open_tab_entities::tab::RankingRules {
+            drop_worst_rounds: 1,
+            ..Default::default()
+        },
+        ..Default::default()
+    });
+    tournament.save(&db, false).await?;
+
+    let loaded_view = LoadedTabView::load(&db, Uuid::from_u128(1)).await?;
+
+    let view = loaded_view.view;
+
+    let target_team_entry = view.team_tab.iter().find(|e| e.team_uuid == Uuid::from_u128(1000)).expect("Expected to find team");
+    let round_totals = target_team_entry.detailed_scores.iter().flatten().map(|s| s.total_score()).collect::<Vec<_>>();
+    assert_eq!(round_totals.len(), 2);
+    let best_round = round_totals.iter().cloned().fold(f64::MIN, f64::max);
+    assert!((target_team_entry.total_score - best_round).abs() < TAB_TOLERANCE, "Incorrect score: {}", target_team_entry.total_score);
+
+    for (prev, next) in view.team_tab.iter().zip(view.team_tab.iter().skip(1)) {
+        assert!(prev.total_score >= next.total_score);
+        assert!(prev.rank <= next.rank);
+    }
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_swing_teams_are_excluded_from_tab() -> Result<(), anyhow::Error> {
+    let db = set_up_db(true).await?;
+
+    let mut team = Team::get(&db, Uuid::from_u128(1000)).await?;
+    team.is_swing = true;
+    team.save(&db, false).await?;
+
+    let loaded_view = LoadedTabView::load(&db, Uuid::from_u128(1)).await?;
+
+    let view = loaded_view.view;
+
+    assert_eq!(view.team_tab.len(), 5);
+    assert!(view.team_tab.iter().all(|e| e.team_uuid != Uuid::from_u128(1000)));
+    assert!(view.speaker_tab.iter().all(|e| e.team_uuid != Uuid::from_u128(1000)));
+    assert_eq!(view.speaker_tab.len(), 15);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_iron_speaker_is_scored_by_average_of_speeches() -> Result<(), anyhow::Error> {
+    let db = set_up_db(true).await?;
+
+    let mut ballot = Ballot::get(&db, Uuid::from_u128(400)).await?;
+    ballot.speeches[2].speaker = Some(Uuid::from_u128(2000));
+    ballot.save(&db, false).await.unwrap();
+
+    let view = open_tab_entities::tab::TabView::load_from_tournament(&db, Uuid::from_u128(1)).await?;
+
+    let iron_speaker = view.speaker_tab.iter().find(|e| e.speaker_uuid == Uuid::from_u128(2000)).expect("Expected to find speaker");
+    let iron_round = iron_speaker.detailed_scores.iter().flatten().find(|s| s.num_speeches == 2).expect("Expected two speeches in one round");
+    assert!((iron_round.score - (61.0 + 61.0 / 3.0) / 2.0).abs() < TAB_TOLERANCE, "Incorrect score: {}", iron_round.score);
+    assert_eq!(iron_speaker.rounds_missed, 0);
+
+    let replaced_speaker = view.speaker_tab.iter().find(|e| e.speaker_uuid == Uuid::from_u128(2001)).expect("Expected to find speaker");
+    assert_eq!(replaced_speaker.rounds_missed, 1);
+
+    let team_entry = view.team_tab.iter().find(|e| e.team_uuid == Uuid::from_u128(1000)).expect("Expected to find team");
+    assert!((team_entry.total_score - 481.8333333333333).abs() < TAB_TOLERANCE, "Incorrect score: {}", team_entry.total_score);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_substitute_speech_counts_for_team_and_can_be_excluded_from_speaker_tab() -> Result<(), anyhow::Error> {
+    let db = set_up_db(true).await?;
+
+    let mut ballot = Ballot::get(&db, Uuid::from_u128(400)).await?;
+    ballot.speeches[2].speaker = Some(Uuid::from_u128(2030));
+    ballot.save(&db, false).await.unwrap();
+    SpeakerSubstitution::new(Uuid::from_u128(100), Uuid::from_u128(1000), Uuid::from_u128(2030), Some(Uuid::from_u128(2001))).save(&db, false).await?;
+
+    let view = open_tab_entities::tab::TabView::load_from_tournament(&db, Uuid::from_u128(1)).await?;
+
+    let team_entry = view.team_tab.iter().find(|e| e.team_uuid == Uuid::from_u128(1000)).expect("Expected to find team");
+    assert!((team_entry.total_score - 481.8333333333333).abs() < TAB_TOLERANCE, "Incorrect score: {}", team_entry.total_score);
+
+    let substitute = view.speaker_tab.iter().find(|e| e.speaker_uuid == Uuid::from_u128(2030)).expect("Expected to find speaker");
+    assert_eq!(substitute.team_uuid, Uuid::from_u128(1003));
+    assert_eq!(substitute.detailed_scores.iter().flatten().filter(|s| s.is_substitute).count(), 1);
+    assert_eq!(substitute.rounds_missed, 1);
+
+    let mut tournament = Tournament::get(&db, Uuid::from_u128(1)).await?;
+    tournament.tab_ranking_config = Some(open_tab_entities::tab::TabRankingConfig {
+        exclude_substitute_speeches: true,
+        ..Default::default()
+    });
+    tournament.save(&db, false).await?;
+
+    let view = open_tab_entities::tab::TabView::load_from_tournament(&db, Uuid::from_u128(1)).await?;
+
+    let substitute = view.speaker_tab.iter().find(|e| e.speaker_uuid == Uuid::from_u128(2030)).expect("Expected to find speaker");
+    assert_eq!(substitute.total_score, 50.0);
+    let team_entry = view.team_tab.iter().find(|e| e.team_uuid == Uuid::from_u128(1000)).expect("Expected to find team");
+    assert!((team_entry.total_score - 481.8333333333333).abs() < TAB_TOLERANCE, "Incorrect score: {}", team_entry.total_score);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_non_aligned_substitute_counts_for_substituted_team() -> Result<(), anyhow::Error> {
+    let db = set_up_db(true).await?;
+
+    let mut ballot = Ballot::get(&db, Uuid::from_u128(400)).await?;
+    ballot.speeches[4].speaker = Some(Uuid::from_u128(2030));
+    ballot.save(&db, false).await.unwrap();
+    SpeakerSubstitution::new(Uuid::from_u128(100), Uuid::from_u128(1005), Uuid::from_u128(2030), Some(Uuid::from_u128(2050))).save(&db, false).await?;
+
+    let view = open_tab_entities::tab::TabView::load_from_tournament(&db, Uuid::from_u128(1)).await?;
+
+    let team_entry = view.team_tab.iter().find(|e| e.team_uuid == Uuid::from_u128(1005)).expect("Expected to find team");
+    assert!((team_entry.total_score - 347.333333333).abs() < TAB_TOLERANCE, "Incorrect score: {}", team_entry.total_score);
+
+    let substitute_team_entry = view.team_tab.iter().find(|e| e.team_uuid == Uuid::from_u128(1003)).expect("Expected to find team");
+    assert_eq!(substitute_team_entry.detailed_scores.iter().flatten().count(), 1);
+    assert_eq!(substitute_team_entry.rounds_missed, 1);
+
+    Ok(())
+}
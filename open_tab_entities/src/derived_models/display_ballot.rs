@@ -1,6 +1,8 @@
 
 use crate::domain::ballot::Ballot;
 use crate::domain::entity::LoadEntity;
+use crate::domain::speaker_substitution::SpeakerSubstitution;
+use crate::domain::score::Score;
 use crate::info::TournamentParticipantsInfo;
 use crate::schema;
 
@@ -8,7 +10,7 @@ use itertools::Itertools;
 use sea_orm::QueryOrder;
 use sea_orm::prelude::Uuid;
 
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 
 
 use serde::{de::IgnoredAny, Deserialize, Serialize};
@@ -45,7 +47,13 @@ impl ResultDebate {
 
         let all_ballot_uuids = debates.iter().map(|debate| debate.ballot_id).chain(backup_ballots.iter().map(|ballot| ballot.ballot_id)).collect_vec();
 
-        let all_ballots_by_id : HashMap<_, _> = Ballot::get_many(db, all_ballot_uuids).await?.into_iter().map(|ballot| (ballot.uuid, crate::derived_models::DisplayBallot::from_ballot_and_info(ballot, &info))).collect();
+        let substitutions = SpeakerSubstitution::get_all_in_rounds(db, vec![round.uuid]).await?;
+
+        let all_ballots_by_id : HashMap<_, _> = Ballot::get_many(db, all_ballot_uuids).await?.into_iter().map(|ballot| {
+            let mut display_ballot = crate::derived_models::DisplayBallot::from_ballot_and_info(ballot, &info);
+            display_ballot.add_substitutes(&substitutions, &info);
+            (display_ballot.uuid, display_ballot)
+        }).collect();
 
         let all_venues_by_id = schema::tournament_venue::Entity::find()
             .filter(schema::tournament_venue::Column::TournamentId.eq(round.tournament_id))
@@ -99,6 +107,8 @@ pub struct DisplayBallot {
 
     pub adjudicators: Vec<DisplayAdjudicator>,
     pub president: Option<DisplayAdjudicator>,
+    #[serde(default)]
+    pub trainees: Vec<DisplayAdjudicator>,
     pub government: DisplayBallotTeam,
     pub opposition: DisplayBallotTeam,
 
@@ -116,7 +126,13 @@ pub struct DisplayBallotTeam {
     pub uuid: Option<Uuid>,
     pub name: Option<String>,
     pub members: Vec<DisplaySpeaker>,
-    pub scores: HashMap<Uuid, i16>,
+    pub scores: HashMap<Uuid, Score>,
+    /// Itemized scores of the adjudicators that scored by category
+    #[serde(default)]
+    pub score_items: HashMap<Uuid, BTreeMap<String, Score>>,
+    /// Scores of trainees, which are not part of the totals
+    #[serde(default)]
+    pub trainee_scores: HashMap<Uuid, Score>,
     pub total_team_score: Option<f64>,
     pub total_speech_score: Option<f64>,
     pub total_score: Option<f64>,
@@ -130,7 +146,13 @@ pub struct DisplaySpeaker {
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct DisplayBallotSpeech {
-    pub scores: HashMap<Uuid, i16>,
+    pub scores: HashMap<Uuid, Score>,
+    /// Itemized scores of the adjudicators that scored by category
+    #[serde(default)]
+    pub score_items: HashMap<Uuid, BTreeMap<String, Score>>,
+    /// Scores of trainees, which are not part of the total
+    #[serde(default)]
+    pub trainee_scores: HashMap<Uuid, Score>,
     pub speaker: Option<DisplaySpeaker>,
     pub position: u8,
     pub role: SpeechRole,
@@ -150,6 +172,11 @@ impl DisplayBallot {
             name: info.participants_by_id.get(&president).map(|adj| adj.name.clone()).unwrap_or("Unknown".into())
         });
 
+        let trainees = ballot.trainees.iter().map(|trainee| DisplayAdjudicator {
+            uuid: *trainee,
+            name: info.participants_by_id.get(trainee).map(|adj| adj.name.clone()).unwrap_or("Unknown".into())
+        }).collect_vec();
+
         let government = DisplayBallotTeam {
             uuid: ballot.government.team,
             name: ballot.government.team.map(|team| info.teams_by_id.get(&team)).flatten().map(|team| team.name.clone()),
@@ -161,6 +188,8 @@ impl DisplayBallot {
                     }).collect_vec()
             }).into_iter().flatten().collect_vec(),
             scores: ballot.government.scores.iter().map(|scores| (*scores.0, scores.1.total())).collect(),
+            score_items: ballot.government.scores.iter().filter_map(|(adj, score)| score.items().map(|items| (*adj, items.clone()))).collect(),
+            trainee_scores: ballot.government.trainee_scores.iter().map(|(adj, score)| (*adj, score.total())).collect(),
             total_team_score: ballot.government.team_score(),
             total_speech_score: ballot.government_speech_total(),
             total_score: ballot.government_total()
@@ -177,6 +206,8 @@ impl DisplayBallot {
                     }).collect_vec()
             }).into_iter().flatten().collect_vec(),
             scores: ballot.opposition.scores.iter().map(|scores| (*scores.0, scores.1.total())).collect(),
+            score_items: ballot.opposition.scores.iter().filter_map(|(adj, score)| score.items().map(|items| (*adj, items.clone()))).collect(),
+            trainee_scores: ballot.opposition.trainee_scores.iter().map(|(adj, score)| (*adj, score.total())).collect(),
             total_team_score: ballot.opposition.team_score(),
             total_speech_score: ballot.opposition_speech_total(),
             total_score: ballot.opposition_total()
@@ -184,6 +215,8 @@ impl DisplayBallot {
 
         let speeches = ballot.speeches.iter().map(|speech| DisplayBallotSpeech {
             scores: speech.scores.iter().map(|scores| (*scores.0, scores.1.total())).collect(),
+            score_items: speech.scores.iter().filter_map(|(adj, score)| score.items().map(|items| (*adj, items.clone()))).collect(),
+            trainee_scores: speech.trainee_scores.iter().map(|(adj, score)| (*adj, score.total())).collect(),
             speaker: speech.speaker.map(|speaker| DisplaySpeaker {
                 uuid: speaker,
                 name: info.participants_by_id.get(&speaker).map(|s| s.name.clone()).unwrap_or("Unknown".into())
@@ -200,29 +233,61 @@ impl DisplayBallot {
             government,
             opposition,
             speeches,
-            president
+            president,
+            trainees
         }
     }
 }
 
+impl DisplayBallot {
+    /// Adds the speakers that substitute for a team to the members of that team,
+    /// so they can be selected for its speeches.
+    pub fn add_substitutes(&mut self, substitutions: &[SpeakerSubstitution], info: &TournamentParticipantsInfo) {
+        for team in [&mut self.government, &mut self.opposition] {
+            for substitution in substitutions.iter().filter(|s| Some(s.team_id) == team.uuid) {
+                if !team.members.iter().any(|m| m.uuid == substitution.speaker_id) {
+                    team.members.push(DisplaySpeaker {
+                        uuid: substitution.speaker_id,
+                        name: info.participants_by_id.get(&substitution.speaker_id).map(|m| m.name.clone()).unwrap_or("Unknown".into())
+                    });
+                }
+            }
+        }
+    }
+}
+
+/// Prefers the itemized score of an adjudicator over their total, if one exists.
+fn merge_score_items<S>(totals: HashMap<Uuid, Score>, mut items: HashMap<Uuid, BTreeMap<String, Score>>, aggregate: fn(Score) -> S, itemized: fn(BTreeMap<String, Score>) -> S) -> HashMap<Uuid, S> {
+    totals.into_iter().map(|(adj, total)| {
+        match items.remove(&adj) {
+            Some(items) => (adj, itemized(items)),
+            None => (adj, aggregate(total))
+        }
+    }).collect()
+}
+
 impl Into<Ballot> for DisplayBallot {
     fn into(self) -> Ballot {
         let adjudicators = self.adjudicators.into_iter().map(|adj| adj.uuid).collect_vec();
         let government = BallotTeam {
             team: self.government.uuid,
-            scores: self.government.scores.into_iter().map(|(adj, score)| (adj, TeamScore::Aggregate { total: score })).collect()
+            scores: merge_score_items(self.government.scores, self.government.score_items, TeamScore::new_aggregate, TeamScore::new_itemized),
+            trainee_scores: self.government.trainee_scores.into_iter().map(|(adj, score)| (adj, TeamScore::new_aggregate(score))).collect(),
         };
         let opposition = BallotTeam {
             team: self.opposition.uuid,
-            scores: self.opposition.scores.into_iter().map(|(adj, score)| (adj, TeamScore::Aggregate { total: score })).collect()
+            scores: merge_score_items(self.opposition.scores, self.opposition.score_items, TeamScore::new_aggregate, TeamScore::new_itemized),
+            trainee_scores: self.opposition.trainee_scores.into_iter().map(|(adj, score)| (adj, TeamScore::new_aggregate(score))).collect(),
         };
         let speeches = self.speeches.into_iter().map(|speech| Speech {
             speaker: speech.speaker.map(|speaker| speaker.uuid),
             position: speech.position,
             role: speech.role,
-            scores: speech.scores.into_iter().map(|(adj, score)| (adj, SpeakerScore::Aggregate { total: score })).collect(),
-            is_opt_out: speech.is_opt_out
+            scores: merge_score_items(speech.scores, speech.score_items, SpeakerScore::new_aggregate, SpeakerScore::new_itemized),
+            is_opt_out: speech.is_opt_out,
+            trainee_scores: speech.trainee_scores.into_iter().map(|(adj, score)| (adj, SpeakerScore::new_aggregate(score))).collect(),
         }).collect_vec();
+        let trainees = self.trainees.into_iter().map(|adj| adj.uuid).collect_vec();
 
         Ballot {
             uuid: self.uuid,
@@ -230,7 +295,8 @@ impl Into<Ballot> for DisplayBallot {
             government,
             opposition,
             speeches,
-            president: None
+            president: None,
+            trainees
         }
     }
 }
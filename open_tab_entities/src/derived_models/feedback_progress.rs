@@ -163,6 +163,18 @@ pub fn get_feedback_requests_from_ballot(ballot: &Ballot, directions: &Vec<(Feed
                     }
                 }
             },
+            (FeedbackSourceRole::Chair, FeedbackTargetRole::Trainee) => {
+                if !ballot.adjudicators.is_empty() {
+                    for trainee in &ballot.trainees {
+                        out.push(FeedbackRequest {
+                            target_id: *trainee,
+                            source_id: SourceId::Participant{uuid: ballot.adjudicators[0]},
+                            source_role: FeedbackSourceRole::Chair,
+                            target_role: FeedbackTargetRole::Trainee
+                        });
+                    }
+                }
+            },
             (FeedbackSourceRole::Wing, FeedbackTargetRole::Chair) => {
                 if ballot.adjudicators.len() > 1 {
                     for adj in &ballot.adjudicators[1..] {
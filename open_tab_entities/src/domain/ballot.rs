@@ -1,4 +1,4 @@
-use std::{collections::{HashMap, HashSet}, iter::zip, cmp::Ordering, error::Error, fmt::Display, str::FromStr};
+use std::{collections::{BTreeMap, HashMap, HashSet}, iter::zip, cmp::Ordering, error::Error, fmt::Display, str::FromStr};
 
 use async_trait::async_trait;
 use sea_orm::{JoinType, FromQueryResult};
@@ -10,7 +10,7 @@ use crate::schema::{self};
 
 use itertools::{izip, Itertools};
 
-use super::{entity::{LoadEntity, TournamentEntityTrait}, BoundTournamentEntityTrait};
+use super::{entity::{LoadEntity, TournamentEntityTrait}, score::Score, BoundTournamentEntityTrait};
 use crate::utilities::BatchLoad;
 
 #[derive(Debug, PartialEq, Eq)]
@@ -20,6 +20,7 @@ pub enum BallotParseError {
     UnknownJudgeRole,
     TooManyPresidents,
     BallotDoesNotExist(String),
+    InvalidScoreItems(String),
     DbErr(DbErr)
 }
 
@@ -58,7 +59,10 @@ pub struct Ballot {
     pub opposition: BallotTeam,
 
     pub adjudicators: Vec<Uuid>,
-    pub president: Option<Uuid>
+    pub president: Option<Uuid>,
+    /// Non-voting adjudicators shadowing the panel
+    #[serde(default)]
+    pub trainees: Vec<Uuid>,
 }
 
 #[derive(Debug, PartialEq, Eq, PartialOrd, Hash, Ord, Serialize, Deserialize, Clone, Copy)]
@@ -100,6 +104,7 @@ impl SpeechRole {
 pub enum JudgeRole {
     Normal,
     President,
+    Trainee,
 }
 
 impl FromStr for JudgeRole {
@@ -109,6 +114,7 @@ impl FromStr for JudgeRole {
         match s {
             "n" => Ok(JudgeRole::Normal),
             "p" => Ok(JudgeRole::President),
+            "t" => Ok(JudgeRole::Trainee),
             _ => Err(BallotParseError::UnknownJudgeRole)
         }
     }
@@ -127,6 +133,7 @@ impl JudgeRole {
         match self {
             JudgeRole::President => "p".into(),
             JudgeRole::Normal => "n".into(),
+            JudgeRole::Trainee => "t".into(),
         }
     }
 }
@@ -138,14 +145,21 @@ pub struct Speech {
     pub position: u8,
     pub scores: HashMap<Uuid, SpeakerScore>,
     #[serde(default)]
-    pub is_opt_out: bool
+    pub is_opt_out: bool,
+    /// Scores given by trainees. These do not count towards the speaker score.
+    #[serde(default)]
+    pub trainee_scores: HashMap<Uuid, SpeakerScore>,
 }
 
 impl Speech {
+    pub fn speaker_category_scores(&self) -> BTreeMap<String, f64> {
+        average_score_items(self.scores.values().filter_map(|s| s.items()))
+    }
+
     pub fn speaker_score(&self) -> Option<f64> {
         if self.scores.len() > 0 {
             Some(
-                self.scores.values().map(|s| s.total() as f64).sum::<f64>() / self.scores.len() as f64
+                self.scores.values().map(|s| s.total().points()).sum::<f64>() / self.scores.len() as f64
             )
         }
         else {
@@ -157,14 +171,21 @@ impl Speech {
 #[derive(Debug, PartialEq, Eq, Default, Serialize, Deserialize, Clone)]
 pub struct BallotTeam {
     pub team: Option<Uuid>,
-    pub scores: HashMap<Uuid, TeamScore>
+    pub scores: HashMap<Uuid, TeamScore>,
+    /// Scores given by trainees. These do not count towards the team score.
+    #[serde(default)]
+    pub trainee_scores: HashMap<Uuid, TeamScore>,
 }
 
 impl BallotTeam {
+    pub fn team_category_scores(&self) -> BTreeMap<String, f64> {
+        average_score_items(self.scores.values().filter_map(|s| s.items()))
+    }
+
     pub fn team_score(&self) -> Option<f64> {
         if self.scores.len() > 0 {
             Some(
-                self.scores.values().map(|s| s.total() as f64).sum::<f64>() / self.scores.len() as f64
+                self.scores.values().map(|s| s.total().points()).sum::<f64>() / self.scores.len() as f64
             )
         }
         else {
@@ -176,17 +197,38 @@ impl BallotTeam {
 #[derive(Debug, PartialEq, Eq, Serialize, Deserialize, Clone)]
 #[serde(tag="type")]
 pub enum SpeakerScore {
-    Aggregate { total: i16 }
+    Aggregate { total: Score },
+    /// Scores per category of the tournament's `ScoreCategorySchema`, keyed by category key.
+    Itemized { items: BTreeMap<String, Score> }
 }
 
 impl SpeakerScore {
-    pub fn new_aggregate(total: i16) -> SpeakerScore {
+    pub fn new_aggregate(total: Score) -> SpeakerScore {
         SpeakerScore::Aggregate { total }
     }
 
-    pub fn total(&self) -> i16{
+    pub fn new_itemized(items: BTreeMap<String, Score>) -> SpeakerScore {
+        SpeakerScore::Itemized { items }
+    }
+
+    pub fn total(&self) -> Score {
         match self {
             SpeakerScore::Aggregate { total: s } => *s,
+            SpeakerScore::Itemized { items } => sum_score_items(items),
+        }
+    }
+
+    pub fn items(&self) -> Option<&BTreeMap<String, Score>> {
+        match self {
+            SpeakerScore::Aggregate { .. } => None,
+            SpeakerScore::Itemized { items } => Some(items),
+        }
+    }
+
+    fn from_columns(manual_total_score: Option<i32>, score_items: Option<&str>) -> Result<SpeakerScore, BallotParseError> {
+        match score_items {
+            Some(items) => Ok(SpeakerScore::Itemized { items: parse_score_items(items)? }),
+            None => Ok(SpeakerScore::Aggregate { total: Score::from_hundredths(manual_total_score.unwrap_or(0)) })
         }
     }
 }
@@ -194,18 +236,66 @@ impl SpeakerScore {
 #[derive(Debug, PartialEq, Eq, Serialize, Deserialize, Clone)]
 #[serde(tag="type")]
 pub enum TeamScore {
-    Aggregate { total: i16 }
+    Aggregate { total: Score },
+    /// Scores per category of the tournament's `ScoreCategorySchema`, keyed by category key.
+    Itemized { items: BTreeMap<String, Score> }
 }
 
 impl TeamScore {
-    pub fn new_aggregate(total: i16) -> TeamScore {
+    pub fn new_aggregate(total: Score) -> TeamScore {
         TeamScore::Aggregate { total }
     }
-    pub fn total(&self) -> i16 {
+
+    pub fn new_itemized(items: BTreeMap<String, Score>) -> TeamScore {
+        TeamScore::Itemized { items }
+    }
+
+    pub fn total(&self) -> Score {
         match self {
             TeamScore::Aggregate { total: s } => *s,
+            TeamScore::Itemized { items } => sum_score_items(items),
+        }
+    }
+
+    pub fn items(&self) -> Option<&BTreeMap<String, Score>> {
+        match self {
+            TeamScore::Aggregate { .. } => None,
+            TeamScore::Itemized { items } => Some(items),
         }
     }
+
+    fn from_columns(manual_total_score: Option<i32>, score_items: Option<&str>) -> Result<TeamScore, BallotParseError> {
+        match score_items {
+            Some(items) => Ok(TeamScore::Itemized { items: parse_score_items(items)? }),
+            None => Ok(TeamScore::Aggregate { total: Score::from_hundredths(manual_total_score.unwrap_or(0)) })
+        }
+    }
+}
+
+/// Saturates instead of overflowing. Validation rejects items that large anyway.
+fn sum_score_items(items: &BTreeMap<String, Score>) -> Score {
+    items.values().fold(Score::ZERO, |total, score| total.saturating_add(*score))
+}
+
+fn parse_score_items(items: &str) -> Result<BTreeMap<String, Score>, BallotParseError> {
+    serde_json::from_str(items).map_err(|_| BallotParseError::InvalidScoreItems(items.to_string()))
+}
+
+fn serialize_score_items(items: Option<&BTreeMap<String, Score>>) -> Option<String> {
+    items.map(|items| serde_json::to_string(items).expect("Serializing a string map can not fail"))
+}
+
+/// Averages itemized scores per category over all adjudicators that submitted itemized scores.
+fn average_score_items<'a, I>(items: I) -> BTreeMap<String, f64> where I: Iterator<Item=&'a BTreeMap<String, Score>> {
+    let mut sums : BTreeMap<String, (f64, usize)> = BTreeMap::new();
+    for items in items {
+        for (key, score) in items.iter() {
+            let entry = sums.entry(key.clone()).or_insert((0.0, 0));
+            entry.0 += score.points();
+            entry.1 += 1;
+        }
+    }
+    sums.into_iter().map(|(key, (sum, count))| (key, sum / count as f64)).collect()
 }
 
 #[async_trait]
@@ -322,7 +412,9 @@ impl Ballot {
     ) -> Result<Self, BallotParseError> {
         adjudicators.sort_by(|a, b| i32::cmp(&a.position,&b.position));
         let (chair, adjudicators) : (Vec<_>, Vec<_>) = adjudicators.into_iter().partition(|a| a.role == JudgeRole::President.to_str());
+        let (trainees, adjudicators) : (Vec<_>, Vec<_>) = adjudicators.into_iter().partition(|a| a.role == JudgeRole::Trainee.to_str());
         let adjudicators = adjudicators.into_iter().map(|a| a.adjudicator_id).collect();
+        let trainees : Vec<Uuid> = trainees.into_iter().map(|a| a.adjudicator_id).collect();
         
         let chair = match chair.len() {
             0 => Ok(None),
@@ -341,31 +433,36 @@ impl Ballot {
         let opp_team_id = opp_team_id.flatten();
 
         let gov_scores : HashMap<Uuid, TeamScore> = team_scores.iter().filter(|s| s.role_id == "g").map(
-            |score| (score.adjudicator_id, TeamScore::Aggregate { total: score.manual_total_score.unwrap() as i16 })
-        ).collect();
+            |score| Ok((score.adjudicator_id, TeamScore::from_columns(score.manual_total_score, score.score_items.as_deref())?))
+        ).collect::<Result<_, BallotParseError>>()?;
         let opp_scores : HashMap<Uuid, TeamScore> = team_scores.iter().filter(|s| s.role_id == "o").map(
-            |score| (score.adjudicator_id, TeamScore::Aggregate { total: score.manual_total_score.unwrap() as i16 })
-        ).collect();
+            |score| Ok((score.adjudicator_id, TeamScore::from_columns(score.manual_total_score, score.score_items.as_deref())?))
+        ).collect::<Result<_, BallotParseError>>()?;
 
         if gov_scores.len() + opp_scores.len() != team_scores.len() {
             return Err(BallotParseError::UnknownTeamRole)
         }
 
+        let (gov_trainee_scores, gov_scores) : (HashMap<_, _>, HashMap<_, _>) = gov_scores.into_iter().partition(|(adj, _)| trainees.contains(adj));
+        let (opp_trainee_scores, opp_scores) : (HashMap<_, _>, HashMap<_, _>) = opp_scores.into_iter().partition(|(adj, _)| trainees.contains(adj));
+
         let government = BallotTeam {
             team: gov_team_id,
-            scores: gov_scores
+            scores: gov_scores,
+            trainee_scores: gov_trainee_scores,
         };
 
         let opposition = BallotTeam {
             team: opp_team_id,
-            scores: opp_scores
+            scores: opp_scores,
+            trainee_scores: opp_trainee_scores,
         };
 
         let mut speech_score_map = HashMap::new();
         for score in  speech_scores.into_iter() {
             speech_score_map.entry((score.speech_role, score.speech_position)).or_insert(HashMap::new()).insert(
                 score.adjudicator_id,
-                SpeakerScore::Aggregate { total: score.manual_total_score.unwrap_or(0) as i16 }
+                SpeakerScore::from_columns(score.manual_total_score, score.score_items.as_deref())?
             );
         };
 
@@ -375,12 +472,14 @@ impl Ballot {
                 let role = SpeechRole::from_str(&s.role)?;
 
                 let scores = speech_score_map.remove(&(s.role, s.position)).unwrap_or_else(HashMap::new);
+                let (trainee_scores, scores) : (HashMap<_, _>, HashMap<_, _>) = scores.into_iter().partition(|(adj, _)| trainees.contains(adj));
                 Ok(Speech {
                     speaker,
                     role,
                     position: s.position as u8,
                     scores,
-                    is_opt_out: s.is_opt_out
+                    is_opt_out: s.is_opt_out,
+                    trainee_scores,
                 })
             }
         ).collect();
@@ -389,7 +488,7 @@ impl Ballot {
         speeches.sort_by(order_speeches);
 
         Ok(
-            Ballot { uuid: ballot.uuid, speeches, government, opposition, adjudicators, president: chair }
+            Ballot { uuid: ballot.uuid, speeches, government, opposition, adjudicators, president: chair, trainees }
         )
 
     }
@@ -423,16 +522,20 @@ impl Ballot {
     }
 
     async fn save_adjudicators<C>(&self, db: &C, is_insert: bool) -> Result<(), DbErr> where C: sea_orm::ConnectionTrait {
-        let current_adjudicators : HashMap<Uuid, (i32, bool)> = if !is_insert {
-             schema::ballot_adjudicator::Entity::find().filter(schema::ballot_adjudicator::Column::BallotId.eq(self.uuid)).all(db).await?.into_iter().map(|a| (a.adjudicator_id, (a.position, a.role == JudgeRole::President.to_str()))).collect()
+        let current_adjudicators : HashMap<Uuid, (i32, String)> = if !is_insert {
+             schema::ballot_adjudicator::Entity::find().filter(schema::ballot_adjudicator::Column::BallotId.eq(self.uuid)).all(db).await?.into_iter().map(|a| (a.adjudicator_id, (a.position, a.role))).collect()
         }
         else {
             HashMap::new()
         };
 
-        let current_adjudicator_uuids = current_adjudicators.keys().map(|x| *x);
+        // Voting adjudicators and trainees are numbered separately, the president is always at position 0
+        let new_adjudicators = self.adjudicators.iter().enumerate().map(|(idx, adj)| (*adj, idx as i32, JudgeRole::Normal))
+            .chain(self.trainees.iter().enumerate().map(|(idx, adj)| (*adj, idx as i32, JudgeRole::Trainee)))
+            .chain(self.president.iter().map(|adj| (*adj, 0, JudgeRole::President)))
+            .collect_vec();
 
-        let to_delete = current_adjudicator_uuids.filter(|a| !self.adjudicators.contains(a) && self.president != Some(*a)).collect_vec();
+        let to_delete = current_adjudicators.keys().filter(|a| !new_adjudicators.iter().any(|(adj, _, _)| adj == *a)).copied().collect_vec();
 
         schema::ballot_adjudicator::Entity::delete_many().filter(
             Condition::all()
@@ -444,59 +547,27 @@ impl Ballot {
             )
         ).exec(db).await?;
 
-        for (idx, adj) in self.adjudicators.iter().enumerate() {
-            let idx = idx as i32;
-            if let Some((prev_pos, was_chair)) = current_adjudicators.get(adj) {
-                if *prev_pos != idx || *was_chair {
+        for (adj, position, role) in new_adjudicators {
+            let role = role.to_str();
+            match current_adjudicators.get(&adj) {
+                Some((prev_position, prev_role)) if *prev_position == position && *prev_role == role => {},
+                Some(_) => {
                     schema::ballot_adjudicator::ActiveModel {
                         ballot_id: ActiveValue::Unchanged(self.uuid),
-                        adjudicator_id: ActiveValue::Unchanged(*adj),
-                        position: ActiveValue::Set(idx),
-                        role: ActiveValue::Set(JudgeRole::Normal.to_str()),
+                        adjudicator_id: ActiveValue::Unchanged(adj),
+                        position: ActiveValue::Set(position),
+                        role: ActiveValue::Set(role),
                     }.update(db).await?;
-                }
-            }
-            else {
-                schema::ballot_adjudicator::ActiveModel {
-                    ballot_id: ActiveValue::Set(self.uuid),
-                    adjudicator_id: ActiveValue::Set(*adj),
-                    position: ActiveValue::Set(idx),
-                    role: ActiveValue::Set(JudgeRole::Normal.to_str()),
-                }.insert(db).await?;
-            }
-        }
-
-        if is_insert {
-            if let Some(president) = self.president {
-                schema::ballot_adjudicator::ActiveModel {
-                    ballot_id: ActiveValue::Unchanged(self.uuid),
-                    adjudicator_id: ActiveValue::Unchanged(president),
-                    position: ActiveValue::Set(0),
-                    role: ActiveValue::Set(JudgeRole::President.to_str()),
-                }.insert(db).await?;
-            }
-        }
-        else {
-            if let Some(new_president) = self.president {
-                if current_adjudicators.contains_key(&new_president) {
-                    schema::ballot_adjudicator::ActiveModel {
-                        ballot_id: ActiveValue::Unchanged(self.uuid),
-                        adjudicator_id: ActiveValue::Unchanged(new_president),
-                        position: ActiveValue::Set(0),
-                        role: ActiveValue::Set(JudgeRole::President.to_str()),
-                    }.update(db).await?;
-                }
-                else {
+                },
+                None => {
                     schema::ballot_adjudicator::ActiveModel {
                         ballot_id: ActiveValue::Set(self.uuid),
-                        adjudicator_id: ActiveValue::Set(new_president),
-                        position: ActiveValue::Set(0),
-                        role: ActiveValue::Set(JudgeRole::President.to_str()),
+                        adjudicator_id: ActiveValue::Set(adj),
+                        position: ActiveValue::Set(position),
+                        role: ActiveValue::Set(role),
                     }.insert(db).await?;
                 }
             }
-            // If there is no president and there was a previous
-            // it has been deleted already
         }
 
         Ok(())
@@ -530,12 +601,13 @@ impl Ballot {
                     role: ActiveValue::Set(role.to_str())
                 }.insert(db).await?;
 
-                for (adj, score) in new_team_entry.scores.iter() {
+                for (adj, score) in new_team_entry.scores.iter().chain(new_team_entry.trainee_scores.iter()) {
                     schema::adjudicator_team_score::ActiveModel {
                         adjudicator_id: ActiveValue::Set(*adj),
                         ballot_id: ActiveValue::Set(self.uuid),
                         role_id: ActiveValue::Set(role.to_str()),
-                        manual_total_score: ActiveValue::Set(Some(score.total() as i32)),
+                        manual_total_score: ActiveValue::Set(Some(score.total().hundredths())),
+                        score_items: ActiveValue::Set(serialize_score_items(score.items())),
                     }.insert(db).await?;
                 }
             }
@@ -548,7 +620,7 @@ impl Ballot {
                     }.update(db).await?;
                 }
 
-                let scores_to_delete = scores.keys().filter(|adj| !new_team_entry.scores.contains_key(*adj)).map(|x| *x).collect_vec();
+                let scores_to_delete = scores.keys().filter(|adj| !new_team_entry.scores.contains_key(*adj) && !new_team_entry.trainee_scores.contains_key(*adj)).copied().collect_vec();
 
                 let mut filter_condition = Condition::any();
                 for adj in scores_to_delete.into_iter() {
@@ -563,14 +635,16 @@ impl Ballot {
                     filter_condition
                 ).exec(db).await?;
 
-                for (adj, score) in new_team_entry.scores.iter() {
+                for (adj, score) in new_team_entry.scores.iter().chain(new_team_entry.trainee_scores.iter()) {
                     if let Some(old_score) = scores.get(adj) {
-                        if score.total() as i32 != old_score.manual_total_score.unwrap() {
+                        let score_items = serialize_score_items(score.items());
+                        if Some(score.total().hundredths()) != old_score.manual_total_score || score_items != old_score.score_items {
                             schema::adjudicator_team_score::ActiveModel {
                                 adjudicator_id: ActiveValue::Unchanged(*adj),
                                 ballot_id: ActiveValue::Unchanged(self.uuid),
                                 role_id: ActiveValue::Unchanged(role.to_str()),
-                                manual_total_score: ActiveValue::Set(Some(score.total() as i32)),
+                                manual_total_score: ActiveValue::Set(Some(score.total().hundredths())),
+                                score_items: ActiveValue::Set(score_items),
                             }.update(db).await?;
                         }
                     }
@@ -579,7 +653,8 @@ impl Ballot {
                             adjudicator_id: ActiveValue::Set(*adj),
                             ballot_id: ActiveValue::Set(self.uuid),
                             role_id: ActiveValue::Set(role.to_str()),
-                            manual_total_score: ActiveValue::Set(Some(score.total() as i32)),
+                            manual_total_score: ActiveValue::Set(Some(score.total().hundredths())),
+                            score_items: ActiveValue::Set(serialize_score_items(score.items())),
                         }.insert(db).await?;
                     }
                 }
@@ -643,15 +718,17 @@ impl Ballot {
                     }.update(db).await?;
                 }
 
-                for (adj, score) in speech.scores.iter() {
+                for (adj, score) in speech.scores.iter().chain(speech.trainee_scores.iter()) {
                     if let Some(prev_score) = prev_scores.get(adj) {
-                        if prev_score.manual_total_score != Some(score.total() as i32) {
+                        let score_items = serialize_score_items(score.items());
+                        if prev_score.manual_total_score != Some(score.total().hundredths()) || prev_score.score_items != score_items {
                             schema::adjudicator_speech_score::ActiveModel {
                                 adjudicator_id: ActiveValue::Unchanged(*adj),
                                 ballot_id: ActiveValue::Unchanged(self.uuid),
                                 speech_role: ActiveValue::Unchanged(prev_speech.role.clone()),
                                 speech_position: ActiveValue::Unchanged(prev_speech.position ),
-                                manual_total_score: ActiveValue::Set(Some(score.total() as i32))
+                                manual_total_score: ActiveValue::Set(Some(score.total().hundredths())),
+                                score_items: ActiveValue::Set(score_items),
                             }.update(db).await?;
                         }
                     }
@@ -661,7 +738,8 @@ impl Ballot {
                             ballot_id: ActiveValue::Set(self.uuid),
                             speech_role: ActiveValue::Set(speech.role.to_str()),
                             speech_position: ActiveValue::Set(speech.position as i32),
-                            manual_total_score: ActiveValue::Set(Some(score.total() as i32)),
+                            manual_total_score: ActiveValue::Set(Some(score.total().hundredths())),
+                            score_items: ActiveValue::Set(serialize_score_items(score.items())),
                         }.insert(db).await?;    
                     }
                 }
@@ -675,13 +753,14 @@ impl Ballot {
                     is_opt_out: ActiveValue::Set(speech.is_opt_out),
                 }.insert(db).await?;
 
-                for (adj, score) in speech.scores.iter() {
+                for (adj, score) in speech.scores.iter().chain(speech.trainee_scores.iter()) {
                     schema::adjudicator_speech_score::ActiveModel {
                         adjudicator_id: ActiveValue::Set(*adj),
                         ballot_id: ActiveValue::Set(self.uuid),
                         speech_role: ActiveValue::Set(speech.role.to_str()),
                         speech_position: ActiveValue::Set(speech.position as i32),
-                        manual_total_score: ActiveValue::Set(Some(score.total() as i32)),
+                        manual_total_score: ActiveValue::Set(Some(score.total().hundredths())),
+                        score_items: ActiveValue::Set(serialize_score_items(score.items())),
                     }.insert(db).await?;
                 }
             }
@@ -867,6 +946,7 @@ impl TournamentEntityTrait for Ballot {
 
         related.extend(self.speeches.iter().filter_map(|s| s.speaker));
         related.extend(self.adjudicators.iter());
+        related.extend(self.trainees.iter());
 
         if let Some(president) = self.president {
             related.push(president);
@@ -1081,6 +1161,54 @@ mod test {
         Ok(())
     }
     
+    #[test]
+    fn test_trainee_scores_do_not_count_towards_speaker_score() -> Result<(), BallotParseError> {
+        let ballot = Ballot::from_models(
+            schema::ballot::Model {
+                uuid: Uuid::from_u128(100),
+            },
+            vec![],
+            vec![
+                schema::ballot_adjudicator::Model {
+                    ballot_id: Uuid::from_u128(100),
+                    adjudicator_id: Uuid::from_u128(301),
+                    position: 0, role: "n".into()
+                },
+                schema::ballot_adjudicator::Model {
+                    ballot_id: Uuid::from_u128(100),
+                    adjudicator_id: Uuid::from_u128(302),
+                    position: 0, role: "t".into()
+                }
+            ],
+            vec![],
+            vec![
+                schema::ballot_speech::Model {
+                    ballot_id: Uuid::from_u128(100),
+                    position: 0,
+                    is_opt_out: false,
+                    role: "g".into(),
+                    speaker_id: None
+                }
+            ],
+            vec![
+                schema::adjudicator_speech_score::Model {
+                    ballot_id:Uuid::from_u128(100),
+                    adjudicator_id: Uuid::from_u128(301),
+                    speech_role: "g".into(), speech_position: 0, manual_total_score: Some(7200), score_items: None },
+                schema::adjudicator_speech_score::Model {
+                    ballot_id:Uuid::from_u128(100),
+                    adjudicator_id: Uuid::from_u128(302),
+                    speech_role: "g".into(), speech_position: 0, manual_total_score: Some(5000), score_items: None }
+            ])?;
+
+        assert_eq!(ballot.adjudicators, vec![Uuid::from_u128(301)]);
+        assert_eq!(ballot.trainees, vec![Uuid::from_u128(302)]);
+        assert_eq!(ballot.speeches[0].trainee_scores, HashMap::from_iter(vec![(Uuid::from_u128(302), SpeakerScore::Aggregate { total: Score::from_points(50) })].into_iter()));
+        assert_eq!(ballot.speeches[0].speaker_score(), Some(72.0));
+
+        Ok(())
+    }
+
     #[test]
     fn test_get_ballot_with_two_presidents() -> Result<(), BallotParseError> {
         let result = Ballot::from_models(
@@ -1259,10 +1387,10 @@ mod test {
                 schema::adjudicator_speech_score::Model {
                     ballot_id:Uuid::from_u128(100),
                     adjudicator_id: Uuid::from_u128(301),
-                    speech_role: "g".into(), speech_position: 0, manual_total_score: Some(72) }
+                    speech_role: "g".into(), speech_position: 0, manual_total_score: Some(7200), score_items: None }
             ])?;
     
-        assert_eq!(ballot.speeches[0].scores, HashMap::from_iter(vec![(Uuid::from_u128(301), SpeakerScore::Aggregate { total: 72 })].into_iter()));
+        assert_eq!(ballot.speeches[0].scores, HashMap::from_iter(vec![(Uuid::from_u128(301), SpeakerScore::Aggregate { total: Score::from_points(72) })].into_iter()));
     
         Ok(())
     }
@@ -1278,13 +1406,47 @@ mod test {
             ],
             vec![],
             vec![
-                schema::adjudicator_team_score::Model { adjudicator_id: Uuid::from_u128(301), ballot_id: Uuid::from_u128(100), role_id: "g".into(), manual_total_score: Some(32) }
+                schema::adjudicator_team_score::Model { adjudicator_id: Uuid::from_u128(301), ballot_id: Uuid::from_u128(100), role_id: "g".into(), manual_total_score: Some(3200), score_items: None }
             ],
             vec![],
             vec![])?;
     
-        assert_eq!(ballot.government.scores, HashMap::from_iter(vec![(Uuid::from_u128(301), TeamScore::Aggregate { total: 32 })].into_iter()));
+        assert_eq!(ballot.government.scores, HashMap::from_iter(vec![(Uuid::from_u128(301), TeamScore::Aggregate { total: Score::from_points(32) })].into_iter()));
     
         Ok(())
     }    
+
+    #[test]
+    fn test_get_ballot_itemized_team_scores() -> Result<(), BallotParseError> {
+        let ballot = Ballot::from_models(
+            schema::ballot::Model {
+                uuid: Uuid::from_u128(100),
+            },
+            vec![
+                schema::ballot_team::Model { ballot_id: Uuid::from_u128(100),role:"g".into(), team_id: None },
+            ],
+            vec![],
+            vec![
+                schema::adjudicator_team_score::Model { adjudicator_id: Uuid::from_u128(301), ballot_id: Uuid::from_u128(100), role_id: "g".into(), manual_total_score: Some(3200), score_items: Some("{\"cooperation\":20,\"strategy\":12}".into()) }
+            ],
+            vec![],
+            vec![])?;
+
+        let score = &ballot.government.scores[&Uuid::from_u128(301)];
+        assert_eq!(score.total(), Score::from_points(32));
+        assert_eq!(score.items().map(|i| i["cooperation"]), Some(Score::from_points(20)));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_itemized_score_serde_roundtrip() {
+        let score = SpeakerScore::new_itemized(BTreeMap::from_iter(vec![("content".to_string(), Score::from_points(30)), ("style".to_string(), Score::from_points(25))]));
+        let serialized = serde_json::to_string(&score).unwrap();
+        assert_eq!(serialized, "{\"type\":\"Itemized\",\"items\":{\"content\":30,\"style\":25}}");
+        assert_eq!(serde_json::from_str::<SpeakerScore>(&serialized).unwrap(), score);
+
+        // Aggregate scores keep their format, so older clients can still read them
+        assert_eq!(serde_json::to_string(&SpeakerScore::new_aggregate(Score::from_points(55))).unwrap(), "{\"type\":\"Aggregate\",\"total\":55}");
+    }
 }
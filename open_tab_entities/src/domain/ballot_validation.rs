@@ -0,0 +1,489 @@
+use std::{collections::{BTreeMap, HashMap}, fmt::Display};
+
+use itertools::Itertools;
+use sea_orm::prelude::*;
+use serde::{Serialize, Deserialize};
+use thiserror::Error;
+
+use super::{ballot::{Ballot, SpeechRole}, entity::LoadEntity, score::Score, tournament::{ScoreCategory, ScoreCategorySchema, Tournament}};
+
+/// Rules a ballot has to satisfy before it is accepted from adjudicators or entered in the tab.
+/// Bounds that are `None` are not checked, so the default configuration accepts every ballot.
+#[derive(Debug, PartialEq, Eq, Serialize, Deserialize, Clone)]
+#[serde(default)]
+pub struct BallotValidationConfig {
+    pub min_speech_score: Option<Score>,
+    pub max_speech_score: Option<Score>,
+    pub min_team_score: Option<Score>,
+    pub max_team_score: Option<Score>,
+    /// Every score has to be a multiple of this step, for example 0.5 to allow half points.
+    /// Steps of zero or less accept every score.
+    pub score_step: Score,
+    /// Largest difference between the scores two voting adjudicators give for the same speech or team
+    pub max_panel_deviation: Option<Score>,
+    /// Whether an adjudicator may give both teams the same total
+    pub allow_tied_team_totals: bool,
+    /// When submitted ballots are accepted without the tab director
+    pub confirmation_policy: BallotConfirmationPolicy,
+    /// Categories that itemized scores have to match.
+    /// Stored on the tournament, and only loaded here so that every validation checks them.
+    #[serde(skip)]
+    pub score_categories: Option<ScoreCategorySchema>,
+}
+
+/// Decides when a submitted ballot replaces the ballot of a debate that has no results yet.
+/// Submissions are only confirmed if they pass validation and match the current draw.
+#[derive(Debug, PartialEq, Eq, Serialize, Deserialize, Clone, Copy, Default)]
+pub enum BallotConfirmationPolicy {
+    /// Every submission has to be accepted manually
+    Manual,
+    /// The first submission for a debate is accepted
+    #[default]
+    FirstSubmission,
+    /// A submission is accepted once a submission by another participant agrees with it
+    MatchingSubmissions,
+    /// Like `MatchingSubmissions`, but a submission by the chair is accepted immediately
+    ChairOrMatchingSubmissions,
+}
+
+impl Default for BallotValidationConfig {
+    fn default() -> Self {
+        BallotValidationConfig {
+            min_speech_score: None,
+            max_speech_score: None,
+            min_team_score: None,
+            max_team_score: None,
+            score_step: Score::from_points(1),
+            max_panel_deviation: None,
+            allow_tied_team_totals: true,
+            confirmation_policy: BallotConfirmationPolicy::default(),
+            score_categories: None,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Serialize, Deserialize, Clone)]
+#[serde(tag="type")]
+pub enum ScoreLocation {
+    Speech { role: SpeechRole, position: u8 },
+    Team { role: SpeechRole },
+}
+
+impl Display for ScoreLocation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let role_name = |role: &SpeechRole| match role {
+            SpeechRole::Government => "government",
+            SpeechRole::Opposition => "opposition",
+            SpeechRole::NonAligned => "non-aligned",
+        };
+        match self {
+            ScoreLocation::Speech { role, position } => write!(f, "{} speech {}", role_name(role), position + 1),
+            ScoreLocation::Team { role } => write!(f, "{} team score", role_name(role)),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Serialize, Deserialize, Clone, Error)]
+#[serde(tag="error")]
+pub enum BallotValidationError {
+    #[error("Score {score} for {location} is outside of the allowed range")]
+    ScoreOutOfRange { location: ScoreLocation, adjudicator_id: Uuid, score: Score, min: Option<Score>, max: Option<Score> },
+    #[error("Score {score} for {location} is not a multiple of {step}")]
+    ScoreNotMultipleOfStep { location: ScoreLocation, adjudicator_id: Uuid, score: Score, step: Score },
+    #[error("Scores for {location} differ by {deviation}, but at most {max_deviation} is allowed")]
+    PanelDeviationTooLarge { location: ScoreLocation, deviation: Score, max_deviation: Score },
+    #[error("Both teams have a total of {total}, but ties are not allowed")]
+    TiedTeamTotals { adjudicator_id: Uuid, total: Score },
+    #[error("Score for {location} has unknown category {category}")]
+    UnknownScoreCategory { location: ScoreLocation, adjudicator_id: Uuid, category: String },
+    #[error("Score for {location} is missing category {category}")]
+    MissingScoreCategory { location: ScoreLocation, adjudicator_id: Uuid, category: String },
+    #[error("Score {score} in category {category} for {location} is outside of the allowed range")]
+    CategoryScoreOutOfRange { location: ScoreLocation, adjudicator_id: Uuid, category: String, score: Score, max: i16 },
+}
+
+/// All violations found in a ballot, so that they can be shown at once.
+#[derive(Debug, PartialEq, Eq, Serialize, Deserialize, Clone, Error)]
+#[error("Invalid ballot: {}", .errors.iter().map(|e| e.to_string()).join(", "))]
+pub struct BallotValidationErrors {
+    pub errors: Vec<BallotValidationError>,
+}
+
+impl BallotValidationConfig {
+    pub async fn load_for_tournament<C>(db: &C, tournament_id: Uuid) -> Result<BallotValidationConfig, anyhow::Error> where C: sea_orm::ConnectionTrait {
+        let tournament = Tournament::get(db, tournament_id).await?;
+        Ok(BallotValidationConfig {
+            score_categories: tournament.score_categories,
+            ..tournament.ballot_validation_config.unwrap_or_default()
+        })
+    }
+
+    pub fn validate(&self, ballot: &Ballot) -> Result<(), BallotValidationErrors> {
+        let mut errors = vec![];
+
+        for speech in ballot.speeches.iter() {
+            let location = ScoreLocation::Speech { role: speech.role, position: speech.position };
+            let scores = sorted_totals(speech.scores.iter().map(|(adj, score)| (*adj, score.total())));
+            let trainee_scores = sorted_totals(speech.trainee_scores.iter().map(|(adj, score)| (*adj, score.total())));
+            self.check_scores(&location, &scores, &trainee_scores, self.min_speech_score, self.max_speech_score, &mut errors);
+
+            let categories = self.score_categories.as_ref().map(|c| &c.speech_categories);
+            for (adjudicator_id, score) in speech.scores.iter().chain(speech.trainee_scores.iter()).sorted_by_key(|(adj, _)| **adj) {
+                check_score_items(&location, *adjudicator_id, score.items(), categories, &mut errors);
+            }
+        }
+
+        for (role, team) in [(SpeechRole::Government, &ballot.government), (SpeechRole::Opposition, &ballot.opposition)] {
+            let location = ScoreLocation::Team { role };
+            let scores = sorted_totals(team.scores.iter().map(|(adj, score)| (*adj, score.total())));
+            let trainee_scores = sorted_totals(team.trainee_scores.iter().map(|(adj, score)| (*adj, score.total())));
+            self.check_scores(&location, &scores, &trainee_scores, self.min_team_score, self.max_team_score, &mut errors);
+
+            let categories = self.score_categories.as_ref().map(|c| &c.team_categories);
+            for (adjudicator_id, score) in team.scores.iter().chain(team.trainee_scores.iter()).sorted_by_key(|(adj, _)| **adj) {
+                check_score_items(&location, *adjudicator_id, score.items(), categories, &mut errors);
+            }
+        }
+
+        if !self.allow_tied_team_totals {
+            let government_totals = adjudicator_team_totals(ballot, SpeechRole::Government);
+            let opposition_totals = adjudicator_team_totals(ballot, SpeechRole::Opposition);
+
+            for (adjudicator_id, total) in government_totals.iter().sorted() {
+                if opposition_totals.get(adjudicator_id) == Some(total) {
+                    errors.push(BallotValidationError::TiedTeamTotals { adjudicator_id: *adjudicator_id, total: *total });
+                }
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        }
+        else {
+            Err(BallotValidationErrors { errors })
+        }
+    }
+
+    fn check_scores(
+        &self,
+        location: &ScoreLocation,
+        scores: &[(Uuid, Score)],
+        trainee_scores: &[(Uuid, Score)],
+        min: Option<Score>,
+        max: Option<Score>,
+        errors: &mut Vec<BallotValidationError>
+    ) {
+        for (adjudicator_id, score) in scores.iter().chain(trainee_scores.iter()) {
+            if min.is_some_and(|min| *score < min) || max.is_some_and(|max| *score > max) {
+                errors.push(BallotValidationError::ScoreOutOfRange { location: location.clone(), adjudicator_id: *adjudicator_id, score: *score, min, max });
+            }
+            if !score.is_multiple_of(self.score_step) {
+                errors.push(BallotValidationError::ScoreNotMultipleOfStep { location: location.clone(), adjudicator_id: *adjudicator_id, score: *score, step: self.score_step });
+            }
+        }
+
+        // Trainees do not vote, so their scores may deviate from the panel
+        if let (Some(max_deviation), Some((lowest, highest))) = (self.max_panel_deviation, scores.iter().map(|(_, score)| *score).minmax().into_option()) {
+            if highest - lowest > max_deviation {
+                errors.push(BallotValidationError::PanelDeviationTooLarge { location: location.clone(), deviation: highest - lowest, max_deviation });
+            }
+        }
+    }
+}
+
+/// Itemized scores have to contain exactly the categories of the tournament, each within its maximum.
+/// Aggregate scores are not checked, since tournaments can switch to itemized scores at any time.
+fn check_score_items(
+    location: &ScoreLocation,
+    adjudicator_id: Uuid,
+    items: Option<&BTreeMap<String, Score>>,
+    categories: Option<&Vec<ScoreCategory>>,
+    errors: &mut Vec<BallotValidationError>
+) {
+    let Some(items) = items else {
+        return;
+    };
+    let categories = categories.map(|c| c.as_slice()).unwrap_or_default();
+
+    for (category, score) in items.iter() {
+        match categories.iter().find(|c| &c.key == category) {
+            Some(category) => {
+                if *score < Score::ZERO || *score > Score::from_points(category.max_score) {
+                    errors.push(BallotValidationError::CategoryScoreOutOfRange { location: location.clone(), adjudicator_id, category: category.key.clone(), score: *score, max: category.max_score });
+                }
+            },
+            None => errors.push(BallotValidationError::UnknownScoreCategory { location: location.clone(), adjudicator_id, category: category.clone() })
+        }
+    }
+
+    for category in categories.iter().filter(|c| !items.contains_key(&c.key)) {
+        errors.push(BallotValidationError::MissingScoreCategory { location: location.clone(), adjudicator_id, category: category.key.clone() });
+    }
+}
+
+fn sorted_totals(scores: impl Iterator<Item=(Uuid, Score)>) -> Vec<(Uuid, Score)> {
+    scores.sorted_by_key(|(adj, _)| *adj).collect()
+}
+
+/// Totals of the speeches and team score each voting adjudicator gave to one team.
+/// Saturates, so that large scores can not overflow the total.
+fn adjudicator_team_totals(ballot: &Ballot, role: SpeechRole) -> HashMap<Uuid, Score> {
+    let team = match role {
+        SpeechRole::Government => &ballot.government,
+        SpeechRole::Opposition => &ballot.opposition,
+        SpeechRole::NonAligned => return HashMap::new(),
+    };
+
+    let mut totals : HashMap<Uuid, Score> = HashMap::new();
+    let speech_scores = ballot.speeches.iter().filter(|s| s.role == role).flat_map(|s| s.scores.iter().map(|(adj, score)| (*adj, score.total())));
+    let team_scores = team.scores.iter().map(|(adj, score)| (*adj, score.total()));
+
+    for (adjudicator_id, score) in speech_scores.chain(team_scores) {
+        let total = totals.entry(adjudicator_id).or_insert(Score::ZERO);
+        *total = total.saturating_add(score);
+    }
+
+    totals
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::{BTreeMap, HashMap};
+
+    use sea_orm::prelude::Uuid;
+
+    use crate::domain::{ballot::{Ballot, BallotTeam, SpeakerScore, Speech, SpeechRole, TeamScore}, score::Score, tournament::{ScoreCategory, ScoreCategorySchema}};
+
+    use super::{BallotValidationConfig, BallotValidationError, ScoreLocation};
+
+    fn speech(role: SpeechRole, position: u8, scores: Vec<(Uuid, i16)>) -> Speech {
+        Speech {
+            speaker: None,
+            role,
+            position,
+            scores: scores.into_iter().map(|(adj, s)| (adj, SpeakerScore::new_aggregate(s.into()))).collect(),
+            is_opt_out: false,
+            trainee_scores: HashMap::new(),
+        }
+    }
+
+    fn team(scores: Vec<(Uuid, i16)>) -> BallotTeam {
+        BallotTeam {
+            team: None,
+            scores: scores.into_iter().map(|(adj, s)| (adj, TeamScore::new_aggregate(s.into()))).collect(),
+            trainee_scores: HashMap::new(),
+        }
+    }
+
+    fn ballot(adj_1: Uuid, adj_2: Uuid, gov_score: i16, opp_score: i16) -> Ballot {
+        Ballot {
+            speeches: vec![
+                speech(SpeechRole::Government, 0, vec![(adj_1, gov_score), (adj_2, gov_score)]),
+                speech(SpeechRole::Opposition, 0, vec![(adj_1, opp_score), (adj_2, opp_score)]),
+            ],
+            government: team(vec![(adj_1, 40), (adj_2, 40)]),
+            opposition: team(vec![(adj_1, 40), (adj_2, 40)]),
+            adjudicators: vec![adj_1, adj_2],
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_default_config_accepts_any_scores() {
+        let ballot = ballot(Uuid::from_u128(1), Uuid::from_u128(2), 200, 2);
+        assert_eq!(BallotValidationConfig::default().validate(&ballot), Ok(()));
+    }
+
+    #[test]
+    fn test_score_bounds_and_step() {
+        let adj_1 = Uuid::from_u128(1);
+        let adj_2 = Uuid::from_u128(2);
+        let mut ballot = ballot(adj_1, adj_2, 72, 75);
+        ballot.speeches[0].trainee_scores.insert(Uuid::from_u128(3), SpeakerScore::new_aggregate(Score::from_points(200)));
+
+        let config = BallotValidationConfig {
+            min_speech_score: Some(Score::from_points(70)),
+            max_speech_score: Some(Score::from_points(80)),
+            score_step: Score::from_points(2),
+            ..Default::default()
+        };
+
+        let errors = config.validate(&ballot).unwrap_err().errors;
+        assert_eq!(errors, vec![
+            BallotValidationError::ScoreOutOfRange {
+                location: ScoreLocation::Speech { role: SpeechRole::Government, position: 0 },
+                adjudicator_id: Uuid::from_u128(3),
+                score: Score::from_points(200),
+                min: Some(Score::from_points(70)),
+                max: Some(Score::from_points(80)),
+            },
+            BallotValidationError::ScoreNotMultipleOfStep {
+                location: ScoreLocation::Speech { role: SpeechRole::Opposition, position: 0 },
+                adjudicator_id: adj_1,
+                score: Score::from_points(75),
+                step: Score::from_points(2),
+            },
+            BallotValidationError::ScoreNotMultipleOfStep {
+                location: ScoreLocation::Speech { role: SpeechRole::Opposition, position: 0 },
+                adjudicator_id: adj_2,
+                score: Score::from_points(75),
+                step: Score::from_points(2),
+            },
+        ]);
+    }
+
+    #[test]
+    fn test_panel_deviation_ignores_trainees() {
+        let adj_1 = Uuid::from_u128(1);
+        let adj_2 = Uuid::from_u128(2);
+        let mut ballot = ballot(adj_1, adj_2, 72, 75);
+        ballot.speeches[0].trainee_scores.insert(Uuid::from_u128(3), SpeakerScore::new_aggregate(Score::from_points(60)));
+
+        let config = BallotValidationConfig {
+            max_panel_deviation: Some(Score::from_points(3)),
+            ..Default::default()
+        };
+        assert_eq!(config.validate(&ballot), Ok(()));
+
+        ballot.speeches[1].scores.insert(adj_2, SpeakerScore::new_aggregate(Score::from_points(79)));
+        let errors = config.validate(&ballot).unwrap_err().errors;
+        assert_eq!(errors, vec![
+            BallotValidationError::PanelDeviationTooLarge {
+                location: ScoreLocation::Speech { role: SpeechRole::Opposition, position: 0 },
+                deviation: Score::from_points(4),
+                max_deviation: Score::from_points(3),
+            }
+        ]);
+    }
+
+    #[test]
+    fn test_tied_team_totals() {
+        let adj_1 = Uuid::from_u128(1);
+        let adj_2 = Uuid::from_u128(2);
+        let mut ballot = ballot(adj_1, adj_2, 75, 75);
+        ballot.speeches[0].scores.insert(adj_2, SpeakerScore::new_aggregate(Score::from_points(76)));
+
+        let config = BallotValidationConfig {
+            allow_tied_team_totals: false,
+            ..Default::default()
+        };
+
+        let errors = config.validate(&ballot).unwrap_err().errors;
+        assert_eq!(errors, vec![
+            BallotValidationError::TiedTeamTotals { adjudicator_id: adj_1, total: Score::from_points(115) }
+        ]);
+    }
+
+    #[test]
+    fn test_itemized_scores_match_categories() {
+        let adj_1 = Uuid::from_u128(1);
+        let adj_2 = Uuid::from_u128(2);
+        let mut ballot = ballot(adj_1, adj_2, 75, 75);
+        ballot.speeches[0].scores.insert(adj_1, SpeakerScore::new_itemized(BTreeMap::from([
+            ("content".to_string(), Score::from_points(40)),
+            ("style".to_string(), Score::from_points(45)),
+        ])));
+        ballot.speeches[1].scores.insert(adj_1, SpeakerScore::new_itemized(BTreeMap::from([
+            ("content".to_string(), Score::from_points(30)),
+            ("humour".to_string(), Score::from_points(10)),
+        ])));
+
+        let config = BallotValidationConfig {
+            score_categories: Some(ScoreCategorySchema {
+                speech_categories: vec![
+                    ScoreCategory { key: "content".into(), name: "Content".into(), max_score: 40 },
+                    ScoreCategory { key: "style".into(), name: "Style".into(), max_score: 40 },
+                ],
+                team_categories: vec![],
+            }),
+            ..Default::default()
+        };
+
+        let errors = config.validate(&ballot).unwrap_err().errors;
+        assert_eq!(errors, vec![
+            BallotValidationError::CategoryScoreOutOfRange {
+                location: ScoreLocation::Speech { role: SpeechRole::Government, position: 0 },
+                adjudicator_id: adj_1,
+                category: "style".into(),
+                score: Score::from_points(45),
+                max: 40,
+            },
+            BallotValidationError::UnknownScoreCategory {
+                location: ScoreLocation::Speech { role: SpeechRole::Opposition, position: 0 },
+                adjudicator_id: adj_1,
+                category: "humour".into(),
+            },
+            BallotValidationError::MissingScoreCategory {
+                location: ScoreLocation::Speech { role: SpeechRole::Opposition, position: 0 },
+                adjudicator_id: adj_1,
+                category: "style".into(),
+            },
+        ]);
+    }
+
+    #[test]
+    fn test_itemized_total_saturates() {
+        let score = SpeakerScore::new_itemized(BTreeMap::from([
+            ("a".to_string(), Score::MAX),
+            ("b".to_string(), Score::from_points(1)),
+        ]));
+        assert_eq!(score.total(), Score::MAX);
+    }
+
+    #[test]
+    fn test_steps_of_one_point_accept_every_score() {
+        let ballot = ballot(Uuid::from_u128(1), Uuid::from_u128(2), 72, 75);
+
+        for score_step in [-1, 0, 1] {
+            let config = BallotValidationConfig {
+                score_step: Score::from_points(score_step),
+                ..Default::default()
+            };
+            assert_eq!(config.validate(&ballot), Ok(()));
+        }
+    }
+
+    #[test]
+    fn test_tied_team_totals_do_not_overflow() {
+        let adj_1 = Uuid::from_u128(1);
+        let adj_2 = Uuid::from_u128(2);
+        let mut ballot = ballot(adj_1, adj_2, 30000, 30000);
+        ballot.speeches.push(speech(SpeechRole::Government, 1, vec![(adj_1, 30000), (adj_2, 30000)]));
+        ballot.speeches.push(speech(SpeechRole::Opposition, 1, vec![(adj_1, 30000), (adj_2, 29999)]));
+
+        let config = BallotValidationConfig {
+            allow_tied_team_totals: false,
+            ..Default::default()
+        };
+
+        let errors = config.validate(&ballot).unwrap_err().errors;
+        assert_eq!(errors, vec![
+            BallotValidationError::TiedTeamTotals { adjudicator_id: adj_1, total: Score::from_hundredths(6_004_000) }
+        ]);
+    }
+
+    #[test]
+    fn test_half_point_steps() {
+        let adj_1 = Uuid::from_u128(1);
+        let adj_2 = Uuid::from_u128(2);
+        let mut ballot = ballot(adj_1, adj_2, 72, 75);
+        ballot.speeches[0].scores.insert(adj_1, SpeakerScore::new_aggregate(Score::from_hundredths(7250)));
+
+        let config = BallotValidationConfig {
+            score_step: Score::from_hundredths(50),
+            ..Default::default()
+        };
+        assert_eq!(config.validate(&ballot), Ok(()));
+
+        ballot.speeches[0].scores.insert(adj_2, SpeakerScore::new_aggregate(Score::from_hundredths(7225)));
+        let errors = config.validate(&ballot).unwrap_err().errors;
+        assert_eq!(errors, vec![
+            BallotValidationError::ScoreNotMultipleOfStep {
+                location: ScoreLocation::Speech { role: SpeechRole::Government, position: 0 },
+                adjudicator_id: adj_2,
+                score: Score::from_hundredths(7225),
+                step: Score::from_hundredths(50),
+            }
+        ]);
+    }
+}
@@ -21,7 +21,35 @@ pub struct TournamentDebate {
     pub ballot_id: Uuid,
     pub venue_id: Option<Uuid>,
     pub is_motion_released_to_non_aligned: bool,
-    pub is_complete: bool
+    pub is_complete: bool,
+    #[serialize]
+    pub pins: Option<DebatePins>
+}
+
+/// Draw positions that are kept fixed when the allocation of a round is re-run.
+/// Positions are stored by index, so a pin applies to whoever occupies the position.
+#[derive(Debug, PartialEq, Eq, Serialize, Deserialize, Clone, Default)]
+pub struct DebatePins {
+    #[serde(default)]
+    pub government: bool,
+    #[serde(default)]
+    pub opposition: bool,
+    /// Indices of the pinned non-aligned speaker positions
+    #[serde(default)]
+    pub non_aligned_speakers: Vec<usize>,
+    #[serde(default)]
+    pub chair: bool,
+    /// Indices of the pinned wings, not counting the chair
+    #[serde(default)]
+    pub wings: Vec<usize>,
+    #[serde(default)]
+    pub president: bool,
+}
+
+impl DebatePins {
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
 }
 
 
@@ -38,7 +66,8 @@ impl TournamentDebate {
             ballot_id,
             venue_id,
             is_motion_released_to_non_aligned: false,
-            is_complete: false
+            is_complete: false,
+            pins: None
         }
     }
 
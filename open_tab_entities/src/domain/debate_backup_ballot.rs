@@ -18,6 +18,8 @@ pub struct DebateBackupBallot {
     pub ballot_id: Uuid,
     pub timestamp: DateTime,
     pub was_seen: bool,
+    /// Participant that submitted the ballot. `None` for ballots entered in the tab.
+    pub author_participant_id: Option<Uuid>,
 }
 
 
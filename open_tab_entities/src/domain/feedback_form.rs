@@ -32,6 +32,8 @@ pub struct FeedbackFormVisibility {
     pub show_chairs_for_wings: bool,
     #[serde(default)]
     pub show_chairs_for_presidents: bool,
+    #[serde(default)]
+    pub show_chairs_for_trainees: bool,
 
     #[serde(default)]
     pub show_wings_for_chairs: bool,
@@ -65,6 +67,7 @@ impl FeedbackFormVisibility {
     pub fn union(&mut self, other: &FeedbackFormVisibility) {
         self.show_chairs_for_wings |= other.show_chairs_for_wings;
         self.show_chairs_for_presidents |= other.show_chairs_for_presidents;
+        self.show_chairs_for_trainees |= other.show_chairs_for_trainees;
 
         self.show_wings_for_chairs |= other.show_wings_for_chairs;
         self.show_wings_for_presidents |= other.show_wings_for_presidents;
@@ -115,12 +118,13 @@ impl FromStr for FeedbackSourceRole {
 }
 
 
-#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
 #[serde(tag="type")]
 pub enum FeedbackTargetRole {
     Chair,
     Wing,
-    President
+    President,
+    Trainee
 }
 
 
@@ -132,6 +136,7 @@ impl FromStr for FeedbackTargetRole {
             "chair" => Ok(FeedbackTargetRole::Chair),
             "wing" => Ok(FeedbackTargetRole::Wing),
             "president" => Ok(FeedbackTargetRole::President),
+            "trainee" => Ok(FeedbackTargetRole::Trainee),
             _ => Err(RoleParseError::InvalidRole(s.into()))
         }
     }
@@ -144,6 +149,7 @@ impl BitOr<&FeedbackFormVisibility> for FeedbackFormVisibility {
         FeedbackFormVisibility {
             show_chairs_for_wings: self.show_chairs_for_wings || rhs.show_chairs_for_wings,
             show_chairs_for_presidents: self.show_chairs_for_presidents || rhs.show_chairs_for_presidents,
+            show_chairs_for_trainees: self.show_chairs_for_trainees || rhs.show_chairs_for_trainees,
             show_wings_for_chairs: self.show_wings_for_chairs || rhs.show_wings_for_chairs,
             show_wings_for_presidents: self.show_wings_for_presidents || rhs.show_wings_for_presidents,
             show_wings_for_wings: self.show_wings_for_wings || rhs.show_wings_for_wings,
@@ -164,6 +170,7 @@ impl FeedbackFormVisibility {
         FeedbackFormVisibility {
             show_chairs_for_wings: true,
             show_chairs_for_presidents: true,
+            show_chairs_for_trainees: true,
             show_wings_for_chairs: true,
             show_wings_for_presidents: true,
             show_wings_for_wings: true,
@@ -188,6 +195,10 @@ impl FeedbackFormVisibility {
             pairs.push((FeedbackSourceRole::Chair, FeedbackTargetRole::President));
         }
 
+        if self.show_chairs_for_trainees {
+            pairs.push((FeedbackSourceRole::Chair, FeedbackTargetRole::Trainee));
+        }
+
         if self.show_wings_for_chairs {
             pairs.push((FeedbackSourceRole::Wing, FeedbackTargetRole::Chair));
         }
@@ -273,6 +284,7 @@ impl<C> BoundTournamentEntityTrait<C> for FeedbackForm where C: sea_orm::Connect
             name: ActiveValue::Set(self.name.clone()),
             show_chairs_for_wings: ActiveValue::Set(self.visibility.show_chairs_for_wings),
             show_chairs_for_presidents: ActiveValue::Set(self.visibility.show_chairs_for_presidents),
+            show_chairs_for_trainees: ActiveValue::Set(self.visibility.show_chairs_for_trainees),
             show_wings_for_chairs: ActiveValue::Set(self.visibility.show_wings_for_chairs),
             show_wings_for_presidents: ActiveValue::Set(self.visibility.show_wings_for_presidents),
             show_wings_for_wings: ActiveValue::Set(self.visibility.show_wings_for_wings),
@@ -388,6 +400,7 @@ impl FeedbackForm {
                 visibility: FeedbackFormVisibility {
                     show_chairs_for_wings: form.show_chairs_for_wings,
                     show_chairs_for_presidents: form.show_chairs_for_presidents,
+                    show_chairs_for_trainees: form.show_chairs_for_trainees,
                     show_wings_for_chairs: form.show_wings_for_chairs,
                     show_wings_for_presidents: form.show_wings_for_presidents,
                     show_wings_for_wings: form.show_wings_for_wings,
@@ -413,6 +426,7 @@ impl FeedbackForm {
                     FeedbackTargetRole::Chair => false,
                     FeedbackTargetRole::Wing => self.visibility.show_chairs_for_wings,
                     FeedbackTargetRole::President => self.visibility.show_chairs_for_presidents,
+                    FeedbackTargetRole::Trainee => self.visibility.show_chairs_for_trainees,
                 }
             },
             FeedbackSourceRole::Wing => {
@@ -420,6 +434,7 @@ impl FeedbackForm {
                     FeedbackTargetRole::Chair => self.visibility.show_wings_for_chairs,
                     FeedbackTargetRole::Wing => self.visibility.show_wings_for_wings,
                     FeedbackTargetRole::President => self.visibility.show_wings_for_presidents,
+                    FeedbackTargetRole::Trainee => false,
                 }
             },
             FeedbackSourceRole::President => {
@@ -427,6 +442,7 @@ impl FeedbackForm {
                     FeedbackTargetRole::Chair => self.visibility.show_presidents_for_chairs,
                     FeedbackTargetRole::Wing => self.visibility.show_presidents_for_wings,
                     FeedbackTargetRole::President => false,
+                    FeedbackTargetRole::Trainee => false,
                 }
             },
             FeedbackSourceRole::Team => {
@@ -434,6 +450,7 @@ impl FeedbackForm {
                     FeedbackTargetRole::Chair => self.visibility.show_teams_for_chairs,
                     FeedbackTargetRole::Wing => self.visibility.show_teams_for_wings,
                     FeedbackTargetRole::President => self.visibility.show_teams_for_presidents,
+                    FeedbackTargetRole::Trainee => false,
                 }
             },
             FeedbackSourceRole::NonAligned => {
@@ -441,6 +458,7 @@ impl FeedbackForm {
                     FeedbackTargetRole::Chair => self.visibility.show_non_aligned_for_chairs,
                     FeedbackTargetRole::Wing => self.visibility.show_non_aligned_for_wings,
                     FeedbackTargetRole::President => self.visibility.show_non_aligned_for_presidents,
+                    FeedbackTargetRole::Trainee => false,
                 }
             },
         }
@@ -234,4 +234,17 @@ impl FeedbackResponse {
 
         vals
     }
+
+    pub async fn get_all_in_debates<C>(db: &C, debate_ids: Vec<Uuid>) -> Result<Vec<Self>, anyhow::Error> where C: sea_orm::ConnectionTrait {
+        let responses = schema::feedback_response::Entity::find()
+            .find_with_related(schema::feedback_response_value::Entity)
+            .filter(schema::feedback_response::Column::SourceDebateId.is_in(debate_ids))
+            .all(db).await?;
+
+        responses.into_iter().map(
+            |(response, response_values)| {
+                FeedbackResponse::from_rows(response, response_values)
+            }
+        ).collect()
+    }
 }
\ No newline at end of file
@@ -1,4 +1,6 @@
 pub mod ballot;
+pub mod ballot_validation;
+pub mod score;
 pub mod team;
 pub mod participant;
 pub mod entity;
@@ -19,6 +21,7 @@ pub mod ballot_speech_timing;
 pub mod clash_declaration;
 pub mod institution_declaration; 
 pub mod tournament_break_category;
+pub mod speaker_substitution;
 
 pub use entity::BoundTournamentEntityTrait;
 
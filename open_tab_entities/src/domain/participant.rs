@@ -22,6 +22,9 @@ pub struct Participant {
     pub registration_key: Option<Vec<u8>>,
     pub is_anonymous: bool,
     pub break_category_id: Option<Uuid>,
+    /// The participant can only take part in debates held in accessible venues
+    #[serde(default)]
+    pub requires_accessible_venue: bool,
 }
 
 impl Participant {
@@ -40,6 +43,7 @@ impl Participant {
             registration_key: None,
             is_anonymous: false,
             break_category_id: None,
+            requires_accessible_venue: false,
         }
     }
 }
@@ -65,7 +69,10 @@ pub struct Speaker {
 pub struct Adjudicator {
     pub chair_skill: i16,
     pub panel_skill: i16,
-    pub unavailable_rounds: Vec<Uuid>
+    pub unavailable_rounds: Vec<Uuid>,
+    /// Trainees shadow panels without a vote and are not allocated as chairs or wings
+    #[serde(default)]
+    pub is_trainee: bool,
 }
 
 #[derive(thiserror::Error, Debug, PartialEq, Eq)]
@@ -162,7 +169,8 @@ impl Participant {
             (None, Some(adj)) => Ok(ParticipantRole::Adjudicator(Adjudicator{
                 chair_skill: adj.chair_skill,
                 panel_skill: adj.panel_skill,
-                unavailable_rounds: adjudicator_overides.get(&adj.uuid).cloned().unwrap_or(vec![])
+                unavailable_rounds: adjudicator_overides.get(&adj.uuid).cloned().unwrap_or(vec![]),
+                is_trainee: adj.is_trainee,
             })),
             (Some(speaker), None) => Ok(ParticipantRole::Speaker(Speaker{team_id: speaker.team_id})),
             (Some(_), Some(_)) => Err(ParticipantParseError::MultipleRoles),
@@ -183,7 +191,8 @@ impl Participant {
             tournament_id: participant.tournament_id,
             institutions: institutions,
             is_anonymous: participant.is_anonymous,
-            break_category_id: participant.break_category_id
+            break_category_id: participant.break_category_id,
+            requires_accessible_venue: participant.requires_accessible_venue,
         })
     }
 }
@@ -274,6 +283,7 @@ impl<C> BoundTournamentEntityTrait<C> for Participant where C: sea_orm::Connecti
                 registration_key: ActiveValue::Set(ent.registration_key.clone()),
                 is_anonymous: ActiveValue::Set(ent.is_anonymous),
                 break_category_id: ActiveValue::Set(ent.break_category_id),
+                requires_accessible_venue: ActiveValue::Set(ent.requires_accessible_venue),
             };
 
             if let Some((_part_model, adj_model, speaker_model, institution_models)) = existing.get(&ent.uuid) {
@@ -288,12 +298,12 @@ impl<C> BoundTournamentEntityTrait<C> for Participant where C: sea_orm::Connecti
                             speaker_model.clone().into()
                         );
                         adj_changes.insert.push(
-                            adjudicator::ActiveModel { uuid: ActiveValue::Set(ent.uuid), chair_skill: ActiveValue::Set(adj.chair_skill), panel_skill: ActiveValue::Set(adj.panel_skill) }
+                            adjudicator::ActiveModel { uuid: ActiveValue::Set(ent.uuid), chair_skill: ActiveValue::Set(adj.chair_skill), panel_skill: ActiveValue::Set(adj.panel_skill), is_trainee: ActiveValue::Set(adj.is_trainee) }
                         );
                     },
                     (ParticipantRole::Adjudicator(adj), Some(_m), None) => {
                         adj_changes.update.push(
-                            adjudicator::ActiveModel { uuid: ActiveValue::Set(ent.uuid), chair_skill: ActiveValue::Set(adj.chair_skill), panel_skill: ActiveValue::Set(adj.panel_skill) }
+                            adjudicator::ActiveModel { uuid: ActiveValue::Set(ent.uuid), chair_skill: ActiveValue::Set(adj.chair_skill), panel_skill: ActiveValue::Set(adj.panel_skill), is_trainee: ActiveValue::Set(adj.is_trainee) }
                         )
                     },
                     (ParticipantRole::Speaker(speaker), None, Some(speaker_model)) => {
@@ -359,6 +369,7 @@ impl<C> BoundTournamentEntityTrait<C> for Participant where C: sea_orm::Connecti
                             uuid: ActiveValue::Set(ent.uuid),
                             chair_skill: ActiveValue::Set(adj.chair_skill),
                             panel_skill: ActiveValue::Set(adj.panel_skill),
+                            is_trainee: ActiveValue::Set(adj.is_trainee),
                         });
                     },
                 }
@@ -463,6 +474,7 @@ fn test_get_speaker() -> Result<(), ParticipantParseError> {
             registration_key: None,
             is_anonymous: false,
             break_category_id: None,
+            requires_accessible_venue: false,
         },
         Some(schema::speaker::Model {
             uuid: Uuid::from_u128(400),
@@ -495,9 +507,10 @@ fn test_get_adjudicator() -> Result<(), ParticipantParseError> {
             registration_key: None,
             is_anonymous: false,
             break_category_id: None,
+            requires_accessible_venue: false,
         },
         None,
-        Some(schema::adjudicator::Model { uuid: Uuid::from_u128(400), chair_skill: 0, panel_skill: 0 }),
+        Some(schema::adjudicator::Model { uuid: Uuid::from_u128(400), chair_skill: 0, panel_skill: 0, is_trainee: false }),
         vec![],
         &HashMap::new()
     )?;
@@ -527,12 +540,13 @@ mod test {
                 registration_key: None,
                 is_anonymous: false,
                 break_category_id: None,
+                requires_accessible_venue: false,
             },
             Some(schema::speaker::Model {
                 uuid: Uuid::from_u128(400),
                 team_id: Some(Uuid::from_u128(200)),
             }),
-            Some(schema::adjudicator::Model { uuid: Uuid::from_u128(400), chair_skill: 0, panel_skill: 0 }),
+            Some(schema::adjudicator::Model { uuid: Uuid::from_u128(400), chair_skill: 0, panel_skill: 0, is_trainee: false }),
             vec![],
             &HashMap::new()
         );
@@ -552,6 +566,7 @@ mod test {
                 registration_key: None,
                 is_anonymous: false,
                 break_category_id: None,
+                requires_accessible_venue: false,
             },
             Some(schema::speaker::Model {
                 uuid: Uuid::from_u128(400),
@@ -51,6 +51,9 @@ pub struct TournamentRound {
     pub round_close_time: Option<chrono::NaiveDateTime>,
     pub feedback_release_time: Option<chrono::NaiveDateTime>,
     pub silent_round_results_release_time: Option<chrono::NaiveDateTime>,
+
+    /// Seed of the random number generator used for the most recent draw of this round
+    pub draw_seed: Option<i64>,
 }
 
 impl TournamentRound {
@@ -67,6 +70,12 @@ impl TournamentRound {
         let rounds = schema::tournament_round::Entity::find().filter(schema::tournament_round::Column::TournamentId.eq(tournament_id)).all(db).await?;
         Ok(rounds.into_iter().map(TournamentRound::from_model).collect())
     }
+
+    /// Time until which adjudicators can change their availability for this round.
+    /// If the draw release is not scheduled, there is no deadline.
+    pub fn availability_change_deadline(&self, deadline_minutes: i32) -> Option<chrono::NaiveDateTime> {
+        self.draw_release_time.map(|t| t - chrono::Duration::minutes(deadline_minutes as i64))
+    }
 }
 
 
@@ -0,0 +1,117 @@
+use std::{fmt::Display, ops::{Add, Sub}};
+
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+
+const HUNDREDTHS_PER_POINT: i32 = 100;
+
+/// A score, stored in hundredths of a point so that fractional scores such as half points are exact.
+/// Serialized as a number of points, so whole scores keep the format of plain integers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Score(i32);
+
+impl Score {
+    pub const ZERO: Score = Score(0);
+    pub const MAX: Score = Score(i32::MAX);
+
+    pub fn from_points(points: i16) -> Score {
+        Score(points as i32 * HUNDREDTHS_PER_POINT)
+    }
+
+    pub fn from_hundredths(hundredths: i32) -> Score {
+        Score(hundredths)
+    }
+
+    pub fn hundredths(&self) -> i32 {
+        self.0
+    }
+
+    pub fn points(&self) -> f64 {
+        self.0 as f64 / HUNDREDTHS_PER_POINT as f64
+    }
+
+    /// Saturates instead of overflowing.
+    pub fn saturating_add(self, other: Score) -> Score {
+        Score(self.0.saturating_add(other.0))
+    }
+
+    /// Steps of zero or less accept every score.
+    pub fn is_multiple_of(&self, step: Score) -> bool {
+        step.0 <= 0 || self.0 % step.0 == 0
+    }
+}
+
+impl From<i16> for Score {
+    fn from(points: i16) -> Self {
+        Score::from_points(points)
+    }
+}
+
+impl Add for Score {
+    type Output = Score;
+
+    fn add(self, other: Score) -> Score {
+        Score(self.0 + other.0)
+    }
+}
+
+impl Sub for Score {
+    type Output = Score;
+
+    fn sub(self, other: Score) -> Score {
+        Score(self.0 - other.0)
+    }
+}
+
+impl Display for Score {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.points())
+    }
+}
+
+impl Serialize for Score {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error> where S: Serializer {
+        if self.0 % HUNDREDTHS_PER_POINT == 0 {
+            serializer.serialize_i32(self.0 / HUNDREDTHS_PER_POINT)
+        }
+        else {
+            serializer.serialize_f64(self.points())
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for Score {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error> where D: Deserializer<'de> {
+        let points = f64::deserialize(deserializer)?;
+        let hundredths = (points * HUNDREDTHS_PER_POINT as f64).round();
+        if !hundredths.is_finite() || hundredths < i32::MIN as f64 || hundredths > i32::MAX as f64 {
+            return Err(de::Error::custom(format!("Score {} is out of range", points)));
+        }
+        Ok(Score(hundredths as i32))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Score;
+
+    #[test]
+    fn test_whole_scores_serialize_as_integers() {
+        assert_eq!(serde_json::to_string(&Score::from_points(75)).unwrap(), "75");
+        assert_eq!(serde_json::from_str::<Score>("75").unwrap(), Score::from_points(75));
+    }
+
+    #[test]
+    fn test_half_points_are_exact() {
+        let score = serde_json::from_str::<Score>("72.5").unwrap();
+        assert_eq!(score.hundredths(), 7250);
+        assert_eq!(serde_json::to_string(&score).unwrap(), "72.5");
+        assert_eq!(score.to_string(), "72.5");
+        assert!(score.is_multiple_of(Score::from_hundredths(50)));
+        assert!(!score.is_multiple_of(Score::from_points(1)));
+    }
+
+    #[test]
+    fn test_out_of_range_scores_are_rejected() {
+        assert!(serde_json::from_str::<Score>("1e300").is_err());
+    }
+}
@@ -0,0 +1,57 @@
+use std::collections::HashMap;
+
+use async_trait::async_trait;
+use itertools::Itertools;
+use sea_orm::prelude::*;
+use serde::{Serialize, Deserialize};
+
+use crate::schema;
+use crate::domain::entity::LoadEntity;
+
+use super::round::TournamentRound;
+
+use open_tab_macros::SimpleEntity;
+
+
+/// Records that a speaker spoke for a team in a round.
+/// The speaker is either a reserve, a member of another team, or a member
+/// of the team giving an additional speech in place of a teammate (an iron speaker).
+#[derive(Debug, PartialEq, Eq, Serialize, Deserialize, Clone, SimpleEntity)]
+#[module_path = "crate::schema::speaker_substitution"]
+#[get_many_tournaments_func = "get_many_tournaments_impl"]
+pub struct SpeakerSubstitution {
+    pub uuid: Uuid,
+    pub round_id: Uuid,
+    pub team_id: Uuid,
+    pub speaker_id: Uuid,
+    /// The team member that did not speak in this round, if any
+    pub replaced_speaker_id: Option<Uuid>,
+}
+
+impl SpeakerSubstitution {
+    pub fn new(round_id: Uuid, team_id: Uuid, speaker_id: Uuid, replaced_speaker_id: Option<Uuid>) -> Self {
+        Self {
+            uuid: Uuid::new_v4(),
+            round_id,
+            team_id,
+            speaker_id,
+            replaced_speaker_id,
+        }
+    }
+
+    async fn get_many_tournaments_impl<C>(db: &C, entities: &Vec<&Self>) -> Result<Vec<Option<Uuid>>, anyhow::Error> where C: sea_orm::ConnectionTrait {
+        let rounds = TournamentRound::get_many(db, entities.iter().map(|s| s.round_id).unique().collect()).await?;
+        let tournament_ids_by_round_id = rounds.into_iter().map(|r| (r.uuid, r.tournament_id)).collect::<HashMap<_, _>>();
+
+        Ok(entities.iter().map(|s| tournament_ids_by_round_id.get(&s.round_id).cloned()).collect())
+    }
+
+    pub async fn get_all_in_rounds<C>(db: &C, round_ids: Vec<Uuid>) -> Result<Vec<SpeakerSubstitution>, anyhow::Error> where C: sea_orm::ConnectionTrait {
+        let substitutions = schema::speaker_substitution::Entity::find()
+            .filter(schema::speaker_substitution::Column::RoundId.is_in(round_ids))
+            .all(db)
+            .await?;
+
+        Ok(substitutions.into_iter().map(Self::from_model).collect())
+    }
+}
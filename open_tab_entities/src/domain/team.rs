@@ -14,7 +14,11 @@ use crate::schema;
 pub struct Team {
     pub uuid: Uuid,
     pub name: String,
-    pub tournament_id: Uuid
+    pub tournament_id: Uuid,
+    /// Swing teams are only formed to fill up the draw.
+    /// They are excluded from the tab and the break.
+    #[serde(default)]
+    pub is_swing: bool,
 }
 
 impl Team {
@@ -1,8 +1,13 @@
 use async_trait::async_trait;
 use open_tab_macros::SimpleEntity;
+use ordered_float::OrderedFloat;
 use sea_orm::prelude::*;
 use serde::{Serialize, Deserialize};
 
+use crate::tab::TabRankingConfig;
+
+use super::{ballot_validation::BallotValidationConfig, entity::LoadEntity};
+
 
 #[derive(Debug, PartialEq, Eq, Serialize, Deserialize, Clone, Default, SimpleEntity)]
 #[module_path = "crate::schema::tournament"]
@@ -16,8 +21,102 @@ pub struct Tournament {
     pub allow_self_declared_clashes: bool,
     pub allow_speaker_self_declared_clashes: bool,
     pub show_declared_clashes: bool,
+    #[serialize]
+    pub score_categories: Option<ScoreCategorySchema>,
+    #[serialize]
+    pub tab_ranking_config: Option<TabRankingConfig>,
+    /// Adjudicators can mark rounds they can not judge via the participant frontend
+    pub allow_self_declared_availability: bool,
+    /// Minutes before the draw release after which adjudicators can no longer change their availability
+    pub availability_deadline_minutes: i32,
+    /// Weights used when drawing rounds. `None` uses the default weights.
+    #[serialize]
+    pub draw_config: Option<DrawConfig>,
+    /// Rules for submitted and entered ballots. `None` accepts every ballot.
+    #[serialize]
+    pub ballot_validation_config: Option<BallotValidationConfig>,
+}
+
+/// Categories in which adjudicators award itemized scores.
+/// The keys of the categories are used as keys in `SpeakerScore::Itemized` and `TeamScore::Itemized`.
+#[derive(Debug, PartialEq, Eq, Serialize, Deserialize, Clone, Default)]
+pub struct ScoreCategorySchema {
+    pub speech_categories: Vec<ScoreCategory>,
+    pub team_categories: Vec<ScoreCategory>,
+}
+
+#[derive(Debug, PartialEq, Eq, Serialize, Deserialize, Clone)]
+pub struct ScoreCategory {
+    pub key: String,
+    pub name: String,
+    pub max_score: i16,
 }
 
+/// Weights the draw uses to trade off clashes, repeated meetings and adjudicator skill.
+/// Clash factors scale the severity of clashes between two kinds of participants,
+/// repeat clash severities are the severity of two participants meeting again.
+#[derive(Debug, PartialEq, Eq, Serialize, Deserialize, Clone)]
+#[serde(default)]
+pub struct DrawConfig {
+    pub adj_adj_clash_factor: OrderedFloat<f32>,
+    pub adj_team_clash_factor: OrderedFloat<f32>,
+    pub adj_speaker_clash_factor: OrderedFloat<f32>,
+    pub team_team_clash_factor: OrderedFloat<f32>,
+    pub team_speaker_clash_factor: OrderedFloat<f32>,
+    pub speaker_speaker_clash_factor: OrderedFloat<f32>,
+
+    pub adj_adj_repeat_clash_severity: u16,
+    pub adj_team_repeat_clash_severity: u16,
+    pub adj_non_aligned_speaker_repeat_clash_severity: u16,
+    pub team_team_repeat_clash_severity: u16,
+    pub team_speaker_repeat_clash_severity: u16,
+    pub non_aligned_speakers_repeat_clash_severity: u16,
+
+    pub feedback_weight: OrderedFloat<f32>,
+    pub moderation_weight: OrderedFloat<f32>,
+    pub max_discussion_improvement_weight: OrderedFloat<f32>,
+    /// Adjudicators with a clash of at least this severity are never placed in a debate
+    pub hard_clash_threshold: i32,
+    pub bias_weight: OrderedFloat<f32>,
+    pub variance_weight: OrderedFloat<f32>,
+    /// Weight of the chair's feedback skill when allocating trainees
+    pub trainee_mentor_weight: OrderedFloat<f32>,
+}
+
+impl Default for DrawConfig {
+    fn default() -> Self {
+        DrawConfig {
+            adj_adj_clash_factor: OrderedFloat(0.3),
+            adj_team_clash_factor: OrderedFloat(1.0),
+            adj_speaker_clash_factor: OrderedFloat(0.5),
+            team_team_clash_factor: OrderedFloat(0.2),
+            team_speaker_clash_factor: OrderedFloat(0.1),
+            speaker_speaker_clash_factor: OrderedFloat(0.1),
+
+            adj_adj_repeat_clash_severity: 40,
+            adj_team_repeat_clash_severity: 10,
+            adj_non_aligned_speaker_repeat_clash_severity: 10,
+            team_team_repeat_clash_severity: 10,
+            team_speaker_repeat_clash_severity: 10,
+            non_aligned_speakers_repeat_clash_severity: 10,
+
+            feedback_weight: OrderedFloat(10.0),
+            moderation_weight: OrderedFloat(10.0),
+            max_discussion_improvement_weight: OrderedFloat(1.0),
+            hard_clash_threshold: 75,
+            bias_weight: OrderedFloat(1.0),
+            variance_weight: OrderedFloat(1.0),
+            trainee_mentor_weight: OrderedFloat(1.0),
+        }
+    }
+}
+
+impl DrawConfig {
+    pub async fn load_for_tournament<C>(db: &C, tournament_id: Uuid) -> Result<DrawConfig, anyhow::Error> where C: sea_orm::ConnectionTrait {
+        let tournament = Tournament::get(db, tournament_id).await?;
+        Ok(tournament.draw_config.unwrap_or_default())
+    }
+}
 
 impl Tournament {
     pub fn new() -> Self {
@@ -29,6 +128,12 @@ impl Tournament {
             allow_self_declared_clashes: false,
             allow_speaker_self_declared_clashes: false,
             show_declared_clashes: false,
+            score_categories: None,
+            tab_ranking_config: None,
+            allow_self_declared_availability: false,
+            availability_deadline_minutes: 60,
+            draw_config: None,
+            ballot_validation_config: None,
         }
     }
 }
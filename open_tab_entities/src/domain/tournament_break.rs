@@ -81,6 +81,9 @@ pub struct TournamentBreak {
     pub award_series_key: Option<String>,
     
     pub release_time: Option<DateTime>,
+
+    /// Seed of the random number generator used to break ties when this break was generated
+    pub draw_seed: Option<i64>,
 }
 
 impl TournamentBreak {
@@ -95,6 +98,7 @@ impl TournamentBreak {
             break_award_prestige: None,
             award_series_key: None,
             release_time: None,
+            draw_seed: None,
         }
     }
 
@@ -140,6 +144,7 @@ impl TournamentBreak {
             break_award_prestige: break_row.break_award_prestige,
             award_series_key: break_row.award_series_key,
             release_time: break_row.release_time,
+            draw_seed: break_row.draw_seed,
         })
     }
 }
@@ -179,6 +184,7 @@ impl<C> BoundTournamentEntityTrait<C> for TournamentBreak where C: sea_orm::Conn
             break_award_prestige: ActiveValue::Set(self.break_award_prestige),
             award_series_key: ActiveValue::Set(self.award_series_key.clone()),
             release_time: ActiveValue::Set(self.release_time),
+            draw_seed: ActiveValue::Set(self.draw_seed),
         };
 
         if guarantee_insert {
@@ -43,6 +43,8 @@ pub enum NonAlignedFoldMethod {
 pub enum TeamAssignmentRule {
     Random,
     InvertPrevious,
+    /// Teams with more government than opposition appearances are preferably placed in opposition
+    BalanceHistory,
 }
 
 #[derive(Debug, PartialEq, Eq, Serialize, Deserialize, Clone)]
@@ -64,10 +66,28 @@ impl FoldDrawConfig {
     }
 }
 
+#[derive(Debug, PartialEq, Eq, Serialize, Deserialize, Clone, Copy, Default)]
+pub enum PreliminariesDrawMode {
+    /// Teams are assigned to debates at random, ignoring clashes
+    Random,
+    /// Teams are assigned to debates so that clashes and repeated encounters are minimized
+    #[default]
+    AvoidClashes,
+    /// Teams with similar tab ranks debate each other.
+    /// Falls back to AvoidClashes if there is no preceding tab.
+    PowerPaired,
+    /// Teams from the same institution are spread so they never face each other
+    InstitutionSeeded,
+}
+
 #[derive(Debug, PartialEq, Eq, Serialize, Deserialize, Clone)]
 #[serde(tag = "type")]
 pub enum RoundGroupConfig {
-    Preliminaries { num_roundtrips: i32 },
+    Preliminaries {
+        num_roundtrips: i32,
+        #[serde(default)]
+        draw_mode: PreliminariesDrawMode
+    },
     FoldDraw {
         round_configs: Vec<FoldDrawConfig>
     },
@@ -76,7 +96,7 @@ pub enum RoundGroupConfig {
 impl RoundGroupConfig {
     pub fn num_rounds(&self) -> i32 {
         match self {
-            RoundGroupConfig::Preliminaries {num_roundtrips} => num_roundtrips * 3,
+            RoundGroupConfig::Preliminaries {num_roundtrips, ..} => num_roundtrips * 3,
             RoundGroupConfig::FoldDraw {round_configs} => round_configs.len() as i32,
         }
     }
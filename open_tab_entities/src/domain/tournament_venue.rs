@@ -14,6 +14,17 @@ pub struct TournamentVenue {
     pub name: String,
     pub tournament_id: Uuid,
     pub ordering_index: i32,
+    /// Maximum number of people in the room, including adjudicators
+    #[serde(default)]
+    pub capacity: Option<i32>,
+    #[serde(default)]
+    pub is_accessible: bool,
+    /// Venues in the same building are close to each other
+    #[serde(default)]
+    pub building: Option<String>,
+    /// Top-room venues are reserved for the first debates of a round
+    #[serde(default)]
+    pub is_top_room: bool,
 }
 
 impl TournamentVenue {
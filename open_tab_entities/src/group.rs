@@ -5,7 +5,7 @@ use open_tab_macros::EntityCollection;
 use serde::{Serialize, Deserialize};
 use sea_orm::{prelude::*, ActiveValue, IntoActiveModel, QueryOrder, QuerySelect};
 
-use crate::{domain::{ballot::Ballot, ballot_speech_timing::BallotSpeechTiming, debate::TournamentDebate, debate_backup_ballot::DebateBackupBallot, entity::{BatchBoundTournamentEntityTrait, LoadEntity}, feedback_form::FeedbackForm, tournament_break_category::TournamentBreakCategory, feedback_question::FeedbackQuestion, feedback_response::FeedbackResponse, participant::Participant, participant_clash::ParticipantClash, round::TournamentRound, team::Team, tournament::Tournament, tournament_break::TournamentBreak, tournament_institution::TournamentInstitution, tournament_plan_edge::TournamentPlanEdge, tournament_plan_node::TournamentPlanNode, tournament_venue::TournamentVenue, institution_declaration::InstitutionDeclaration, clash_declaration::ClashDeclaration, BoundTournamentEntityTrait}, schema::tournament_log};
+use crate::{domain::{ballot::Ballot, ballot_speech_timing::BallotSpeechTiming, debate::TournamentDebate, debate_backup_ballot::DebateBackupBallot, entity::{BatchBoundTournamentEntityTrait, LoadEntity}, feedback_form::FeedbackForm, tournament_break_category::TournamentBreakCategory, feedback_question::FeedbackQuestion, feedback_response::FeedbackResponse, participant::Participant, participant_clash::ParticipantClash, round::TournamentRound, team::Team, tournament::Tournament, tournament_break::TournamentBreak, tournament_institution::TournamentInstitution, tournament_plan_edge::TournamentPlanEdge, tournament_plan_node::TournamentPlanNode, tournament_venue::TournamentVenue, institution_declaration::InstitutionDeclaration, clash_declaration::ClashDeclaration, speaker_substitution::SpeakerSubstitution, BoundTournamentEntityTrait}, schema::tournament_log};
 
 
 #[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone)]
@@ -77,6 +77,7 @@ pub enum Entity {
     BallotSpeechTiming(BallotSpeechTiming),
     ClashDeclaration(ClashDeclaration),
     InstitutionDeclaration(InstitutionDeclaration),
+    SpeakerSubstitution(SpeakerSubstitution),
 }
 
 pub trait GroupedEntityMapTrait<T, E> where T: EntityTypeIdTrait, E: EntityGroupEntityTrait<T> {
@@ -132,11 +133,30 @@ pub enum EntityOperationType {
     Delete
 }
 
+/// Who caused a change recorded in the tournament log
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone)]
+#[serde(tag="type")]
+pub enum LogAuthor {
+    /// A change made on the server, such as a participant's submission
+    User { user_id: Uuid },
+    /// A change made in a desktop app. The user is set by the server
+    /// to the account that pushed the change.
+    App { app_id: Uuid, name: String, user_id: Option<Uuid> },
+}
+
+impl LogAuthor {
+    pub fn from_log_entry(entry: &tournament_log::Model) -> Option<LogAuthor> {
+        entry.author.as_ref().and_then(|author| serde_json::from_str(author).ok())
+    }
+}
+
 pub struct EntityChangeSet<T, E, G, D> {
     pub entity_states: HashMap<(T, Uuid), NewEntityState<E>>,
     operation_log: Vec<(T, Uuid, EntityOperationType)>,
     altered_types: HashSet<T>,
     pub tournament_id: Uuid,
+    /// Recorded with every log entry written for this change set
+    pub author: Option<LogAuthor>,
     _group_map_type: std::marker::PhantomData<G>,
     _delete_map_type: std::marker::PhantomData<D>
 }
@@ -148,6 +168,7 @@ impl<T, E, G, D> EntityChangeSet<T, E, G, D> where E: EntityGroupEntityTrait<T>,
             operation_log: Vec::new(),
             altered_types: HashSet::new(),
             tournament_id,
+            author: None,
             _group_map_type: std::marker::PhantomData,
             _delete_map_type: std::marker::PhantomData
         }
@@ -164,6 +185,11 @@ impl<T, E, G, D> EntityChangeSet<T, E, G, D> where E: EntityGroupEntityTrait<T>,
         out
     }
 
+    pub fn with_author(mut self, author: LogAuthor) -> Self {
+        self.author = Some(author);
+        self
+    }
+
     pub fn has_changes_for_type(&self, type_: T) -> bool {
         self.altered_types.contains(&type_)
     }
@@ -189,11 +215,11 @@ impl<T, E, G, D> EntityChangeSet<T, E, G, D> where E: EntityGroupEntityTrait<T>,
         self.altered_types.insert(entity_type);
     }
 
-    pub async fn save_all_and_log<C>(&self, db: &C) -> Result<Uuid, anyhow::Error> where C: sea_orm::ConnectionTrait {
+    pub async fn save_all_and_log<C>(&self, db: &C) -> Result<Uuid, anyhow::Error> where C: sea_orm::ConnectionTrait, E: Serialize, T: Serialize {
         self.save_all_with_options_and_log(db, false).await
     }
 
-    pub async fn save_all_with_options_and_log<C>(&self, db: &C, guarantee_insert: bool) -> Result<Uuid, anyhow::Error> where C: sea_orm::ConnectionTrait {
+    pub async fn save_all_with_options_and_log<C>(&self, db: &C, guarantee_insert: bool) -> Result<Uuid, anyhow::Error> where C: sea_orm::ConnectionTrait, E: Serialize, T: Serialize {
         self.save_all_with_options(db, guarantee_insert).await?;
         let head = self.save_log(db).await?;
 
@@ -272,7 +298,10 @@ impl<T, E, G, D> EntityChangeSet<T, E, G, D> where E: EntityGroupEntityTrait<T>,
         Ok(())
     }
 
-    pub async fn save_log<C>(&self, transaction: &C) -> Result<Uuid, anyhow::Error> where C: sea_orm::ConnectionTrait {
+    /// Appends a log entry for every entity in the group.
+    /// Each entry stores a snapshot of the entity, which serves as the common ancestor
+    /// when merging concurrent changes during sync.
+    pub async fn save_log<C>(&self, transaction: &C) -> Result<Uuid, anyhow::Error> where C: sea_orm::ConnectionTrait, E: Serialize, T: Serialize {
         let last_log_entry = crate::schema::tournament_log::Entity::find()
         .filter(crate::schema::tournament_log::Column::TournamentId.eq(self.tournament_id))
         .order_by_desc(crate::schema::tournament_log::Column::SequenceIdx)
@@ -290,18 +319,25 @@ impl<T, E, G, D> EntityChangeSet<T, E, G, D> where E: EntityGroupEntityTrait<T>,
         };
 
         let now = chrono::offset::Local::now().naive_local();
+        let author = self.author.as_ref().map(serde_json::to_string).transpose()?;
 
-        let new_entries = self.entity_states.iter().map(|e| e.clone()).enumerate().map(|(idx, ((type_id, uuid), _state))| {
+        let new_entries = self.entity_states.iter().enumerate().map(|(idx, ((type_id, uuid), state))| {
             let version_uuid = Uuid::new_v4();
-            crate::schema::tournament_log::ActiveModel {
+            let snapshot = match state {
+                NewEntityState::Exists(e) => serde_json::to_string(&EntityState::<&E, T>::Exists(e))?,
+                NewEntityState::Deleted => serde_json::to_string(&EntityState::<&E, T>::Deleted { uuid: *uuid, type_: *type_id })?,
+            };
+            Ok(crate::schema::tournament_log::ActiveModel {
                 uuid: ActiveValue::Set(version_uuid),
                 timestamp: ActiveValue::Set(now),
                 sequence_idx: ActiveValue::Set(last_sequence_idx + 1 + idx as i32),
                 tournament_id: ActiveValue::Set(self.tournament_id),
                 target_type: ActiveValue::Set(type_id.as_str().to_string()),
-                target_uuid: ActiveValue::Set(*uuid)
-            }
-        }).collect_vec();
+                target_uuid: ActiveValue::Set(*uuid),
+                snapshot: ActiveValue::Set(Some(snapshot)),
+                author: ActiveValue::Set(author.clone()),
+            })
+        }).collect::<Result<Vec<_>, serde_json::Error>>()?;
 
         if new_entries.len() > 0 {
             log_head = new_entries[new_entries.len() - 1].uuid.clone().unwrap();
@@ -97,7 +97,8 @@ pub fn make_mock_tournament_with_options(options: MockOption) -> EntityGroup {
             uuid,
             name,
             tournament_id: tournament_uuid,
-            ordering_index: 0
+            ordering_index: 0,
+            ..Default::default()
         }
     }).collect_vec();
     
@@ -108,6 +109,7 @@ pub fn make_mock_tournament_with_options(options: MockOption) -> EntityGroup {
             uuid,
             name,
             tournament_id: tournament_uuid,
+            is_swing: false,
         }
     }).collect_vec();
 
@@ -152,6 +154,7 @@ pub fn make_mock_tournament_with_options(options: MockOption) -> EntityGroup {
                 registration_key: Some(registration_key.to_vec()),
                 is_anonymous: false,
                 break_category_id: None,
+                requires_accessible_venue: false,
             }
         }).collect_vec();
 
@@ -189,6 +192,7 @@ pub fn make_mock_tournament_with_options(options: MockOption) -> EntityGroup {
             registration_key: Some(registration_key.to_vec()),
             is_anonymous: false,
             break_category_id: None,
+            requires_accessible_venue: false,
         }
     }).collect_vec();
 
@@ -297,6 +301,7 @@ pub fn make_mock_tournament_with_options(options: MockOption) -> EntityGroup {
                                         position,
                                         scores: HashMap::new(),
                                         is_opt_out: false,
+                                        trainee_scores: HashMap::new(),
                                     }
                                 )
                             }
@@ -310,6 +315,7 @@ pub fn make_mock_tournament_with_options(options: MockOption) -> EntityGroup {
                                     position: speaker_idx as u8,
                                     scores: HashMap::new(),
                                     is_opt_out: false,
+                                    trainee_scores: HashMap::new(),
                                 }
                             }))
                         }
@@ -353,7 +359,7 @@ pub fn make_mock_tournament_with_options(options: MockOption) -> EntityGroup {
     let prelim_plan_node = TournamentPlanNode::new(
         tournament_uuid,
         PlanNodeType::Round {
-            config: RoundGroupConfig::Preliminaries { num_roundtrips: 1 },
+            config: RoundGroupConfig::Preliminaries { num_roundtrips: 1, draw_mode: Default::default() },
             rounds: if rounds.len() > 0 {vec![
                 rounds[0].uuid,
                 rounds[1].uuid,
@@ -1,2 +1,2 @@
-pub use crate::domain::{participant::{Participant, ParticipantRole, Speaker, Adjudicator}, ballot::{Ballot, BallotTeam, Speech, SpeakerScore, TeamScore, SpeechRole}, BoundTournamentEntityTrait, tournament::Tournament, debate::TournamentDebate, round::TournamentRound, team::Team};
+pub use crate::domain::{participant::{Participant, ParticipantRole, Speaker, Adjudicator}, ballot::{Ballot, BallotTeam, Speech, SpeakerScore, TeamScore, SpeechRole}, score::Score, BoundTournamentEntityTrait, tournament::Tournament, debate::TournamentDebate, round::TournamentRound, team::Team};
 pub use crate::group::{EntityGroup, Entity};
\ No newline at end of file
@@ -9,6 +9,7 @@ pub struct Model {
     pub uuid: Uuid,
     pub chair_skill: i16,
     pub panel_skill: i16,
+    pub is_trainee: bool,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
@@ -12,6 +12,7 @@ pub struct Model {
     pub ballot_id: Uuid,
     pub timestamp: DateTime,
     pub was_seen: bool,
+    pub author_participant_id: Option<Uuid>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
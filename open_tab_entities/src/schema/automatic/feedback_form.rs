@@ -11,6 +11,7 @@ pub struct Model {
     pub name: String,
     pub show_chairs_for_presidents: bool,
     pub show_chairs_for_wings: bool,
+    pub show_chairs_for_trainees: bool,
     pub show_wings_for_chairs: bool,
     pub show_wings_for_presidents: bool,
     pub show_wings_for_wings: bool,
@@ -27,6 +27,7 @@ pub mod participant_clash;
 pub mod participant_tournament_institution;
 pub mod published_tournament;
 pub mod speaker;
+pub mod speaker_substitution;
 pub mod team;
 pub mod tournament;
 pub mod tournament_break;
@@ -39,6 +40,8 @@ pub mod tournament_debate;
 pub mod tournament_entity;
 pub mod tournament_institution;
 pub mod tournament_log;
+pub mod tournament_log_pruned_entry;
+pub mod tournament_log_snapshot;
 pub mod tournament_plan_edge;
 pub mod tournament_plan_node;
 pub mod tournament_plan_node_round;
@@ -13,6 +13,7 @@ pub struct Model {
     pub name: String,
     pub is_anonymous: bool,
     pub break_category_id: Option<Uuid>,
+    pub requires_accessible_venue: bool,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
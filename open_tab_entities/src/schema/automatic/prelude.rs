@@ -25,6 +25,7 @@ pub use super::participant_clash::Entity as ParticipantClash;
 pub use super::participant_tournament_institution::Entity as ParticipantTournamentInstitution;
 pub use super::published_tournament::Entity as PublishedTournament;
 pub use super::speaker::Entity as Speaker;
+pub use super::speaker_substitution::Entity as SpeakerSubstitution;
 pub use super::team::Entity as Team;
 pub use super::tournament::Entity as Tournament;
 pub use super::tournament_break::Entity as TournamentBreak;
@@ -37,6 +38,8 @@ pub use super::tournament_debate::Entity as TournamentDebate;
 pub use super::tournament_entity::Entity as TournamentEntity;
 pub use super::tournament_institution::Entity as TournamentInstitution;
 pub use super::tournament_log::Entity as TournamentLog;
+pub use super::tournament_log_pruned_entry::Entity as TournamentLogPrunedEntry;
+pub use super::tournament_log_snapshot::Entity as TournamentLogSnapshot;
 pub use super::tournament_plan_edge::Entity as TournamentPlanEdge;
 pub use super::tournament_plan_node::Entity as TournamentPlanNode;
 pub use super::tournament_plan_node_round::Entity as TournamentPlanNodeRound;
@@ -9,6 +9,7 @@ pub struct Model {
     pub uuid: Uuid,
     pub name: String,
     pub tournament_id: Uuid,
+    pub is_swing: bool,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
@@ -14,6 +14,12 @@ pub struct Model {
     pub allow_self_declared_clashes: bool,
     pub allow_speaker_self_declared_clashes: bool,
     pub show_declared_clashes: bool,
+    pub score_categories: Option<String>,
+    pub tab_ranking_config: Option<String>,
+    pub allow_self_declared_availability: bool,
+    pub availability_deadline_minutes: i32,
+    pub draw_config: Option<String>,
+    pub ballot_validation_config: Option<String>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
@@ -38,6 +44,10 @@ pub enum Relation {
     TournamentInstitution,
     #[sea_orm(has_many = "super::tournament_log::Entity")]
     TournamentLog,
+    #[sea_orm(has_many = "super::tournament_log_pruned_entry::Entity")]
+    TournamentLogPrunedEntry,
+    #[sea_orm(has_many = "super::tournament_log_snapshot::Entity")]
+    TournamentLogSnapshot,
     #[sea_orm(has_many = "super::tournament_plan_node::Entity")]
     TournamentPlanNode,
     #[sea_orm(has_many = "super::tournament_round::Entity")]
@@ -110,6 +120,18 @@ impl Related<super::tournament_log::Entity> for Entity {
     }
 }
 
+impl Related<super::tournament_log_pruned_entry::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::TournamentLogPrunedEntry.def()
+    }
+}
+
+impl Related<super::tournament_log_snapshot::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::TournamentLogSnapshot.def()
+    }
+}
+
 impl Related<super::tournament_plan_node::Entity> for Entity {
     fn to() -> RelationDef {
         Relation::TournamentPlanNode.def()
@@ -12,6 +12,7 @@ pub struct Model {
     pub break_award_prestige: Option<i32>,
     pub award_series_key: Option<String>,
     pub release_time: Option<DateTime>,
+    pub draw_seed: Option<i64>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
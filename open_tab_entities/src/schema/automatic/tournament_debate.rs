@@ -13,6 +13,7 @@ pub struct Model {
     pub venue_id: Option<Uuid>,
     pub is_motion_released_to_non_aligned: bool,
     pub is_complete: bool,
+    pub pins: Option<String>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
@@ -12,6 +12,8 @@ pub struct Model {
     pub timestamp: DateTime,
     pub target_type: String,
     pub target_uuid: Uuid,
+    pub snapshot: Option<String>,
+    pub author: Option<String>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
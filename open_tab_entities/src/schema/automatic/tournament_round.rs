@@ -19,6 +19,7 @@ pub struct Model {
     pub is_silent: bool,
     pub feedback_release_time: Option<DateTime>,
     pub silent_round_results_release_time: Option<DateTime>,
+    pub draw_seed: Option<i64>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
@@ -10,6 +10,10 @@ pub struct Model {
     pub tournament_id: Uuid,
     pub name: String,
     pub ordering_index: i32,
+    pub capacity: Option<i32>,
+    pub is_accessible: bool,
+    pub building: Option<String>,
+    pub is_top_room: bool,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
@@ -1,4 +1,4 @@
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 
 use crate::{derived_models::get_participant_public_name, domain::entity::LoadEntity, info::TournamentParticipantsInfo, prelude::{Participant, Team}};
 
@@ -22,7 +22,9 @@ pub struct AugmentedTeamTabEntry {
     pub total_score: f64,
     pub avg_score: Option<f64>,
     pub detailed_scores: Vec<Option<TeamTabEntryDetailedScore>>,
-    pub member_ranks: Vec<u32>
+    pub member_ranks: Vec<u32>,
+    #[serde(default)]
+    pub team_category_totals: BTreeMap<String, f64>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -36,6 +38,8 @@ pub struct AugmentedSpeakerTabEntry {
     pub avg_score: Option<f64>,
     pub detailed_scores: Vec<Option<SpeakerTabEntryDetailedScore>>,
     pub is_anonymous: bool,
+    #[serde(default)]
+    pub category_totals: BTreeMap<String, f64>,
 }
 
 impl AugmentedTabView {
@@ -76,7 +80,8 @@ impl AugmentedSpeakerTabEntry {
             detailed_scores: entry.detailed_scores.clone(),
             is_anonymous,
             team_name,
-            speaker_name
+            speaker_name,
+            category_totals: entry.category_totals.clone(),
         }
     }
 }
@@ -96,7 +101,8 @@ impl AugmentedTeamTabEntry {
             total_score: entry.total_score,
             avg_score: entry.avg_score,
             detailed_scores: entry.detailed_scores.clone(),
-            member_ranks: entry.member_ranks.clone()
+            member_ranks: entry.member_ranks.clone(),
+            team_category_totals: entry.team_category_totals.clone(),
         }
     }
 }
@@ -1,11 +1,12 @@
 use std::fmt::Debug;
 use std::hash::Hash;
 use std::iter::{zip, self};
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
 
 
 use crate::derived_models::BreakNodeBackgroundInfo;
 use crate::domain::entity::LoadEntity;
+use crate::domain::speaker_substitution::SpeakerSubstitution;
 use crate::domain::tournament_plan_node::PlanNodeType;
 use crate::info::{get_tournament_teams_members, TournamentParticipantsInfo};
 use serde::{Serialize, Deserialize};
@@ -20,6 +21,8 @@ use itertools::Itertools;
 use ordered_float::OrderedFloat;
 pub use sea_orm::prelude::Uuid;
 
+use super::ranking::TabRankingConfig;
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TabView {
     pub num_rounds: u32,
@@ -37,14 +40,22 @@ pub struct TeamTabEntry {
     pub total_score: f64,
     pub avg_score: Option<f64>,
     pub detailed_scores: Vec<Option<TeamTabEntryDetailedScore>>,
-    pub member_ranks: Vec<u32>
+    pub member_ranks: Vec<u32>,
+    /// Sum of the itemized team scores per category over all rounds
+    #[serde(default)]
+    pub team_category_totals: BTreeMap<String, f64>,
+    /// Number of rounds without a result for the team
+    #[serde(default)]
+    pub rounds_missed: u32,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TeamTabEntryDetailedScore {
     pub team_score: Option<f64>,
     pub speaker_score: f64,
-    pub role: TeamRoundRole
+    pub role: TeamRoundRole,
+    #[serde(default)]
+    pub team_category_scores: BTreeMap<String, f64>,
 }
 
 impl TeamTabEntryDetailedScore {
@@ -71,13 +82,56 @@ pub struct SpeakerTabEntry {
     pub total_score: f64,
     pub avg_score: Option<f64>,
     pub detailed_scores: Vec<Option<SpeakerTabEntryDetailedScore>>,
+    /// Sum of the itemized speech scores per category over all rounds
+    #[serde(default)]
+    pub category_totals: BTreeMap<String, f64>,
+    /// Number of rounds in which the speaker did not speak for their own team
+    #[serde(default)]
+    pub rounds_missed: u32,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SpeakerTabEntryDetailedScore {
     pub score: f64,
     pub team_role: TeamRoundRole,
-    pub speech_position: u8
+    pub speech_position: u8,
+    #[serde(default)]
+    pub category_scores: BTreeMap<String, f64>,
+    /// Whether the speech was given for a team the speaker is not a member of
+    #[serde(default)]
+    pub is_substitute: bool,
+    /// Number of speeches given in the round. Iron speakers are scored by the average of their speeches.
+    #[serde(default = "default_num_speeches")]
+    pub num_speeches: u32,
+}
+
+fn default_num_speeches() -> u32 {
+    1
+}
+
+impl SpeakerTabEntryDetailedScore {
+    /// Adds another speech of the same speaker in the same debate
+    fn add_speech(&mut self, score: f64, category_scores: &BTreeMap<String, f64>) {
+        let n = self.num_speeches as f64;
+        self.score = (self.score * n + score) / (n + 1.0);
+        let keys = self.category_scores.keys().chain(category_scores.keys()).cloned().collect::<BTreeSet<_>>();
+        for key in keys {
+            let previous = self.category_scores.get(&key).cloned().unwrap_or(0.0);
+            let value = category_scores.get(&key).cloned().unwrap_or(0.0);
+            self.category_scores.insert(key, (previous * n + value) / (n + 1.0));
+        }
+        self.num_speeches += 1;
+    }
+}
+
+fn sum_category_scores<'a, I>(scores: I) -> BTreeMap<String, f64> where I: Iterator<Item=&'a BTreeMap<String, f64>> {
+    let mut totals = BTreeMap::new();
+    for scores in scores {
+        for (key, score) in scores.iter() {
+            *totals.entry(key.clone()).or_insert(0.0) += score;
+        }
+    }
+    totals
 }
 
 impl TabView {
@@ -87,12 +141,21 @@ impl TabView {
         ).all(db).await?;
 
         let team_members = get_tournament_teams_members(db, tournament_uuid).await?;
+        let ranking = TabRankingConfig::load_for_tournament(db, tournament_uuid).await?;
 
-        Self::load_from_rounds(db, rounds.into_iter().map(|r| r.uuid).collect(), &team_members).await
+        Self::load_from_rounds(db, rounds.into_iter().map(|r| r.uuid).collect(), &team_members, &ranking).await
     }
 
-    pub async fn load_from_rounds<C>(db: &C, round_ids: Vec<Uuid>, team_members: &HashMap<Uuid, Vec<Uuid>>) -> Result<TabView, anyhow::Error> where C: ConnectionTrait {
+    /// Loads the tab for the given rounds. Scores of dropped rounds are excluded from the
+    /// total and average scores, but remain visible in the detailed scores.
+    ///
+    /// Speeches count for the team they were given for. For government and opposition this is the
+    /// team on the ballot, non-aligned speeches count for the team of a recorded substitution, if any.
+    pub async fn load_from_rounds<C>(db: &C, round_ids: Vec<Uuid>, team_members: &HashMap<Uuid, Vec<Uuid>>, ranking: &TabRankingConfig) -> Result<TabView, anyhow::Error> where C: ConnectionTrait {
         let num_round_ids = round_ids.len();
+        let substitute_teams = SpeakerSubstitution::get_all_in_rounds(db, round_ids.clone()).await?.into_iter().map(
+            |s| ((s.round_id, s.speaker_id), s.team_id)
+        ).collect::<HashMap<_, _>>();
         let rounds_with_debates = schema::tournament_round::Entity::find()
         .find_with_related(schema::tournament_debate::Entity)
         .filter(schema::tournament_round::Column::Uuid.is_in(round_ids))
@@ -104,13 +167,20 @@ impl TabView {
         
         // Include uuid to ensure order is always stable, even when indices overlap
         let round_order = rounds_with_debates.iter().map(|(round, _)| round).sorted_by_key(|r| (r.index, r.uuid)).map(|r| r.uuid).collect_vec();
+        // Rounds without debates have not taken place, so nobody can miss them
+        let held_rounds = rounds_with_debates.iter().filter(|(_, debates)| !debates.is_empty()).map(|(round, _)| round.uuid).collect_vec();
 
         let speaker_teams = team_members.iter().flat_map(|(team_id, members)| {
             members.iter().map(|member| (*member, *team_id))
         }).collect::<HashMap<_, _>>();
 
         let mut team_detailed_scores = team_members.keys().map(|k| (*k, HashMap::new())).collect::<HashMap<_, _>>();
-        let mut speaker_detailed_scores = team_members.values().flat_map(|m| m.iter().map(|k| (*k, HashMap::new()))).collect::<HashMap<_, _>>();
+        let mut speaker_detailed_scores : HashMap<Uuid, HashMap<Uuid, SpeakerTabEntryDetailedScore>> = team_members.values().flat_map(|m| m.iter().map(|k| (*k, HashMap::new()))).collect::<HashMap<_, _>>();
+        let mut team_head_to_head_wins = vec![];
+        // Rounds in which a speaker spoke for their own team
+        let mut speaker_rounds_present : HashMap<Uuid, HashSet<Uuid>> = HashMap::new();
+        // Ballot each speaker spoke in per round, to tell iron speakers from speakers in two debates
+        let mut speaker_round_ballots = HashMap::new();
         for (round, debates) in rounds_with_debates {
             let mut non_aligned_teams = HashSet::new();
             let mut non_aligned_teams_opt_out_count = HashMap::new();
@@ -118,14 +188,24 @@ impl TabView {
             for debate in debates.iter() {
                 let ballot = ballots_by_id.get(&debate.ballot_id).expect("Guaranteed by db constraints");
 
+                if let (Some(gov), Some(opp), Some(gov_total), Some(opp_total)) = (ballot.government.team, ballot.opposition.team, ballot.government_total(), ballot.opposition_total()) {
+                    if gov_total > opp_total {
+                        team_head_to_head_wins.push((gov, opp));
+                    }
+                    else if opp_total > gov_total {
+                        team_head_to_head_wins.push((opp, gov));
+                    }
+                }
+
                 for role in vec![TeamRoundRole::Government, TeamRoundRole::Opposition] {
                     let (team_score, speaker_scores) = Self::detail_score_for_debate_side(&ballot, &role);
 
-                    let team_id = match &role {
-                        TeamRoundRole::Government => ballot.government.team,
-                        TeamRoundRole::Opposition => ballot.opposition.team,
+                    let ballot_team = match &role {
+                        TeamRoundRole::Government => &ballot.government,
+                        TeamRoundRole::Opposition => &ballot.opposition,
                         _ => unreachable!()
                     };
+                    let team_id = ballot_team.team;
 
                     if let Some(team_id) = team_id {
                         let team_entries = team_detailed_scores.entry(team_id).or_insert_with(|| HashMap::new());
@@ -135,14 +215,31 @@ impl TabView {
                         team_entries.insert(round.uuid, TeamTabEntryDetailedScore {
                             team_score,
                             speaker_score: speaker_scores.into_iter().sum(),
-                            role
+                            role,
+                            team_category_scores: ballot_team.team_category_scores(),
                         });
                     }
                 }
 
                 for speech in &ballot.speeches {
                     if let Some(speaker) = speech.speaker {
-                        let speaker_team = speaker_teams.get(&speaker);
+                        let member_team = speaker_teams.get(&speaker);
+                        let speaker_team = match speech.role {
+                            SpeechRole::Government => ballot.government.team.as_ref(),
+                            SpeechRole::Opposition => ballot.opposition.team.as_ref(),
+                            SpeechRole::NonAligned => substitute_teams.get(&(round.uuid, speaker)).or(member_team),
+                        };
+                        let is_substitute = speaker_team.is_some() && speaker_team != member_team;
+
+                        if speaker_team.is_some() && !is_substitute {
+                            speaker_rounds_present.entry(speaker).or_default().insert(round.uuid);
+                        }
+
+                        if let Some(previous_ballot) = speaker_round_ballots.insert((speaker, round.uuid), ballot.uuid) {
+                            if previous_ballot != ballot.uuid {
+                                return Err(anyhow::Error::msg(format!("Speaker {} can not be in the same round twice", speaker)));
+                            }
+                        }
                         
                         let score = if speech.is_opt_out {
                             match speech.role {
@@ -178,20 +275,27 @@ impl TabView {
                             },
                         }
 
-                        if !speech.is_opt_out {
+                        let is_excluded = is_substitute && ranking.exclude_substitute_speeches;
+                        if !speech.is_opt_out && !is_excluded {
                             let speaker_entries = speaker_detailed_scores.entry(speaker).or_insert_with(|| HashMap::new());
-                            if speaker_entries.contains_key(&round.uuid) {
-                                return Err(anyhow::Error::msg(format!("Speaker {} can not be in the same round twice", speaker)));
+                            let category_scores = speech.speaker_category_scores();
+                            if let Some(entry) = speaker_entries.get_mut(&round.uuid) {
+                                entry.add_speech(score, &category_scores);
+                            }
+                            else {
+                                speaker_entries.insert(round.uuid, SpeakerTabEntryDetailedScore {
+                                    score,
+                                    team_role: match speech.role {
+                                        SpeechRole::Government => TeamRoundRole::Government,
+                                        SpeechRole::Opposition => TeamRoundRole::Opposition,
+                                        SpeechRole::NonAligned => TeamRoundRole::NonAligned
+                                    },
+                                    speech_position: speech.position,
+                                    category_scores,
+                                    is_substitute,
+                                    num_speeches: 1,
+                                });
                             }
-                            speaker_entries.insert(round.uuid, SpeakerTabEntryDetailedScore {
-                                score,
-                                team_role: match speech.role {
-                                    SpeechRole::Government => TeamRoundRole::Government,
-                                    SpeechRole::Opposition => TeamRoundRole::Opposition,
-                                    SpeechRole::NonAligned => TeamRoundRole::NonAligned
-                                },
-                                speech_position: speech.position
-                            });    
                         }
                     }
                 }
@@ -212,83 +316,78 @@ impl TabView {
                 team_entries.insert(round.uuid, TeamTabEntryDetailedScore {
                     team_score: None,
                     speaker_score: speaker_score,
-                    role: TeamRoundRole::NonAligned
+                    role: TeamRoundRole::NonAligned,
+                    team_category_scores: BTreeMap::new(),
                 });
             }
         }
 
-        let mut speaker_tab = speaker_detailed_scores.into_iter().map(
-            |(speaker_id, per_round_score)| {
+        // Swing teams only fill up the draw, so neither they nor their members are ranked
+        let swing_team_ids = schema::team::Entity::find()
+            .filter(schema::team::Column::IsSwing.eq(true))
+            .filter(schema::team::Column::Uuid.is_in(team_detailed_scores.keys().cloned().collect_vec()))
+            .all(db).await?.into_iter().map(|t| t.uuid).collect::<HashSet<_>>();
+        team_detailed_scores.retain(|team_id, _| !swing_team_ids.contains(team_id));
+        speaker_detailed_scores.retain(|speaker_id, _| !speaker_teams.get(speaker_id).map(|t| swing_team_ids.contains(t)).unwrap_or(false));
+
+        let speaker_counted_scores = speaker_detailed_scores.iter().map(
+            |(speaker_id, per_round_score)| (*speaker_id, ranking.speaker_ranking.counted_scores(&per_round_score.values().map(|s| s.score).collect_vec()))
+        ).collect::<HashMap<_, _>>();
+        let speaker_ranks = ranking.speaker_ranking.rank(&speaker_counted_scores, &[]);
+        let speaker_rank_map = speaker_ranks.iter().cloned().collect::<HashMap<_, _>>();
+
+        let speaker_tab = speaker_ranks.into_iter().map(
+            |(speaker_id, rank)| {
+                let per_round_score = &speaker_detailed_scores[&speaker_id];
+                let counted_scores = &speaker_counted_scores[&speaker_id];
                 SpeakerTabEntry {
-                    rank: 0,
+                    rank,
                     speaker_uuid: speaker_id,
                     team_uuid: speaker_teams.get(&speaker_id).cloned().unwrap_or_default(),
-                    total_score: per_round_score.values().map(|s| s.score).sum(),
-                    avg_score: if per_round_score.values().len() > 0 {
-                        Some(per_round_score.values().map(|s| s.score).sum::<f64>() /  per_round_score.values().len() as f64)
+                    total_score: counted_scores.iter().sum(),
+                    avg_score: if !counted_scores.is_empty() {
+                        Some(counted_scores.iter().sum::<f64>() / counted_scores.len() as f64)
                     }
                     else {
                         None
                     },
-                    detailed_scores: round_order.iter().map(|r| per_round_score.get(&r).cloned()).collect_vec(),
-                }
-            }
-        ).sorted_by_key(|s| -OrderedFloat(s.total_score)).collect_vec();
-
-        let mut prev_val = None;
-        let mut prev_rank = 0;
-        let mut speaker_rank_map = HashMap::new();
-        for (i, speaker) in speaker_tab.iter_mut().enumerate() {
-            match prev_val {
-                Some(prev_val) if prev_val == speaker.total_score => {
-                    speaker.rank = prev_rank;
-                },
-                _ => {
-                    speaker.rank = i as u32;
+                    detailed_scores: round_order.iter().map(|r| per_round_score.get(r).cloned()).collect_vec(),
+                    category_totals: sum_category_scores(per_round_score.values().map(|s| &s.category_scores)),
+                    rounds_missed: held_rounds.iter().filter(
+                        |r| !speaker_rounds_present.get(&speaker_id).map(|rounds| rounds.contains(r)).unwrap_or(false)
+                    ).count() as u32,
                 }
             }
+        ).collect_vec();
 
-            speaker_rank_map.insert(speaker.speaker_uuid, speaker.rank);
-            prev_val = Some(speaker.total_score);
-            prev_rank = speaker.rank;
-        }
+        let team_counted_scores = team_detailed_scores.iter().map(
+            |(team_id, per_round_score)| (*team_id, ranking.team_ranking.counted_scores(&per_round_score.values().map(|s| s.total_score()).collect_vec()))
+        ).collect::<HashMap<_, _>>();
+        let team_ranks = ranking.team_ranking.rank(&team_counted_scores, &team_head_to_head_wins);
 
-        let mut team_tab = team_detailed_scores.into_iter().map(
-            |(team_id, per_round_score)| {
+        let team_tab = team_ranks.into_iter().map(
+            |(team_id, rank)| {
+                let per_round_score = &team_detailed_scores[&team_id];
+                let counted_scores = &team_counted_scores[&team_id];
                 TeamTabEntry {
-                    rank: 0,
+                    rank,
                     team_uuid: team_id,
-                    total_score: per_round_score.values().map(|s| s.total_score()).sum(),
-                    avg_score: if per_round_score.values().len() > 0 {
-                        Some(per_round_score.values().map(|s| s.total_score()).sum::<f64>() /  per_round_score.values().len() as f64)
+                    total_score: counted_scores.iter().sum(),
+                    avg_score: if !counted_scores.is_empty() {
+                        Some(counted_scores.iter().sum::<f64>() / counted_scores.len() as f64)
                     }
                     else {
                         None
                     },
-                    detailed_scores: round_order.iter().map(|r| per_round_score.get(&r).cloned()).collect_vec(),
+                    detailed_scores: round_order.iter().map(|r| per_round_score.get(r).cloned()).collect_vec(),
                     member_ranks: team_members.get(&team_id).map(|members| {
                         members.iter().filter_map(|member| speaker_rank_map.get(member).cloned()).sorted().collect_vec()
-                    }).unwrap_or(vec![])
+                    }).unwrap_or(vec![]),
+                    team_category_totals: sum_category_scores(per_round_score.values().map(|s| &s.team_category_scores)),
+                    rounds_missed: held_rounds.iter().filter(|r| !per_round_score.contains_key(r)).count() as u32,
                 }
             }
-        ).sorted_by_key(|s| -OrderedFloat(s.total_score)).collect_vec();
-
-        let mut prev_val = None;
-        let mut prev_rank = 0;
-        for (i, team) in team_tab.iter_mut().enumerate() {
-            match prev_val {
-                Some(prev_val) if prev_val == team.total_score => {
-                    team.rank = prev_rank;
-                },
-                _ => {
-                    team.rank = i as u32;
-                }
-            }
-
-            speaker_rank_map.insert(team.team_uuid, team.rank);
-            prev_val = Some(team.total_score);
-            prev_rank = team.rank;
-        }
+        ).collect_vec();
 
         let team_index = team_tab.iter().enumerate().map(|(i, t)| (t.team_uuid, i)).collect::<HashMap<_, _>>();
         let speaker_index = speaker_tab.iter().enumerate().map(|(i, t)| (t.speaker_uuid, i)).collect::<HashMap<_, _>>();
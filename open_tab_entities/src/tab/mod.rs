@@ -5,6 +5,7 @@ use std::collections::{HashMap, HashSet};
 
 mod base;
 mod augmented;
+mod ranking;
 pub use base::{TabView};
 
 use crate::derived_models::BreakNodeBackgroundInfo;
@@ -24,6 +25,7 @@ use ordered_float::OrderedFloat;
 pub use sea_orm::prelude::Uuid;
 pub use self::base::*;
 pub use self::augmented::*;
+pub use self::ranking::*;
 
 #[derive(Debug, Clone, Serialize, serde::Deserialize)]
 pub struct BreakingAdjudicatorInfo {
@@ -62,10 +64,13 @@ impl BreakRelevantTabView {
             None => (vec![], vec![], vec![])
         };
 
+        let ranking = TabRankingConfig::load_for_tournament(db, target_node.tournament_id).await?;
+
         let tab = TabView::load_from_rounds(
             db,
             break_background.preceding_rounds.clone(),
             &speaker_info.team_members,
+            &ranking,
         ).await?;
 
         Ok(BreakRelevantTabView {
@@ -0,0 +1,214 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+
+use itertools::Itertools;
+use ordered_float::OrderedFloat;
+use sea_orm::prelude::Uuid;
+use serde::{Serialize, Deserialize};
+
+use crate::domain::{entity::LoadEntity, tournament::Tournament};
+
+/// Rules by which the team and speaker tabs are ordered.
+/// The default reproduces ranking by total score without tie-breakers.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub struct TabRankingConfig {
+    #[serde(default)]
+    pub team_ranking: RankingRules,
+    #[serde(default)]
+    pub speaker_ranking: RankingRules,
+    /// Leave speeches given for another team out of the speaker tab.
+    /// They still count towards the points of the team they were given for.
+    #[serde(default)]
+    pub exclude_substitute_speeches: bool,
+}
+
+impl TabRankingConfig {
+    pub async fn load_for_tournament<C>(db: &C, tournament_id: Uuid) -> Result<TabRankingConfig, anyhow::Error> where C: sea_orm::ConnectionTrait {
+        let tournament = Tournament::get(db, tournament_id).await?;
+        Ok(tournament.tab_ranking_config.unwrap_or_default())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RankingRules {
+    pub primary: RankingCriterion,
+    /// Applied in order to entries that are tied on all previous criteria
+    #[serde(default)]
+    pub tie_breakers: Vec<RankingCriterion>,
+    /// Number of best round results that are not counted
+    #[serde(default)]
+    pub drop_best_rounds: u32,
+    /// Number of worst round results that are not counted
+    #[serde(default)]
+    pub drop_worst_rounds: u32,
+    /// Entries that missed more rounds are not eligible to break
+    #[serde(default)]
+    pub max_rounds_missed_for_break: Option<u32>,
+}
+
+impl Default for RankingRules {
+    fn default() -> Self {
+        RankingRules {
+            primary: RankingCriterion::TotalScore,
+            tie_breakers: vec![],
+            drop_best_rounds: 0,
+            drop_worst_rounds: 0,
+            max_rounds_missed_for_break: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag="type")]
+pub enum RankingCriterion {
+    TotalScore,
+    AverageScore,
+    MedianScore,
+    HighestRoundScore,
+    /// Number of debates won against the other entries in the tie.
+    /// Only meaningful for teams, speakers are never separated by this criterion.
+    HeadToHead,
+}
+
+impl RankingRules {
+    /// Removes the dropped rounds from a set of round results.
+    /// Entries with fewer rounds than there are dropped rounds keep at least one result.
+    pub fn counted_scores(&self, scores: &[f64]) -> Vec<f64> {
+        let sorted = scores.iter().cloned().sorted_by_key(|s| OrderedFloat(*s)).collect_vec();
+        let num_dropped = (self.drop_best_rounds + self.drop_worst_rounds) as usize;
+        if sorted.len() <= num_dropped {
+            let median = sorted.len() / 2;
+            return sorted.into_iter().skip(median).take(1).collect();
+        }
+        sorted[self.drop_worst_rounds as usize..sorted.len() - self.drop_best_rounds as usize].to_vec()
+    }
+
+    pub fn is_break_eligible(&self, rounds_missed: u32) -> bool {
+        self.max_rounds_missed_for_break.map(|max| rounds_missed <= max).unwrap_or(true)
+    }
+
+    /// Orders the entries by the ranking rules and assigns ranks.
+    /// Entries that are tied on all criteria share a rank.
+    /// `counted_scores` must already have dropped rounds removed, `head_to_head_wins`
+    /// contains the winner and loser of every direct encounter.
+    pub fn rank<K>(&self, counted_scores: &HashMap<K, Vec<f64>>, head_to_head_wins: &[(K, K)]) -> Vec<(K, u32)> where K: Copy + Eq + Hash + Ord {
+        let mut groups = vec![counted_scores.keys().cloned().sorted().collect_vec()];
+
+        for criterion in std::iter::once(&self.primary).chain(self.tie_breakers.iter()) {
+            groups = groups.into_iter().flat_map(|group| {
+                if group.len() <= 1 {
+                    return vec![group];
+                }
+                let keys = group.iter().map(|k| (*k, OrderedFloat(criterion_value(*criterion, k, &group, counted_scores, head_to_head_wins)))).collect::<HashMap<_, _>>();
+
+                group.into_iter()
+                    .sorted_by(|a, b| keys[b].cmp(&keys[a]))
+                    .group_by(|k| keys[k])
+                    .into_iter()
+                    .map(|(_, g)| g.collect_vec())
+                    .collect_vec()
+            }).collect();
+        }
+
+        let mut out = vec![];
+        for group in groups {
+            let rank = out.len() as u32;
+            out.extend(group.into_iter().map(|k| (k, rank)));
+        }
+        out
+    }
+}
+
+fn criterion_value<K>(criterion: RankingCriterion, key: &K, group: &[K], counted_scores: &HashMap<K, Vec<f64>>, head_to_head_wins: &[(K, K)]) -> f64 where K: Copy + Eq + Hash {
+    let scores = &counted_scores[key];
+    match criterion {
+        RankingCriterion::TotalScore => scores.iter().sum(),
+        RankingCriterion::AverageScore => {
+            if scores.is_empty() {
+                0.0
+            }
+            else {
+                scores.iter().sum::<f64>() / scores.len() as f64
+            }
+        },
+        RankingCriterion::MedianScore => {
+            let sorted = scores.iter().cloned().sorted_by_key(|s| OrderedFloat(*s)).collect_vec();
+            match sorted.len() {
+                0 => 0.0,
+                n if n % 2 == 1 => sorted[n / 2],
+                n => (sorted[n / 2 - 1] + sorted[n / 2]) / 2.0
+            }
+        },
+        RankingCriterion::HighestRoundScore => scores.iter().cloned().map(OrderedFloat).max().map(|s| s.0).unwrap_or(0.0),
+        RankingCriterion::HeadToHead => {
+            head_to_head_wins.iter().filter(|(winner, loser)| winner == key && group.contains(loser)).count() as f64
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::HashMap;
+
+    use super::{RankingCriterion, RankingRules};
+
+    fn ranks(rules: &RankingRules, scores: Vec<(u32, Vec<f64>)>, head_to_head: Vec<(u32, u32)>) -> Vec<(u32, u32)> {
+        let scores = scores.into_iter().map(|(k, s)| (k, rules.counted_scores(&s))).collect::<HashMap<_, _>>();
+        rules.rank(&scores, &head_to_head)
+    }
+
+    #[test]
+    fn test_default_ranks_by_total_and_shares_ties() {
+        let rules = RankingRules::default();
+        let result = ranks(&rules, vec![(1, vec![10.0, 10.0]), (2, vec![15.0, 10.0]), (3, vec![5.0, 15.0])], vec![]);
+        assert_eq!(result, vec![(2, 0), (1, 1), (3, 1)]);
+    }
+
+    #[test]
+    fn test_tie_breakers_apply_in_order() {
+        let rules = RankingRules {
+            tie_breakers: vec![RankingCriterion::MedianScore, RankingCriterion::HighestRoundScore],
+            ..Default::default()
+        };
+        let result = ranks(&rules, vec![
+            (1, vec![10.0, 10.0, 10.0]),
+            (2, vec![5.0, 10.0, 15.0]),
+            (3, vec![2.0, 12.0, 16.0]),
+        ], vec![]);
+        assert_eq!(result, vec![(3, 0), (2, 1), (1, 2)]);
+    }
+
+    #[test]
+    fn test_head_to_head_only_counts_wins_in_tie() {
+        let rules = RankingRules {
+            tie_breakers: vec![RankingCriterion::HeadToHead],
+            ..Default::default()
+        };
+        let result = ranks(&rules, vec![(1, vec![20.0]), (2, vec![20.0]), (3, vec![10.0])], vec![(1, 3), (1, 3), (2, 1)]);
+        assert_eq!(result, vec![(2, 0), (1, 1), (3, 2)]);
+    }
+
+    #[test]
+    fn test_drop_rounds() {
+        let rules = RankingRules {
+            drop_best_rounds: 1,
+            drop_worst_rounds: 1,
+            ..Default::default()
+        };
+        assert_eq!(rules.counted_scores(&[3.0, 1.0, 5.0, 4.0]), vec![3.0, 4.0]);
+        assert_eq!(rules.counted_scores(&[3.0, 1.0]), vec![3.0]);
+        assert_eq!(rules.counted_scores(&[]), Vec::<f64>::new());
+    }
+
+    #[test]
+    fn test_break_eligibility_by_rounds_missed() {
+        assert!(RankingRules::default().is_break_eligible(3));
+
+        let rules = RankingRules {
+            max_rounds_missed_for_break: Some(1),
+            ..Default::default()
+        };
+        assert!(rules.is_break_eligible(1));
+        assert!(!rules.is_break_eligible(2));
+    }
+}
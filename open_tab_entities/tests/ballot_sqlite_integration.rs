@@ -1,8 +1,8 @@
-use std::{collections::HashMap, default};
+use std::{collections::{BTreeMap, HashMap}, default};
 
 use chrono::TimeZone;
 use itertools::Itertools;
-use open_tab_entities::domain::{ballot::{Ballot, self, BallotTeam, Speech, SpeakerScore, TeamScore}, tournament::Tournament, round::TournamentRound, debate::TournamentDebate, entity::{LoadEntity, LoadError}, debate_backup_ballot::DebateBackupBallot};
+use open_tab_entities::domain::{ballot::{Ballot, self, BallotTeam, Speech, SpeakerScore, TeamScore}, tournament::Tournament, round::TournamentRound, debate::TournamentDebate, entity::{LoadEntity, LoadError}, debate_backup_ballot::DebateBackupBallot, score::Score};
 use sea_orm::{prelude::*, Database, Statement, ActiveValue};
 use migration::MigratorTrait;
 
@@ -26,7 +26,13 @@ pub async fn set_up_db(with_mock_env: bool) -> Result<DatabaseConnection, DbErr>
             last_modified: chrono::DateTime::from_timestamp_millis(786910980).map(|t| t.naive_utc()).unwrap(),
             allow_self_declared_clashes: false,
             allow_speaker_self_declared_clashes: false,
-            show_declared_clashes: false
+            show_declared_clashes: false,
+            score_categories: None,
+            tab_ranking_config: None,
+            allow_self_declared_availability: false,
+            availability_deadline_minutes: 60,
+            draw_config: None,
+            ballot_validation_config: None,
         }.into();
         a.insert(&db).await?;
          open_tab_entities::schema::team::Entity::insert_many(vec![
@@ -189,13 +195,14 @@ async fn test_ballot_teams_roundtrip() -> Result<(), anyhow::Error> {
         government: BallotTeam {
             team: Some(Uuid::from_u128(200)),
             scores: HashMap::from_iter(
-                vec![(Uuid::from_u128(402), TeamScore::Aggregate { total: 140 }), (Uuid::from_u128(403), TeamScore::Aggregate { total: 143 })].into_iter()
+                vec![(Uuid::from_u128(402), TeamScore::Aggregate { total: Score::from_points(140) }), (Uuid::from_u128(403), TeamScore::Aggregate { total: Score::from_points(143) })].into_iter()
             ),
             ..Default::default()
         },
         opposition: BallotTeam {
             team: Some(Uuid::from_u128(201)),
-            scores: HashMap::from_iter(vec![(Uuid::from_u128(401), TeamScore::Aggregate { total: 140 })].into_iter())
+            scores: HashMap::from_iter(vec![(Uuid::from_u128(401), TeamScore::Aggregate { total: Score::from_points(140) })].into_iter()),
+            trainee_scores: HashMap::new()
         },
         ..Default::default()
     }, true).await?;
@@ -227,8 +234,9 @@ async fn judge_not_in_adjudicators_can_not_score_team() -> Result<(), anyhow::Er
         government: BallotTeam {
             team: Some(Uuid::from_u128(200)),
             scores: HashMap::from_iter(
-                vec![(Uuid::from_u128(405), TeamScore::Aggregate { total: 54 })].into_iter(),
-            )
+                vec![(Uuid::from_u128(405), TeamScore::Aggregate { total: Score::from_points(54) })].into_iter(),
+            ),
+            trainee_scores: HashMap::new()
         },
         ..Default::default()
     };
@@ -246,8 +254,9 @@ async fn test_speeches_roundtrip() -> Result<(), anyhow::Error> {
         adjudicators: (401..=404).map(|u| Uuid::from_u128(u as u128)).collect(),
         speeches: vec![
             Speech { speaker: None, role: ballot::SpeechRole::Government, position: 0, is_opt_out: false, scores: HashMap::from_iter(
-                vec![(Uuid::from_u128(401), SpeakerScore::Aggregate { total: 54 }), (Uuid::from_u128(402), SpeakerScore::Aggregate { total: 32 })].into_iter(),
-            )
+                vec![(Uuid::from_u128(401), SpeakerScore::Aggregate { total: Score::from_points(54) }), (Uuid::from_u128(402), SpeakerScore::Aggregate { total: Score::from_points(32) })].into_iter(),
+            ),
+            trainee_scores: HashMap::new()
         }],
         ..Default::default()
     }, true).await?;
@@ -263,8 +272,9 @@ async fn test_judge_not_in_adjudicators_can_not_score_speech() -> Result<(), any
         adjudicators: (401..=404).map(|u| Uuid::from_u128(u as u128)).collect(),
         speeches: vec![
             Speech { speaker: None, role: ballot::SpeechRole::Government, position: 0, is_opt_out: false, scores: HashMap::from_iter(
-                vec![(Uuid::from_u128(405), SpeakerScore::Aggregate { total: 54 })].into_iter(),
-            )
+                vec![(Uuid::from_u128(405), SpeakerScore::Aggregate { total: Score::from_points(54) })].into_iter(),
+            ),
+            trainee_scores: HashMap::new()
         }],
         ..Default::default()
     };
@@ -283,6 +293,7 @@ async fn test_change_team() -> Result<(), anyhow::Error> {
         government: BallotTeam {
             team: Some(Uuid::from_u128(200)),
             scores: HashMap::from_iter(vec![].into_iter()),
+            trainee_scores: HashMap::new(),
         },
         ..Default::default()
     };
@@ -304,10 +315,12 @@ async fn test_swap_team() -> Result<(), anyhow::Error> {
         government: BallotTeam {
             team: Some(Uuid::from_u128(200)),
             scores: HashMap::from_iter(vec![].into_iter()),
+            trainee_scores: HashMap::new(),
         },
         opposition: BallotTeam {
             team: Some(Uuid::from_u128(201)),
             scores: HashMap::from_iter(vec![].into_iter()),
+            trainee_scores: HashMap::new(),
         },
         ..Default::default()
     };
@@ -366,14 +379,16 @@ async fn test_reorder_annotators_keeps_scores_with_judges() -> Result<(), anyhow
         adjudicators: vec![Uuid::from_u128(401), Uuid::from_u128(402)],
         government: BallotTeam {
             team: None,
-            scores: HashMap::from_iter(vec![(Uuid::from_u128(401), TeamScore::Aggregate { total: 23 })])
+            scores: HashMap::from_iter(vec![(Uuid::from_u128(401), TeamScore::Aggregate { total: Score::from_points(23) })]),
+            trainee_scores: HashMap::new()
         },
         opposition: BallotTeam {
             team: None,
-            scores: HashMap::from_iter(vec![(Uuid::from_u128(402), TeamScore::Aggregate { total: 53 })])
+            scores: HashMap::from_iter(vec![(Uuid::from_u128(402), TeamScore::Aggregate { total: Score::from_points(53) })]),
+            trainee_scores: HashMap::new()
         },
         speeches: vec![
-            Speech { speaker: None, role: ballot::SpeechRole::Government, position: 0, is_opt_out: false, scores: HashMap::from_iter(vec![(Uuid::from_u128(401), SpeakerScore::Aggregate { total: 43 })]) }
+            Speech { speaker: None, role: ballot::SpeechRole::Government, position: 0, is_opt_out: false, scores: HashMap::from_iter(vec![(Uuid::from_u128(401), SpeakerScore::Aggregate { total: Score::from_points(43) })]), trainee_scores: HashMap::new() }
         ],
         ..Default::default()
     };
@@ -396,7 +411,8 @@ async fn test_remove_adjudicators_deletes_team_scores() -> Result<(), anyhow::Er
         adjudicators: vec![Uuid::from_u128(401), Uuid::from_u128(402)],
         government: BallotTeam {
             team: None,
-            scores: HashMap::from_iter(vec![(Uuid::from_u128(401), TeamScore::Aggregate { total: 23 }), (Uuid::from_u128(402), TeamScore::Aggregate { total: 53 })])
+            scores: HashMap::from_iter(vec![(Uuid::from_u128(401), TeamScore::Aggregate { total: Score::from_points(23) }), (Uuid::from_u128(402), TeamScore::Aggregate { total: Score::from_points(53) })]),
+            trainee_scores: HashMap::new()
         },
         speeches: vec![
             Speech {
@@ -405,8 +421,9 @@ async fn test_remove_adjudicators_deletes_team_scores() -> Result<(), anyhow::Er
                 position: 0,
                 is_opt_out: false,
                 scores: HashMap::from_iter(
-                    vec![(Uuid::from_u128(401), SpeakerScore::Aggregate { total: 43 })]
-                )
+                    vec![(Uuid::from_u128(401), SpeakerScore::Aggregate { total: Score::from_points(43) })]
+                ),
+                trainee_scores: HashMap::new()
             }
         ],
         ..Default::default()
@@ -436,8 +453,9 @@ async fn test_remove_adjudicators_deletes_speech_scores() -> Result<(), anyhow::
                 position: 0,
                 is_opt_out: false,
                 scores: HashMap::from_iter(
-                    vec![(Uuid::from_u128(402), SpeakerScore::Aggregate { total: 43 }), (Uuid::from_u128(401), SpeakerScore::Aggregate { total: 43 })]
-                )
+                    vec![(Uuid::from_u128(402), SpeakerScore::Aggregate { total: Score::from_points(43) }), (Uuid::from_u128(401), SpeakerScore::Aggregate { total: Score::from_points(43) })]
+                ),
+                trainee_scores: HashMap::new()
             }
         ],
         ..Default::default()
@@ -453,6 +471,101 @@ async fn test_remove_adjudicators_deletes_speech_scores() -> Result<(), anyhow::
     Ok(())
 }
 
+#[tokio::test]
+async fn test_itemized_scores_roundtrip() -> Result<(), anyhow::Error> {
+    let items = BTreeMap::from_iter(vec![("content".to_string(), Score::from_points(20)), ("style".to_string(), Score::from_points(18)), ("strategy".to_string(), Score::from_points(15))]);
+    let ballot = Ballot {
+        uuid: Uuid::from_u128(100),
+        adjudicators: vec![Uuid::from_u128(401), Uuid::from_u128(402)],
+        government: BallotTeam {
+            team: None,
+            scores: HashMap::from_iter(vec![
+                (Uuid::from_u128(401), TeamScore::new_itemized(BTreeMap::from_iter(vec![("cooperation".to_string(), Score::from_points(30))]))),
+                (Uuid::from_u128(402), TeamScore::Aggregate { total: Score::from_points(31) })
+            ]),
+            trainee_scores: HashMap::new()
+        },
+        speeches: vec![
+            Speech {
+                speaker: None,
+                role: ballot::SpeechRole::Government,
+                position: 0,
+                is_opt_out: false,
+                scores: HashMap::from_iter(
+                    vec![(Uuid::from_u128(401), SpeakerScore::new_itemized(items)), (Uuid::from_u128(402), SpeakerScore::Aggregate { total: Score::from_points(52) })]
+                ),
+                trainee_scores: HashMap::new()
+            }
+        ],
+        ..Default::default()
+    };
+    assert_eq!(ballot.speeches[0].scores[&Uuid::from_u128(401)].total(), Score::from_points(53));
+
+    test_ballot_roundtrip(ballot, true).await
+}
+
+#[tokio::test]
+async fn test_half_point_scores_roundtrip() -> Result<(), anyhow::Error> {
+    let ballot = Ballot {
+        uuid: Uuid::from_u128(100),
+        adjudicators: vec![Uuid::from_u128(401)],
+        government: BallotTeam {
+            team: None,
+            scores: HashMap::from_iter(vec![
+                (Uuid::from_u128(401), TeamScore::Aggregate { total: Score::from_hundredths(3050) })
+            ]),
+            trainee_scores: HashMap::new()
+        },
+        speeches: vec![
+            Speech {
+                speaker: None,
+                role: ballot::SpeechRole::Government,
+                position: 0,
+                is_opt_out: false,
+                scores: HashMap::from_iter(
+                    vec![(Uuid::from_u128(401), SpeakerScore::new_itemized(BTreeMap::from_iter(vec![("content".to_string(), Score::from_hundredths(3050)), ("style".to_string(), Score::from_points(23))])))]
+                ),
+                trainee_scores: HashMap::new()
+            }
+        ],
+        ..Default::default()
+    };
+    assert_eq!(ballot.speeches[0].scores[&Uuid::from_u128(401)].total(), Score::from_hundredths(5350));
+
+    test_ballot_roundtrip(ballot, true).await
+}
+
+#[tokio::test]
+async fn test_change_aggregate_to_itemized_score() -> Result<(), anyhow::Error> {
+    let db = set_up_db(true).await?;
+    let mut ballot = Ballot {
+        uuid: Uuid::from_u128(100),
+        adjudicators: vec![Uuid::from_u128(401)],
+        speeches: vec![
+            Speech {
+                speaker: None,
+                role: ballot::SpeechRole::Government,
+                position: 0,
+                is_opt_out: false,
+                scores: HashMap::from_iter(
+                    vec![(Uuid::from_u128(401), SpeakerScore::Aggregate { total: Score::from_points(53) })]
+                ),
+                trainee_scores: HashMap::new()
+            }
+        ],
+        ..Default::default()
+    };
+
+    ballot.save(&db, true).await?;
+
+    // Same total as before, so only the items differ
+    ballot.speeches[0].scores.insert(Uuid::from_u128(401), SpeakerScore::new_itemized(BTreeMap::from_iter(vec![("content".to_string(), Score::from_points(30)), ("style".to_string(), Score::from_points(23))])));
+
+    test_ballot_roundtrip_in_db(&db, ballot, false).await?;
+
+    Ok(())
+}
+
 #[tokio::test]
 async fn test_get_tournament_from_independent_ballot() -> Result<(), anyhow::Error> {
     let db = set_up_db(true).await?;
@@ -559,7 +672,8 @@ async fn test_get_tournament_from_backup_ballot() -> Result<(), anyhow::Error> {
         debate_id: debate.uuid,
         ballot_id: ballot2.uuid,
         timestamp: chrono::Utc::now().naive_utc(),
-        was_seen: true
+        was_seen: true,
+        author_participant_id: None
     };
     backup_ballot.save(&db, true).await?;
 
@@ -719,4 +833,39 @@ async fn test_delete_ballot_deletes_only_targets() -> Result<(), anyhow::Error>
     }
 
     Ok(())
-}
\ No newline at end of file
+}
+#[tokio::test]
+async fn test_trainees_roundtrip() -> Result<(), anyhow::Error> {
+    let db = set_up_db(true).await?;
+    let mut ballot = Ballot {
+        uuid: Uuid::from_u128(100),
+        adjudicators: vec![Uuid::from_u128(401)],
+        trainees: vec![Uuid::from_u128(402)],
+        speeches: vec![
+            Speech {
+                speaker: None,
+                role: ballot::SpeechRole::Government,
+                position: 0,
+                is_opt_out: false,
+                scores: HashMap::from_iter(
+                    vec![(Uuid::from_u128(401), SpeakerScore::Aggregate { total: Score::from_points(43) })]
+                ),
+                trainee_scores: HashMap::from_iter(
+                    vec![(Uuid::from_u128(402), SpeakerScore::Aggregate { total: Score::from_points(70) })]
+                )
+            }
+        ],
+        ..Default::default()
+    };
+
+    ballot.save(&db, true).await?;
+
+    ballot.adjudicators = vec![Uuid::from_u128(401), Uuid::from_u128(402)];
+    ballot.trainees = vec![];
+    let trainee_scores = std::mem::take(&mut ballot.speeches[0].trainee_scores);
+    ballot.speeches[0].scores.extend(trainee_scores);
+
+    test_ballot_roundtrip_in_db(&db, ballot, false).await?;
+
+    Ok(())
+}
@@ -32,14 +32,15 @@ fn make_changeset() -> (EntityGroup, Ballot) {
         government: BallotTeam {
             team: Some(Uuid::from_u128(200)),
             scores: HashMap::from_iter(
-                vec![(Uuid::from_u128(401), TeamScore::Aggregate { total: 140 })].into_iter()
+                vec![(Uuid::from_u128(401), TeamScore::Aggregate { total: Score::from_points(140) })].into_iter()
             ),
             ..Default::default()
         },
         speeches: vec![
             Speech { speaker: Some(Uuid::from_u128(402)), role: ballot::SpeechRole::Government, position: 0, is_opt_out: false, scores: HashMap::from_iter(
-                vec![(Uuid::from_u128(401), SpeakerScore::Aggregate { total: 54 })]
-            )
+                vec![(Uuid::from_u128(401), SpeakerScore::Aggregate { total: Score::from_points(54) })]
+            ),
+            trainee_scores: HashMap::new()
         }],
         ..Default::default()
     };
@@ -70,6 +71,7 @@ fn make_changeset() -> (EntityGroup, Ballot) {
             uuid: Uuid::from_u128(200),
             name: "Team 1".into(),
             tournament_id: Uuid::from_u128(1),
+            is_swing: false,
         }
     ));
     let mut p = Participant::new_with_uuid(
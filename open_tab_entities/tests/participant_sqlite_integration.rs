@@ -22,7 +22,13 @@ pub async fn set_up_db(with_mock_env: bool) -> Result<DatabaseConnection, anyhow
             last_modified: chrono::DateTime::from_timestamp_millis(786910980).map(|t| t.naive_utc()).unwrap(),
             allow_self_declared_clashes: false,
             allow_speaker_self_declared_clashes: false,
-            show_declared_clashes: false
+            show_declared_clashes: false,
+            score_categories: None,
+            tab_ranking_config: None,
+            allow_self_declared_availability: false,
+            availability_deadline_minutes: 60,
+            draw_config: None,
+            ballot_validation_config: None,
         }.into();
         tournament.insert(&db).await?;
 
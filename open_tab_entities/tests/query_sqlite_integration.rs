@@ -43,7 +43,7 @@ async fn test_find_team_roles() -> Result<(), anyhow::Error> {
             ..Default::default()
         },
         speeches: vec![
-            Speech { speaker:Some(Uuid::from_u128(2000)), role: SpeechRole::Government, position: 0, is_opt_out: false, scores: Default::default() },
+            Speech { speaker:Some(Uuid::from_u128(2000)), role: SpeechRole::Government, position: 0, is_opt_out: false, scores: Default::default(), trainee_scores: HashMap::new() },
         ],
         ..Default::default()
     };
@@ -102,6 +102,7 @@ async fn test_find_non_aligned_roles() -> Result<(), anyhow::Error> {
                 speaker: Some(Uuid::from_u128(2000)),
                 role: open_tab_entities::prelude::SpeechRole::NonAligned,
                 scores: HashMap::new(),
+                trainee_scores: HashMap::new(),
             },
         ],
         ..Default::default()
@@ -122,6 +123,7 @@ async fn test_find_non_aligned_roles() -> Result<(), anyhow::Error> {
                 speaker: Some(Uuid::from_u128(2000)),
                 role: open_tab_entities::prelude::SpeechRole::NonAligned,
                 scores: HashMap::new(),
+                trainee_scores: HashMap::new(),
             },
         ],
         ..Default::default()
@@ -261,6 +263,7 @@ async fn test_multiple_roles() -> Result<(), anyhow::Error> {
                 speaker: Some(Uuid::from_u128(2000)),
                 role: open_tab_entities::prelude::SpeechRole::NonAligned,
                 scores: HashMap::new(),
+                trainee_scores: HashMap::new(),
             },
         ],
         government: BallotTeam {
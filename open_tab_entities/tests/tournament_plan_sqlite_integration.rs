@@ -93,7 +93,7 @@ async fn test_save_empty_round_node() {
         TournamentPlanNode {
             uuid: Uuid::from_u128(600),
             tournament_id: Uuid::from_u128(1),
-            config: PlanNodeType::Round { config: RoundGroupConfig::Preliminaries { num_roundtrips: 1 }, rounds: vec![] }
+            config: PlanNodeType::Round { config: RoundGroupConfig::Preliminaries { num_roundtrips: 1, draw_mode: Default::default() }, rounds: vec![] }
         }
         ,
         true
@@ -113,7 +113,7 @@ async fn test_save_round_node_with_rounds() {
         TournamentPlanNode {
             uuid: Uuid::from_u128(600),
             tournament_id: Uuid::from_u128(1),
-            config: PlanNodeType::Round { config: RoundGroupConfig::Preliminaries { num_roundtrips: 1 }, rounds: vec![Uuid::from_u128(109)] }}
+            config: PlanNodeType::Round { config: RoundGroupConfig::Preliminaries { num_roundtrips: 1, draw_mode: Default::default() }, rounds: vec![Uuid::from_u128(109)] }}
         ,
         true
     ).await.unwrap();
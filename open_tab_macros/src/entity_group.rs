@@ -381,7 +381,7 @@ pub fn entity_group_derive_impl(input: TokenStream) -> TokenStream {
     });
 
     let try_get_many_fn = quote! {
-        async fn try_get_many_with_type<C>(db: &C, entity_type: EntityTypeId, ids: Vec<Uuid>) -> Result<Vec<Option<Entity>>, anyhow::Error> where C: sea_orm::ConnectionTrait {
+        pub async fn try_get_many_with_type<C>(db: &C, entity_type: EntityTypeId, ids: Vec<Uuid>) -> Result<Vec<Option<Entity>>, anyhow::Error> where C: sea_orm::ConnectionTrait {
             Ok(match entity_type {
                 #(#try_get_many_with_type_arms),*,
                 _ => panic!("Unknown Entity Type {:?}", entity_type)
@@ -337,6 +337,7 @@ mod test {
                     avg_score: Some(90.0),
                     detailed_scores: vec![],
                     member_ranks: vec![1, 4, 5],
+                    team_category_totals: Default::default(),
                 },
                 AugmentedTeamTabEntry {
                     team_name: "Team B".into(),
@@ -346,6 +347,7 @@ mod test {
                     avg_score: Some(85.0),
                     detailed_scores: vec![],
                     member_ranks: vec![2, 3, 6],
+                    team_category_totals: Default::default(),
                 },
             ],
             speaker_tab: vec![
@@ -362,19 +364,29 @@ mod test {
                             score: 30.0,
                             team_role: TeamRoundRole::Government,
                             speech_position: 1,
+                            category_scores: Default::default(),
+                            is_substitute: false,
+                            num_speeches: 1,
                         }),
                         Some(SpeakerTabEntryDetailedScore {
                             score: 35.0,
                             team_role: TeamRoundRole::Government,
                             speech_position: 2,
+                            category_scores: Default::default(),
+                            is_substitute: false,
+                            num_speeches: 1,
                         }),
                         Some(SpeakerTabEntryDetailedScore {
                             score: 30.0,
                             team_role: TeamRoundRole::Government,
                             speech_position: 3,
+                            category_scores: Default::default(),
+                            is_substitute: false,
+                            num_speeches: 1,
                         }),
                     ],
-                    is_anonymous: false
+                    is_anonymous: false,
+                    category_totals: Default::default(),
                 },
                 AugmentedSpeakerTabEntry {
                     speaker_name: "Speaker B".into(),
@@ -389,19 +401,29 @@ mod test {
                             score: 25.0,
                             team_role: TeamRoundRole::Opposition,
                             speech_position: 1,
+                            category_scores: Default::default(),
+                            is_substitute: false,
+                            num_speeches: 1,
                         }),
                         Some(SpeakerTabEntryDetailedScore {
                             score: 30.0,
                             team_role: TeamRoundRole::Opposition,
                             speech_position: 2,
+                            category_scores: Default::default(),
+                            is_substitute: false,
+                            num_speeches: 1,
                         }),
                         Some(SpeakerTabEntryDetailedScore {
                             score: 30.0,
                             team_role: TeamRoundRole::Opposition,
                             speech_position: 3,
+                            category_scores: Default::default(),
+                            is_substitute: false,
+                            num_speeches: 1,
                         }),
                     ],
-                    is_anonymous: false
+                    is_anonymous: false,
+                    category_totals: Default::default(),
                 },
                 AugmentedSpeakerTabEntry {
                     rank: 3,
@@ -416,19 +438,29 @@ mod test {
                             score: 20.0,
                             team_role: TeamRoundRole::Government,
                             speech_position: 1,
+                            category_scores: Default::default(),
+                            is_substitute: false,
+                            num_speeches: 1,
                         }),
                         Some(SpeakerTabEntryDetailedScore {
                             score: 30.0,
                             team_role: TeamRoundRole::Government,
                             speech_position: 2,
+                            category_scores: Default::default(),
+                            is_substitute: false,
+                            num_speeches: 1,
                         }),
                         Some(SpeakerTabEntryDetailedScore {
                             score: 30.0,
                             team_role: TeamRoundRole::Government,
                             speech_position: 3,
+                            category_scores: Default::default(),
+                            is_substitute: false,
+                            num_speeches: 1,
                         }),
                     ],
-                    is_anonymous: false
+                    is_anonymous: false,
+                    category_totals: Default::default(),
                 },
             ],
         };
@@ -24,6 +24,7 @@ use axum::http::request::Parts;
 // for `call`
 
 use crate::response::TypedAPIError;
+use crate::staff::{TournamentPermission, TournamentRole};
 use crate::tournament;
 use crate::{
     response::APIError,
@@ -141,26 +142,47 @@ impl AuthenticatedUser {
         }
     }
     
-    pub async fn check_is_authorized_for_tournament_administration<C>(
+    /// The staff role of the user in the tournament.
+    /// Tournament specific tokens have no role in other tournaments.
+    pub async fn get_tournament_role<C>(
         &self,
         db: &C,
         tournament_id: Uuid,
-    ) -> Result<bool, anyhow::Error>
+    ) -> Result<Option<TournamentRole>, anyhow::Error>
     where
         C: sea_orm::ConnectionTrait,
     {
         if let Some(authorized_only_for_tournament_id) = self.authorized_only_for_tournament {
-            return Ok(authorized_only_for_tournament_id == tournament_id);
-        } else {
-            let user_tournament = open_tab_entities::schema::user_tournament::Entity::find_by_id((
-                self.uuid,
-                tournament_id,
-            ))
-            .one(db)
-            .await?;
-
-            Ok(user_tournament.is_some())
+            if authorized_only_for_tournament_id != tournament_id {
+                return Ok(None);
+            }
         }
+
+        let user_tournament = open_tab_entities::schema::user_tournament::Entity::find_by_id((
+            self.uuid,
+            tournament_id,
+        ))
+        .one(db)
+        .await?;
+
+        user_tournament.map(|user_tournament| user_tournament.role.parse()).transpose()
+    }
+
+    pub async fn check_has_permission<C>(
+        &self,
+        db: &C,
+        tournament_id: Uuid,
+        permission: TournamentPermission,
+    ) -> Result<bool, anyhow::Error>
+    where
+        C: sea_orm::ConnectionTrait,
+    {
+        Ok(
+            self.get_tournament_role(db, tournament_id)
+                .await?
+                .map(|role| role.has_permission(permission))
+                .unwrap_or(false)
+        )
     }
 
     pub async fn check_is_authorized_as_participant<C>(
@@ -1,6 +1,7 @@
 
 use std::collections::hash_map::RandomState;
 use std::collections::HashMap;
+use std::sync::Arc;
 
 use axum::{Router, Json};
 use axum::extract::{Path, State};
@@ -8,14 +9,16 @@ use axum::routing::{post, get};
 use chrono::Utc;
 
 
+use open_tab_entities::domain::ballot_validation::{BallotValidationConfig, BallotValidationError};
 use open_tab_entities::domain::debate_backup_ballot::DebateBackupBallot;
 use open_tab_entities::domain::entity::LoadEntity;
 
-use open_tab_entities::{prelude::*};
+use open_tab_entities::{prelude::*, LogAuthor};
 use open_tab_entities::domain::ballot::Ballot;
 use open_tab_entities::schema::{self};
-use sea_orm::{prelude::*, JoinType, QuerySelect, TransactionTrait};
+use sea_orm::{prelude::*, JoinType, QueryOrder, QuerySelect, TransactionTrait};
 use serde::{Serialize, Deserialize};
+use tokio::sync::RwLock;
 
 use itertools::Itertools;
 
@@ -25,7 +28,9 @@ use crate::auth::{AuthenticatedUser, ExtractAuthenticatedUser};
 
 use open_tab_entities::domain::round::check_release_date;
 
-use crate::response::APIError;
+use crate::notify::ParticipantNotificationManager;
+use crate::response::{APIError, TypedAPIError};
+use crate::staff::TournamentPermission;
 use crate::state::AppState;
 
 
@@ -52,7 +57,7 @@ pub struct DisplayBallotTeam {
     pub uuid: Uuid,
     pub name: String,
     pub members: Vec<DisplaySpeaker>,
-    pub scores: HashMap<Uuid, i16>,
+    pub scores: HashMap<Uuid, Score>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -63,7 +68,7 @@ pub struct DisplaySpeaker {
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct DisplayBallotSpeech {
-    pub scores: HashMap<Uuid, i16>,
+    pub scores: HashMap<Uuid, Score>,
     pub speaker: Option<DisplaySpeaker>,
     pub position: u8,
     pub role: SpeechRole
@@ -206,6 +211,34 @@ pub struct SubmitBallotResponse {
     pub ballot_id: Uuid
 }
 
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(tag="error")]
+pub enum SubmitBallotError {
+    InvalidBallot { errors: Vec<BallotValidationError> },
+    Other { message: String }
+}
+
+impl std::fmt::Display for SubmitBallotError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SubmitBallotError::InvalidBallot { errors } => write!(f, "Invalid ballot: {}", errors.iter().map(|e| e.to_string()).join(", ")),
+            SubmitBallotError::Other { message } => write!(f, "{}", message)
+        }
+    }
+}
+
+impl From<String> for SubmitBallotError {
+    fn from(message: String) -> Self {
+        SubmitBallotError::Other { message }
+    }
+}
+
+impl From<APIError> for TypedAPIError<SubmitBallotError> {
+    fn from(err: APIError) -> Self {
+        TypedAPIError::new_with_status(err.code, err.message)
+    }
+}
+
 pub async fn check_is_authorized_for_debate_result_submission<C>(
     db: &C,
     user: &AuthenticatedUser,
@@ -221,7 +254,7 @@ pub async fn check_is_authorized_for_debate_result_submission<C>(
     //let debate = r.0;
     let round = r.1.into_iter().next().unwrap();
 
-    if user.check_is_authorized_for_tournament_administration(db, round.tournament_id).await? {
+    if user.check_has_permission(db, round.tournament_id, TournamentPermission::ManageTournament).await? {
         return Ok(true);
     }
 
@@ -267,7 +300,8 @@ async fn get_ballot_submission(
     let submission = submission.ok_or(APIError::new_with_status(axum::http::StatusCode::NOT_FOUND, "Submission not found"))?;
 
 
-    if !check_is_authorized_for_debate_result_submission(&db, &user, submission.debate_id).await? {
+    let may_promote = user.check_has_permission(&db, get_debate_tournament_id(&db, submission.debate_id).await?, TournamentPermission::PromoteBallots).await?;
+    if !(may_promote || check_is_authorized_for_debate_result_submission(&db, &user, submission.debate_id).await?) {
         return  Err(APIError::new_with_status(axum::http::StatusCode::FORBIDDEN, "Not authorized for debate"))?;
     }
 
@@ -304,11 +338,12 @@ async fn get_debate(
 
 async fn submit_ballot(
     State(db): State<DatabaseConnection>,
+    State(notifications): State<Arc<RwLock<ParticipantNotificationManager>>>,
     Path(debate_id): Path<Uuid>,
     ExtractAuthenticatedUser(user): ExtractAuthenticatedUser,
     Json(request): Json<SubmitBallotRequest>,
-) -> Result<Json<SubmitBallotResponse>, APIError> {
-    if !check_is_authorized_for_debate_result_submission(&db, &user, debate_id).await? {
+) -> Result<Json<SubmitBallotResponse>, TypedAPIError<SubmitBallotError>> {
+    if !check_is_authorized_for_debate_result_submission(&db, &user, debate_id).await.map_err(APIError::from)? {
         return  Err(APIError::new_with_status(axum::http::StatusCode::FORBIDDEN, "Not authorized for debate"))?;
     }
 
@@ -332,16 +367,24 @@ async fn submit_ballot(
     )?.ok_or(APIError::new_with_status(axum::http::StatusCode::INTERNAL_SERVER_ERROR, "Error getting tournament id"))?.tournament_id;
 
     let mut ballot = request.ballot;
+    let validation_config = BallotValidationConfig::load_for_tournament(&transaction, tournament_id).await.map_err(APIError::from)?;
+    validation_config.validate(&ballot).map_err(
+        |e| TypedAPIError::<SubmitBallotError>::new_with_status(axum::http::StatusCode::BAD_REQUEST, SubmitBallotError::InvalidBallot { errors: e.errors })
+    )?;
+
     let ballot_uuid = Uuid::new_v4();
     ballot.uuid = ballot_uuid;
 
+    let author_participant_id = user.participant_id_in_tournament(&transaction, tournament_id).await.map_err(APIError::from)?;
+
     let submission_uuid = Uuid::new_v4();
     let submission = DebateBackupBallot {
         uuid: submission_uuid,
         debate_id,
         ballot_id: ballot.uuid,
         timestamp: Utc::now().naive_utc(),
-        was_seen: false
+        was_seen: false,
+        author_participant_id,
     };
 
     let group = EntityGroup::new_from_entities(
@@ -350,9 +393,9 @@ async fn submit_ballot(
             Entity::Ballot(ballot),
             Entity::DebateBackupBallot(submission)
         ]
-    );
+    ).with_author(LogAuthor::User { user_id: user.uuid });
 
-    group.save_all_and_log(&transaction).await?;
+    group.save_all_and_log(&transaction).await.map_err(APIError::from)?;
 
     transaction.commit().await.map_err(
         |e| {
@@ -360,6 +403,7 @@ async fn submit_ballot(
             APIError::new_with_status(axum::http::StatusCode::INTERNAL_SERVER_ERROR, "Error committing transaction")
         }
     )?;
+    notifications.read().await.notify_tournament_log_head(&db, tournament_id).await;
 
     Ok(Json(SubmitBallotResponse {
         submission_id: submission_uuid,
@@ -367,12 +411,118 @@ async fn submit_ballot(
     }))
 }
 
+async fn get_debate_tournament_id<C>(db: &C, debate_id: Uuid) -> Result<Uuid, APIError> where C: sea_orm::ConnectionTrait {
+    let round = schema::tournament_round::Entity::find()
+        .inner_join(schema::tournament_debate::Entity)
+        .filter(schema::tournament_debate::Column::Uuid.eq(debate_id))
+        .one(db)
+        .await?;
+
+    round.map(|r| r.tournament_id).ok_or(APIError::new_with_status(axum::http::StatusCode::NOT_FOUND, "Debate not found"))
+}
+
+/// Makes a submitted ballot the result of its debate
+async fn promote_ballot_submission(
+    State(db): State<DatabaseConnection>,
+    State(notifications): State<Arc<RwLock<ParticipantNotificationManager>>>,
+    Path(submission_id): Path<Uuid>,
+    ExtractAuthenticatedUser(user): ExtractAuthenticatedUser,
+) -> Result<Json<()>, TypedAPIError<SubmitBallotError>> {
+    let transaction = db.begin().await.map_err(APIError::from)?;
+
+    let submission = DebateBackupBallot::try_get(&transaction, submission_id).await.map_err(APIError::from)?
+        .ok_or(APIError::new_with_status(axum::http::StatusCode::NOT_FOUND, "Submission not found"))?;
+    let mut debate = TournamentDebate::get(&transaction, submission.debate_id).await.map_err(APIError::from)?;
+    let tournament_id = get_debate_tournament_id(&transaction, debate.uuid).await?;
+
+    if !user.check_has_permission(&transaction, tournament_id, TournamentPermission::PromoteBallots).await.map_err(APIError::from)? {
+        return Err(APIError::new_with_status(axum::http::StatusCode::FORBIDDEN, "Not authorized to promote ballots"))?;
+    }
+
+    let ballot = Ballot::get(&transaction, submission.ballot_id).await.map_err(APIError::from)?;
+    let validation_config = BallotValidationConfig::load_for_tournament(&transaction, tournament_id).await.map_err(APIError::from)?;
+    validation_config.validate(&ballot).map_err(
+        |e| TypedAPIError::<SubmitBallotError>::new_with_status(axum::http::StatusCode::BAD_REQUEST, SubmitBallotError::InvalidBallot { errors: e.errors })
+    )?;
+
+    debate.ballot_id = submission.ballot_id;
+    debate.is_complete = true;
+
+    let group = EntityGroup::new_from_entities(
+        tournament_id,
+        vec![
+            Entity::TournamentDebate(debate),
+            Entity::DebateBackupBallot(DebateBackupBallot {
+                was_seen: true,
+                ..submission
+            })
+        ]
+    ).with_author(LogAuthor::User { user_id: user.uuid });
+    group.save_all_and_log(&transaction).await.map_err(APIError::from)?;
+
+    transaction.commit().await.map_err(APIError::from)?;
+    notifications.read().await.notify_tournament_log_head(&db, tournament_id).await;
+
+    Ok(Json(()))
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PendingSubmission {
+    pub submission_id: Uuid,
+    pub debate_id: Uuid,
+    pub round_id: Uuid,
+    pub debate_index: i32,
+    pub timestamp: chrono::NaiveDateTime,
+    pub author_participant_id: Option<Uuid>,
+}
+
+/// Lists submitted ballots that were not yet promoted or discarded, oldest first,
+/// so staff without access to the log can find them
+async fn get_pending_submissions(
+    State(db): State<DatabaseConnection>,
+    Path(tournament_id): Path<Uuid>,
+    ExtractAuthenticatedUser(user): ExtractAuthenticatedUser,
+) -> Result<Json<Vec<PendingSubmission>>, APIError> {
+    if !user.check_has_permission(&db, tournament_id, TournamentPermission::PromoteBallots).await? {
+        return Err(APIError::new_with_status(axum::http::StatusCode::FORBIDDEN, "Not authorized to promote ballots"));
+    }
+
+    let submissions = schema::debate_backup_ballot::Entity::find()
+        .find_also_related(schema::tournament_debate::Entity)
+        .join(JoinType::InnerJoin, schema::tournament_debate::Relation::TournamentRound.def())
+        .filter(
+            schema::tournament_round::Column::TournamentId.eq(tournament_id).and(
+                schema::debate_backup_ballot::Column::WasSeen.eq(false)
+            )
+        )
+        .order_by_asc(schema::debate_backup_ballot::Column::Timestamp)
+        .all(&db)
+        .await?;
+
+    Ok(Json(submissions.into_iter().filter_map(|(submission, debate)| {
+        let debate = debate?;
+        Some(PendingSubmission {
+            submission_id: submission.uuid,
+            debate_id: debate.uuid,
+            round_id: debate.round_id,
+            debate_index: debate.index,
+            timestamp: submission.timestamp,
+            author_participant_id: submission.author_participant_id,
+        })
+    }).collect()))
+}
 
 pub(crate) fn router() -> Router<AppState> {
     Router::new()
+        .route(
+            "/tournament/:tournament_id/submissions", get(get_pending_submissions)
+        )
         .route(
             "/submission/:submission_id", get(get_ballot_submission)
         )
+        .route(
+            "/submission/:submission_id/promote", post(promote_ballot_submission)
+        )
         .route(
             "/debate/:debate_id/submissions", post(submit_ballot)
         ).route(
@@ -1,4 +1,4 @@
-use open_tab_entities::{info::TournamentParticipantsInfo, schema, tab::TabView};
+use open_tab_entities::{info::TournamentParticipantsInfo, schema, tab::{TabRankingConfig, TabView}};
 use sea_orm::{prelude::Uuid, ConnectionTrait, EntityTrait, QueryFilter, QueryOrder, ColumnTrait};
 use serde::Deserialize;
 use tokio::sync::RwLock;
@@ -127,7 +127,8 @@ impl CacheManager {
                 tracing::debug!("Cache miss for tab with key {:?}", key);
                 let participant_info = self.get_tournament_participants_info(tournament_id, db).await?;
                 let mut cache = self.cache.write().await;
-                let tab = TabView::load_from_rounds(db, round_ids, &participant_info.team_members).await?;
+                let ranking = TabRankingConfig::load_for_tournament(db, tournament_id).await?;
+                let tab = TabView::load_from_rounds(db, round_ids, &participant_info.team_members, &ranking).await?;
                 cache.insert(key, current_tournament_version, &tab)?;
                 Ok(tab)
             }
@@ -0,0 +1,144 @@
+use std::collections::HashMap;
+
+use axum::extract::{Path, State};
+use axum::http::StatusCode;
+use axum::routing::post;
+use axum::{Json, Router};
+use chrono::Utc;
+use itertools::Itertools;
+use open_tab_entities::schema::{tournament_log, tournament_log_pruned_entry, tournament_log_snapshot};
+use sea_orm::{prelude::*, sea_query::Expr, DatabaseConnection, IntoActiveModel, QueryOrder, TransactionTrait};
+use serde::{Deserialize, Serialize};
+
+use crate::auth::ExtractAuthenticatedUser;
+use crate::response::APIError;
+use crate::staff::TournamentPermission;
+use crate::state::AppState;
+
+/// SQLite limits the number of variables in a statement
+const CHUNK_SIZE: usize = 100;
+
+/// Number of compactions for which pruned entries keep their entity snapshot.
+/// Older pruned entries still resolve as `since` or last common ancestor, but merges
+/// against them conflict on the whole entity and their history has no values.
+pub const SNAPSHOT_RETENTION_COMPACTIONS: u64 = 3;
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct LogSnapshotInfo {
+    pub snapshot_id: Uuid,
+    /// The last log entry included in the snapshot
+    pub log_position: Uuid,
+    pub pruned_entries: u64,
+}
+
+/// Consolidates the tournament log up to its current head.
+///
+/// For every entity, only the latest entry at or before the head is kept, which together
+/// form the state at the snapshot. A sync from the start of the log therefore replays the
+/// snapshot followed by the entries added afterwards.
+/// Pruned entries are no longer part of the log that is synced, but they keep their position,
+/// snapshot and author. They therefore remain usable as `since` or as last common ancestor
+/// by clients that synced before the compaction, and still show up in the entity history.
+/// To bound the storage of the log, snapshots of entries pruned by earlier compactions are
+/// dropped after [`SNAPSHOT_RETENTION_COMPACTIONS`] compactions.
+pub async fn compact_tournament_log<C>(db: &C, tournament_id: Uuid) -> Result<Option<LogSnapshotInfo>, anyhow::Error> where C: sea_orm::ConnectionTrait {
+    let log = tournament_log::Entity::find()
+        .filter(tournament_log::Column::TournamentId.eq(tournament_id))
+        .order_by_asc(tournament_log::Column::SequenceIdx)
+        .all(db)
+        .await?;
+
+    let Some(head) = log.last() else {
+        return Ok(None);
+    };
+    let snapshot_id = Uuid::new_v4();
+    let head_uuid = head.uuid;
+
+    tournament_log_snapshot::Model {
+        uuid: snapshot_id,
+        tournament_id,
+        log_entry_id: head.uuid,
+        sequence_idx: head.sequence_idx,
+        timestamp: Utc::now().naive_utc(),
+    }.into_active_model().insert(db).await?;
+
+    let latest_entries = log.iter()
+        .map(|entry| ((entry.target_type.as_str(), entry.target_uuid), entry.uuid))
+        .collect::<HashMap<_, _>>();
+
+    let pruned_entries = log.iter()
+        .filter(|entry| latest_entries[&(entry.target_type.as_str(), entry.target_uuid)] != entry.uuid)
+        .collect_vec();
+
+    for chunk in pruned_entries.chunks(CHUNK_SIZE) {
+        tournament_log_pruned_entry::Entity::insert_many(chunk.iter().map(|entry| {
+            tournament_log_pruned_entry::Model {
+                uuid: entry.uuid,
+                tournament_id,
+                sequence_idx: entry.sequence_idx,
+                snapshot_id,
+                timestamp: entry.timestamp,
+                target_type: entry.target_type.clone(),
+                target_uuid: entry.target_uuid,
+                snapshot: entry.snapshot.clone(),
+                author: entry.author.clone(),
+            }.into_active_model()
+        })).exec(db).await?;
+
+        tournament_log::Entity::delete_many()
+            .filter(tournament_log::Column::Uuid.is_in(chunk.iter().map(|entry| entry.uuid)))
+            .exec(db)
+            .await?;
+    }
+
+    let expired_snapshot_ids = tournament_log_snapshot::Entity::find()
+        .filter(tournament_log_snapshot::Column::TournamentId.eq(tournament_id))
+        .order_by_desc(tournament_log_snapshot::Column::SequenceIdx)
+        .order_by_desc(tournament_log_snapshot::Column::Timestamp)
+        .all(db)
+        .await?
+        .into_iter()
+        .skip(SNAPSHOT_RETENTION_COMPACTIONS as usize)
+        .map(|snapshot| snapshot.uuid)
+        .collect_vec();
+
+    for chunk in expired_snapshot_ids.chunks(CHUNK_SIZE) {
+        tournament_log_pruned_entry::Entity::update_many()
+            .col_expr(tournament_log_pruned_entry::Column::Snapshot, Expr::value(Option::<String>::None))
+            .filter(tournament_log_pruned_entry::Column::SnapshotId.is_in(chunk.iter().cloned()))
+            .exec(db)
+            .await?;
+    }
+
+    Ok(Some(LogSnapshotInfo {
+        snapshot_id,
+        log_position: head_uuid,
+        pruned_entries: pruned_entries.len() as u64,
+    }))
+}
+
+async fn compact_log(
+    State(db): State<DatabaseConnection>,
+    ExtractAuthenticatedUser(user): ExtractAuthenticatedUser,
+    Path(tournament_id): Path<Uuid>,
+) -> Result<Json<LogSnapshotInfo>, APIError> {
+    let transaction = db.begin().await?;
+    if !user.check_has_permission(&transaction, tournament_id, TournamentPermission::ManageTournament).await? {
+        transaction.rollback().await?;
+        return Err(APIError::new_with_status(StatusCode::FORBIDDEN, "User is not authorized for tournament administration"));
+    }
+
+    let snapshot = compact_tournament_log(&transaction, tournament_id).await?;
+    let Some(snapshot) = snapshot else {
+        transaction.rollback().await?;
+        return Err(APIError::new_with_status(StatusCode::NOT_FOUND, "Tournament has no log"));
+    };
+    transaction.commit().await?;
+
+    Ok(Json(snapshot))
+}
+
+pub(crate) fn router() -> Router<AppState> {
+    Router::new()
+        .route("/tournament/:tournament_id/log/compact", post(compact_log))
+}
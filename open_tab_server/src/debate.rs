@@ -12,7 +12,7 @@ use std::sync::{Arc};
 
 use open_tab_entities::domain::entity::LoadEntity;
 
-use open_tab_entities::{prelude::*, domain};
+use open_tab_entities::{prelude::*, domain, LogAuthor};
 use open_tab_entities::domain::ballot::{BallotParseError};
 use open_tab_entities::schema::{self};
 use sea_orm::{prelude::*};
@@ -75,7 +75,7 @@ async fn update_debate_state(
 
     let mut entities = EntityGroup::new(
         round.tournament_id
-    );
+    ).with_author(LogAuthor::User { user_id: user.uuid });
 
     match request {
         UpdateDebateStateRequest::NonAlignedMotionRelease{release} => {
@@ -88,7 +88,9 @@ async fn update_debate_state(
 
     entities.save_all_and_log(&db).await?;
 
-    notifications.as_ref().read().await.notify_debate_non_aligned_motion_release_state(&db, debate_id).await?;
+    let notifications = notifications.as_ref().read().await;
+    notifications.notify_debate_non_aligned_motion_release_state(&db, debate_id).await?;
+    notifications.notify_tournament_log_head(&db, round.tournament_id).await;
 
     Ok(())
 }
@@ -325,14 +327,15 @@ async fn set_debate_timing(
     }
 
 
-    let mut group = EntityGroup::new(tournament_id);
+    let mut group = EntityGroup::new(tournament_id).with_author(LogAuthor::User { user_id: user.uuid });
     group.add(Entity::BallotSpeechTiming(timing));
     group.save_all_and_log(&db).await?;
 
-
+    let notifications = notifications.read().await;
     for event in events {
-        notifications.read().await.notify_debate(&db, debate_id, event).await?;
+        notifications.notify_debate(&db, debate_id, event).await?;
     }
+    notifications.notify_tournament_log_head(&db, tournament_id).await;
 
     Ok(())
 }
@@ -1,15 +1,17 @@
+use std::sync::Arc;
 use std::{str::FromStr, collections::HashMap};
 
 use axum::{extract::{Path, State}, response::{IntoResponse, Response}, routing::{get, post}, Json, Router};
 use axum::http::StatusCode;
 use itertools::Itertools;
-use open_tab_entities::{derived_models::{compute_question_summary_values, SummaryValue}, domain::{entity::LoadEntity, feedback_form::{FeedbackForm, FeedbackSourceRole, FeedbackTargetRole}, feedback_question::{FeedbackQuestion, QuestionType, DEFAULT_TEXT_MAX_LENGTH}, feedback_response::{FeedbackResponse, FeedbackResponseValue}}, prelude::{Participant, Team}, schema, Entity, EntityGroup};
+use open_tab_entities::{derived_models::{compute_question_summary_values, SummaryValue}, domain::{entity::LoadEntity, feedback_form::{FeedbackForm, FeedbackSourceRole, FeedbackTargetRole}, feedback_question::{FeedbackQuestion, QuestionType}, feedback_response::{FeedbackResponse, FeedbackResponseValue}}, prelude::{Participant, Team}, schema, Entity, EntityGroup, LogAuthor};
 use rand::{thread_rng, seq::SliceRandom};
 use sea_orm::{DatabaseConnection, prelude::Uuid, EntityTrait, QueryFilter, RelationTrait, JoinType, QuerySelect, ColumnTrait, TransactionTrait, QueryOrder};
 use serde::{Serialize, Deserialize};
+use tokio::sync::RwLock;
 
 
-use crate::{auth::ExtractAuthenticatedUser, response::APIError, state::AppState};
+use crate::{auth::ExtractAuthenticatedUser, notify::ParticipantNotificationManager, response::APIError, staff::TournamentPermission, state::AppState};
 
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -155,6 +157,7 @@ async fn get_relevant_questions<C>(db: &C, tournament_id: Uuid, source_role: Fee
 
 async fn submit_feedback_form(
     State(db): State<DatabaseConnection>,
+    State(notifications): State<Arc<RwLock<ParticipantNotificationManager>>>,
     ExtractAuthenticatedUser(user): ExtractAuthenticatedUser,
     Path((source_role, target_role, debate_id, target_id, source_id)): Path<(String, String, Uuid, Uuid, Uuid)>,
     Json(submission): Json<FeedbackFormSubmissionRequest>,
@@ -162,6 +165,7 @@ async fn submit_feedback_form(
     let source_role = FeedbackSourceRole::from_str(&source_role)?;
     let target_role = FeedbackTargetRole::from_str(&target_role)?;
 
+    let connection = db.clone();
     let db = db.begin().await?;
     
     let tournament_id = match source_role {
@@ -252,7 +256,7 @@ async fn submit_feedback_form(
 
     let (source_participant_id, source_team_id) = match source_role {
         FeedbackSourceRole::Chair | FeedbackSourceRole::Wing | FeedbackSourceRole::President | FeedbackSourceRole::NonAligned => {
-            let is_authorized = user.check_is_authorized_as_participant(&db, source_id).await?  || user.check_is_authorized_for_tournament_administration(&db, tournament_id).await?;
+            let is_authorized = user.check_is_authorized_as_participant(&db, source_id).await?  || user.check_has_permission(&db, tournament_id, TournamentPermission::ManageTournament).await?;
             if !is_authorized {
                 return Err(APIError::new_with_status(StatusCode::FORBIDDEN, "User is not allowed to submit feedback for this participant"))
             }
@@ -261,7 +265,7 @@ async fn submit_feedback_form(
         },
         FeedbackSourceRole::Team => {
             
-            if !(user.check_is_authorized_as_member_of_team(&db, source_id).await? || user.check_is_authorized_for_tournament_administration(&db, tournament_id).await?) {
+            if !(user.check_is_authorized_as_member_of_team(&db, source_id).await? || user.check_has_permission(&db, tournament_id, TournamentPermission::ManageTournament).await?) {
                 return Err(APIError::new_with_status(StatusCode::FORBIDDEN, "User is not allowed to submit feedback for this participant"))
             }
             (None, Some(source_id))
@@ -293,10 +297,11 @@ async fn submit_feedback_form(
     let group = EntityGroup::new_from_entities(
         tournament_id,
         vec![Entity::FeedbackResponse(submission)]
-    );
+    ).with_author(LogAuthor::User { user_id: user.uuid });
     group.save_all_and_log(&db).await?;
 
     db.commit().await?;
+    notifications.read().await.notify_tournament_log_head(&connection, tournament_id).await;
 
     return Ok(
         FeedbackFormSubmissionResponse {
@@ -331,7 +336,11 @@ struct ParticipantFeedbackIndividualValueList {
 
 async fn get_participant_feedback_summary(State(db): State<DatabaseConnection>, Path(participant_id): Path<Uuid>, ExtractAuthenticatedUser(user): ExtractAuthenticatedUser) -> Result<Json<ParticipantFeedbackSummary>, APIError> {
     if !user.check_is_authorized_as_participant(&db, participant_id).await? {
-        return Err(APIError::new_with_status(StatusCode::FORBIDDEN, "User is not allowed to view feedback for this participant"))
+        let participant = schema::participant::Entity::find_by_id(participant_id).one(&db).await?
+            .ok_or(APIError::new_with_status(StatusCode::NOT_FOUND, "Participant not found"))?;
+        if !user.check_has_permission(&db, participant.tournament_id, TournamentPermission::ReadFeedback).await? {
+            return Err(APIError::new_with_status(StatusCode::FORBIDDEN, "User is not allowed to view feedback for this participant"))
+        }
     }
     let now = chrono::Utc::now().naive_utc();
 
@@ -31,6 +31,8 @@ pub mod cache;
 pub mod config;
 pub mod assets;
 pub mod commands;
+pub mod staff;
+pub mod compaction;
 
 use state::AppState;
 
@@ -79,6 +81,10 @@ pub async fn app_with_state(state: AppState) -> axum::Router<()> {
             round::router()
         ).merge(
             user_profile::router()
+        ).merge(
+            staff::router()
+        ).merge(
+            compaction::router()
         )
     )
     .layer(
@@ -1,7 +1,7 @@
 use std::{collections::{HashMap, HashSet}, sync::{Weak, Arc}, convert::Infallible, pin::Pin, time::Duration};
 
 use open_tab_entities::{EntityGroup, schema, domain::{self, entity::LoadEntity, ballot::SpeechRole}};
-use sea_orm::{prelude::Uuid, DatabaseConnection, EntityTrait, QueryFilter, ColumnTrait, ConnectionTrait};
+use sea_orm::{prelude::Uuid, DatabaseConnection, EntityTrait, QueryFilter, QueryOrder, ColumnTrait, ConnectionTrait};
 use tokio::{sync::{broadcast::{Sender}, Mutex, RwLock}};
 use tokio_stream::{Stream, wrappers::BroadcastStream, StreamExt};
 
@@ -66,6 +66,14 @@ pub struct ParticipantEvent {
     pub event: ParticipantEventType,
 }
 
+/// Sent to sync clients whenever the tournament log on the server changes
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct LogHeadEvent {
+    pub tournament_id: Uuid,
+    /// The latest log entry. None if the tournament has no log yet.
+    pub log_head: Option<Uuid>,
+}
+
 pub struct TournamentBroadcastState {
     pub round_times: HashMap<Uuid, HashMap<ReleaseTime, Option<chrono::NaiveDateTime>>>,
 }
@@ -74,6 +82,7 @@ pub struct ParticipantNotificationManager {
     pub participant_broadcast_senders: HashMap<Uuid, Sender<ParticipantEvent>>,
     pub tournament_broadcast_senders: HashMap<Uuid, Sender<ParticipantEvent>>,
     pub tournament_broadcast_states: WeakValueHashMap<Uuid, Weak<Mutex<TournamentBroadcastState>>>,
+    pub tournament_log_senders: HashMap<Uuid, Sender<LogHeadEvent>>,
 }
 
 impl ParticipantNotificationManager {
@@ -82,6 +91,7 @@ impl ParticipantNotificationManager {
             participant_broadcast_senders: HashMap::new(),
             tournament_broadcast_senders: HashMap::new(),
             tournament_broadcast_states: WeakValueHashMap::new(),
+            tournament_log_senders: HashMap::new(),
         }
     }
     
@@ -141,6 +151,52 @@ impl ParticipantNotificationManager {
         Ok(Box::pin(stream))
     }
 
+    /// Streams the current log head of the tournament, followed by every new head
+    pub async fn subscribe_to_tournament_log<C>(&mut self, db: &C, tournament_id: Uuid) -> Result<Pin<Box<dyn Stream<Item=Result<Event, Infallible>> + Send>>, anyhow::Error> where C: sea_orm::ConnectionTrait {
+        // Subscribe before reading the head, so no change can be missed in between
+        let receiver = self.tournament_log_senders
+            .entry(tournament_id)
+            .or_insert_with(|| Sender::new(16))
+            .subscribe();
+
+        let current_head = LogHeadEvent {
+            tournament_id,
+            log_head: get_tournament_log_head(db, tournament_id).await?,
+        };
+
+        let stream = tokio_stream::once(current_head).chain(
+            BroadcastStream::new(receiver).filter_map(|e| e.ok())
+        );
+
+        let stream = stream.map(|e| Ok(Event::default().event(
+            "log_head"
+        ).data(serde_json::to_string(&e).unwrap())));
+
+        Ok(Box::pin(stream))
+    }
+
+    /// Announces the latest log entry to all sync clients of the tournament.
+    /// Must be called after the transaction that wrote to the log has been committed.
+    /// The changes are saved at that point, so failures are only logged.
+    pub async fn notify_tournament_log_head<C>(&self, db: &C, tournament_id: Uuid) where C: ConnectionTrait {
+        let Some(sender) = self.tournament_log_senders.get(&tournament_id) else {
+            return;
+        };
+        if sender.receiver_count() == 0 {
+            return;
+        }
+
+        let log_head = match get_tournament_log_head(db, tournament_id).await {
+            Ok(log_head) => log_head,
+            Err(err) => {
+                tracing::error!("Failed to load log head of tournament {}: {}", tournament_id, err);
+                return;
+            }
+        };
+        //We ignore the send error
+        let _ = sender.send(LogHeadEvent { tournament_id, log_head });
+    }
+
     pub async fn notify_debate_non_aligned_motion_release_state<C>(&self, db: &C, debate_id: Uuid) -> Result<(), anyhow::Error> where C: ConnectionTrait {
         self.notify_debate(db, debate_id, ParticipantEvent {
             event: ParticipantEventType::DebateMotionReleaseUpdated {
@@ -235,6 +291,15 @@ impl ParticipantNotificationManager {
     }
 }
 
+async fn get_tournament_log_head<C>(db: &C, tournament_id: Uuid) -> Result<Option<Uuid>, anyhow::Error> where C: ConnectionTrait {
+    let head = schema::tournament_log::Entity::find()
+        .filter(schema::tournament_log::Column::TournamentId.eq(tournament_id))
+        .order_by_desc(schema::tournament_log::Column::SequenceIdx)
+        .one(db)
+        .await?;
+    Ok(head.map(|entry| entry.uuid))
+}
+
 pub async fn get_participant_events(
     State(db): State<DatabaseConnection>,
     State(notifications): State<Arc<RwLock<ParticipantNotificationManager>>>,
@@ -1,13 +1,15 @@
+use std::sync::Arc;
 use std::{collections::HashMap, vec};
 
 use axum::{extract::{Path, State}, Json, Router, routing::{get, post}};
 use axum::http::StatusCode;
 use itertools::Itertools;
-use open_tab_entities::{derived_models::get_tournament_feedback_directions, domain::{self, ballot::SpeechRole, clash_declaration::ClashDeclaration, entity::LoadEntity, feedback_form::{FeedbackSourceRole, FeedbackTargetRole}, institution_declaration::InstitutionDeclaration}, schema::{self}, EntityGroup};
+use open_tab_entities::{derived_models::get_tournament_feedback_directions, domain::{self, ballot::SpeechRole, clash_declaration::ClashDeclaration, entity::LoadEntity, feedback_form::{FeedbackSourceRole, FeedbackTargetRole}, institution_declaration::InstitutionDeclaration, score::Score}, schema::{self}, EntityGroup, LogAuthor};
 use sea_orm::{DatabaseConnection, TransactionTrait, prelude::*, QuerySelect, QueryOrder};
 use serde::{Serialize, Deserialize};
+use tokio::sync::RwLock;
 
-use crate::{auth::{ExtractAuthenticatedUser, MaybeExtractAuthenticatedUser}, response::APIError, state::AppState};
+use crate::{auth::{ExtractAuthenticatedUser, MaybeExtractAuthenticatedUser}, notify::ParticipantNotificationManager, response::APIError, staff::TournamentPermission, state::AppState};
 
 use open_tab_entities::domain::round::check_release_date;
 
@@ -154,6 +156,9 @@ pub enum ParticipantRoundRoleInfo {
     President {
         debate: ParticipantDebateInfo,
     },
+    Trainee {
+        debate: ParticipantDebateInfo,
+    },
     Multiple
 }
 
@@ -161,7 +166,7 @@ pub enum ParticipantRoundRoleInfo {
 #[serde(tag="score_status")]
 pub enum TeamScoreInfo {
     Hidden,
-    Shown{total_score: f32, adjudicator_scores: Vec<i16>}
+    Shown{total_score: f32, adjudicator_scores: Vec<Score>}
 }
 
 impl TeamScoreInfo {
@@ -196,7 +201,7 @@ impl TeamScoreInfo {
 pub enum SpeakerScoreInfo {
     Hidden,
     DidNotParticipate,
-    Shown{total_score: f32, adjudicator_scores: Vec<i16>}
+    Shown{total_score: f32, adjudicator_scores: Vec<Score>}
 }
 
 impl SpeakerScoreInfo {
@@ -219,7 +224,7 @@ async fn get_participant_info(
         .one(&transaction).await?;
 
     let is_admin = if let Some(participant_query_result) = &participant_query_result  {
-        user.check_is_authorized_for_tournament_administration(&transaction, participant_query_result.1.as_ref().expect("Guaranteed by consistency constraints").uuid).await?
+        user.check_has_permission(&transaction, participant_query_result.1.as_ref().expect("Guaranteed by consistency constraints").uuid, TournamentPermission::ReadScores).await?
     } else {
         false
     };
@@ -238,7 +243,7 @@ async fn get_participant_info(
     let (participant, tournament) = participant_query_result.unwrap();
     let tournament = tournament.unwrap(); // Guaranteed by consistency constraints
 
-    let has_access = user.check_is_authorized_for_tournament_administration(&transaction, tournament.uuid).await?;
+    let has_access = user.check_has_permission(&transaction, tournament.uuid, TournamentPermission::ReadScores).await?;
 
     let has_access = match has_access {
         true => true,
@@ -344,6 +349,9 @@ async fn get_participant_info(
             else if ballot.president == Some(participant_id) {
                 (d.round_id, ParticipantRoundRoleInfo::President { debate: ParticipantDebateInfo::new_from(d, v) })
             }
+            else if ballot.trainees.contains(&participant_id) {
+                (d.round_id, ParticipantRoundRoleInfo::Trainee { debate: ParticipantDebateInfo::new_from(d, v) })
+            }
             else {
                 panic!("Adjudicator {} not found in ballot", participant_id);
             }
@@ -439,14 +447,14 @@ async fn get_participant_info(
             };
 
             let show_motion = check_release_date(current_time, round.full_motion_release_time) || match &role {
-                ParticipantRoundRoleInfo::Adjudicator{..} | ParticipantRoundRoleInfo::TeamSpeaker{..} | ParticipantRoundRoleInfo::President {..} => 
+                ParticipantRoundRoleInfo::Adjudicator{..} | ParticipantRoundRoleInfo::TeamSpeaker{..} | ParticipantRoundRoleInfo::President {..} | ParticipantRoundRoleInfo::Trainee {..} => 
                 check_release_date(current_time, round.team_motion_release_time),
                 ParticipantRoundRoleInfo::NonAlignedSpeaker{debate, ..} => check_release_date(current_time, round.debate_start_time) && debate.is_motion_released_to_non_aligned,
                 ParticipantRoundRoleInfo::NotDrawn | ParticipantRoundRoleInfo::Multiple => false
             };
 
             let next_reload_time = match &role {
-                ParticipantRoundRoleInfo::Adjudicator{..} | ParticipantRoundRoleInfo::TeamSpeaker{..} | ParticipantRoundRoleInfo::President {..} => {
+                ParticipantRoundRoleInfo::Adjudicator{..} | ParticipantRoundRoleInfo::TeamSpeaker{..} | ParticipantRoundRoleInfo::President {..} | ParticipantRoundRoleInfo::Trainee {..} => {
                     vec![round.draw_release_time, round.team_motion_release_time, round.debate_start_time, round.round_close_time]
                 }
                 ParticipantRoundRoleInfo::NonAlignedSpeaker{ ..} => {
@@ -548,6 +556,9 @@ async fn get_participant_info(
             if feedback_directions.contains(&(*request_source_role, FeedbackTargetRole::President)) {
                 out.extend(ballot.president.iter());
             }
+            if feedback_directions.contains(&(*request_source_role, FeedbackTargetRole::Trainee)) {
+                out.extend(ballot.trainees.iter());
+            }
 
             out
         }
@@ -644,6 +655,27 @@ async fn get_participant_info(
                     })
                 )
             }
+            if feedback_directions.contains(&(request_source_role, FeedbackTargetRole::Trainee)) {
+                out.extend(ballot.trainees.iter().map(|trainee| {
+                    let submissions = relevant_submission_map.get(
+                        &(
+                            debate_info.uuid,
+                            *trainee
+                        )
+                    ).unwrap_or(&empty_vec);
+                    FeedbackSubmissionInfo {
+                        source_role: request_source_role,
+                        target_role: FeedbackTargetRole::Trainee,
+                        target_id: *trainee,
+                        target_name: relevant_names.get(trainee).expect("Missing name").clone(),
+                        round_name: round_name.clone(),
+                        round_id: *round_id,
+                        debate_id: debate_info.uuid,
+                        submitted_responses: submissions.iter().map(|s| s.uuid).collect(),
+                        source_id
+                    }
+                }));
+            }
 
             out
         }
@@ -715,7 +747,8 @@ struct ParticipantShortInfoResponse {
     name: String,
     tournament_name: String,
     role: ParticipantRoleInfo,
-    can_edit_clashes: bool
+    can_edit_clashes: bool,
+    can_edit_availability: bool
 }
 
 async fn get_participant_short_info(
@@ -761,6 +794,7 @@ async fn get_participant_short_info(
                 tournament.allow_self_declared_clashes
             }
         },
+        can_edit_availability: matches!(role, ParticipantRoleInfo::Adjudicator) && tournament.allow_self_declared_availability,
         role
     }))
 }
@@ -793,6 +827,7 @@ pub async fn get_participant_settings(
 
 pub async fn update_participant_settings(
     State(db): State<DatabaseConnection>,
+    State(notifications): State<Arc<RwLock<ParticipantNotificationManager>>>,
     ExtractAuthenticatedUser(user): ExtractAuthenticatedUser,
     Path(participant_id): Path<Uuid>,
     Json(new_settings): Json<ParticipantSettings>
@@ -807,12 +842,13 @@ pub async fn update_participant_settings(
         let tournament_id = participant.tournament_id;
         let mut entity_group = EntityGroup::new(
             participant.tournament_id
-        );
+        ).with_author(LogAuthor::User { user_id: user.uuid });
         participant.is_anonymous = new_settings.is_anonymous;
         entity_group.add(
             open_tab_entities::Entity::Participant(participant)
         );
         entity_group.save_all_and_log(&db).await?;
+        notifications.read().await.notify_tournament_log_head(&db, tournament_id).await;
         Ok(())
     }
     else {
@@ -966,16 +1002,15 @@ pub async fn get_participant_declared_clashes(
     ExtractAuthenticatedUser(user): ExtractAuthenticatedUser,
     Path(participant_id): Path<Uuid>,
 ) -> Result<Json<ParticipantDeclaredClashList>, APIError> {
-    if !user.check_is_authorized_as_participant(&db, participant_id).await? {
-        let err = APIError::new_with_status(StatusCode::FORBIDDEN, "You are not authorized to view this participant");
-        return Err(err);
-    }
-
     let participant = schema::participant::Entity::find_by_id(participant_id)
     .find_also_related(schema::tournament::Entity)
     .one(&db).await?;
 
-    if let Some((participant, Some(tournament))) = participant {
+    if let Some((_participant, Some(tournament))) = participant {
+        if !(user.check_is_authorized_as_participant(&db, participant_id).await? || user.check_has_permission(&db, tournament.uuid, TournamentPermission::ReadClashes).await?) {
+            let err = APIError::new_with_status(StatusCode::FORBIDDEN, "You are not authorized to view this participant");
+            return Err(err);
+        }
         if !tournament.allow_self_declared_clashes {
             return Err(APIError::new_with_status(StatusCode::FORBIDDEN, "Self-declared clashes are not allowed in this tournament"));
         }
@@ -1062,6 +1097,7 @@ pub struct UpdateParticipantClashesRequest {
 
 pub async fn update_participant_clash_declarations(
     State(db): State<DatabaseConnection>,
+    State(notifications): State<Arc<RwLock<ParticipantNotificationManager>>>,
     ExtractAuthenticatedUser(user): ExtractAuthenticatedUser,
     Path(participant_id): Path<Uuid>,
     Json(request): Json<UpdateParticipantClashesRequest>
@@ -1071,6 +1107,7 @@ pub async fn update_participant_clash_declarations(
         return Err(err);
     }
 
+    let connection = db.clone();
     let db = db.begin().await?;
 
     let participant = schema::participant::Entity::find_by_id(participant_id)
@@ -1096,7 +1133,7 @@ pub async fn update_participant_clash_declarations(
         
         let mut entity_group = EntityGroup::new(
             participant.tournament_id
-        );
+        ).with_author(LogAuthor::User { user_id: user.uuid });
         let existing_clash_declarations = open_tab_entities::schema::clash_declaration::Entity::find()
         .filter(
             open_tab_entities::schema::clash_declaration::Column::SourceParticipantId.eq(participant_id)
@@ -1188,6 +1225,7 @@ pub async fn update_participant_clash_declarations(
         entity_group.save_all_and_log(&db).await?;
 
         db.commit().await?;
+        notifications.read().await.notify_tournament_log_head(&connection, participant.tournament_id).await;
         Ok(())
     }
     else {
@@ -1195,6 +1233,137 @@ pub async fn update_participant_clash_declarations(
     }
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ParticipantAvailabilityResponse {
+    pub rounds: Vec<RoundAvailability>
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RoundAvailability {
+    pub round_id: Uuid,
+    pub round_index: u64,
+    pub is_available: bool,
+    pub change_deadline: Option<chrono::NaiveDateTime>,
+    pub can_change: bool
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UpdateParticipantAvailabilityRequest {
+    #[serde(default)]
+    pub unavailable_rounds: Vec<Uuid>,
+    #[serde(default)]
+    pub available_rounds: Vec<Uuid>
+}
+
+async fn load_adjudicator_for_availability<C>(db: &C, participant_id: Uuid) -> Result<(domain::participant::Participant, domain::tournament::Tournament), APIError> where C: sea_orm::ConnectionTrait {
+    let participant = domain::participant::Participant::try_get(db, participant_id).await?;
+    let participant = match participant {
+        Some(participant) => participant,
+        None => return Err(APIError::new_with_status(StatusCode::NOT_FOUND, "Participant not found"))
+    };
+
+    if !matches!(participant.role, domain::participant::ParticipantRole::Adjudicator(_)) {
+        return Err(APIError::new_with_status(StatusCode::FORBIDDEN, "Only adjudicators can declare their availability"));
+    }
+
+    let tournament = domain::tournament::Tournament::get(db, participant.tournament_id).await?;
+    if !tournament.allow_self_declared_availability {
+        return Err(APIError::new_with_status(StatusCode::FORBIDDEN, "Self-declared availability is not allowed in this tournament"));
+    }
+
+    Ok((participant, tournament))
+}
+
+pub async fn get_participant_availability(
+    State(db): State<DatabaseConnection>,
+    ExtractAuthenticatedUser(user): ExtractAuthenticatedUser,
+    Path(participant_id): Path<Uuid>,
+) -> Result<Json<ParticipantAvailabilityResponse>, APIError> {
+    if !user.check_is_authorized_as_participant(&db, participant_id).await? {
+        let err = APIError::new_with_status(StatusCode::FORBIDDEN, "You are not authorized to view this participant");
+        return Err(err);
+    }
+
+    let (participant, tournament) = load_adjudicator_for_availability(&db, participant_id).await?;
+    let unavailable_rounds = match &participant.role {
+        domain::participant::ParticipantRole::Adjudicator(adj) => adj.unavailable_rounds.clone(),
+        _ => unreachable!("Checked in load_adjudicator_for_availability")
+    };
+
+    let current_time = chrono::Utc::now().naive_utc();
+    let rounds = domain::round::TournamentRound::get_all_in_tournament(&db, tournament.uuid).await?;
+
+    let rounds = rounds.into_iter().sorted_by_key(|r| r.index).map(|round| {
+        let change_deadline = round.availability_change_deadline(tournament.availability_deadline_minutes);
+        RoundAvailability {
+            round_id: round.uuid,
+            round_index: round.index,
+            is_available: !unavailable_rounds.contains(&round.uuid),
+            change_deadline,
+            can_change: !check_release_date(current_time, change_deadline)
+        }
+    }).collect();
+
+    Ok(Json(ParticipantAvailabilityResponse { rounds }))
+}
+
+pub async fn update_participant_availability(
+    State(db): State<DatabaseConnection>,
+    State(notifications): State<Arc<RwLock<ParticipantNotificationManager>>>,
+    ExtractAuthenticatedUser(user): ExtractAuthenticatedUser,
+    Path(participant_id): Path<Uuid>,
+    Json(request): Json<UpdateParticipantAvailabilityRequest>
+) -> Result<(), APIError> {
+    if !user.check_is_authorized_as_participant(&db, participant_id).await? {
+        let err = APIError::new_with_status(StatusCode::FORBIDDEN, "You are not authorized to view this participant");
+        return Err(err);
+    }
+
+    let connection = db.clone();
+    let db = db.begin().await?;
+    let (mut participant, tournament) = load_adjudicator_for_availability(&db, participant_id).await?;
+
+    let changed_round_ids = request.unavailable_rounds.iter().chain(request.available_rounds.iter()).cloned().unique().collect_vec();
+    let changed_rounds = domain::round::TournamentRound::try_get_many(&db, changed_round_ids).await?;
+
+    let current_time = chrono::Utc::now().naive_utc();
+    for round in changed_rounds.iter() {
+        match round {
+            Some(round) if round.tournament_id == tournament.uuid => {
+                if check_release_date(current_time, round.availability_change_deadline(tournament.availability_deadline_minutes)) {
+                    db.rollback().await?;
+                    return Err(APIError::new_with_status(StatusCode::FORBIDDEN, "The deadline for changing availability has passed"));
+                }
+            },
+            _ => {
+                db.rollback().await?;
+                return Err(APIError::new_with_status(StatusCode::NOT_FOUND, "Round not found"));
+            }
+        }
+    }
+
+    if let domain::participant::ParticipantRole::Adjudicator(adj) = &mut participant.role {
+        adj.unavailable_rounds.retain(|r| !request.available_rounds.contains(r));
+        for round_id in request.unavailable_rounds {
+            if !adj.unavailable_rounds.contains(&round_id) && !request.available_rounds.contains(&round_id) {
+                adj.unavailable_rounds.push(round_id);
+            }
+        }
+    }
+
+    let mut entity_group = EntityGroup::new(
+        participant.tournament_id
+    ).with_author(LogAuthor::User { user_id: user.uuid });
+    entity_group.add(
+        open_tab_entities::Entity::Participant(participant)
+    );
+    entity_group.save_all_and_log(&db).await?;
+
+    db.commit().await?;
+    notifications.read().await.notify_tournament_log_head(&connection, tournament.uuid).await;
+    Ok(())
+}
+
 pub fn router() -> Router<AppState> {
     Router::new()
     .route("/tournament/:tournament_id/participants", get(list_participants))
@@ -1204,4 +1373,6 @@ pub fn router() -> Router<AppState> {
     .route("/participant/:participant_id/settings", post(update_participant_settings))
     .route("/participant/:participant_id/clashes", get(get_participant_declared_clashes))
     .route("/participant/:participant_id/clashes", post(update_participant_clash_declarations))
+    .route("/participant/:participant_id/availability", get(get_participant_availability))
+    .route("/participant/:participant_id/availability", post(update_participant_availability))
 }
@@ -1,12 +1,14 @@
+use std::sync::Arc;
 use axum::{extract::{State, Path}, Json, Router, routing::{post, get}};
 use chrono::Duration;
 use axum::http::StatusCode;
 
-use open_tab_entities::{derived_models::{DrawPresentationInfo, LoadDrawError}, domain::{self, entity::LoadEntity}, schema, EntityGroup};
+use open_tab_entities::{derived_models::{DrawPresentationInfo, LoadDrawError}, domain::{self, entity::LoadEntity}, schema, EntityGroup, LogAuthor};
 use sea_orm::{prelude::Uuid, DatabaseConnection, EntityTrait, TransactionTrait};
 use serde::{Serialize, Deserialize};
+use tokio::sync::RwLock;
 
-use crate::{auth::ExtractAuthenticatedUser, response::APIError, state::AppState};
+use crate::{auth::ExtractAuthenticatedUser, notify::ParticipantNotificationManager, response::APIError, staff::TournamentPermission, state::AppState};
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 struct DrawPresentationInfoWithTime {
@@ -27,7 +29,7 @@ async fn get_draw_presentation(
         return Err(APIError::new_with_status(StatusCode::NOT_FOUND, "Round not found"))
     }
     let round = round.unwrap();
-    if !user.check_is_authorized_for_tournament_administration(&db, round.tournament_id).await? {
+    if !user.check_has_permission(&db, round.tournament_id, TournamentPermission::ReadDraw).await? {
         return Err(APIError::new_with_status(StatusCode::FORBIDDEN, "User is not authorized for this tournament"))
     }
     let presentation_info = DrawPresentationInfo::load_for_round(&db, round_id).await;
@@ -64,9 +66,11 @@ struct ReleaseMotionResponse {
 
 async fn set_motion_release(
     State(db): State<DatabaseConnection>,
+    State(notifications): State<Arc<RwLock<ParticipantNotificationManager>>>,
     ExtractAuthenticatedUser(user): ExtractAuthenticatedUser,
     Path(round_id): Path<Uuid>,
 ) -> Result<Json<ReleaseMotionResponse>, APIError> {
+    let connection = db.clone();
     let db = db.begin().await?;
     let round = domain::round::TournamentRound::try_get(&db, round_id).await?;
 
@@ -78,7 +82,7 @@ async fn set_motion_release(
     let mut round = round.unwrap();
     let tournament_id = round.tournament_id;
 
-    if !user.check_is_authorized_for_tournament_administration(&db, tournament_id).await? {
+    if !user.check_has_permission(&db, tournament_id, TournamentPermission::ManageTournament).await? {
         db.rollback().await?;
         return Err(APIError::new_with_status(StatusCode::FORBIDDEN, "User is not authorized for this tournament"))
     }
@@ -99,7 +103,7 @@ async fn set_motion_release(
 
     let mut entity_group = EntityGroup::new(
         tournament_id
-    );
+    ).with_author(LogAuthor::User { user_id: user.uuid });
 
     entity_group.add(
         open_tab_entities::Entity::TournamentRound(round)
@@ -107,6 +111,7 @@ async fn set_motion_release(
 
     entity_group.save_all_and_log(&db).await?;
     db.commit().await?;
+    notifications.read().await.notify_tournament_log_head(&connection, tournament_id).await;
 
     Ok(Json(
         ReleaseMotionResponse {
@@ -11,6 +11,7 @@ use serde::Serialize;
 use crate::{
     auth::MaybeExtractAuthenticatedUser,
     response::APIError,
+    staff::TournamentPermission,
     state::AppState
 };
 
@@ -40,8 +41,11 @@ async fn get_draw_handler(
     let tournament = tournament.unwrap(); // Guaranteed by db constraints
     
     let mut is_authorized = false;
+    // Staff that may read the draw also see it before its release
+    let mut can_read_unreleased_draw = false;
     if let Some(user) = user {
-        is_authorized = user.check_is_authorized_in_tournament(&db, tournament.uuid).await.unwrap_or(false);
+        can_read_unreleased_draw = user.check_has_permission(&db, tournament.uuid, TournamentPermission::ReadDraw).await.unwrap_or(false);
+        is_authorized = can_read_unreleased_draw || user.check_is_authorized_in_tournament(&db, tournament.uuid).await.unwrap_or(false);
     }
 
     if !is_authorized {
@@ -61,7 +65,7 @@ async fn get_draw_handler(
     }
 
     let now = chrono::Utc::now().naive_utc();
-    if !check_release_date(now, round.draw_release_time) {
+    if !can_read_unreleased_draw && !check_release_date(now, round.draw_release_time) {
         return Err(APIError::new_with_status(StatusCode::FORBIDDEN, "Draw has not been released yet"));
     }
 
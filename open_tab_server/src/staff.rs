@@ -0,0 +1,255 @@
+use std::str::FromStr;
+
+use axum::extract::{Path, State};
+use axum::http::StatusCode;
+use axum::routing::{delete, get, post};
+use axum::{Json, Router};
+use open_tab_entities::schema::{user, user_tournament};
+use sea_orm::{prelude::*, ActiveValue, DatabaseConnection, IntoActiveModel, QueryOrder, TransactionTrait};
+use serde::{Deserialize, Serialize};
+
+use crate::auth::ExtractAuthenticatedUser;
+use crate::response::APIError;
+use crate::state::AppState;
+
+
+/// The role a user has as staff of a tournament
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum TournamentRole {
+    /// Full access to the tournament
+    TabDirector,
+    /// Can see draws, but not scores
+    AdjudicationCore,
+    /// Can accept submitted ballots as debate results
+    BallotEntry,
+    /// Can see declared clashes and feedback to handle equity complaints,
+    /// but neither draws nor scores
+    Equity,
+    /// Can see everything, but change nothing
+    Observer,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TournamentPermission {
+    /// See the tournament, its settings and its rounds
+    ViewTournament,
+    /// See draws before they are released to participants
+    ReadDraw,
+    /// See ballots, scores and participant details
+    ReadScores,
+    /// See the clashes participants declared
+    ReadClashes,
+    /// See the feedback participants received
+    ReadFeedback,
+    /// Download the full tournament log
+    ReadLog,
+    /// Accept a submitted ballot as the result of a debate
+    PromoteBallots,
+    /// Push changes to the tournament and change its settings
+    ManageTournament,
+    /// Invite and remove staff
+    ManageStaff,
+}
+
+impl TournamentRole {
+    pub fn has_permission(&self, permission: TournamentPermission) -> bool {
+        match self {
+            TournamentRole::TabDirector => true,
+            TournamentRole::AdjudicationCore => matches!(
+                permission,
+                TournamentPermission::ViewTournament | TournamentPermission::ReadDraw
+            ),
+            TournamentRole::Equity => matches!(
+                permission,
+                TournamentPermission::ViewTournament | TournamentPermission::ReadClashes | TournamentPermission::ReadFeedback
+            ),
+            TournamentRole::BallotEntry => matches!(
+                permission,
+                TournamentPermission::ViewTournament | TournamentPermission::PromoteBallots
+            ),
+            TournamentRole::Observer => matches!(
+                permission,
+                TournamentPermission::ViewTournament | TournamentPermission::ReadDraw | TournamentPermission::ReadScores
+                    | TournamentPermission::ReadClashes | TournamentPermission::ReadFeedback | TournamentPermission::ReadLog
+            ),
+        }
+    }
+
+    pub fn to_str(&self) -> &'static str {
+        match self {
+            TournamentRole::TabDirector => "TabDirector",
+            TournamentRole::AdjudicationCore => "AdjudicationCore",
+            TournamentRole::BallotEntry => "BallotEntry",
+            TournamentRole::Equity => "Equity",
+            TournamentRole::Observer => "Observer",
+        }
+    }
+}
+
+impl FromStr for TournamentRole {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "TabDirector" => Ok(TournamentRole::TabDirector),
+            "AdjudicationCore" => Ok(TournamentRole::AdjudicationCore),
+            "BallotEntry" => Ok(TournamentRole::BallotEntry),
+            "Equity" => Ok(TournamentRole::Equity),
+            "Observer" => Ok(TournamentRole::Observer),
+            _ => Err(anyhow::anyhow!("Unknown tournament role {}", s)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StaffMemberInfo {
+    pub user_id: Uuid,
+    pub identifier: String,
+    pub role: TournamentRole,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StaffList {
+    pub staff: Vec<StaffMemberInfo>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InviteStaffRequest {
+    pub user_email: String,
+    pub role: TournamentRole,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InviteStaffResponse {
+    pub user_id: Uuid,
+}
+
+async fn get_staff(
+    State(db): State<DatabaseConnection>,
+    ExtractAuthenticatedUser(user): ExtractAuthenticatedUser,
+    Path(tournament_id): Path<Uuid>,
+) -> Result<Json<StaffList>, APIError> {
+    if !user.check_has_permission(&db, tournament_id, TournamentPermission::ManageStaff).await? {
+        return Err(APIError::new_with_status(StatusCode::FORBIDDEN, "User is not allowed to manage staff for this tournament"));
+    }
+
+    let staff = user_tournament::Entity::find()
+        .find_also_related(user::Entity)
+        .filter(user_tournament::Column::TournamentId.eq(tournament_id))
+        .order_by_asc(user_tournament::Column::UserId)
+        .all(&db)
+        .await?;
+
+    let staff = staff.into_iter().map(|(user_tournament, user)| {
+        Ok(StaffMemberInfo {
+            user_id: user_tournament.user_id,
+            identifier: user.and_then(|u| u.user_email).unwrap_or("Anonymous User".to_string()),
+            role: user_tournament.role.parse()?,
+        })
+    }).collect::<Result<Vec<_>, anyhow::Error>>()?;
+
+    Ok(Json(StaffList { staff }))
+}
+
+/// Gives the user with the given email a role in the tournament.
+/// If the user already is staff, their role is replaced.
+async fn invite_staff(
+    State(db): State<DatabaseConnection>,
+    ExtractAuthenticatedUser(user): ExtractAuthenticatedUser,
+    Path(tournament_id): Path<Uuid>,
+    Json(request): Json<InviteStaffRequest>,
+) -> Result<Json<InviteStaffResponse>, APIError> {
+    let transaction = db.begin().await?;
+    if !user.check_has_permission(&transaction, tournament_id, TournamentPermission::ManageStaff).await? {
+        transaction.rollback().await?;
+        return Err(APIError::new_with_status(StatusCode::FORBIDDEN, "User is not allowed to manage staff for this tournament"));
+    }
+
+    let invited_user = user::Entity::find()
+        .filter(user::Column::UserEmail.eq(&request.user_email))
+        .one(&transaction)
+        .await?;
+    let Some(invited_user) = invited_user else {
+        transaction.rollback().await?;
+        return Err(APIError::new_with_status(StatusCode::NOT_FOUND, "No user with this email exists"));
+    };
+
+    let existing_entry = user_tournament::Entity::find_by_id((invited_user.uuid, tournament_id))
+        .one(&transaction)
+        .await?;
+
+    match existing_entry {
+        Some(existing_entry) => {
+            if existing_entry.role != request.role.to_str() {
+                check_keeps_tab_director(&transaction, tournament_id, &existing_entry).await?;
+                let mut existing_entry = existing_entry.into_active_model();
+                existing_entry.role = ActiveValue::Set(request.role.to_str().to_string());
+                existing_entry.update(&transaction).await?;
+            }
+        },
+        None => {
+            user_tournament::Model {
+                user_id: invited_user.uuid,
+                tournament_id,
+                role: request.role.to_str().to_string(),
+            }.into_active_model().insert(&transaction).await?;
+        }
+    }
+
+    transaction.commit().await?;
+
+    Ok(Json(InviteStaffResponse { user_id: invited_user.uuid }))
+}
+
+async fn revoke_staff(
+    State(db): State<DatabaseConnection>,
+    ExtractAuthenticatedUser(user): ExtractAuthenticatedUser,
+    Path((tournament_id, user_id)): Path<(Uuid, Uuid)>,
+) -> Result<Json<()>, APIError> {
+    let transaction = db.begin().await?;
+    if !user.check_has_permission(&transaction, tournament_id, TournamentPermission::ManageStaff).await? {
+        transaction.rollback().await?;
+        return Err(APIError::new_with_status(StatusCode::FORBIDDEN, "User is not allowed to manage staff for this tournament"));
+    }
+
+    let entry = user_tournament::Entity::find_by_id((user_id, tournament_id))
+        .one(&transaction)
+        .await?;
+    let Some(entry) = entry else {
+        transaction.rollback().await?;
+        return Err(APIError::new_with_status(StatusCode::NOT_FOUND, "User is not staff of this tournament"));
+    };
+
+    check_keeps_tab_director(&transaction, tournament_id, &entry).await?;
+    entry.delete(&transaction).await?;
+    transaction.commit().await?;
+
+    Ok(Json(()))
+}
+
+/// A tournament can not be left without a tab director, since nobody
+/// could manage it anymore.
+async fn check_keeps_tab_director<C>(db: &C, tournament_id: Uuid, changed_entry: &user_tournament::Model) -> Result<(), APIError> where C: sea_orm::ConnectionTrait {
+    if changed_entry.role != TournamentRole::TabDirector.to_str() {
+        return Ok(());
+    }
+
+    let num_tab_directors = user_tournament::Entity::find()
+        .filter(user_tournament::Column::TournamentId.eq(tournament_id))
+        .filter(user_tournament::Column::Role.eq(TournamentRole::TabDirector.to_str()))
+        .count(db)
+        .await?;
+
+    if num_tab_directors <= 1 {
+        return Err(APIError::new_with_status(StatusCode::BAD_REQUEST, "The last tab director of a tournament can not be removed"));
+    }
+
+    Ok(())
+}
+
+pub(crate) fn router() -> Router<AppState> {
+    Router::new()
+        .route("/tournament/:tournament_id/staff", get(get_staff))
+        .route("/tournament/:tournament_id/staff", post(invite_staff))
+        .route("/tournament/:tournament_id/staff/:user_id", delete(revoke_staff))
+}
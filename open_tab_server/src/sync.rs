@@ -1,19 +1,28 @@
 use std::collections::{HashMap, HashSet};
+use std::convert::Infallible;
+use std::io::{Read, Write};
 use std::sync::Arc;
+use std::time::Duration;
 
+use axum::body::Bytes;
 use axum::extract::DefaultBodyLimit;
+use axum::http::{header, HeaderMap, HeaderValue};
+use axum::response::sse::{Event, KeepAlive, Sse};
+use axum::response::{IntoResponse, Response};
 use axum::{extract::{Query, Path, State}, Router, routing::{get, post}, Json};
 use chrono::Utc;
 use axum::http::StatusCode;
 use itertools::Itertools;
-use open_tab_entities::{get_changed_entities_from_log, Entity, EntityGroup, EntityState, EntityTypeId, EntityTypeIdTrait, NewEntityState};
+use open_tab_entities::{get_changed_entities_from_log, Entity, EntityGroup, EntityState, EntityTypeId, EntityTypeIdTrait, LogAuthor, NewEntityState};
 use sea_orm::{prelude::*, AccessMode, DatabaseConnection, IntoActiveModel, IsolationLevel, QueryOrder, QuerySelect, TransactionTrait};
+use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
 
 use tokio::sync::RwLock;
+use tokio_stream::Stream;
 use tracing::error_span;
 
-use crate::{state::AppState, response::APIError, auth::ExtractAuthenticatedUser};
+use crate::{state::AppState, response::APIError, auth::ExtractAuthenticatedUser, staff::TournamentPermission};
 
 
 
@@ -33,6 +42,8 @@ pub struct LogEntry<T> {
     pub target_type: T,
     pub target_uuid: Uuid,
     pub timestamp: DateTime,
+    #[serde(default)]
+    pub author: Option<LogAuthor>,
 }
 
 impl <T>From<&open_tab_entities::schema::tournament_log::Model> for LogEntry<T> where T: EntityTypeIdTrait {
@@ -42,6 +53,7 @@ impl <T>From<&open_tab_entities::schema::tournament_log::Model> for LogEntry<T>
             target_type: model.target_type.clone().into(),
             target_uuid: model.target_uuid,
             timestamp: model.timestamp,
+            author: LogAuthor::from_log_entry(model),
         }
     }
 }
@@ -58,7 +70,42 @@ pub struct FatLog<E, T> where T: EntityTypeIdTrait {
     >
 }
 
+/// The most log entries transferred in a single pull or push
+pub const MAX_LOG_PAGE_SIZE: u64 = 1000;
+
+/// Upper bound for the size of a decompressed log, so a small compressed
+/// body can not exhaust the server's memory
+const MAX_DECOMPRESSED_LOG_SIZE: u64 = 1024 * 1024 * 200;
+
+/// A part of the log since some entry.
+/// If `has_more` is set, the next page starts after the last entry of this one.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FatLogPage<E, T> where T: EntityTypeIdTrait {
+    #[serde(flatten)]
+    pub log: FatLog<E, T>,
+    #[serde(default)]
+    pub has_more: bool,
+}
+
 pub async fn get_log_since<C>(transaction: &C, tournament_id: Uuid, since: Option<Uuid>) -> Result<Vec<open_tab_entities::schema::tournament_log::Model>, anyhow::Error> where C: sea_orm::ConnectionTrait  {
+    get_log_page_since(transaction, tournament_id, since, None).await
+}
+
+/// Finds the sequence index of a log entry.
+/// Entries removed by log compaction keep their position, so clients that
+/// synced before the compaction can still continue from them.
+pub async fn get_log_position<C>(db: &C, log_entry_id: Uuid) -> Result<Option<i32>, anyhow::Error> where C: sea_orm::ConnectionTrait {
+    let entry = open_tab_entities::schema::tournament_log::Entity::find_by_id(log_entry_id).one(db).await?;
+    if let Some(entry) = entry {
+        return Ok(Some(entry.sequence_idx));
+    }
+
+    let pruned_entry = open_tab_entities::schema::tournament_log_pruned_entry::Entity::find_by_id(log_entry_id).one(db).await?;
+    Ok(pruned_entry.map(|entry| entry.sequence_idx))
+}
+
+/// Returns at most `limit` entries of the log after `since`
+pub async fn get_log_page_since<C>(transaction: &C, tournament_id: Uuid, since: Option<Uuid>, limit: Option<u64>) -> Result<Vec<open_tab_entities::schema::tournament_log::Model>, anyhow::Error> where C: sea_orm::ConnectionTrait  {
     let log_query: Select<open_tab_entities::schema::tournament_log::Entity> = open_tab_entities::schema::tournament_log::Entity::find()
         .filter(open_tab_entities::schema::tournament_log::Column::TournamentId.eq(tournament_id))
         .order_by_asc(open_tab_entities::schema::tournament_log::Column::SequenceIdx);
@@ -66,21 +113,98 @@ pub async fn get_log_since<C>(transaction: &C, tournament_id: Uuid, since: Optio
     let log_query = match since {
         None => log_query,
         Some(since) => {
-            let model = open_tab_entities::schema::tournament_log::Entity::find_by_id(since).one(transaction).await?.ok_or(anyhow::anyhow!("Since is not a valid log entry"))?;
-            log_query.filter(open_tab_entities::schema::tournament_log::Column::SequenceIdx.gt(model.sequence_idx))
+            let sequence_idx = get_log_position(transaction, since).await?.ok_or(anyhow::anyhow!("Since is not a valid log entry"))?;
+            log_query.filter(open_tab_entities::schema::tournament_log::Column::SequenceIdx.gt(sequence_idx))
         }
     };
 
     let log = log_query
+        .limit(limit)
         .all(transaction)
         .await?;
     Ok(log)
 }
 
 
+/// The length of the longest prefix of `page` that does not end between two changes of an entity
+/// without a snapshot. We only know the current state of such entities, so the page must
+/// contain all their changes. If there is no such prefix, the page is left as it is.
+async fn get_consistent_page_length<C>(transaction: &C, tournament_id: Uuid, page: &[open_tab_entities::schema::tournament_log::Model]) -> Result<usize, anyhow::Error> where C: sea_orm::ConnectionTrait {
+    let Some(last_entry) = page.last() else {
+        return Ok(0);
+    };
+
+    let has_snapshot = |entry: &open_tab_entities::schema::tournament_log::Model| {
+        entry.snapshot.as_ref().and_then(|snapshot| serde_json::from_str::<EntityState<Entity, EntityTypeId>>(snapshot).ok()).is_some()
+    };
+
+    // The index of the next change of the same entity for every entry without a snapshot.
+    // Changes after the page count as `page.len()`.
+    let mut next_change_idx = vec![None; page.len()];
+    let mut next_entity_changes = HashMap::new();
+    for (idx, entry) in page.iter().enumerate().rev() {
+        if !has_snapshot(entry) {
+            next_change_idx[idx] = next_entity_changes.get(&entry.target_uuid).copied();
+        }
+        next_entity_changes.insert(entry.target_uuid, idx);
+    }
+
+    let entities_without_later_change = page.iter().zip(next_change_idx.iter())
+        .filter(|(entry, next_idx)| !has_snapshot(entry) && next_idx.is_none())
+        .map(|(entry, _)| entry.target_uuid)
+        .unique()
+        .collect_vec();
+
+    let mut changed_after_page = HashSet::new();
+    for chunk in entities_without_later_change.chunks(500) {
+        let later_changes = open_tab_entities::schema::tournament_log::Entity::find()
+            .filter(open_tab_entities::schema::tournament_log::Column::TournamentId.eq(tournament_id))
+            .filter(open_tab_entities::schema::tournament_log::Column::SequenceIdx.gt(last_entry.sequence_idx))
+            .filter(open_tab_entities::schema::tournament_log::Column::TargetUuid.is_in(chunk.iter().cloned()))
+            .all(transaction)
+            .await?;
+        changed_after_page.extend(later_changes.into_iter().map(|entry| entry.target_uuid));
+    }
+
+    let mut consistent_length = None;
+    let mut required_length = 0;
+    for (idx, entry) in page.iter().enumerate() {
+        if !has_snapshot(entry) {
+            let next_idx = next_change_idx[idx].or_else(|| changed_after_page.contains(&entry.target_uuid).then_some(page.len()));
+            if let Some(next_idx) = next_idx {
+                required_length = required_length.max(next_idx + 1);
+            }
+        }
+        if required_length <= idx + 1 {
+            consistent_length = Some(idx + 1);
+        }
+    }
+
+    Ok(consistent_length.unwrap_or(page.len()))
+}
+
 pub async fn get_entity_changes_since<C>(transaction: &C, tournament_id: Uuid, since: Option<Uuid>) -> Result<FatLog<Entity, EntityTypeId>, anyhow::Error>
     where C: sea_orm::ConnectionTrait  {
-    let log = get_log_since(transaction, tournament_id, since).await?;
+    Ok(get_entity_changes_page_since(transaction, tournament_id, since, None).await?.log)
+}
+
+/// Like `get_entity_changes_since`, but stops after `limit` log entries.
+pub async fn get_entity_changes_page_since<C>(transaction: &C, tournament_id: Uuid, since: Option<Uuid>, limit: Option<u64>) -> Result<FatLogPage<Entity, EntityTypeId>, anyhow::Error>
+    where C: sea_orm::ConnectionTrait  {
+    // We fetch one more entry than requested to find out if there is another page
+    let mut log = get_log_page_since(transaction, tournament_id, since, limit.map(|limit| limit + 1)).await?;
+    let has_more = limit.is_some_and(|limit| log.len() as u64 > limit);
+    if let Some(limit) = limit {
+        log.truncate(limit as usize);
+    }
+    // Entities without a snapshot can only be sent in their current state, which is
+    // inconsistent with the page if they change again later. In that case, we end the
+    // page early, so that it contains all of their changes, if possible.
+    if has_more {
+        let consistent_length = get_consistent_page_length(transaction, tournament_id, &log).await?;
+        log.truncate(consistent_length);
+    }
+
     let flat_log = log.iter().map(
         LogEntry::from
     ).collect::<Vec<LogEntry<EntityTypeId>>>();
@@ -91,6 +215,16 @@ pub async fn get_entity_changes_since<C>(transaction: &C, tournament_id: Uuid, s
     let latest_entries = entity_entries.iter_mut().map(|((_entity_type, _entity_uuid), entries)| {
         entries.pop().unwrap() // This can never be empty, otherwise the key would not be in the group map
     }).collect::<Vec<_>>();
+    // If later pages follow, entities may have changed again after this page.
+    // We send them as they were at the end of the page, so each page is consistent on its own.
+    let mut page_snapshots = if has_more {
+        latest_entries.iter().filter_map(|entry| {
+            let snapshot = serde_json::from_str::<EntityState<Entity, EntityTypeId>>(entry.snapshot.as_ref()?).ok()?;
+            Some((entry.uuid, snapshot))
+        }).collect::<HashMap<_, _>>()
+    } else {
+        HashMap::new()
+    };
     let versioned_entities = get_changed_entities_from_log(transaction, latest_entries).await?;
 
     let versioned_entities_map = versioned_entities.into_iter().map(|entity| {
@@ -106,27 +240,69 @@ pub async fn get_entity_changes_since<C>(transaction: &C, tournament_id: Uuid, s
             uuid: entity_uuid,
             old_versions: entries.into_iter().map(|entry| entry.target_uuid).collect::<Vec<_>>(),
             current_version: latest_version.version,
-            current_value: latest_version.entity.clone().into()
+            current_value: page_snapshots.remove(&latest_version.version).unwrap_or_else(|| latest_version.entity.clone())
         })
     }).into_grouping_map().collect::<Vec<_>>();
 
-    Ok(FatLog {
-        log: flat_log,
-        entities
+    Ok(FatLogPage {
+        log: FatLog {
+            log: flat_log,
+            entities
+        },
+        has_more
     })
 }
 
+fn accepts_gzip(headers: &HeaderMap) -> bool {
+    headers.get_all(header::ACCEPT_ENCODING).iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .any(|encoding| encoding.split(';').next().map(|e| e.trim()) == Some("gzip"))
+}
+
+fn is_gzip(headers: &HeaderMap) -> bool {
+    headers.get(header::CONTENT_ENCODING).is_some_and(|value| value == "gzip")
+}
+
+/// Serializes a log for transfer, compressing it with gzip if requested.
+/// Logs are very repetitive, so this shrinks them considerably.
+pub fn encode_log_body<T>(value: &T, compress: bool) -> Result<Vec<u8>, anyhow::Error> where T: Serialize {
+    let json = serde_json::to_vec(value)?;
+    if !compress {
+        return Ok(json);
+    }
+    let mut encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+    encoder.write_all(&json)?;
+    Ok(encoder.finish()?)
+}
+
+/// Reverses `encode_log_body`
+pub fn decode_log_body<T>(body: &[u8], is_compressed: bool) -> Result<T, anyhow::Error> where T: DeserializeOwned {
+    if !is_compressed {
+        return Ok(serde_json::from_slice(body)?);
+    }
+    let mut json = Vec::new();
+    flate2::read::GzDecoder::new(body).take(MAX_DECOMPRESSED_LOG_SIZE + 1).read_to_end(&mut json)?;
+    if json.len() as u64 > MAX_DECOMPRESSED_LOG_SIZE {
+        return Err(anyhow::anyhow!("Decompressed log is too large"));
+    }
+    Ok(serde_json::from_slice(&json)?)
+}
+
+/// Returns the log since the `since` entry, in pages of at most `limit` entries.
+/// The response is gzip compressed if the client accepts it.
 async fn get_log(
     State(db): State<DatabaseConnection>,
     ExtractAuthenticatedUser(user): ExtractAuthenticatedUser,
     Path(tournament_id): Path<Uuid>,
-    Query(params): Query<HashMap<String, String>>
-) -> Result<Json<FatLog<Entity, EntityTypeId>>, APIError> {
+    Query(params): Query<HashMap<String, String>>,
+    headers: HeaderMap,
+) -> Result<Response, APIError> {
     let tournament = open_tab_entities::schema::tournament::Entity::find_by_id(tournament_id).one(&db).await?;
     if tournament.is_none() {
         return Err(APIError::new_with_status(StatusCode::NOT_FOUND, "Tournament not found"));
     }
-    if !user.check_is_authorized_for_tournament_administration(&db, tournament_id).await? {
+    if !user.check_has_permission(&db, tournament_id, TournamentPermission::ReadLog).await? {
         return Err(APIError::new_with_status(StatusCode::FORBIDDEN, "User is not authorized for tournament administration"));
     }
 
@@ -141,34 +317,69 @@ async fn get_log(
         Some(Ok(since)) => Ok(Some(since))
     }?;
 
+    let limit = match params.get("limit").map(|limit| limit.parse::<u64>()) {
+        None => MAX_LOG_PAGE_SIZE,
+        Some(Ok(limit)) if limit > 0 => limit.min(MAX_LOG_PAGE_SIZE),
+        Some(_) => return Err(APIError::new_with_status(StatusCode::BAD_REQUEST, "limit must be a positive number")),
+    };
+
     let transaction = db.begin().await.map_err(|_| {
         error_span!("Failed to start transaction");
         APIError::new("Failed to start transaction".into())
     })?;
-    let fat_log = get_entity_changes_since(&transaction, tournament_id, since).await?;
+    let page = get_entity_changes_page_since(&transaction, tournament_id, since, Some(limit)).await?;
     transaction.rollback().await?;
-    Ok(Json(
-        fat_log
-    ))
+
+    let compress = accepts_gzip(&headers);
+    let body = encode_log_body(&page, compress)?;
+    let mut response = (
+        [(header::CONTENT_TYPE, HeaderValue::from_static("application/json"))],
+        body
+    ).into_response();
+    if compress {
+        response.headers_mut().insert(header::CONTENT_ENCODING, HeaderValue::from_static("gzip"));
+    }
+    Ok(response)
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub enum MergeStrategy {
     Reject,
-    AlwaysLocal
+    AlwaysLocal,
+    /// Merges entities that were changed on both sides field by field,
+    /// using their version at the last common ancestor as the base.
+    /// Fields that were changed differently on both sides keep the local value
+    /// and are reported as conflicts.
+    ThreeWay
+}
+
+/// A field that was changed differently on both sides since the last common ancestor.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct MergeConflict {
+    pub entity_type: EntityTypeId,
+    pub entity_uuid: Uuid,
+    /// JSON pointer into the serialized entity. Empty if the entity could not be merged at all.
+    pub path: String,
+    /// None if the field does not exist in that version, or if the common ancestor is unknown
+    pub base: Option<serde_json::Value>,
+    pub local: Option<serde_json::Value>,
+    pub remote: Option<serde_json::Value>,
 }
 
 pub enum ReconciliationOutcome {
     Reject,
     InvalidTournament,
-    Success {new_last_common_ancestor: Uuid, entity_group: Option<EntityGroup>}
+    Success {new_last_common_ancestor: Uuid, entity_group: Option<EntityGroup>},
+    /// The changes were integrated, but some fields kept their local value
+    Conflict {new_last_common_ancestor: Uuid, entity_group: Option<EntityGroup>, conflicts: Vec<MergeConflict>}
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum APIReconciliationOutcome {
     Reject,
     InvalidTournament,
-    Success {new_last_common_ancestor: Uuid}
+    Success {new_last_common_ancestor: Uuid},
+    Conflict {new_last_common_ancestor: Uuid, conflicts: Vec<MergeConflict>}
 }
 
 impl From<ReconciliationOutcome> for APIReconciliationOutcome {
@@ -178,12 +389,159 @@ impl From<ReconciliationOutcome> for APIReconciliationOutcome {
             ReconciliationOutcome::InvalidTournament => APIReconciliationOutcome::InvalidTournament,
             ReconciliationOutcome::Success {new_last_common_ancestor, entity_group: _} => APIReconciliationOutcome::Success {
                 new_last_common_ancestor,
+            },
+            ReconciliationOutcome::Conflict {new_last_common_ancestor, entity_group: _, conflicts} => APIReconciliationOutcome::Conflict {
+                new_last_common_ancestor,
+                conflicts
+            }
+        }
+    }
+}
+
+fn entity_state_to_value(state: &EntityState<Entity, EntityTypeId>) -> Result<Option<serde_json::Value>, serde_json::Error> {
+    match state {
+        EntityState::Exists(e) => Ok(Some(serde_json::to_value(e)?)),
+        EntityState::Deleted { .. } => Ok(None)
+    }
+}
+
+struct FieldConflict {
+    path: String,
+    base: Option<serde_json::Value>,
+    local: Option<serde_json::Value>,
+    remote: Option<serde_json::Value>,
+}
+
+fn merge_values(
+    base: Option<&serde_json::Value>,
+    local: Option<&serde_json::Value>,
+    remote: Option<&serde_json::Value>,
+    path: String,
+    conflicts: &mut Vec<FieldConflict>
+) -> Option<serde_json::Value> {
+    if local == remote || base == remote {
+        return local.cloned();
+    }
+    if base == local {
+        return remote.cloned();
+    }
+
+    if let (Some(serde_json::Value::Object(base)), Some(serde_json::Value::Object(local)), Some(serde_json::Value::Object(remote))) = (base, local, remote) {
+        let mut merged = serde_json::Map::new();
+        for key in base.keys().chain(local.keys()).chain(remote.keys()).unique() {
+            let key_path = format!("{}/{}", path, key.replace('~', "~0").replace('/', "~1"));
+            if let Some(value) = merge_values(base.get(key), local.get(key), remote.get(key), key_path, conflicts) {
+                merged.insert(key.clone(), value);
+            }
+        }
+        return Some(serde_json::Value::Object(merged));
+    }
+
+    conflicts.push(FieldConflict { path, base: base.cloned(), local: local.cloned(), remote: remote.cloned() });
+    local.cloned()
+}
+
+/// Three-way merge of two concurrent versions of the same entity.
+/// Without a known base, the versions only merge if they are identical.
+/// Returns the merged state, which keeps the local value for every conflict.
+pub fn merge_entity_states(
+    base: Option<&EntityState<Entity, EntityTypeId>>,
+    local: &EntityState<Entity, EntityTypeId>,
+    remote: &EntityState<Entity, EntityTypeId>
+) -> Result<(EntityState<Entity, EntityTypeId>, Vec<MergeConflict>), anyhow::Error> {
+    let entity_type = local.get_type();
+    let entity_uuid = local.get_uuid();
+
+    let local_value = entity_state_to_value(local)?;
+    let remote_value = entity_state_to_value(remote)?;
+
+    let whole_entity_conflict = |base_value: Option<serde_json::Value>| MergeConflict {
+        entity_type,
+        entity_uuid,
+        path: "".into(),
+        base: base_value,
+        local: local_value.clone(),
+        remote: remote_value.clone(),
+    };
+
+    let base_value = match base {
+        Some(base) => entity_state_to_value(base)?,
+        None => {
+            if local_value == remote_value {
+                return Ok((local.clone(), vec![]));
             }
+            return Ok((local.clone(), vec![whole_entity_conflict(None)]));
         }
+    };
+
+    let mut field_conflicts = vec![];
+    let merged_value = merge_values(base_value.as_ref(), local_value.as_ref(), remote_value.as_ref(), "".into(), &mut field_conflicts);
+
+    let merged = match merged_value {
+        Some(value) => match serde_json::from_value::<Entity>(value) {
+            Ok(entity) => EntityState::Exists(entity),
+            // Merging fields of different versions can produce an invalid entity
+            Err(_) => return Ok((local.clone(), vec![whole_entity_conflict(base_value)]))
+        },
+        None => EntityState::Deleted { uuid: entity_uuid, type_: entity_type }
+    };
+
+    let conflicts = field_conflicts.into_iter().map(|conflict| MergeConflict {
+        entity_type,
+        entity_uuid,
+        path: conflict.path,
+        base: conflict.base,
+        local: conflict.local,
+        remote: conflict.remote
+    }).collect();
+
+    Ok((merged, conflicts))
+}
+
+/// Loads the log entries of the given entities, including entries pruned by a compaction, oldest first
+async fn get_entity_log_entries<C>(db: &C, tournament_id: Uuid, target_uuids: Vec<Uuid>, max_sequence_idx: Option<i32>) -> Result<Vec<open_tab_entities::schema::tournament_log::Model>, anyhow::Error> where C: sea_orm::ConnectionTrait {
+    let mut entries = vec![];
+    for chunk in target_uuids.chunks(500) {
+        let mut log_query = open_tab_entities::schema::tournament_log::Entity::find()
+            .filter(open_tab_entities::schema::tournament_log::Column::TournamentId.eq(tournament_id))
+            .filter(open_tab_entities::schema::tournament_log::Column::TargetUuid.is_in(chunk.to_vec()));
+        let mut pruned_query = open_tab_entities::schema::tournament_log_pruned_entry::Entity::find()
+            .filter(open_tab_entities::schema::tournament_log_pruned_entry::Column::TournamentId.eq(tournament_id))
+            .filter(open_tab_entities::schema::tournament_log_pruned_entry::Column::TargetUuid.is_in(chunk.to_vec()));
+        if let Some(max_sequence_idx) = max_sequence_idx {
+            log_query = log_query.filter(open_tab_entities::schema::tournament_log::Column::SequenceIdx.lte(max_sequence_idx));
+            pruned_query = pruned_query.filter(open_tab_entities::schema::tournament_log_pruned_entry::Column::SequenceIdx.lte(max_sequence_idx));
+        }
+
+        entries.extend(log_query.all(db).await?);
+        entries.extend(pruned_query.all(db).await?.into_iter().map(open_tab_entities::schema::tournament_log::Model::from));
     }
+    entries.sort_by_key(|entry| entry.sequence_idx);
+    Ok(entries)
 }
 
+/// Loads the version of the entities at the last common ancestor from the log snapshots.
+/// Entities whose latest version at that point has no snapshot are omitted.
+async fn get_ancestor_versions<C>(db: &C, tournament_id: Uuid, last_common_ancestor: Option<Uuid>, entities: &HashSet<(EntityTypeId, Uuid)>) -> Result<HashMap<(EntityTypeId, Uuid), EntityState<Entity, EntityTypeId>>, anyhow::Error> where C: sea_orm::ConnectionTrait {
+    let last_common_ancestor = match last_common_ancestor {
+        Some(last_common_ancestor) => last_common_ancestor,
+        None => return Ok(HashMap::new())
+    };
+    let ancestor_sequence_idx = get_log_position(db, last_common_ancestor).await?.ok_or(anyhow::anyhow!("Since is not a valid log entry"))?;
 
+    // Pruned entries keep their snapshots, so the base is found even if the ancestor has been compacted away
+    let entries = get_entity_log_entries(db, tournament_id, entities.iter().map(|(_, uuid)| *uuid).collect_vec(), Some(ancestor_sequence_idx)).await?;
+
+    // Later entries replace earlier ones, so that an outdated snapshot is never used as the base
+    let latest_entries = entries.into_iter().map(|entry| ((EntityTypeId::from(entry.target_type.clone()), entry.target_uuid), entry)).collect::<HashMap<_, _>>();
+
+    Ok(
+        latest_entries.into_iter()
+            .filter(|(key, _)| entities.contains(key))
+            .filter_map(|(key, entry)| entry.snapshot.and_then(|snapshot| serde_json::from_str(&snapshot).ok()).map(|state| (key, state)))
+            .collect()
+    )
+}
 
 pub async fn reconcile_changes<C>(
     db: &C,
@@ -217,9 +575,22 @@ pub async fn reconcile_changes<C>(
         .limit(1)
         .one(db).await?.map(|m| m.sequence_idx).unwrap_or(0)
     };
-    let locally_changed_entities = local_log.iter().map(|entry| (entry.target_type.clone().into(), entry.target_uuid, entry.uuid)).collect::<HashSet<_>>();
 
     let existing_entries = local_log.iter().map(|entry| entry.uuid).collect::<HashSet<_>>();
+    let remote_entries = changes.log.iter().map(|entry| entry.uuid).collect::<HashSet<_>>();
+
+    // Only entries the remote does not know yet count as local changes.
+    // Later entries replace earlier ones, so this holds the latest local version of each entity.
+    let latest_local_entries = local_log.iter()
+        .filter(|entry| !remote_entries.contains(&entry.uuid))
+        .map(|entry| ((EntityTypeId::from(entry.target_type.clone()), entry.target_uuid), entry.clone()))
+        .collect::<HashMap<_, _>>();
+
+    let remote_snapshots = changes.entities.values().flat_map(|entries| {
+        entries.iter().map(|entry| Ok((entry.current_version, serde_json::to_string(&entry.current_value)?)))
+    }).collect::<Result<HashMap<_, _>, serde_json::Error>>()?;
+    let remote_authors = changes.log.iter().map(|entry| Ok((entry.uuid, entry.author.as_ref().map(serde_json::to_string).transpose()?)))
+        .collect::<Result<HashMap<_, _>, serde_json::Error>>()?;
 
     let mut remote_log_models = changes.log.iter().filter(|entry| !existing_entries.contains(&entry.uuid) ).enumerate().map(
         |(idx, entry)| {
@@ -229,7 +600,9 @@ pub async fn reconcile_changes<C>(
                 target_type: entry.target_type.as_str().into(),
                 target_uuid: entry.target_uuid,
                 timestamp: entry.timestamp,
-                sequence_idx: head_sequence_idx + idx as i32 + 1
+                sequence_idx: head_sequence_idx + idx as i32 + 1,
+                snapshot: remote_snapshots.get(&entry.uuid).cloned(),
+                author: remote_authors.get(&entry.uuid).cloned().flatten(),
             }.into_active_model()
         }
     ).collect_vec();
@@ -242,20 +615,56 @@ pub async fn reconcile_changes<C>(
     let new_last_common_ancestor = remote_log_models.last().map(|model| model.uuid.clone().unwrap()).unwrap_or_else(|| last_common_ancestor.unwrap());
     let new_head_idx = remote_log_models.last().map(|model| model.sequence_idx.clone().unwrap()).unwrap_or(head_sequence_idx);
 
-    let remote_changes_entities = changes.entities.iter().flat_map(|(entity_type, entries)| {
-        entries.iter().map(|entry| (entity_type.clone(), entry.uuid, entry.current_version))
-    }).collect::<HashSet<_>>();
+    let remote_changed_entities = changes.entities.iter().flat_map(|(entity_type, entries)| {
+        entries.iter().filter(|entry| !existing_entries.contains(&entry.current_version)).map(|entry| ((*entity_type, entry.uuid), entry.current_value.clone()))
+    }).collect::<HashMap<_, _>>();
+
+    let conflicting_entities = remote_changed_entities.keys().filter(|key| latest_local_entries.contains_key(key)).cloned().collect::<HashSet<_>>();
+
+    let local_versions = get_changed_entities_from_log(
+        db,
+        conflicting_entities.iter().map(|key| latest_local_entries[key].clone()).collect()
+    ).await?.into_iter().map(|versioned| ((versioned.entity.get_type(), versioned.entity.get_uuid()), versioned.entity)).collect::<HashMap<_, _>>();
+
+    let mut merged_entities = HashMap::new();
+    let mut conflicts = vec![];
+    // Entities whose resolved state differs from the remote state need a new log entry,
+    // so that the resolution is propagated back.
+    let mut relogged_entities = vec![];
 
-    let conflicting_entities = locally_changed_entities.intersection(&remote_changes_entities).collect::<HashSet<_>>();
+    match merge_strategy {
+        MergeStrategy::ThreeWay => {
+            let ancestor_versions = get_ancestor_versions(db, tournament_id, last_common_ancestor, &conflicting_entities).await?;
 
-    conflicting_entities.iter().enumerate().for_each(|(idx, (entity_type, uuid, _version))| {
+            for key in conflicting_entities.iter() {
+                let remote = &remote_changed_entities[key];
+                let (merged, entity_conflicts) = merge_entity_states(ancestor_versions.get(key), &local_versions[key], remote)?;
+                conflicts.extend(entity_conflicts);
+
+                if &merged != remote {
+                    relogged_entities.push((*key, serde_json::to_string(&merged)?));
+                }
+                merged_entities.insert(*key, merged);
+            }
+        },
+        MergeStrategy::AlwaysLocal | MergeStrategy::Reject => {
+            for key in conflicting_entities.iter() {
+                relogged_entities.push((*key, serde_json::to_string(&local_versions[key])?));
+            }
+        }
+    }
+
+    relogged_entities.into_iter().sorted_by_key(|((_, uuid), _)| *uuid).enumerate().for_each(|(idx, ((entity_type, uuid), snapshot))| {
         remote_log_models.push(open_tab_entities::schema::tournament_log::Model {
             uuid: Uuid::new_v4(),
             tournament_id,
             target_type: entity_type.as_str().into(),
-            target_uuid: uuid.clone(),
+            target_uuid: uuid,
             timestamp: Utc::now().naive_utc(),
-            sequence_idx: new_head_idx + idx as i32 + 1
+            sequence_idx: new_head_idx + idx as i32 + 1,
+            snapshot: Some(snapshot),
+            // The resolution is made on this side, so it is attributed to the author of the local version
+            author: latest_local_entries[&(entity_type, uuid)].author.clone(),
         }.into_active_model());
     });
 
@@ -264,12 +673,15 @@ pub async fn reconcile_changes<C>(
         open_tab_entities::schema::tournament_log::Entity::insert_many(remote_log_models).exec(db).await?;
     }
     
-    let conflicting_entities = conflicting_entities.into_iter().map(|(entity_type, uuid, _version)| (entity_type.clone(), uuid.clone())).collect::<HashSet<_>>();
     // We bypass the normal save logic here, since we save the entire log at once
     let mut entities_to_save = vec![];
     for (entity_type, entities) in changes.entities.into_iter() {
         for entry in entities {
-            if !conflicting_entities.contains(&(entity_type.clone(), entry.uuid)) {
+            let key = (entity_type, entry.uuid);
+            if let Some(merged) = merged_entities.remove(&key) {
+                entities_to_save.push(merged);
+            }
+            else if !conflicting_entities.contains(&key) {
                 entities_to_save.push(entry.current_value);
             }
         }
@@ -377,10 +789,23 @@ pub async fn reconcile_changes<C>(
     }
      */
 
+    let entity_group = if return_entity_group { Some(group) } else { None };
+
+    if !conflicts.is_empty() {
+        tracing::info!("Reconciliation kept local values for {} conflicting fields", conflicts.len());
+        return Ok(
+            ReconciliationOutcome::Conflict {
+                new_last_common_ancestor,
+                entity_group,
+                conflicts
+            }
+        );
+    }
+
     Ok(
         ReconciliationOutcome::Success {
             new_last_common_ancestor,
-            entity_group: if return_entity_group { Some(group) } else { None }
+            entity_group
         }
     )
 }
@@ -398,22 +823,41 @@ pub struct SyncRequestResponse {
 }
 
 
+/// Pushed changes are attributed to the pushing user, since the server can not
+/// verify any other claim. Changes made in an app keep the app identity.
+fn attribute_pushed_log(log: &mut FatLog<Entity, EntityTypeId>, user_id: Uuid) {
+    for entry in log.log.iter_mut() {
+        entry.author = Some(match entry.author.take() {
+            Some(LogAuthor::App { app_id, name, .. }) => LogAuthor::App { app_id, name, user_id: Some(user_id) },
+            _ => LogAuthor::User { user_id }
+        });
+    }
+}
+
 async fn handle_sync_push_request(
     State(db): State<DatabaseConnection>,
     State(notifications): State<Arc<RwLock<crate::notify::ParticipantNotificationManager>>>,
     ExtractAuthenticatedUser(user): ExtractAuthenticatedUser,
     Path(tournament_id): Path<Uuid>,
-    Json(request_body): Json<SyncRequest<Entity, EntityTypeId>>
+    headers: HeaderMap,
+    body: Bytes,
 ) -> Result<Json<SyncRequestResponse>, APIError> {
     let tournament = open_tab_entities::schema::tournament::Entity::find_by_id(tournament_id).one(&db).await?;
     if tournament.is_none() {
         return Err(APIError::new_with_status(StatusCode::NOT_FOUND, "Tournament not found"));
     }
 
-    if !user.check_is_authorized_for_tournament_administration(&db, tournament_id).await? {
+    if !user.check_has_permission(&db, tournament_id, TournamentPermission::ManageTournament).await? {
         return Err(APIError::new_with_status(StatusCode::FORBIDDEN, "User is not authorized for tournament administration"));
     }
 
+    let request_body = decode_log_body::<SyncRequest<Entity, EntityTypeId>>(&body, is_gzip(&headers)).map_err(|e| {
+        APIError::new_with_status(StatusCode::BAD_REQUEST, format!("Invalid sync request: {}", e))
+    })?;
+    if request_body.log.log.len() as u64 > MAX_LOG_PAGE_SIZE {
+        return Err(APIError::new_with_status(StatusCode::PAYLOAD_TOO_LARGE, format!("At most {} log entries can be pushed at once", MAX_LOG_PAGE_SIZE)));
+    }
+
     let transaction: sea_orm::DatabaseTransaction = db.begin_with_config(
         Some(IsolationLevel::Serializable),
         Some(AccessMode::ReadWrite)
@@ -422,11 +866,13 @@ async fn handle_sync_push_request(
         APIError::new("Failed to start transaction".into())
     })?;
 
+    let mut log = request_body.log;
+    attribute_pushed_log(&mut log, user.uuid);
  
     let outcome = reconcile_changes(
         &transaction,
         tournament_id,
-        request_body.log,
+        log,
         request_body.last_common_ancestor,
         MergeStrategy::Reject,
         true
@@ -447,10 +893,12 @@ async fn handle_sync_push_request(
             transaction.rollback().await?;
             return Err(APIError::new_with_status(StatusCode::BAD_REQUEST, "Invalid tournament"));
         },
-        ReconciliationOutcome::Success { entity_group, .. } => {
-            notifications.read().await.process_entities(&transaction, entity_group.as_ref().unwrap()).await;
+        ReconciliationOutcome::Success { entity_group, .. } | ReconciliationOutcome::Conflict { entity_group, .. } => {
+            let notifications = notifications.read().await;
+            notifications.process_entities(&transaction, entity_group.as_ref().unwrap()).await;
 
             transaction.commit().await?;
+            notifications.notify_tournament_log_head(&db, tournament_id).await;
             return Ok(
                 Json(
                     SyncRequestResponse {
@@ -462,8 +910,76 @@ async fn handle_sync_push_request(
     }
 }
 
+/// Announces new log heads of the tournament, so clients can pull as soon as
+/// something changes instead of polling.
+async fn get_log_events(
+    State(db): State<DatabaseConnection>,
+    State(notifications): State<Arc<RwLock<crate::notify::ParticipantNotificationManager>>>,
+    ExtractAuthenticatedUser(user): ExtractAuthenticatedUser,
+    Path(tournament_id): Path<Uuid>,
+) -> Result<Sse<impl Stream<Item = Result<Event, Infallible>>>, APIError> {
+    let tournament = open_tab_entities::schema::tournament::Entity::find_by_id(tournament_id).one(&db).await?;
+    if tournament.is_none() {
+        return Err(APIError::new_with_status(StatusCode::NOT_FOUND, "Tournament not found"));
+    }
+    if !user.check_has_permission(&db, tournament_id, TournamentPermission::ReadLog).await? {
+        return Err(APIError::new_with_status(StatusCode::FORBIDDEN, "User is not authorized to read the tournament log"));
+    }
+
+    let stream = notifications.write().await.subscribe_to_tournament_log(&db, tournament_id).await?;
+
+    Ok(Sse::new(stream).keep_alive(
+        KeepAlive::new()
+            .interval(Duration::from_secs(15))
+            .text("keep-alive-text"),
+    ))
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EntityHistoryEntry {
+    pub version: Uuid,
+    pub timestamp: DateTime,
+    pub author: Option<LogAuthor>,
+    /// The entity after the change. None for entries written before snapshots were recorded.
+    pub value: Option<EntityState<Entity, EntityTypeId>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EntityHistory {
+    pub entries: Vec<EntityHistoryEntry>,
+}
+
+/// Lists every logged change to an entity, oldest first.
+/// Changes pruned by a log compaction are included.
+async fn get_entity_history(
+    State(db): State<DatabaseConnection>,
+    ExtractAuthenticatedUser(user): ExtractAuthenticatedUser,
+    Path((tournament_id, entity_id)): Path<(Uuid, Uuid)>,
+) -> Result<Json<EntityHistory>, APIError> {
+    if !user.check_has_permission(&db, tournament_id, TournamentPermission::ReadLog).await? {
+        return Err(APIError::new_with_status(StatusCode::FORBIDDEN, "User is not authorized to read the tournament log"));
+    }
+
+    let entries = get_entity_log_entries(&db, tournament_id, vec![entity_id], None).await?;
+
+    if entries.is_empty() {
+        return Err(APIError::new_with_status(StatusCode::NOT_FOUND, "Entity not found"));
+    }
+
+    Ok(Json(EntityHistory {
+        entries: entries.iter().map(|entry| EntityHistoryEntry {
+            version: entry.uuid,
+            timestamp: entry.timestamp,
+            author: LogAuthor::from_log_entry(entry),
+            value: entry.snapshot.as_ref().and_then(|snapshot| serde_json::from_str(snapshot).ok()),
+        }).collect()
+    }))
+}
+
 pub fn router() -> Router<AppState> {
     Router::new()
+    .route("/tournament/:tournament_id/entity/:entity_id/history", get(get_entity_history))
     .route("/tournament/:tournament_id/log", get(get_log))
+    .route("/tournament/:tournament_id/log/events", get(get_log_events))
     .route("/tournament/:tournament_id/log", post(handle_sync_push_request)).layer(DefaultBodyLimit::max(1024 * 1024 * 10))
 }
\ No newline at end of file
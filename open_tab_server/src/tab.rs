@@ -8,7 +8,7 @@ use sea_orm::{prelude::*, sea_query::IntoCondition, DatabaseConnection, DbBacken
 use serde::{Serialize, Deserialize};
 use std::{collections::HashMap, sync::Arc};
 
-use crate::{auth::MaybeExtractAuthenticatedUser, cache::CacheManager, response::APIError, state::AppState};
+use crate::{auth::MaybeExtractAuthenticatedUser, response::APIError, staff::TournamentPermission, state::AppState};
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct TabResponse {
@@ -40,7 +40,7 @@ pub async fn get_current_tab(
     let allow_unchecked_access = published_tournament.map(|t| t.show_tab).unwrap_or(false);
     if !allow_unchecked_access {
         if let Some(user) = user {
-            if !user.check_is_authorized_in_tournament(&db, tournament_id).await? {
+            if !user.check_is_authorized_in_tournament(&db, tournament_id).await? && !user.check_has_permission(&db, tournament_id, TournamentPermission::ReadScores).await? {
                 let err = APIError::new_with_status(StatusCode::FORBIDDEN, "You are not authorized for this tournament");
                 return Err(err);
             }
@@ -25,6 +25,7 @@ use serde::{Serialize, Deserialize};
 use crate::auth::{create_key, ExtractAuthenticatedUser, MaybeExtractAuthenticatedUser};
 use crate::participants::{get_round_status_at_time, RoundStatus};
 use crate::response::APIError;
+use crate::staff::{TournamentPermission, TournamentRole};
 use crate::state::AppState;
 
 
@@ -71,6 +72,7 @@ pub async fn create_tournament_handler(State(db) : State<DatabaseConnection>, Ex
     let user_tournament = open_tab_entities::schema::user_tournament::Model {
         user_id: user.uuid,
         tournament_id: uuid,
+        role: TournamentRole::TabDirector.to_str().to_string(),
     };
     user_tournament.into_active_model().insert(&db).await?;
 
@@ -164,7 +166,7 @@ pub async fn get_tournament_settings_handler(
     ExtractAuthenticatedUser(user) : ExtractAuthenticatedUser,
     Path(tournament_id): Path<Uuid>,
 ) -> Result<Json<TournamentPublicationSettings>, APIError> {
-    if !user.check_is_authorized_for_tournament_administration(&db, tournament_id).await? {
+    if !user.check_has_permission(&db, tournament_id, TournamentPermission::ViewTournament).await? {
         let err = APIError::new_with_status(StatusCode::FORBIDDEN, "You are not authorized for this tournament");
         return Err(err);
     }
@@ -197,7 +199,7 @@ pub async fn update_tournament_settings_handler(
     Path(tournament_id): Path<Uuid>,
     Json(request): Json<TournamentPublicationSettings>,
 ) -> Result<(), APIError> {
-    if !user.check_is_authorized_for_tournament_administration(&db, tournament_id).await? {
+    if !user.check_has_permission(&db, tournament_id, TournamentPermission::ManageTournament).await? {
         let err = APIError::new_with_status(StatusCode::FORBIDDEN, "You are not authorized for this tournament");
         return Err(err);
     }
@@ -532,7 +534,7 @@ pub async fn get_public_tournament_info_handler(
 
     if info.is_none() {
         if let Some(user) = user {
-            if user.check_is_authorized_for_tournament_administration(&db, tournament_id).await? {
+            if user.check_has_permission(&db, tournament_id, TournamentPermission::ViewTournament).await? {
                 let tournament = open_tab_entities::schema::tournament::Entity::find()
                     .filter(open_tab_entities::schema::tournament::Column::Uuid.eq(tournament_id))
                     .one(&db)
@@ -633,7 +635,7 @@ pub async fn get_admin_view(
     ExtractAuthenticatedUser(user) : ExtractAuthenticatedUser,
     Path(tournament_id): Path<Uuid>,
 ) -> Result<Json<TournamentAdminView>, APIError> {
-    if !user.check_is_authorized_for_tournament_administration(&db, tournament_id).await? {
+    if !user.check_has_permission(&db, tournament_id, TournamentPermission::ViewTournament).await? {
         let err = APIError::new_with_status(StatusCode::FORBIDDEN, "You are not authorized for this tournament");
         return Err(err);
     }
@@ -2,8 +2,8 @@ mod common;
 use std::collections::HashMap;
 
 use base64::Engine;
-use open_tab_entities::{EntityGroup, domain::entity::LoadEntity, Entity, prelude::{Ballot, BallotTeam, TeamScore, Speech, SpeakerScore}};
-use open_tab_server::{auth::create_key, ballot::{GetDebateResponse, GetBallotSubmissionResponse, SubmitBallotRequest, SubmitBallotResponse}};
+use open_tab_entities::{EntityGroup, domain::entity::LoadEntity, Entity, LogAuthor, prelude::{Ballot, BallotTeam, TeamScore, Speech, SpeakerScore, Score}};
+use open_tab_server::{auth::create_key, ballot::{GetDebateResponse, GetBallotSubmissionResponse, SubmitBallotRequest, SubmitBallotResponse}, sync::EntityHistory};
 use sea_orm::{prelude::Uuid, DatabaseConnection, IntoActiveModel, ActiveModelTrait};
 use tracing_test::traced_test;
 
@@ -15,7 +15,7 @@ fn make_team_score_map(scores: Vec<(u128, i16)>) -> HashMap<Uuid, TeamScore> {
         scores.into_iter().map(|(uuid, score)| {
             (
                 Uuid::from_u128(uuid),
-                TeamScore::new_aggregate(score)
+                TeamScore::new_aggregate(score.into())
             )
         })
     )
@@ -27,7 +27,7 @@ fn make_speaker_score_map(scores: Vec<(u128, i16)>) -> HashMap<Uuid, SpeakerScor
             |(uuid, score)| {
                 (
                     Uuid::from_u128(uuid),
-                    SpeakerScore::new_aggregate(score)
+                    SpeakerScore::new_aggregate(score.into())
                 )
             }
         )
@@ -61,28 +61,32 @@ fn make_demo_ballot() -> Ballot {
                 scores: make_speaker_score_map(vec![(3000, 50), (3001, 100), (3002, 75)]),
                 position: 0,
                 is_opt_out: false,
-                role: open_tab_entities::prelude::SpeechRole::Government
+                role: open_tab_entities::prelude::SpeechRole::Government,
+                trainee_scores: HashMap::new()
             },
             Speech {
                 speaker: Some(Uuid::from_u128(2010)),
                 scores: make_speaker_score_map(vec![(3000, 50), (3001, 100), (3002, 75)]),
                 position: 0,
                 is_opt_out: false,
-                role: open_tab_entities::prelude::SpeechRole::Opposition
+                role: open_tab_entities::prelude::SpeechRole::Opposition,
+                trainee_scores: HashMap::new()
             },
             Speech {
                 speaker: Some(Uuid::from_u128(2001)),
                 scores: make_speaker_score_map(vec![(3000, 50), (3001, 100), (3002, 75)]),
                 position: 1,
                 is_opt_out: false,
-                role: open_tab_entities::prelude::SpeechRole::Opposition
+                role: open_tab_entities::prelude::SpeechRole::Opposition,
+                trainee_scores: HashMap::new()
             },
             Speech {
                 speaker: Some(Uuid::from_u128(2011)),
                 scores: make_speaker_score_map(vec![(3000, 50), (3001, 100), (3002, 75)]),
                 position: 1,
                 is_opt_out: false,
-                role: open_tab_entities::prelude::SpeechRole::Government
+                role: open_tab_entities::prelude::SpeechRole::Government,
+                trainee_scores: HashMap::new()
             },
 
             Speech {
@@ -90,21 +94,24 @@ fn make_demo_ballot() -> Ballot {
                 scores: make_speaker_score_map(vec![(3000, 50), (3001, 100), (3002, 75)]),
                 position: 0,
                 is_opt_out: false,
-                role: open_tab_entities::prelude::SpeechRole::NonAligned
+                role: open_tab_entities::prelude::SpeechRole::NonAligned,
+                trainee_scores: HashMap::new()
             },
             Speech {
                 speaker: Some(Uuid::from_u128(2021)),
                 scores: make_speaker_score_map(vec![(3000, 50), (3001, 100), (3002, 75)]),
                 position: 1,
                 is_opt_out: false,
-                role: open_tab_entities::prelude::SpeechRole::NonAligned
+                role: open_tab_entities::prelude::SpeechRole::NonAligned,
+                trainee_scores: HashMap::new()
             },
             Speech {
                 speaker: Some(Uuid::from_u128(2022)),
                 scores: make_speaker_score_map(vec![(3000, 50), (3001, 100), (3002, 75)]),
                 position: 2,
                 is_opt_out: false,
-                role: open_tab_entities::prelude::SpeechRole::NonAligned
+                role: open_tab_entities::prelude::SpeechRole::NonAligned,
+                trainee_scores: HashMap::new()
             },
 
             Speech {
@@ -112,14 +119,16 @@ fn make_demo_ballot() -> Ballot {
                 scores: make_speaker_score_map(vec![(3000, 50), (3001, 100), (3002, 75)]),
                 position: 2,
                 is_opt_out: false,
-                role: open_tab_entities::prelude::SpeechRole::Opposition
+                role: open_tab_entities::prelude::SpeechRole::Opposition,
+                trainee_scores: HashMap::new()
             },
             Speech {
                 speaker: Some(Uuid::from_u128(2012)),
                 scores: make_speaker_score_map(vec![(3000, 50), (3001, 100), (3002, 75)]),
                 position: 2,
                 is_opt_out: false,
-                role: open_tab_entities::prelude::SpeechRole::Government
+                role: open_tab_entities::prelude::SpeechRole::Government,
+                trainee_scores: HashMap::new()
             },
 
         ],
@@ -197,14 +206,41 @@ async fn test_submit_ballot_adds_entry() {
     );
     assert_eq!(
         ballot.ballot.speeches[0].scores.get(&Uuid::from_u128(3000)).unwrap(),
-        &50
+        &Score::from_points(50)
     );
     assert_eq!(
         ballot.ballot.government.scores.get(&Uuid::from_u128(3000)).unwrap(),
-        &50
+        &Score::from_points(50)
     );
 }
 
+#[tokio::test]
+#[traced_test]
+async fn test_submitted_ballot_is_attributed_to_user() {
+    let mut fixture = common::Fixture::new(
+        FixtureOptions {
+            mock_default_tournament: true,
+            ..Default::default()
+        }
+    ).await;
+
+    let mut response = fixture.post_json(
+        &format!("/api/debate/{}/submissions", Uuid::from_u128(200)),
+        SubmitBallotRequest {
+            ballot: make_demo_ballot()
+        }
+    ).await;
+    assert_eq!(response.status(), 200);
+    let submission_id = response.json::<SubmitBallotResponse>().await.submission_id;
+
+    let mut response = fixture.get(&format!("/api/tournament/{}/entity/{}/history", Uuid::from_u128(1), submission_id)).await;
+    assert_eq!(response.status(), 200);
+    let history = response.json::<EntityHistory>().await;
+
+    assert_eq!(history.entries.len(), 1);
+    assert_eq!(history.entries[0].author, Some(LogAuthor::User { user_id: Uuid::from_u128(900_000) }));
+}
+
 fn get_test_user_key() -> open_tab_entities::schema::user_access_key::Model {
     let raw_key = [0, 0, 0, 1];
     create_key(&raw_key, Uuid::from_u128(13000), None, None, false).unwrap()
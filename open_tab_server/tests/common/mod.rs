@@ -3,7 +3,7 @@ use std::{borrow::BorrowMut, future::Future};
 use axum::{response::Response, http::{Request, request::Builder}, body::Body};
 use http_body::{combinators::UnsyncBoxBody, Body as _};
 use open_tab_entities::{mock::{self, MockOption}, EntityTypeId};
-use open_tab_server::{auth::{CreateUserRequest, CreateUserResponse, GetTokenRequest, GetTokenResponse, create_key, hash_password}, state::AppState};
+use open_tab_server::{auth::{CreateUserRequest, CreateUserResponse, GetTokenRequest, GetTokenResponse, create_key, hash_password}, staff::TournamentRole, state::AppState};
 use sea_orm::{prelude::Uuid, IntoActiveModel, ActiveModelTrait, DatabaseConnection};
 use tower::Service;
 use base64::{engine::general_purpose, Engine as _};
@@ -13,12 +13,15 @@ use base64::{engine::general_purpose, Engine as _};
 pub struct FixtureOptions
  {
     pub mock_default_tournament: bool,
-    pub use_participant_account: Option<Uuid>
+    pub use_participant_account: Option<Uuid>,
+    /// Role of the test user in the mock tournament, defaults to tab director
+    pub staff_role: Option<TournamentRole>,
 }
 
 pub struct Fixture {
     pub app: axum::Router,
     pub auth: Auth,
+    db: Option<DatabaseConnection>,
 }
 
 pub enum Auth {
@@ -34,6 +37,8 @@ pub enum Auth {
 
 pub struct APIResponse {
     response: Response<UnsyncBoxBody<axum::body::Bytes, axum::Error>>,
+    /// Server-sent event data that has been received, but not consumed yet
+    pending_events: String,
 }
 
 impl APIResponse {
@@ -52,6 +57,23 @@ impl APIResponse {
         serde_json::from_slice(&buf).unwrap()
     }
 
+    #[allow(dead_code)]
+    pub fn header(&self, name: &str) -> Option<String> {
+        self.response.headers().get(name).map(|value| value.to_str().unwrap().to_string())
+    }
+
+    #[allow(dead_code)]
+    pub async fn bytes(&mut self) -> Vec<u8> {
+        let mut buf = Vec::new();
+
+        let body = self.response.body_mut();
+
+        while let Some(next) = body.data().await {
+            buf.extend_from_slice(&next.unwrap());
+        }
+        buf
+    }
+
     pub async fn text(&mut self) -> String {
         let mut buf = Vec::new();
 
@@ -62,12 +84,42 @@ impl APIResponse {
         }
         String::from_utf8(buf).unwrap()
     }
+
+    /// Waits for the next server-sent event with the given name and parses its data.
+    /// Returns None if the stream ends first.
+    #[allow(dead_code)]
+    pub async fn next_event<T: serde::de::DeserializeOwned>(&mut self, event_name: &str) -> Option<T> {
+        loop {
+            while let Some(end) = self.pending_events.find("\n\n") {
+                let frame = self.pending_events[..end].to_string();
+                self.pending_events.drain(..end + 2);
+
+                let mut name = None;
+                let mut data = String::new();
+                for line in frame.lines() {
+                    if let Some(value) = line.strip_prefix("event:") {
+                        name = Some(value.trim().to_string());
+                    }
+                    else if let Some(value) = line.strip_prefix("data:") {
+                        data.push_str(value.trim());
+                    }
+                }
+                if name.as_deref() == Some(event_name) {
+                    return Some(serde_json::from_str(&data).unwrap());
+                }
+            }
+
+            let next = self.response.body_mut().data().await?;
+            self.pending_events.push_str(std::str::from_utf8(&next.unwrap()).unwrap());
+        }
+    }
 }
 
 impl From<Response<UnsyncBoxBody<axum::body::Bytes, axum::Error>>> for APIResponse {
     fn from(response: Response<UnsyncBoxBody<hyper::body::Bytes, axum::Error>>) -> Self {
         Self {
             response,
+            pending_events: String::new(),
         }
     }
 }
@@ -83,6 +135,7 @@ impl Fixture {
     Fut: Future<Output = ()>,
      {
         let mut auth = Auth::None;
+        let mut db = None;
         let app = if options.mock_default_tournament {
             let state = AppState::new_test_app().await;
             let group = mock::make_mock_tournament_with_options(MockOption {
@@ -118,12 +171,14 @@ impl Fixture {
                 let user_tournament = open_tab_entities::schema::user_tournament::Model {
                     user_id: new_user_uuid,
                     tournament_id: tournaments[0].uuid,
+                    role: options.staff_role.unwrap_or(TournamentRole::TabDirector).to_str().to_string(),
                 };
                 user_tournament.into_active_model().insert(&state.db).await.unwrap();    
             }
 
             auth = Auth::Bearer { token: base64::engine::general_purpose::URL_SAFE.encode(&raw_key) };
             setup_func(state.db.clone()).await;
+            db = Some(state.db.clone());
             open_tab_server::app_with_state(state).await
         }
         else {
@@ -132,10 +187,17 @@ impl Fixture {
 
         Self {
             app,
-            auth
+            auth,
+            db
         }
     }
 
+    /// The database of the app. Only available with a mocked tournament.
+    #[allow(dead_code)]
+    pub fn db(&self) -> DatabaseConnection {
+        self.db.clone().expect("Fixture has no mock tournament")
+    }
+
     #[allow(dead_code)]
     pub async fn default() -> Self {
         Self::new(FixtureOptions::default()).await
@@ -211,6 +273,19 @@ impl Fixture {
             .unwrap().into()
     }
 
+    #[allow(dead_code)]
+    pub async fn get_with_header(&mut self, path: &str, name: &str, value: &str) -> APIResponse {
+        let request = self.get_base_request()
+            .uri(path)
+            .header(name, value)
+            .body(Body::empty())
+            .unwrap();
+        self.app.borrow_mut()
+            .call(request)
+            .await
+            .unwrap().into()
+    }
+
     #[allow(dead_code)]
     pub async fn post_json_no_body(&mut self, path: &str) -> APIResponse
     {
@@ -226,6 +301,19 @@ impl Fixture {
             .unwrap().into()
     }
 
+    #[allow(dead_code)]
+    pub async fn delete(&mut self, path: &str) -> APIResponse {
+        let request = self.get_base_request()
+            .method("DELETE")
+            .uri(path)
+            .body(Body::empty())
+            .unwrap();
+        self.app.borrow_mut()
+            .call(request)
+            .await
+            .unwrap().into()
+    }
+
     #[allow(dead_code)]
     pub async fn post_json<T>(&mut self, path: &str, body: T) -> APIResponse where T: serde::Serialize
     {
@@ -245,6 +333,26 @@ impl Fixture {
             .await
             .unwrap().into()
     }
+
+    #[allow(dead_code)]
+    pub async fn post_gzip_json<T>(&mut self, path: &str, body: T) -> APIResponse where T: serde::Serialize
+    {
+        let request = self.get_base_request()
+            .method("POST")
+            .header("Content-Type", "application/json")
+            .header("Content-Encoding", "gzip")
+            .uri(path)
+            .body(
+                Body::from(
+                    open_tab_server::sync::encode_log_body(&body, true).unwrap()
+                )
+            )
+            .unwrap();
+        self.app.borrow_mut()
+            .call(request)
+            .await
+            .unwrap().into()
+    }
 }
 
 #[allow(dead_code)]
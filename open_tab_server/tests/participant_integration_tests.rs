@@ -3,7 +3,7 @@ mod common;
 
 
 use open_tab_entities::{EntityGroup, domain::entity::LoadEntity, Entity};
-use open_tab_server::participants::{ParticipantInfoResponse, Motion};
+use open_tab_server::participants::{ParticipantInfoResponse, Motion, ParticipantAvailabilityResponse, UpdateParticipantAvailabilityRequest};
 use sea_orm::{prelude::Uuid, DatabaseConnection};
 
 
@@ -287,3 +287,125 @@ async fn test_wing_does_see_request_for_silent_round() {
         }
     ));
 }
+
+async fn allow_self_declared_availability(db: DatabaseConnection) {
+    let mut tournament = open_tab_entities::domain::tournament::Tournament::get(&db, Uuid::from_u128(1)).await.unwrap();
+    tournament.allow_self_declared_availability = true;
+    tournament.availability_deadline_minutes = 60;
+    EntityGroup::new_from_entities(
+        Uuid::from_u128(1),
+        vec![
+        Entity::Tournament(tournament)
+    ]).save_all_and_log(&db).await.unwrap();
+}
+
+async fn allow_self_declared_availability_with_upcoming_draw(db: DatabaseConnection) {
+    allow_self_declared_availability(db.clone()).await;
+    let mut round_2 = open_tab_entities::domain::round::TournamentRound::get(&db, Uuid::from_u128(101)).await.unwrap();
+    round_2.draw_release_time = Some(chrono::Utc::now().naive_utc() + chrono::Duration::minutes(30));
+    EntityGroup::new_from_entities(
+        Uuid::from_u128(1),
+        vec![
+        Entity::TournamentRound(round_2)
+    ]).save_all_and_log(&db).await.unwrap();
+}
+
+#[tokio::test]
+
+async fn test_adjudicator_can_mark_round_unavailable() {
+    let mut fixture: common::Fixture = common::Fixture::new_with_setup(
+        FixtureOptions {
+            mock_default_tournament: true,
+            use_participant_account: Some(Uuid::from_u128(3000)),
+            ..Default::default()
+        },
+        Box::new(allow_self_declared_availability)
+    ).await;
+
+    let response = fixture
+        .post_json(&format!("/api/participant/{}/availability", Uuid::from_u128(3000)), UpdateParticipantAvailabilityRequest {
+            unavailable_rounds: vec![Uuid::from_u128(101)],
+            available_rounds: vec![]
+        })
+        .await;
+    assert_eq!(response.status(), 200);
+
+    let mut response = fixture
+        .get(&format!("/api/participant/{}/availability", Uuid::from_u128(3000)))
+        .await;
+    assert_eq!(response.status(), 200);
+    let body = response.json::<ParticipantAvailabilityResponse>().await;
+    let availability = body.rounds.iter().map(|r| (r.round_id, r.is_available)).collect::<Vec<_>>();
+    assert_eq!(availability, vec![
+        (Uuid::from_u128(100), true),
+        (Uuid::from_u128(101), false),
+        (Uuid::from_u128(102), true),
+    ]);
+}
+
+#[tokio::test]
+
+async fn test_adjudicator_can_not_change_availability_after_deadline() {
+    let mut fixture: common::Fixture = common::Fixture::new_with_setup(
+        FixtureOptions {
+            mock_default_tournament: true,
+            use_participant_account: Some(Uuid::from_u128(3000)),
+            ..Default::default()
+        },
+        Box::new(allow_self_declared_availability_with_upcoming_draw)
+    ).await;
+
+    let response = fixture
+        .post_json(&format!("/api/participant/{}/availability", Uuid::from_u128(3000)), UpdateParticipantAvailabilityRequest {
+            unavailable_rounds: vec![Uuid::from_u128(101)],
+            available_rounds: vec![]
+        })
+        .await;
+    assert_eq!(response.status(), 403);
+
+    let mut response = fixture
+        .get(&format!("/api/participant/{}/availability", Uuid::from_u128(3000)))
+        .await;
+    let body = response.json::<ParticipantAvailabilityResponse>().await;
+    assert!(body.rounds.iter().all(|r| r.is_available));
+    assert!(!body.rounds[1].can_change);
+    assert!(body.rounds[2].can_change);
+}
+
+#[tokio::test]
+
+async fn test_availability_can_not_be_declared_without_tournament_setting() {
+    let mut fixture: common::Fixture = common::Fixture::new(
+        FixtureOptions {
+            mock_default_tournament: true,
+            use_participant_account: Some(Uuid::from_u128(3000)),
+            ..Default::default()
+        }
+    ).await;
+
+    let response = fixture
+        .get(&format!("/api/participant/{}/availability", Uuid::from_u128(3000)))
+        .await;
+    assert_eq!(response.status(), 403);
+}
+
+#[tokio::test]
+
+async fn test_speaker_can_not_declare_availability() {
+    let mut fixture: common::Fixture = common::Fixture::new_with_setup(
+        FixtureOptions {
+            mock_default_tournament: true,
+            use_participant_account: Some(Uuid::from_u128(2000)),
+            ..Default::default()
+        },
+        Box::new(allow_self_declared_availability)
+    ).await;
+
+    let response = fixture
+        .post_json(&format!("/api/participant/{}/availability", Uuid::from_u128(2000)), UpdateParticipantAvailabilityRequest {
+            unavailable_rounds: vec![Uuid::from_u128(101)],
+            available_rounds: vec![]
+        })
+        .await;
+    assert_eq!(response.status(), 403);
+}
@@ -0,0 +1,241 @@
+mod common;
+use open_tab_entities::{domain::{debate_backup_ballot::DebateBackupBallot, entity::LoadEntity}, prelude::{Ballot, BallotTeam, TournamentDebate}, Entity, EntityGroup, EntityTypeId};
+use open_tab_server::{auth::{CreateUserRequest, CreateUserResponse}, ballot::PendingSubmission, staff::{InviteStaffRequest, StaffList, TournamentRole}, sync::{FatLog, SyncRequest}};
+use sea_orm::{prelude::Uuid, DatabaseConnection};
+use tracing_test::traced_test;
+
+use crate::common::FixtureOptions;
+
+
+async fn make_staff_fixture(role: TournamentRole) -> common::Fixture {
+    common::Fixture::new(
+        FixtureOptions {
+            mock_default_tournament: true,
+            staff_role: Some(role),
+            ..Default::default()
+        }
+    ).await
+}
+
+async fn create_user_with_email(fixture: &mut common::Fixture, user_email: &str) -> Uuid {
+    let mut response = fixture.post_json("/api/users", CreateUserRequest {
+        password: "testtest".to_string(),
+        user_email: Some(user_email.to_string()),
+    }).await;
+    assert_eq!(response.status(), 200);
+    response.json::<CreateUserResponse>().await.uuid
+}
+
+#[tokio::test]
+#[traced_test]
+async fn test_observer_can_read_log_but_not_push() {
+    let mut fixture = make_staff_fixture(TournamentRole::Observer).await;
+    let tournament_id = Uuid::from_u128(1);
+
+    let response = fixture.get(&format!("/api/tournament/{}/log", tournament_id)).await;
+    assert_eq!(response.status(), 200);
+
+    let response = fixture.post_json(&format!("/api/tournament/{}/log", tournament_id), SyncRequest {
+        log: FatLog::<Entity, EntityTypeId> { log: vec![], entities: Default::default() },
+        last_common_ancestor: None
+    }).await;
+    assert_eq!(response.status(), 403);
+}
+
+#[tokio::test]
+#[traced_test]
+async fn test_adjudication_core_can_not_read_log() {
+    let mut fixture = make_staff_fixture(TournamentRole::AdjudicationCore).await;
+
+    let response = fixture.get(&format!("/api/tournament/{}/admin", Uuid::from_u128(1))).await;
+    assert_eq!(response.status(), 200);
+
+    let response = fixture.get(&format!("/api/tournament/{}/log", Uuid::from_u128(1))).await;
+    assert_eq!(response.status(), 403);
+}
+
+#[tokio::test]
+#[traced_test]
+async fn test_adjudication_core_can_read_unreleased_draw() {
+    let mut fixture = make_staff_fixture(TournamentRole::AdjudicationCore).await;
+
+    let response = fixture.get(&format!("/api/rounds/{}/draw", Uuid::from_u128(100))).await;
+    assert_eq!(response.status(), 200);
+}
+
+#[tokio::test]
+#[traced_test]
+async fn test_ballot_entry_can_not_read_draw() {
+    let mut fixture = make_staff_fixture(TournamentRole::BallotEntry).await;
+
+    let response = fixture.get(&format!("/api/rounds/{}/draw", Uuid::from_u128(100))).await;
+    assert_eq!(response.status(), 404);
+}
+
+#[tokio::test]
+#[traced_test]
+async fn test_observer_can_read_tab() {
+    let mut fixture = make_staff_fixture(TournamentRole::Observer).await;
+
+    let response = fixture.get(&format!("/api/tournament/{}/tab", Uuid::from_u128(1))).await;
+    assert_eq!(response.status(), 200);
+}
+
+#[tokio::test]
+#[traced_test]
+async fn test_adjudication_core_can_not_read_tab() {
+    let mut fixture = make_staff_fixture(TournamentRole::AdjudicationCore).await;
+
+    let response = fixture.get(&format!("/api/tournament/{}/tab", Uuid::from_u128(1))).await;
+    assert_eq!(response.status(), 403);
+}
+
+#[tokio::test]
+#[traced_test]
+async fn test_equity_can_read_feedback_but_not_draw() {
+    let mut fixture = make_staff_fixture(TournamentRole::Equity).await;
+
+    let response = fixture.get(&format!("/api/participant/{}/feedback", Uuid::from_u128(2000))).await;
+    assert_eq!(response.status(), 200);
+
+    let response = fixture.get(&format!("/api/rounds/{}/draw", Uuid::from_u128(100))).await;
+    assert_eq!(response.status(), 404);
+}
+
+#[tokio::test]
+#[traced_test]
+async fn test_adjudication_core_can_not_read_feedback() {
+    let mut fixture = make_staff_fixture(TournamentRole::AdjudicationCore).await;
+
+    let response = fixture.get(&format!("/api/participant/{}/feedback", Uuid::from_u128(2000))).await;
+    assert_eq!(response.status(), 403);
+}
+
+const SUBMISSION_ID : u128 = 14000;
+const SUBMITTED_BALLOT_ID : u128 = 14001;
+
+async fn make_staff_fixture_with_submission(role: TournamentRole) -> (common::Fixture, DatabaseConnection) {
+    let fixture = common::Fixture::new_with_setup(
+        FixtureOptions {
+            mock_default_tournament: true,
+            staff_role: Some(role),
+            ..Default::default()
+        },
+        |db| async move {
+            EntityGroup::new_from_entities(
+                Uuid::from_u128(1),
+                vec![
+                    Entity::Ballot(Ballot {
+                        uuid: Uuid::from_u128(SUBMITTED_BALLOT_ID),
+                        government: BallotTeam { team: Some(Uuid::from_u128(1000)), ..Default::default() },
+                        opposition: BallotTeam { team: Some(Uuid::from_u128(1001)), ..Default::default() },
+                        ..Default::default()
+                    }),
+                    Entity::DebateBackupBallot(DebateBackupBallot {
+                        uuid: Uuid::from_u128(SUBMISSION_ID),
+                        debate_id: Uuid::from_u128(200),
+                        ballot_id: Uuid::from_u128(SUBMITTED_BALLOT_ID),
+                        timestamp: chrono::Utc::now().naive_utc(),
+                        was_seen: false,
+                        author_participant_id: None,
+                    })
+                ]
+            ).save_all_and_log(&db).await.unwrap();
+        }
+    ).await;
+    let db = fixture.db();
+
+    (fixture, db)
+}
+
+#[tokio::test]
+#[traced_test]
+async fn test_ballot_entry_can_promote_submission() {
+    let (mut fixture, db) = make_staff_fixture_with_submission(TournamentRole::BallotEntry).await;
+
+    let mut response = fixture.get(&format!("/api/tournament/{}/submissions", Uuid::from_u128(1))).await;
+    assert_eq!(response.status(), 200);
+    let pending = response.json::<Vec<PendingSubmission>>().await;
+    assert_eq!(pending.iter().map(|s| s.submission_id).collect::<Vec<_>>(), vec![Uuid::from_u128(SUBMISSION_ID)]);
+    assert_eq!(pending[0].debate_id, Uuid::from_u128(200));
+
+    let response = fixture.get(&format!("/api/submission/{}", Uuid::from_u128(SUBMISSION_ID))).await;
+    assert_eq!(response.status(), 200);
+
+    let response = fixture.post_json_no_body(&format!("/api/submission/{}/promote", Uuid::from_u128(SUBMISSION_ID))).await;
+    assert_eq!(response.status(), 200);
+
+    let debate = TournamentDebate::get(&db, Uuid::from_u128(200)).await.unwrap();
+    assert_eq!(debate.ballot_id, Uuid::from_u128(SUBMITTED_BALLOT_ID));
+    assert!(debate.is_complete);
+    assert!(DebateBackupBallot::get(&db, Uuid::from_u128(SUBMISSION_ID)).await.unwrap().was_seen);
+
+    let mut response = fixture.get(&format!("/api/tournament/{}/submissions", Uuid::from_u128(1))).await;
+    assert!(response.json::<Vec<PendingSubmission>>().await.is_empty());
+
+    let response = fixture.get(&format!("/api/tournament/{}/log", Uuid::from_u128(1))).await;
+    assert_eq!(response.status(), 403);
+}
+
+#[tokio::test]
+#[traced_test]
+async fn test_observer_can_not_promote_submission() {
+    let (mut fixture, db) = make_staff_fixture_with_submission(TournamentRole::Observer).await;
+
+    let response = fixture.post_json_no_body(&format!("/api/submission/{}/promote", Uuid::from_u128(SUBMISSION_ID))).await;
+    assert_eq!(response.status(), 403);
+
+    let response = fixture.get(&format!("/api/tournament/{}/submissions", Uuid::from_u128(1))).await;
+    assert_eq!(response.status(), 403);
+
+    let debate = TournamentDebate::get(&db, Uuid::from_u128(200)).await.unwrap();
+    assert_ne!(debate.ballot_id, Uuid::from_u128(SUBMITTED_BALLOT_ID));
+}
+
+#[tokio::test]
+#[traced_test]
+async fn test_tab_director_can_invite_and_revoke_staff() {
+    let mut fixture = make_staff_fixture(TournamentRole::TabDirector).await;
+    let tournament_id = Uuid::from_u128(1);
+    let user_id = create_user_with_email(&mut fixture, "equity@example.com").await;
+
+    let response = fixture.post_json(&format!("/api/tournament/{}/staff", tournament_id), InviteStaffRequest {
+        user_email: "equity@example.com".to_string(),
+        role: TournamentRole::Equity,
+    }).await;
+    assert_eq!(response.status(), 200);
+
+    let mut response = fixture.get(&format!("/api/tournament/{}/staff", tournament_id)).await;
+    assert_eq!(response.status(), 200);
+    let staff = response.json::<StaffList>().await.staff;
+    assert_eq!(staff.len(), 2);
+    assert_eq!(staff.iter().find(|s| s.user_id == user_id).unwrap().role, TournamentRole::Equity);
+
+    let response = fixture.delete(&format!("/api/tournament/{}/staff/{}", tournament_id, user_id)).await;
+    assert_eq!(response.status(), 200);
+
+    let mut response = fixture.get(&format!("/api/tournament/{}/staff", tournament_id)).await;
+    assert_eq!(response.json::<StaffList>().await.staff.len(), 1);
+}
+
+#[tokio::test]
+#[traced_test]
+async fn test_last_tab_director_can_not_be_removed() {
+    let mut fixture = make_staff_fixture(TournamentRole::TabDirector).await;
+
+    let response = fixture.delete(&format!("/api/tournament/{}/staff/{}", Uuid::from_u128(1), Uuid::from_u128(900_000))).await;
+    assert_eq!(response.status(), 400);
+}
+
+#[tokio::test]
+#[traced_test]
+async fn test_observer_can_not_invite_staff() {
+    let mut fixture = make_staff_fixture(TournamentRole::Observer).await;
+    create_user_with_email(&mut fixture, "observer@example.com").await;
+
+    let response = fixture.post_json(&format!("/api/tournament/{}/staff", Uuid::from_u128(1)), InviteStaffRequest {
+        user_email: "observer@example.com".to_string(),
+        role: TournamentRole::TabDirector,
+    }).await;
+    assert_eq!(response.status(), 403);
+}
@@ -1,9 +1,11 @@
 mod common;
 use std::collections::HashMap;
 
-use open_tab_entities::{prelude::Participant, Entity, EntityGroup, EntityState, EntityTypeId};
-use open_tab_server::{sync::{FatLog, SyncRequest, LogEntry, EntityEntry}, participants::ParticipantInfoResponse};
-use sea_orm::{prelude::Uuid, DatabaseConnection, IntoActiveModel, ActiveModelTrait};
+use migration::MigratorTrait;
+use open_tab_entities::{domain::entity::LoadEntity, prelude::Participant, Entity, EntityGroup, EntityState, EntityTypeId, LogAuthor};
+use open_tab_server::{sync::{decode_log_body, get_entity_changes_page_since, merge_entity_states, reconcile_changes, APIReconciliationOutcome, EntityHistory, FatLog, FatLogPage, SyncRequest, MAX_LOG_PAGE_SIZE, SyncRequestResponse, LogEntry, EntityEntry, MergeStrategy, ReconciliationOutcome}, compaction::{compact_tournament_log, LogSnapshotInfo, SNAPSHOT_RETENTION_COMPACTIONS}, participants::ParticipantInfoResponse, staff::TournamentRole, notify::LogHeadEvent};
+use open_tab_entities::schema::{tournament_entity, tournament_log, tournament_log_pruned_entry};
+use sea_orm::{prelude::Uuid, sea_query::Expr, DatabaseConnection, IntoActiveModel, ActiveModelTrait, ColumnTrait, EntityTrait, QueryFilter, QueryOrder};
 use tracing_test::traced_test;
 
 use crate::common::FixtureOptions;
@@ -29,6 +31,7 @@ async fn test_add_participant() {
                 chair_skill: 1,
                 panel_skill: 2,
                 unavailable_rounds: vec![],
+                is_trainee: false,
             }
         ),
         default_tournament_uuid,
@@ -47,6 +50,7 @@ async fn test_add_participant() {
             target_type: EntityTypeId::Participant,
             target_uuid: participant.uuid,
             timestamp: chrono::offset::Local::now().naive_utc(),
+            author: None,
         }
     ], entities: HashMap::from_iter(
         vec![
@@ -80,6 +84,328 @@ async fn test_add_participant() {
     assert_eq!(body.name, "Peter G.")
 }
 
+#[tokio::test]
+#[traced_test]
+async fn test_pushed_changes_are_attributed_to_pushing_user() {
+    let mut fixture = common::Fixture::new(
+        FixtureOptions {
+            mock_default_tournament: true,
+            ..Default::default()
+        }
+    ).await;
+    let default_tournament_uuid = Uuid::from_u128(1);
+
+    let mut participant = Participant::get(&fixture.db(), Uuid::from_u128(2000)).await.unwrap();
+    participant.name = "Renamed".into();
+    let app_id = Uuid::from_u128(300_000);
+
+    let mut response = fixture.get(&format!("/api/tournament/{}/log", default_tournament_uuid)).await;
+    let last_log = response.json::<FatLog<Entity, EntityTypeId>>().await.log.last().unwrap().uuid;
+
+    let version = Uuid::from_u128(200_000);
+    let sync_request = SyncRequest {
+        log: FatLog {
+            log: vec![
+                LogEntry {
+                    uuid: version,
+                    target_type: EntityTypeId::Participant,
+                    target_uuid: participant.uuid,
+                    timestamp: chrono::offset::Local::now().naive_utc(),
+                    author: Some(LogAuthor::App { app_id, name: "Tab Room".into(), user_id: None }),
+                }
+            ],
+            entities: HashMap::from_iter(vec![
+                (EntityTypeId::Participant, vec![EntityEntry {
+                    uuid: participant.uuid,
+                    old_versions: vec![],
+                    current_version: version,
+                    current_value: EntityState::Exists(Entity::Participant(participant.clone()))
+                }])
+            ])
+        },
+        last_common_ancestor: Some(last_log)
+    };
+    let response = fixture.post_json(&format!("/api/tournament/{}/log", default_tournament_uuid), sync_request).await;
+    assert_eq!(response.status(), 200);
+
+    let mut response = fixture.get(&format!("/api/tournament/{}/entity/{}/history", default_tournament_uuid, participant.uuid)).await;
+    assert_eq!(response.status(), 200);
+    let history = response.json::<EntityHistory>().await;
+    let last_entry = history.entries.last().unwrap();
+    assert_eq!(last_entry.version, version);
+    assert_eq!(last_entry.author, Some(LogAuthor::App { app_id, name: "Tab Room".into(), user_id: Some(Uuid::from_u128(900_000)) }));
+    assert_eq!(last_entry.value, Some(EntityState::Exists(Entity::Participant(participant))));
+}
+
+#[tokio::test]
+async fn test_log_events_announce_new_log_heads() {
+    let mut fixture = common::Fixture::new(
+        FixtureOptions {
+            mock_default_tournament: true,
+            ..Default::default()
+        }
+    ).await;
+    let default_tournament_uuid = Uuid::from_u128(1);
+
+    let mut response = fixture.get(&format!("/api/tournament/{}/log", default_tournament_uuid)).await;
+    let last_log = response.json::<FatLog<Entity, EntityTypeId>>().await.log.last().unwrap().uuid;
+
+    let mut events = fixture.get(&format!("/api/tournament/{}/log/events", default_tournament_uuid)).await;
+    assert_eq!(events.status(), 200);
+    let event = tokio::time::timeout(std::time::Duration::from_secs(5), events.next_event::<LogHeadEvent>("log_head")).await.unwrap();
+    assert_eq!(event, Some(LogHeadEvent { tournament_id: default_tournament_uuid, log_head: Some(last_log) }));
+
+    let mut participant = Participant::get(&fixture.db(), Uuid::from_u128(2000)).await.unwrap();
+    participant.name = "Renamed".into();
+    let version = Uuid::from_u128(200_000);
+    let sync_request = SyncRequest {
+        log: FatLog {
+            log: vec![
+                LogEntry {
+                    uuid: version,
+                    target_type: EntityTypeId::Participant,
+                    target_uuid: participant.uuid,
+                    timestamp: chrono::offset::Local::now().naive_utc(),
+                    author: None,
+                }
+            ],
+            entities: HashMap::from_iter(vec![
+                (EntityTypeId::Participant, vec![EntityEntry {
+                    uuid: participant.uuid,
+                    old_versions: vec![],
+                    current_version: version,
+                    current_value: EntityState::Exists(Entity::Participant(participant))
+                }])
+            ])
+        },
+        last_common_ancestor: Some(last_log)
+    };
+    let response = fixture.post_json(&format!("/api/tournament/{}/log", default_tournament_uuid), sync_request).await;
+    assert_eq!(response.status(), 200);
+
+    let event = tokio::time::timeout(std::time::Duration::from_secs(5), events.next_event::<LogHeadEvent>("log_head")).await.unwrap();
+    assert_eq!(event, Some(LogHeadEvent { tournament_id: default_tournament_uuid, log_head: Some(version) }));
+}
+
+#[tokio::test]
+async fn test_log_events_require_log_access() {
+    let mut fixture = common::Fixture::new(
+        FixtureOptions {
+            mock_default_tournament: true,
+            staff_role: Some(TournamentRole::BallotEntry),
+            ..Default::default()
+        }
+    ).await;
+
+    let response = fixture.get(&format!("/api/tournament/{}/log/events", Uuid::from_u128(1))).await;
+    assert_eq!(response.status(), 403);
+}
+#[tokio::test]
+async fn test_log_can_be_read_in_pages() {
+    let mut fixture = common::Fixture::new(
+        FixtureOptions {
+            mock_default_tournament: true,
+            ..Default::default()
+        }
+    ).await;
+    let default_tournament_uuid = Uuid::from_u128(1);
+
+    let mut response = fixture.get(&format!("/api/tournament/{}/log", default_tournament_uuid)).await;
+    let full_log = response.json::<FatLogPage<Entity, EntityTypeId>>().await;
+    assert!(!full_log.has_more);
+
+    let mut paged_entries = vec![];
+    let mut since: Option<Uuid> = None;
+    loop {
+        let path = match since {
+            Some(since) => format!("/api/tournament/{}/log?limit=50&since={}", default_tournament_uuid, since),
+            None => format!("/api/tournament/{}/log?limit=50", default_tournament_uuid),
+        };
+        let mut response = fixture.get(&path).await;
+        assert_eq!(response.status(), 200);
+        let page = response.json::<FatLogPage<Entity, EntityTypeId>>().await;
+        assert!(page.log.log.len() <= 50);
+        paged_entries.extend(page.log.log.iter().map(|entry| entry.uuid));
+        since = page.log.log.last().map(|entry| entry.uuid);
+        if !page.has_more {
+            break;
+        }
+    }
+
+    assert!(full_log.log.log.len() > 50);
+    assert_eq!(paged_entries, full_log.log.log.iter().map(|entry| entry.uuid).collect::<Vec<_>>());
+}
+
+#[tokio::test]
+async fn test_log_can_be_transferred_compressed() {
+    let mut fixture = common::Fixture::new(
+        FixtureOptions {
+            mock_default_tournament: true,
+            ..Default::default()
+        }
+    ).await;
+    let default_tournament_uuid = Uuid::from_u128(1);
+
+    let mut response = fixture.get_with_header(&format!("/api/tournament/{}/log", default_tournament_uuid), "Accept-Encoding", "gzip, deflate").await;
+    assert_eq!(response.status(), 200);
+    assert_eq!(response.header("Content-Encoding"), Some("gzip".to_string()));
+    let log = decode_log_body::<FatLogPage<Entity, EntityTypeId>>(&response.bytes().await, true).unwrap();
+    let last_log = log.log.log.last().unwrap().uuid;
+
+    let mut participant = Participant::get(&fixture.db(), Uuid::from_u128(2000)).await.unwrap();
+    participant.name = "Compressed".into();
+    let version = Uuid::from_u128(200_000);
+    let sync_request = SyncRequest {
+        log: FatLog {
+            log: vec![
+                LogEntry {
+                    uuid: version,
+                    target_type: EntityTypeId::Participant,
+                    target_uuid: participant.uuid,
+                    timestamp: chrono::offset::Local::now().naive_utc(),
+                    author: None,
+                }
+            ],
+            entities: HashMap::from_iter(vec![
+                (EntityTypeId::Participant, vec![EntityEntry {
+                    uuid: participant.uuid,
+                    old_versions: vec![],
+                    current_version: version,
+                    current_value: EntityState::Exists(Entity::Participant(participant))
+                }])
+            ])
+        },
+        last_common_ancestor: Some(last_log)
+    };
+    let response = fixture.post_gzip_json(&format!("/api/tournament/{}/log", default_tournament_uuid), sync_request).await;
+    assert_eq!(response.status(), 200);
+
+    let participant = Participant::get(&fixture.db(), Uuid::from_u128(2000)).await.unwrap();
+    assert_eq!(participant.name, "Compressed");
+}
+fn rename_participant_request(mut participant: Participant, name: &str, version: Uuid, last_common_ancestor: Uuid) -> SyncRequest<Entity, EntityTypeId> {
+    participant.name = name.into();
+    SyncRequest {
+        log: FatLog {
+            log: vec![
+                LogEntry {
+                    uuid: version,
+                    target_type: EntityTypeId::Participant,
+                    target_uuid: participant.uuid,
+                    timestamp: chrono::offset::Local::now().naive_utc(),
+                    author: None,
+                }
+            ],
+            entities: HashMap::from_iter(vec![
+                (EntityTypeId::Participant, vec![EntityEntry {
+                    uuid: participant.uuid,
+                    old_versions: vec![],
+                    current_version: version,
+                    current_value: EntityState::Exists(Entity::Participant(participant))
+                }])
+            ])
+        },
+        last_common_ancestor: Some(last_common_ancestor)
+    }
+}
+
+#[tokio::test]
+async fn test_compaction_keeps_latest_entry_per_entity() {
+    let mut fixture = common::Fixture::new(
+        FixtureOptions {
+            mock_default_tournament: true,
+            ..Default::default()
+        }
+    ).await;
+    let default_tournament_uuid = Uuid::from_u128(1);
+    let log_path = format!("/api/tournament/{}/log", default_tournament_uuid);
+
+    let mut response = fixture.get(&log_path).await;
+    let last_log = response.json::<FatLogPage<Entity, EntityTypeId>>().await.log.log.last().unwrap().uuid;
+
+    let participant = Participant::get(&fixture.db(), Uuid::from_u128(2000)).await.unwrap();
+    let first_version = Uuid::from_u128(200_000);
+    let second_version = Uuid::from_u128(200_001);
+    let response = fixture.post_json(&log_path, rename_participant_request(participant.clone(), "First", first_version, last_log)).await;
+    assert_eq!(response.status(), 200);
+    let response = fixture.post_json(&log_path, rename_participant_request(participant.clone(), "Second", second_version, first_version)).await;
+    assert_eq!(response.status(), 200);
+
+    let mut response = fixture.post_json_no_body(&format!("/api/tournament/{}/log/compact", default_tournament_uuid)).await;
+    assert_eq!(response.status(), 200);
+    let snapshot = response.json::<LogSnapshotInfo>().await;
+    assert_eq!(snapshot.log_position, second_version);
+    assert!(snapshot.pruned_entries >= 1);
+
+    let mut response = fixture.get(&log_path).await;
+    let log = response.json::<FatLogPage<Entity, EntityTypeId>>().await;
+    let mut seen_entities = std::collections::HashSet::new();
+    assert!(log.log.log.iter().all(|entry| seen_entities.insert((entry.target_type, entry.target_uuid))));
+    assert!(!log.log.log.iter().any(|entry| entry.uuid == first_version));
+    assert_eq!(log.log.log.last().unwrap().uuid, second_version);
+
+    // The audit trail of the entity keeps the pruned change and its author
+    let mut response = fixture.get(&format!("/api/tournament/{}/entity/{}/history", default_tournament_uuid, participant.uuid)).await;
+    let history = response.json::<EntityHistory>().await;
+    let pruned_entry = history.entries.iter().find(|entry| entry.version == first_version).unwrap();
+    assert!(pruned_entry.author.is_some());
+    assert!(matches!(&pruned_entry.value, Some(EntityState::Exists(Entity::Participant(p))) if p.name == "First"));
+    assert_eq!(history.entries.last().unwrap().version, second_version);
+
+    // A client that last synced at a pruned entry can still continue from there
+    let mut response = fixture.get(&format!("{}?since={}", log_path, first_version)).await;
+    assert_eq!(response.status(), 200);
+    let log = response.json::<FatLogPage<Entity, EntityTypeId>>().await;
+    assert_eq!(log.log.log.iter().map(|entry| entry.uuid).collect::<Vec<_>>(), vec![second_version]);
+}
+
+#[tokio::test]
+async fn test_push_after_compaction_keeps_ancestor_semantics() {
+    let mut fixture = common::Fixture::new(
+        FixtureOptions {
+            mock_default_tournament: true,
+            ..Default::default()
+        }
+    ).await;
+    let default_tournament_uuid = Uuid::from_u128(1);
+    let log_path = format!("/api/tournament/{}/log", default_tournament_uuid);
+
+    let mut response = fixture.get(&log_path).await;
+    let last_log = response.json::<FatLogPage<Entity, EntityTypeId>>().await.log.log.last().unwrap().uuid;
+
+    let participant = Participant::get(&fixture.db(), Uuid::from_u128(2000)).await.unwrap();
+    let first_version = Uuid::from_u128(200_000);
+    let second_version = Uuid::from_u128(200_001);
+    fixture.post_json(&log_path, rename_participant_request(participant.clone(), "First", first_version, last_log)).await;
+    fixture.post_json(&log_path, rename_participant_request(participant.clone(), "Second", second_version, first_version)).await;
+
+    let response = fixture.post_json_no_body(&format!("/api/tournament/{}/log/compact", default_tournament_uuid)).await;
+    assert_eq!(response.status(), 200);
+
+    // The server has changes after the pruned ancestor, so this push must be rejected as before
+    let mut response = fixture.post_json(&log_path, rename_participant_request(participant.clone(), "Stale", Uuid::from_u128(200_002), first_version)).await;
+    assert_eq!(response.status(), 200);
+    assert!(matches!(response.json::<SyncRequestResponse>().await.outcome, APIReconciliationOutcome::Reject));
+
+    let mut response = fixture.post_json(&log_path, rename_participant_request(participant.clone(), "Third", Uuid::from_u128(200_003), second_version)).await;
+    assert_eq!(response.status(), 200);
+    assert!(matches!(response.json::<SyncRequestResponse>().await.outcome, APIReconciliationOutcome::Success { .. }));
+    assert_eq!(Participant::get(&fixture.db(), participant.uuid).await.unwrap().name, "Third");
+}
+
+#[tokio::test]
+async fn test_compaction_requires_management_access() {
+    let mut fixture = common::Fixture::new(
+        FixtureOptions {
+            mock_default_tournament: true,
+            staff_role: Some(TournamentRole::Observer),
+            ..Default::default()
+        }
+    ).await;
+
+    let response = fixture.post_json_no_body(&format!("/api/tournament/{}/log/compact", Uuid::from_u128(1))).await;
+    assert_eq!(response.status(), 403);
+}
 
 
 async fn create_second_tournament(db: DatabaseConnection) {
@@ -96,7 +422,8 @@ async fn create_second_tournament(db: DatabaseConnection) {
 
     let user_tournament = open_tab_entities::schema::user_tournament::Model {
         user_id: Uuid::from_u128(900_000),
-        tournament_id: Uuid::from_u128(2)
+        tournament_id: Uuid::from_u128(2),
+        role: TournamentRole::TabDirector.to_str().to_string(),
     };
     user_tournament.into_active_model().insert(&db).await.unwrap();
 
@@ -123,6 +450,7 @@ async fn test_can_add_participant_to_empty_tournament() {
                 chair_skill: 1,
                 panel_skill: 2,
                 unavailable_rounds: vec![],
+                is_trainee: false,
             }
         ),
         tournament_2_uuid,
@@ -135,6 +463,7 @@ async fn test_can_add_participant_to_empty_tournament() {
             target_type: EntityTypeId::Participant,
             target_uuid: participant.uuid,
             timestamp: chrono::offset::Local::now().naive_utc(),
+            author: None,
         }
     ], entities: HashMap::from_iter(
         vec![
@@ -189,6 +518,7 @@ async fn test_can_not_add_participant_to_other_tournament() {
                 chair_skill: 1,
                 panel_skill: 2,
                 unavailable_rounds: vec![],
+                is_trainee: false,
             }
         ),
         Uuid::from_u128(1),
@@ -201,6 +531,7 @@ async fn test_can_not_add_participant_to_other_tournament() {
             target_type: EntityTypeId::Participant,
             target_uuid: participant.uuid,
             timestamp: chrono::offset::Local::now().naive_utc(),
+            author: None,
         }
     ], entities: HashMap::from_iter(
         vec![
@@ -265,6 +596,7 @@ async fn test_can_delete_participant() {
             target_type: EntityTypeId::Participant,
             target_uuid: participant_uuid,
             timestamp: chrono::offset::Local::now().naive_utc(),
+            author: None,
         }
     ], entities: HashMap::from_iter(
         vec![
@@ -329,6 +661,7 @@ async fn test_can_delete_non_existant_participant() {
             target_type: EntityTypeId::Participant,
             target_uuid: participant_uuid,
             timestamp: chrono::offset::Local::now().naive_utc(),
+            author: None,
         }
     ], entities: HashMap::from_iter(
         vec![
@@ -381,6 +714,7 @@ async fn test_can_not_delete_participant_to_other_tournament() {
             target_type: EntityTypeId::Participant,
             target_uuid: participant_uuid,
             timestamp: chrono::offset::Local::now().naive_utc(),
+            author: None,
         }
     ], entities: HashMap::from_iter(
         vec![
@@ -409,4 +743,303 @@ async fn test_can_not_delete_participant_to_other_tournament() {
     .await;
 
     assert_eq!(response.status(), 200);
-}
\ No newline at end of file
+}
+
+async fn set_up_merge_db() -> (DatabaseConnection, Uuid) {
+    let db = sea_orm::Database::connect("sqlite::memory:").await.unwrap();
+    migration::Migrator::up(&db, None).await.unwrap();
+
+    let tournament_uuid = Uuid::from_u128(1);
+    let mut changes = EntityGroup::new(tournament_uuid);
+    changes.add(Entity::Tournament(open_tab_entities::prelude::Tournament {
+        uuid: tournament_uuid,
+        name: "Merge".into(),
+        ..Default::default()
+    }));
+    changes.add(Entity::Participant(make_adjudicator("Peter G.", 1)));
+    let last_common_ancestor = changes.save_all_and_log(&db).await.unwrap();
+
+    (db, last_common_ancestor)
+}
+
+fn make_adjudicator(name: &str, chair_skill: i16) -> Participant {
+    Participant::new_with_uuid(
+        Uuid::from_u128(100_000),
+        name.into(),
+        open_tab_entities::domain::participant::ParticipantRole::Adjudicator(
+            open_tab_entities::domain::participant::Adjudicator {
+                chair_skill,
+                panel_skill: 2,
+                unavailable_rounds: vec![],
+                is_trainee: false,
+            }
+        ),
+        Uuid::from_u128(1),
+    )
+}
+
+fn make_remote_participant_log(participant: Participant) -> FatLog<Entity, EntityTypeId> {
+    let version = Uuid::from_u128(200_000);
+    FatLog { log: vec![
+        LogEntry {
+            uuid: version,
+            target_type: EntityTypeId::Participant,
+            target_uuid: participant.uuid,
+            timestamp: chrono::offset::Local::now().naive_utc(),
+            author: None,
+        }
+    ], entities: HashMap::from_iter(
+        vec![
+            (EntityTypeId::Participant, vec![EntityEntry {
+                uuid: participant.uuid,
+                old_versions: vec![],
+                current_version: version,
+                current_value: EntityState::Exists(Entity::Participant(participant))
+            }])
+        ]
+    ) }
+}
+
+#[tokio::test]
+async fn test_three_way_merge_combines_changes_to_different_fields() {
+    let (db, last_common_ancestor) = set_up_merge_db().await;
+
+    let mut local_changes = EntityGroup::new(Uuid::from_u128(1));
+    local_changes.add(Entity::Participant(make_adjudicator("Peter Gabriel", 1)));
+    local_changes.save_all_and_log(&db).await.unwrap();
+
+    let outcome = reconcile_changes(
+        &db,
+        Uuid::from_u128(1),
+        make_remote_participant_log(make_adjudicator("Peter G.", 5)),
+        Some(last_common_ancestor),
+        MergeStrategy::ThreeWay,
+        false
+    ).await.unwrap();
+
+    assert!(matches!(outcome, ReconciliationOutcome::Success { .. }));
+
+    let participant = Participant::get(&db, Uuid::from_u128(100_000)).await.unwrap();
+    assert_eq!(participant, make_adjudicator("Peter Gabriel", 5));
+}
+
+#[tokio::test]
+async fn test_three_way_merge_reports_conflicting_field() {
+    let (db, last_common_ancestor) = set_up_merge_db().await;
+
+    let mut local_changes = EntityGroup::new(Uuid::from_u128(1));
+    local_changes.add(Entity::Participant(make_adjudicator("Peter Gabriel", 1)));
+    local_changes.save_all_and_log(&db).await.unwrap();
+
+    let outcome = reconcile_changes(
+        &db,
+        Uuid::from_u128(1),
+        make_remote_participant_log(make_adjudicator("Peter Green", 5)),
+        Some(last_common_ancestor),
+        MergeStrategy::ThreeWay,
+        false
+    ).await.unwrap();
+
+    let conflicts = match outcome {
+        ReconciliationOutcome::Conflict { conflicts, .. } => conflicts,
+        _ => panic!("Expected a conflict")
+    };
+    assert_eq!(conflicts.len(), 1);
+    assert_eq!(conflicts[0].entity_uuid, Uuid::from_u128(100_000));
+    assert_eq!(conflicts[0].path, "/Participant/name");
+    assert_eq!(conflicts[0].base, Some("Peter G.".into()));
+    assert_eq!(conflicts[0].local, Some("Peter Gabriel".into()));
+    assert_eq!(conflicts[0].remote, Some("Peter Green".into()));
+
+    let participant = Participant::get(&db, Uuid::from_u128(100_000)).await.unwrap();
+    assert_eq!(participant, make_adjudicator("Peter Gabriel", 5));
+}
+
+#[tokio::test]
+async fn test_three_way_merge_uses_pruned_ancestor_as_base() {
+    let (db, last_common_ancestor) = set_up_merge_db().await;
+
+    let mut local_changes = EntityGroup::new(Uuid::from_u128(1));
+    local_changes.add(Entity::Participant(make_adjudicator("Peter Gabriel", 1)));
+    local_changes.save_all_and_log(&db).await.unwrap();
+
+    // The ancestor version of the participant is superseded and therefore pruned
+    let snapshot = compact_tournament_log(&db, Uuid::from_u128(1)).await.unwrap().unwrap();
+    assert!(snapshot.pruned_entries >= 1);
+
+    let outcome = reconcile_changes(
+        &db,
+        Uuid::from_u128(1),
+        make_remote_participant_log(make_adjudicator("Peter G.", 5)),
+        Some(last_common_ancestor),
+        MergeStrategy::ThreeWay,
+        false
+    ).await.unwrap();
+
+    assert!(matches!(outcome, ReconciliationOutcome::Success { .. }));
+
+    let participant = Participant::get(&db, Uuid::from_u128(100_000)).await.unwrap();
+    assert_eq!(participant, make_adjudicator("Peter Gabriel", 5));
+}
+
+#[tokio::test]
+async fn test_compaction_drops_snapshots_of_old_pruned_entries() {
+    let (db, _) = set_up_merge_db().await;
+    // The setup logs the tournament and the participant in no fixed order
+    let first_entry = tournament_log::Entity::find()
+        .filter(tournament_log::Column::TargetUuid.eq(Uuid::from_u128(100_000)))
+        .one(&db)
+        .await
+        .unwrap()
+        .unwrap()
+        .uuid;
+
+    for chair_skill in 2..(3 + SNAPSHOT_RETENTION_COMPACTIONS as i16) {
+        let mut changes = EntityGroup::new(Uuid::from_u128(1));
+        changes.add(Entity::Participant(make_adjudicator("Peter G.", chair_skill)));
+        changes.save_all_and_log(&db).await.unwrap();
+        compact_tournament_log(&db, Uuid::from_u128(1)).await.unwrap().unwrap();
+    }
+
+    let pruned_entries = tournament_log_pruned_entry::Entity::find()
+        .filter(tournament_log_pruned_entry::Column::TournamentId.eq(Uuid::from_u128(1)))
+        .order_by_asc(tournament_log_pruned_entry::Column::SequenceIdx)
+        .all(&db)
+        .await
+        .unwrap();
+
+    let participant_entries = pruned_entries.iter().filter(|e| e.target_uuid == Uuid::from_u128(100_000)).collect::<Vec<_>>();
+    assert_eq!(participant_entries.len(), 1 + SNAPSHOT_RETENTION_COMPACTIONS as usize);
+    // The entry pruned by the first compaction lost its snapshot, but is still known
+    assert_eq!(participant_entries[0].uuid, first_entry);
+    assert!(participant_entries[0].snapshot.is_none());
+    assert!(participant_entries[1..].iter().all(|e| e.snapshot.is_some()));
+}
+
+async fn remove_log_snapshots(db: &DatabaseConnection) {
+    // Log entries written before snapshots were introduced
+    tournament_log::Entity::update_many()
+        .col_expr(tournament_log::Column::Snapshot, Expr::value(Option::<String>::None))
+        .exec(db)
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn test_log_pages_without_snapshots_stay_consistent() {
+    let (db, _) = set_up_merge_db().await;
+    for chair_skill in 2..4 {
+        let mut changes = EntityGroup::new(Uuid::from_u128(1));
+        changes.add(Entity::Participant(make_adjudicator("Peter G.", chair_skill)));
+        changes.save_all_and_log(&db).await.unwrap();
+    }
+    let mut other_adjudicator = make_adjudicator("Paul", 1);
+    other_adjudicator.uuid = Uuid::from_u128(100_001);
+    for name in ["Paul", "Paul S."] {
+        other_adjudicator.name = name.into();
+        let mut changes = EntityGroup::new(Uuid::from_u128(1));
+        changes.add(Entity::Participant(other_adjudicator.clone()));
+        changes.save_all_and_log(&db).await.unwrap();
+    }
+    remove_log_snapshots(&db).await;
+
+    let full_log = get_entity_changes_page_since(&db, Uuid::from_u128(1), None, None).await.unwrap();
+    let log = full_log.log.log.iter().map(|entry| entry.uuid).collect::<Vec<_>>();
+    assert_eq!(log.len(), 6);
+
+    // The other adjudicator changes again after the fifth entry, so the page ends before it
+    let page = get_entity_changes_page_since(&db, Uuid::from_u128(1), None, Some(5)).await.unwrap();
+    assert!(page.has_more);
+    assert_eq!(page.log.log.iter().map(|entry| entry.uuid).collect::<Vec<_>>(), log[..4]);
+    let participants = &page.log.entities[&EntityTypeId::Participant];
+    assert_eq!(participants.len(), 1);
+    assert_eq!(participants[0].current_value, EntityState::Exists(Entity::Participant(make_adjudicator("Peter G.", 3))));
+
+    let page = get_entity_changes_page_since(&db, Uuid::from_u128(1), Some(log[3]), Some(5)).await.unwrap();
+    assert!(!page.has_more);
+    assert_eq!(page.log.log.iter().map(|entry| entry.uuid).collect::<Vec<_>>(), log[4..]);
+
+    // Without a consistent way to split the page, it still never exceeds the limit
+    let page = get_entity_changes_page_since(&db, Uuid::from_u128(1), None, Some(1)).await.unwrap();
+    assert!(page.has_more);
+    assert_eq!(page.log.log.iter().map(|entry| entry.uuid).collect::<Vec<_>>(), log[..1]);
+}
+
+#[tokio::test]
+async fn test_push_many_changes_without_snapshots() {
+    let mut fixture = common::Fixture::new(
+        FixtureOptions {
+            mock_default_tournament: true,
+            ..Default::default()
+        }
+    ).await;
+    let default_tournament_uuid = Uuid::from_u128(1);
+
+    // The local copy of the tournament starts out in the same state as the server
+    let local_db = sea_orm::Database::connect("sqlite::memory:").await.unwrap();
+    migration::Migrator::up(&local_db, None).await.unwrap();
+    open_tab_entities::mock::make_mock_tournament_with_options(open_tab_entities::mock::MockOption {
+        deterministic_uuids: true,
+        ..Default::default()
+    }).save_all(&local_db).await.unwrap();
+    let remote_entities = tournament_entity::Entity::find().all(&fixture.db()).await.unwrap();
+    tournament_entity::Entity::insert_many(remote_entities.into_iter().map(|e| e.into_active_model())).exec(&local_db).await.unwrap();
+    let remote_log = tournament_log::Entity::find().order_by_asc(tournament_log::Column::SequenceIdx).all(&fixture.db()).await.unwrap();
+    let mut last_common_ancestor = remote_log.last().map(|entry| entry.uuid);
+    tournament_log::Entity::insert_many(remote_log.into_iter().map(|e| e.into_active_model())).exec(&local_db).await.unwrap();
+
+    let mut participant = Participant::get(&local_db, Uuid::from_u128(2000)).await.unwrap();
+    for idx in 0..(MAX_LOG_PAGE_SIZE + 100) {
+        participant.name = format!("Participant {}", idx);
+        let mut changes = EntityGroup::new(default_tournament_uuid);
+        changes.add(Entity::Participant(participant.clone()));
+        changes.save_all_and_log(&local_db).await.unwrap();
+    }
+    remove_log_snapshots(&local_db).await;
+
+    let mut num_pages = 0;
+    loop {
+        let page = get_entity_changes_page_since(&local_db, default_tournament_uuid, last_common_ancestor, Some(MAX_LOG_PAGE_SIZE)).await.unwrap();
+        assert!(page.log.log.len() as u64 <= MAX_LOG_PAGE_SIZE);
+        let mut response = fixture.post_gzip_json(
+            &format!("/api/tournament/{}/log", default_tournament_uuid),
+            SyncRequest { log: page.log, last_common_ancestor }
+        ).await;
+        assert_eq!(response.status(), 200);
+        match response.json::<SyncRequestResponse>().await.outcome {
+            APIReconciliationOutcome::Success { new_last_common_ancestor } => last_common_ancestor = Some(new_last_common_ancestor),
+            outcome => panic!("Unexpected outcome {:?}", outcome),
+        }
+        num_pages += 1;
+        if !page.has_more {
+            break;
+        }
+    }
+
+    assert_eq!(num_pages, 2);
+    let participant = Participant::get(&fixture.db(), Uuid::from_u128(2000)).await.unwrap();
+    assert_eq!(participant.name, format!("Participant {}", MAX_LOG_PAGE_SIZE + 99));
+}
+
+#[test]
+fn test_merge_without_ancestor_conflicts_on_whole_entity() {
+    let local = EntityState::Exists(Entity::Participant(make_adjudicator("Peter Gabriel", 1)));
+    let remote = EntityState::Exists(Entity::Participant(make_adjudicator("Peter G.", 5)));
+
+    let (merged, conflicts) = merge_entity_states(None, &local, &remote).unwrap();
+
+    assert_eq!(merged, local);
+    assert_eq!(conflicts.len(), 1);
+    assert_eq!(conflicts[0].path, "");
+}
+
+#[test]
+fn test_merge_deletion_with_unchanged_remote() {
+    let base = EntityState::Exists(Entity::Participant(make_adjudicator("Peter G.", 1)));
+    let local = EntityState::Deleted { uuid: Uuid::from_u128(100_000), type_: EntityTypeId::Participant };
+
+    let (merged, conflicts) = merge_entity_states(Some(&base), &local, &base).unwrap();
+
+    assert_eq!(merged, local);
+    assert!(conflicts.is_empty());
+}
@@ -109,6 +109,15 @@ async function loadTournamentInfo({ params, fetch, cookies, url }) {
             )
         }
 
+        if (participantInfo.can_edit_availability) {
+            additionalLinks.push(
+                {
+                    name: "Availability",
+                    url: `/tournament/${params.tournament_id}/home/${participantId}/availability`,
+                }
+            )
+        }
+
         additionalLinks.push(
             {
                 name: "Settings",
@@ -1,5 +1,5 @@
 import { env } from '$env/dynamic/public'
-import { redirect } from '@sveltejs/kit';
+import { fail, redirect } from '@sveltejs/kit';
 
 import { makeAuthenticatedRequestServerOnly } from '$lib/api';
 
@@ -79,7 +79,7 @@ function parseScores(scoresList, adjudicators) {
         if (score !== undefined && score !== null && score !== "") {
             // @ts-ignore
             scores[adj] = {
-                "total": parseInt(score),
+                "total": parseFloat(score),
                 "type": "Aggregate"
             }
         }
@@ -159,6 +159,14 @@ export const actions = {
             body: JSON.stringify(ballot),
         });*/
 
+        if (!res.ok) {
+            let error = (await res.json()).message;
+            if (error.error === "InvalidBallot") {
+                return fail(400, { validationErrors: error.errors });
+            }
+            return fail(res.status, { message: error.message || "Could not submit ballot" });
+        }
+
         throw redirect(302, `/tournament/${params.tournament_id}/submission/${(await res.json()).submission_id}`);
     }
 };
\ No newline at end of file
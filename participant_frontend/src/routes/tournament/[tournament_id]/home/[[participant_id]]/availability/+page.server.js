@@ -0,0 +1,44 @@
+import { makeAuthenticatedRequestServerOnly } from '$lib/api';
+
+/** @type {import('./$types').PageServerLoad} */
+export async function load({ params, cookies }) {
+    let participantId = params.participant_id;
+    let res = await makeAuthenticatedRequestServerOnly(
+        `api/participant/${participantId}/availability`,
+        cookies,
+        {}
+    )
+    const { rounds } = await res.json();
+
+    return {
+        rounds,
+    };
+}
+
+/** @type {import('./$types').Actions} */
+export const actions = {
+    default: async ({request, params, cookies}) => {
+        let participantId = params.participant_id;
+        const data = await request.formData();
+        let availableRounds = new Set(data.getAll("available_rounds[]"));
+        let changeableRounds = data.getAll("changeable_rounds[]");
+
+        await makeAuthenticatedRequestServerOnly(
+            `api/participant/${participantId}/availability`,
+            cookies,
+            {
+                method: "POST",
+                headers: {
+                    "Content-Type": "application/json",
+                },
+                body: JSON.stringify({
+                    available_rounds: changeableRounds.filter((r) => availableRounds.has(r)),
+                    unavailable_rounds: changeableRounds.filter((r) => !availableRounds.has(r)),
+                }),
+            }
+        );
+        return {
+            status: 200,
+        };
+    }
+}